@@ -0,0 +1,137 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::Rng;
+use rust_kzg_bn254::{
+    blob::Blob, domain::Domain, kzg::Kzg, polynomial::Polynomial, verifier::OpeningClaim,
+};
+use std::time::Duration;
+
+// Blob sizes in bytes, 4KB up to 1MB, the largest that fits in the 131072
+// point SRS loaded below.
+const BLOB_SIZES: [usize; 4] = [4 * 1024, 32 * 1024, 256 * 1024, 1024 * 1024];
+
+// Number of blobs committed to, proven and verified per iteration.
+const BATCH_SIZE: usize = 16;
+
+fn random_polynomials(rng: &mut impl Rng, size: usize) -> Vec<Polynomial> {
+    (0..BATCH_SIZE)
+        .map(|_| {
+            let random_blob: Vec<u8> = (0..size).map(|_| rng.gen_range(32..=126) as u8).collect();
+            Blob::from_bytes_and_pad(&random_blob)
+                .to_polynomial()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn bench_kzg_batch(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let kzg = Kzg::setup(
+        "src/test-files/mainnet-data/g1.131072.point",
+        "",
+        "src/test-files/mainnet-data/g2.point.powerOf2",
+        268435456,
+        131072,
+    )
+    .unwrap();
+
+    let mut prove_group = c.benchmark_group("bench_kzg_batch_proof");
+    for size in BLOB_SIZES {
+        let polys = random_polynomials(&mut rng, size);
+        assert!(
+            polys[0].len() <= kzg.get_g1_points().len(),
+            "blob of {size} bytes exceeds the loaded SRS"
+        );
+        let indices: Vec<u64> = polys
+            .iter()
+            .map(|poly| {
+                rng.gen_range(0..poly.get_length_of_padded_blob_as_fr_vector())
+                    .try_into()
+                    .unwrap()
+            })
+            .collect();
+
+        prove_group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+        prove_group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| {
+                kzg.compute_commitments_and_proofs(&polys, &indices)
+                    .unwrap()
+            })
+        });
+    }
+    prove_group.finish();
+
+    let mut verify_group = c.benchmark_group("bench_kzg_batch_verify");
+    for size in BLOB_SIZES {
+        let polys = random_polynomials(&mut rng, size);
+        assert!(
+            polys[0].len() <= kzg.get_g1_points().len(),
+            "blob of {size} bytes exceeds the loaded SRS"
+        );
+        let claims: Vec<OpeningClaim> = polys
+            .iter()
+            .map(|poly| {
+                let index = rng.gen_range(0..poly.get_length_of_padded_blob_as_fr_vector());
+                let commitment = kzg.commit(poly).unwrap();
+                let proof = kzg
+                    .compute_kzg_proof_with_roots_of_unity(poly, index.try_into().unwrap())
+                    .unwrap();
+                let value_fr = *poly.get_at_index(index).unwrap();
+                let z_fr = Domain::new(poly.len()).unwrap().element(index);
+                OpeningClaim::new(commitment, proof, value_fr, z_fr)
+            })
+            .collect();
+
+        verify_group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+        verify_group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| assert!(kzg.verify_proofs_batched(&claims)))
+        });
+    }
+    verify_group.finish();
+
+    let mut blob_verify_group = c.benchmark_group("bench_kzg_batch_verify_blob");
+    for size in BLOB_SIZES {
+        let blobs: Vec<Blob> = (0..BATCH_SIZE)
+            .map(|_| {
+                let random_blob: Vec<u8> =
+                    (0..size).map(|_| rng.gen_range(32..=126) as u8).collect();
+                Blob::from_bytes_and_pad(&random_blob)
+            })
+            .collect();
+        assert!(
+            blobs[0].to_polynomial().unwrap().len() <= kzg.get_g1_points().len(),
+            "blob of {size} bytes exceeds the loaded SRS"
+        );
+        let commitments: Vec<_> = blobs
+            .iter()
+            .map(|blob| kzg.commit(&blob.to_polynomial().unwrap()).unwrap())
+            .collect();
+        let proofs: Vec<_> = blobs
+            .iter()
+            .zip(&commitments)
+            .map(|(blob, commitment)| kzg.compute_blob_kzg_proof(blob, commitment).unwrap())
+            .collect();
+
+        blob_verify_group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+        blob_verify_group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| {
+                kzg.verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+                    .unwrap()
+            })
+        });
+    }
+    blob_verify_group.finish();
+}
+
+fn criterion_config() -> Criterion {
+    Criterion::default()
+        .warm_up_time(Duration::from_secs(5))  // Warm-up time
+        .measurement_time(Duration::from_secs(10))  // Measurement time
+        .sample_size(10) // Number of samples to take
+}
+
+criterion_group!(
+    name = benches;
+    config = criterion_config();
+    targets = bench_kzg_batch
+);
+criterion_main!(benches);
@@ -1,8 +1,19 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand::Rng;
 use rust_kzg_bn254::{blob::Blob, kzg::Kzg};
 use std::time::Duration;
 
+// Blob sizes in bytes, 4KB up to 16MB. Sizes that don't fit in the loaded SRS
+// are skipped.
+const BLOB_SIZES: [usize; 6] = [
+    4 * 1024,
+    32 * 1024,
+    256 * 1024,
+    1024 * 1024,
+    4 * 1024 * 1024,
+    16 * 1024 * 1024,
+];
+
 fn bench_kzg_commit(c: &mut Criterion) {
     let mut rng = rand::thread_rng();
     let mut kzg = Kzg::setup(
@@ -14,32 +25,23 @@ fn bench_kzg_commit(c: &mut Criterion) {
     )
     .unwrap();
 
-    c.bench_function("bench_kzg_commit_10000", |b| {
-        let random_blob: Vec<u8> = (0..10000).map(|_| rng.gen_range(32..=126) as u8).collect();
-        let input = Blob::from_bytes_and_pad(&random_blob);
-        let input_poly = input.to_polynomial().unwrap();
-        kzg.data_setup_custom(1, input.len().try_into().unwrap())
-            .unwrap();
-        b.iter(|| kzg.commit(&input_poly).unwrap());
-    });
-
-    c.bench_function("bench_kzg_commit_30000", |b| {
-        let random_blob: Vec<u8> = (0..30000).map(|_| rng.gen_range(32..=126) as u8).collect();
+    let mut group = c.benchmark_group("bench_kzg_commit");
+    for size in BLOB_SIZES {
+        let random_blob: Vec<u8> = (0..size).map(|_| rng.gen_range(32..=126) as u8).collect();
         let input = Blob::from_bytes_and_pad(&random_blob);
         let input_poly = input.to_polynomial().unwrap();
+        if input_poly.len() > kzg.get_g1_points().len() {
+            continue;
+        }
         kzg.data_setup_custom(1, input.len().try_into().unwrap())
             .unwrap();
-        b.iter(|| kzg.commit(&input_poly).unwrap());
-    });
 
-    c.bench_function("bench_kzg_commit_50000", |b| {
-        let random_blob: Vec<u8> = (0..50000).map(|_| rng.gen_range(32..=126) as u8).collect();
-        let input = Blob::from_bytes_and_pad(&random_blob);
-        let input_poly = input.to_polynomial().unwrap();
-        kzg.data_setup_custom(1, input.len().try_into().unwrap())
-            .unwrap();
-        b.iter(|| kzg.commit(&input_poly).unwrap());
-    });
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input_poly, |b, poly| {
+            b.iter(|| kzg.commit(poly).unwrap())
+        });
+    }
+    group.finish();
 }
 
 fn criterion_config() -> Criterion {
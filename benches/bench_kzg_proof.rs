@@ -1,8 +1,19 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand::Rng;
 use rust_kzg_bn254::{blob::Blob, kzg::Kzg};
 use std::time::Duration;
 
+// Blob sizes in bytes, 4KB up to 16MB. Sizes that don't fit in the loaded SRS
+// are skipped.
+const BLOB_SIZES: [usize; 6] = [
+    4 * 1024,
+    32 * 1024,
+    256 * 1024,
+    1024 * 1024,
+    4 * 1024 * 1024,
+    16 * 1024 * 1024,
+];
+
 fn bench_kzg_proof(c: &mut Criterion) {
     let mut rng = rand::thread_rng();
     let mut kzg = Kzg::setup(
@@ -14,47 +25,54 @@ fn bench_kzg_proof(c: &mut Criterion) {
     )
     .unwrap();
 
-    c.bench_function("bench_kzg_proof_10000", |b| {
-        let random_blob: Vec<u8> = (0..10000).map(|_| rng.gen_range(32..=126) as u8).collect();
+    let mut group = c.benchmark_group("bench_kzg_proof");
+    for size in BLOB_SIZES {
+        let random_blob: Vec<u8> = (0..size).map(|_| rng.gen_range(32..=126) as u8).collect();
         let input = Blob::from_bytes_and_pad(&random_blob);
         let input_poly = input.to_polynomial().unwrap();
+        if input_poly.len() > kzg.get_g1_points().len() {
+            continue;
+        }
         kzg.data_setup_custom(1, input.len().try_into().unwrap())
             .unwrap();
-        let index =
-            rand::thread_rng().gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector());
-        b.iter(|| {
-            kzg.compute_kzg_proof_with_roots_of_unity(&input_poly, index.try_into().unwrap())
-                .unwrap()
-        });
-    });
+        let index = rng.gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector());
 
-    c.bench_function("bench_kzg_proof_30000", |b| {
-        let random_blob: Vec<u8> = (0..30000).map(|_| rng.gen_range(32..=126) as u8).collect();
-        let input = Blob::from_bytes_and_pad(&random_blob);
-        let input_poly = input.to_polynomial().unwrap();
-        kzg.data_setup_custom(1, input.len().try_into().unwrap())
-            .unwrap();
-        let index =
-            rand::thread_rng().gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector());
-        b.iter(|| {
-            kzg.compute_kzg_proof_with_roots_of_unity(&input_poly, index.try_into().unwrap())
-                .unwrap()
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input_poly, |b, poly| {
+            b.iter(|| {
+                kzg.compute_kzg_proof_with_roots_of_unity(poly, index.try_into().unwrap())
+                    .unwrap()
+            })
         });
-    });
+    }
+    group.finish();
+}
 
-    c.bench_function("bench_kzg_proof_50000", |b| {
-        let random_blob: Vec<u8> = (0..50000).map(|_| rng.gen_range(32..=126) as u8).collect();
-        let input = Blob::from_bytes_and_pad(&random_blob);
-        let input_poly = input.to_polynomial().unwrap();
-        kzg.data_setup_custom(1, input.len().try_into().unwrap())
+fn bench_kzg_all_proofs(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let kzg = Kzg::setup(
+        "src/test-files/mainnet-data/g1.131072.point",
+        "",
+        "src/test-files/mainnet-data/g2.point.powerOf2",
+        268435456,
+        131072,
+    )
+    .unwrap();
+
+    // FK20 proves every root of unity at once, so only the smaller sizes
+    let mut group = c.benchmark_group("bench_kzg_all_proofs");
+    for size in &BLOB_SIZES[..3] {
+        let random_blob: Vec<u8> = (0..*size).map(|_| rng.gen_range(32..=126) as u8).collect();
+        let input_poly = Blob::from_bytes_and_pad(&random_blob)
+            .to_polynomial()
             .unwrap();
-        let index =
-            rand::thread_rng().gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector());
-        b.iter(|| {
-            kzg.compute_kzg_proof_with_roots_of_unity(&input_poly, index.try_into().unwrap())
-                .unwrap()
+
+        group.throughput(Throughput::Bytes(*size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input_poly, |b, poly| {
+            b.iter(|| kzg.compute_all_proofs(poly).unwrap())
         });
-    });
+    }
+    group.finish();
 }
 
 fn criterion_config() -> Criterion {
@@ -67,6 +85,6 @@ fn criterion_config() -> Criterion {
 criterion_group!(
     name = benches;
     config = criterion_config();
-    targets = bench_kzg_proof
+    targets = bench_kzg_proof, bench_kzg_all_proofs
 );
 criterion_main!(benches);
@@ -1,8 +1,19 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand::Rng;
 use rust_kzg_bn254::{blob::Blob, kzg::Kzg};
 use std::time::Duration;
 
+// Blob sizes in bytes, 4KB up to 16MB. Sizes that don't fit in the loaded SRS
+// are skipped.
+const BLOB_SIZES: [usize; 6] = [
+    4 * 1024,
+    32 * 1024,
+    256 * 1024,
+    1024 * 1024,
+    4 * 1024 * 1024,
+    16 * 1024 * 1024,
+];
+
 fn bench_kzg_verify(c: &mut Criterion) {
     let mut rng = rand::thread_rng();
     let mut kzg = Kzg::setup(
@@ -14,56 +25,30 @@ fn bench_kzg_verify(c: &mut Criterion) {
     )
     .unwrap();
 
-    c.bench_function("bench_kzg_verify_10000", |b| {
-        let random_blob: Vec<u8> = (0..10000).map(|_| rng.gen_range(32..=126) as u8).collect();
-        let input = Blob::from_bytes_and_pad(&random_blob);
-        let input_poly = input.to_polynomial().unwrap();
-        kzg.data_setup_custom(1, input.len().try_into().unwrap())
-            .unwrap();
-        let index =
-            rand::thread_rng().gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector());
-        let commitment = kzg.commit(&input_poly.clone()).unwrap();
-        let proof = kzg
-            .compute_kzg_proof_with_roots_of_unity(&input_poly, index.try_into().unwrap())
-            .unwrap();
-        let value_fr = input_poly.get_at_index(index).unwrap();
-        let z_fr = kzg.get_nth_root_of_unity(index).unwrap();
-        b.iter(|| kzg.verify_kzg_proof(commitment, proof, value_fr.clone(), z_fr.clone()));
-    });
-
-    c.bench_function("bench_kzg_verify_30000", |b| {
-        let random_blob: Vec<u8> = (0..30000).map(|_| rng.gen_range(32..=126) as u8).collect();
+    let mut group = c.benchmark_group("bench_kzg_verify");
+    for size in BLOB_SIZES {
+        let random_blob: Vec<u8> = (0..size).map(|_| rng.gen_range(32..=126) as u8).collect();
         let input = Blob::from_bytes_and_pad(&random_blob);
         let input_poly = input.to_polynomial().unwrap();
+        if input_poly.len() > kzg.get_g1_points().len() {
+            continue;
+        }
         kzg.data_setup_custom(1, input.len().try_into().unwrap())
             .unwrap();
-        let index =
-            rand::thread_rng().gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector());
-        let commitment = kzg.commit(&input_poly.clone()).unwrap();
+        let index = rng.gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector());
+        let commitment = kzg.commit(&input_poly).unwrap();
         let proof = kzg
             .compute_kzg_proof_with_roots_of_unity(&input_poly, index.try_into().unwrap())
             .unwrap();
-        let value_fr = input_poly.get_at_index(index).unwrap();
-        let z_fr = kzg.get_nth_root_of_unity(index).unwrap();
-        b.iter(|| kzg.verify_kzg_proof(commitment, proof, value_fr.clone(), z_fr.clone()));
-    });
+        let value_fr = *input_poly.get_at_index(index).unwrap();
+        let z_fr = *kzg.get_nth_root_of_unity(index).unwrap();
 
-    c.bench_function("bench_kzg_verify_50000", |b| {
-        let random_blob: Vec<u8> = (0..50000).map(|_| rng.gen_range(32..=126) as u8).collect();
-        let input = Blob::from_bytes_and_pad(&random_blob);
-        let input_poly = input.to_polynomial().unwrap();
-        kzg.data_setup_custom(1, input.len().try_into().unwrap())
-            .unwrap();
-        let index =
-            rand::thread_rng().gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector());
-        let commitment = kzg.commit(&input_poly.clone()).unwrap();
-        let proof = kzg
-            .compute_kzg_proof_with_roots_of_unity(&input_poly, index.try_into().unwrap())
-            .unwrap();
-        let value_fr = input_poly.get_at_index(index).unwrap();
-        let z_fr = kzg.get_nth_root_of_unity(index).unwrap();
-        b.iter(|| kzg.verify_kzg_proof(commitment, proof, value_fr.clone(), z_fr.clone()));
-    });
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| kzg.verify_kzg_proof(commitment, proof, value_fr, z_fr))
+        });
+    }
+    group.finish();
 }
 
 fn criterion_config() -> Criterion {
@@ -0,0 +1,106 @@
+//! Autotune bench for the work-splitting tunables on `KzgConfig`.
+//!
+//! Each group sweeps one tunable over a range of values while the others stay
+//! at their defaults, and measures commit + proof over several blob sizes. Run
+//! it on the target machine with
+//!
+//!     cargo bench --bench bench_work_split
+//!
+//! and pick, per tunable, the smallest value after which the larger blob sizes
+//! stop getting faster. On wide machines the sequential cutoffs usually want to
+//! go up (scheduling more tasks costs more than it saves), on small machines
+//! the minimum MSM chunk length usually wants to go down.
+use ark_bn254::G1Affine;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::Rng;
+use rust_kzg_bn254::{blob::Blob, config::KzgConfig, kzg::Kzg, polynomial::Polynomial};
+use std::time::Duration;
+
+// Blob sizes in bytes, chosen to straddle the default cutoffs.
+const BLOB_SIZES: [usize; 4] = [4 * 1024, 32 * 1024, 256 * 1024, 1024 * 1024];
+
+const CUTOFFS: [usize; 5] = [1 << 6, 1 << 8, 1 << 10, 1 << 12, 1 << 14];
+
+fn commit_and_prove(kzg: &Kzg, poly: &Polynomial) -> (G1Affine, G1Affine) {
+    (
+        kzg.commit(poly).unwrap(),
+        kzg.compute_kzg_proof_with_roots_of_unity(poly, 0).unwrap(),
+    )
+}
+
+fn bench_tunable(
+    c: &mut Criterion,
+    kzg: &mut Kzg,
+    polys: &[(usize, Polynomial)],
+    name: &str,
+    configure: fn(KzgConfig, usize) -> KzgConfig,
+) {
+    let mut group = c.benchmark_group(format!("bench_work_split_{}", name));
+    for &cutoff in CUTOFFS.iter() {
+        kzg.set_config(configure(KzgConfig::new(), cutoff));
+        for (size, poly) in polys {
+            kzg.data_setup_custom(1, poly.len().try_into().unwrap())
+                .unwrap();
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}={}", name, cutoff), size),
+                poly,
+                |b, poly| b.iter(|| commit_and_prove(kzg, poly)),
+            );
+        }
+    }
+    group.finish();
+    kzg.set_config(KzgConfig::new());
+}
+
+fn bench_work_split(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut kzg = Kzg::setup(
+        "src/test-files/mainnet-data/g1.131072.point",
+        "",
+        "src/test-files/mainnet-data/g2.point.powerOf2",
+        268435456,
+        131072,
+    )
+    .unwrap();
+
+    let polys: Vec<(usize, Polynomial)> = BLOB_SIZES
+        .iter()
+        .map(|&size| {
+            let random_blob: Vec<u8> = (0..size).map(|_| rng.gen_range(32..=126) as u8).collect();
+            (
+                size,
+                Blob::from_bytes_and_pad(&random_blob)
+                    .to_polynomial()
+                    .unwrap(),
+            )
+        })
+        .filter(|(_, poly)| poly.len() <= kzg.get_g1_points().len())
+        .collect();
+
+    bench_tunable(c, &mut kzg, &polys, "msm_sequential_cutoff", |config, v| {
+        config.with_msm_sequential_cutoff(v)
+    });
+    bench_tunable(c, &mut kzg, &polys, "msm_min_chunk_len", |config, v| {
+        config.with_msm_min_chunk_len(v)
+    });
+    bench_tunable(c, &mut kzg, &polys, "fft_sequential_cutoff", |config, v| {
+        config.with_fft_sequential_cutoff(v)
+    });
+    bench_tunable(c, &mut kzg, &polys, "small_blob_threshold", |config, v| {
+        config.with_small_blob_threshold(v)
+    });
+}
+
+fn criterion_config() -> Criterion {
+    Criterion::default()
+        .warm_up_time(Duration::from_secs(2))  // Warm-up time
+        .measurement_time(Duration::from_secs(5))  // Measurement time
+        .sample_size(10) // Number of samples to take
+}
+
+criterion_group!(
+    name = benches;
+    config = criterion_config();
+    targets = bench_work_split
+);
+criterion_main!(benches);
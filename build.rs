@@ -0,0 +1,9 @@
+fn main() {
+    // link arguments the Node.js addon needs to load on every platform
+    #[cfg(feature = "node")]
+    napi_build::setup();
+
+    // Rust side of the mobile bindings, included by src/mobile.rs
+    #[cfg(feature = "mobile")]
+    uniffi::generate_scaffolding("src/rust_kzg_bn254.udl").unwrap();
+}
@@ -0,0 +1,92 @@
+#ifndef RUST_KZG_BN254_H
+#define RUST_KZG_BN254_H
+
+/* Generated with cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Number of bytes of a serialized point or field element.
+#define KZG_BYTES 32
+
+// The call succeeded.
+#define KZG_OK 0
+
+// A required pointer was null.
+#define KZG_NULL_POINTER 1
+
+// An argument could not be decoded or is out of range.
+#define KZG_INVALID_INPUT 2
+
+// The computation failed.
+#define KZG_ERROR 3
+
+// An SRS loaded by `kzg_setup_from_bytes`, freed with `kzg_free`.
+typedef struct KzgHandle KzgHandle;
+
+// Loads the SRS from the concatenated 32-byte G1 points and the 64-byte
+// `[tau]G2`, in the big-endian encoding of the SRS files, and stores a new
+// handle in `out`.
+//
+// # Safety
+//
+// The buffers must be valid for their lengths and `out` valid for a write.
+int32_t kzg_setup_from_bytes(const uint8_t *g1,
+                             size_t g1_len,
+                             const uint8_t *g2_tau,
+                             size_t g2_tau_len,
+                             struct KzgHandle **out);
+
+// Frees a handle from `kzg_setup_from_bytes`. Null is ignored.
+//
+// # Safety
+//
+// `handle` must come from `kzg_setup_from_bytes` and not be used afterwards.
+void kzg_free(struct KzgHandle *handle);
+
+// Pads the payload into a blob and writes its commitment to
+// `commitment_out`.
+//
+// # Safety
+//
+// `handle` must be live, `payload` valid for `payload_len` bytes and
+// `commitment_out` valid for `KZG_BYTES` bytes.
+int32_t kzg_commit(const struct KzgHandle *handle,
+                   const uint8_t *payload,
+                   size_t payload_len,
+                   uint8_t *commitment_out);
+
+// Pads the payload into a blob and opens it at its `index`-th field
+// element, writing the proof, the value and the root of unity it is
+// opened at.
+//
+// # Safety
+//
+// `handle` must be live, `payload` valid for `payload_len` bytes and each
+// output valid for `KZG_BYTES` bytes.
+int32_t kzg_prove(const struct KzgHandle *handle,
+                  const uint8_t *payload,
+                  size_t payload_len,
+                  uint64_t index,
+                  uint8_t *proof_out,
+                  uint8_t *value_out,
+                  uint8_t *z_out);
+
+// Checks the proof opens the commitment to the value at `z`, writing 1 to
+// `valid_out` if it does and 0 otherwise.
+//
+// # Safety
+//
+// `handle` must be live, each input valid for `KZG_BYTES` bytes and
+// `valid_out` valid for a write.
+int32_t kzg_verify(const struct KzgHandle *handle,
+                   const uint8_t *commitment,
+                   const uint8_t *proof,
+                   const uint8_t *value,
+                   const uint8_t *z,
+                   uint8_t *valid_out);
+
+#endif /* RUST_KZG_BN254_H */
@@ -0,0 +1,17 @@
+#!/usr/bin/env sh
+# Builds the library as a cdylib for the C, Node.js, mobile and wasm
+# bindings. The crate itself is an rlib only, so Rust dependents and
+# `--no-default-features` builds never link a shared library.
+#
+# usage: scripts/build-cdylib.sh <features> [cargo rustc options]
+#   scripts/build-cdylib.sh ffi
+#   scripts/build-cdylib.sh wasm --no-default-features --target wasm32-unknown-unknown
+set -eu
+
+if [ $# -lt 1 ]; then
+    echo "usage: $0 <features> [cargo rustc options]" >&2
+    exit 1
+fi
+features=$1
+shift
+exec cargo rustc --release --lib --crate-type cdylib --features "$features" "$@"
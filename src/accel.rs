@@ -0,0 +1,105 @@
+//! Hooks for accelerated BN254 operations.
+//!
+//! zkVM guests such as RISC Zero and SP1 prove BN254 arithmetic far more
+//! cheaply through the precompiles their host provides than by executing
+//! arkworks instruction by instruction. `EmbeddedKzg` runs its group and
+//! pairing operations through a `Bn254Ops` implementation, so a guest swaps
+//! in its precompiles with `EmbeddedKzg::with_ops` and leaves the rest of the
+//! verification path unchanged. Every operation has an arkworks default, so
+//! an implementation only overrides the ones its VM accelerates.
+
+use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, AffineRepr, VariableBaseMSM};
+use ark_ff::Zero;
+
+/// The BN254 operations `EmbeddedKzg` performs.
+pub trait Bn254Ops {
+    /// Returns `sum scalars[i] * bases[i]` over the shorter of the two.
+    fn g1_msm(bases: &[G1Affine], scalars: &[Fr]) -> G1Projective {
+        let len = bases.len().min(scalars.len());
+        G1Projective::msm_unchecked(&bases[..len], &scalars[..len])
+    }
+
+    /// Returns `scalar * point` in G1.
+    fn g1_mul(point: &G1Affine, scalar: &Fr) -> G1Projective {
+        *point * scalar
+    }
+
+    /// Returns `scalar * point` in G2.
+    fn g2_mul(point: &G2Affine, scalar: &Fr) -> G2Projective {
+        *point * scalar
+    }
+
+    /// Returns whether the product of `e(g1[i], g2[i])` is the identity.
+    fn pairing_check(g1: &[G1Affine], g2: &[G2Affine]) -> bool {
+        Bn254::multi_pairing(g1.iter().copied(), g2.iter().copied()).is_zero()
+    }
+}
+
+/// Runs every operation with arkworks, the default for `EmbeddedKzg`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArkworksOps;
+
+impl Bn254Ops for ArkworksOps {}
+
+/// returns `scalar * G1`
+pub(crate) fn g1_generator_mul<B: Bn254Ops>(scalar: &Fr) -> G1Projective {
+    B::g1_mul(&G1Affine::generator(), scalar)
+}
+
+/// returns `scalar * G2`
+pub(crate) fn g2_generator_mul<B: Bn254Ops>(scalar: &Fr) -> G2Projective {
+    B::g2_mul(&G2Affine::generator(), scalar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES, embedded::EmbeddedKzg};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    const G1_POINTS: &[u8] = include_bytes!("test-files/g1.point");
+    const G2_POINTS: &[u8] = include_bytes!("test-files/g2.point");
+
+    static MSMS: AtomicUsize = AtomicUsize::new(0);
+    static PAIRINGS: AtomicUsize = AtomicUsize::new(0);
+
+    // stands in for a VM's precompiles, counting the calls routed to it
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct CountingOps;
+
+    impl Bn254Ops for CountingOps {
+        fn g1_msm(bases: &[G1Affine], scalars: &[Fr]) -> G1Projective {
+            MSMS.fetch_add(1, Ordering::SeqCst);
+            ArkworksOps::g1_msm(bases, scalars)
+        }
+
+        fn pairing_check(g1: &[G1Affine], g2: &[G2Affine]) -> bool {
+            PAIRINGS.fetch_add(1, Ordering::SeqCst);
+            ArkworksOps::pairing_check(g1, g2)
+        }
+    }
+
+    #[test]
+    fn test_ops_hooks() {
+        let kzg = EmbeddedKzg::from_bytes(&G1_POINTS[..32 * 64], &G2_POINTS[64..128]).unwrap();
+        let accelerated = kzg.clone().with_ops::<CountingOps>();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let z = Fr::from(77u64);
+
+        let commitment = accelerated.commit(&polynomial).unwrap();
+        let (proof, value) = accelerated.compute_proof_at_point(&polynomial, z).unwrap();
+        assert_eq!(MSMS.load(Ordering::SeqCst), 2);
+        assert_eq!(commitment, kzg.commit(&polynomial).unwrap());
+        assert_eq!(
+            (proof, value),
+            kzg.compute_proof_at_point(&polynomial, z).unwrap()
+        );
+
+        assert!(accelerated.verify_proof(commitment, proof, value, z));
+        assert!(!accelerated.verify_proof(commitment, proof, value + Fr::from(1u64), z));
+        assert_eq!(PAIRINGS.load(Ordering::SeqCst), 2);
+    }
+}
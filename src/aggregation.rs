@@ -0,0 +1,176 @@
+//! One opening proof for a whole batch of blobs.
+//!
+//! The blobs' polynomials are combined with powers of a challenge into one
+//! polynomial, whose commitment is the same combination of the blobs'
+//! commitments, and it is opened at a point derived from every blob and
+//! commitment. A light verifier holding the blobs evaluates them at that
+//! point, which needs no SRS, and checks the whole batch with one pairing
+//! check. Unlike `batch::BlobBatch`, which carries an opening per blob, the
+//! aggregate is a single commitment, value and proof however many blobs
+//! there are.
+
+use crate::{
+    blob::Blob, consts::AGGREGATED_OPENING_DOMAIN, encoding::fr_to_bytes, errors::KzgError,
+    kzg::Kzg, polynomial::Polynomial, transcript::Transcript,
+};
+use ark_bn254::{Fr, G1Affine};
+
+/// The opening of the combination of a batch of blobs at the point derived
+/// from them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggregatedBlobProof {
+    commitment: G1Affine,
+    z: Fr,
+    value: Fr,
+    proof: G1Affine,
+}
+
+impl AggregatedBlobProof {
+    /// Combines `blobs`, committed to by `commitments` in the same order,
+    /// and opens the combination at the point derived from all of them,
+    /// with the challenge hash configured on `kzg`.
+    pub fn new(kzg: &Kzg, blobs: &[Blob], commitments: &[G1Affine]) -> Result<Self, KzgError> {
+        let polynomials = to_polynomials(blobs, commitments)?;
+        let z = opening_point(kzg, &polynomials, commitments)?;
+        let (proof, values) =
+            kzg.compute_aggregated_proof_with_commitments(&polynomials, commitments, z)?;
+        let (commitment, value) = kzg.combine_openings(commitments, &values, z)?;
+        Ok(AggregatedBlobProof {
+            commitment,
+            z,
+            value,
+            proof,
+        })
+    }
+
+    /// Returns the commitment to the combined polynomial.
+    pub fn get_commitment(&self) -> G1Affine {
+        self.commitment
+    }
+
+    /// Returns the evaluation point.
+    pub fn get_z(&self) -> Fr {
+        self.z
+    }
+
+    /// Returns the evaluation of the combined polynomial at `z`.
+    pub fn get_value(&self) -> Fr {
+        self.value
+    }
+
+    /// Returns the KZG proof of the evaluation.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+
+    /// Checks the aggregate opens the combination of `blobs` under
+    /// `commitments`. Re-derives the point and the combination from them,
+    /// evaluates the blobs at the point, and makes one pairing check.
+    pub fn verify(
+        &self,
+        kzg: &Kzg,
+        blobs: &[Blob],
+        commitments: &[G1Affine],
+    ) -> Result<bool, KzgError> {
+        let polynomials = to_polynomials(blobs, commitments)?;
+        if self.z != opening_point(kzg, &polynomials, commitments)? {
+            return Ok(false);
+        }
+        let values: Vec<Fr> = polynomials
+            .iter()
+            .map(|polynomial| Kzg::evaluate_polynomial_in_evaluation_form(polynomial, self.z))
+            .collect();
+        if (self.commitment, self.value) != kzg.combine_openings(commitments, &values, self.z)? {
+            return Ok(false);
+        }
+        Ok(kzg.verify_kzg_proof(self.commitment, self.proof, self.value, self.z))
+    }
+}
+
+/// converts the blobs to polynomials, checking there is one commitment per
+/// blob and at least one blob
+fn to_polynomials(blobs: &[Blob], commitments: &[G1Affine]) -> Result<Vec<Polynomial>, KzgError> {
+    if blobs.is_empty() || blobs.len() != commitments.len() {
+        return Err(KzgError::GenericError(
+            "blobs and commitments must be equal in number and not empty".to_string(),
+        ));
+    }
+    blobs
+        .iter()
+        .map(|blob| {
+            blob.to_polynomial()
+                .map_err(|err| KzgError::SerializationError(err.to_string()))
+        })
+        .collect()
+}
+
+/// hashes the number of blobs, then each blob's length, field elements and
+/// commitment, into the point the combination is opened at
+fn opening_point(
+    kzg: &Kzg,
+    polynomials: &[Polynomial],
+    commitments: &[G1Affine],
+) -> Result<Fr, KzgError> {
+    let mut transcript = Transcript::new(
+        kzg.get_config().get_challenge_hash(),
+        AGGREGATED_OPENING_DOMAIN,
+    );
+    transcript.append_u64(polynomials.len() as u64);
+    for (polynomial, commitment) in polynomials.iter().zip(commitments) {
+        transcript.append_u64(polynomial.len() as u64);
+        for element in polynomial.as_slice() {
+            transcript.append_bytes(&fr_to_bytes(element));
+        }
+        transcript.append_g1(commitment)?;
+    }
+    transcript.challenge()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    #[test]
+    fn test_aggregated_blob_proof() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let blobs: Vec<Blob> = [100, 700, GETTYSBURG_ADDRESS_BYTES.len()]
+            .iter()
+            .map(|&len| Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..len]))
+            .collect();
+        let commitments: Vec<G1Affine> = blobs
+            .iter()
+            .map(|blob| kzg.blob_to_kzg_commitment(blob).unwrap())
+            .collect();
+        let aggregate = AggregatedBlobProof::new(&kzg, &blobs, &commitments).unwrap();
+        assert!(aggregate.verify(&kzg, &blobs, &commitments).unwrap());
+        assert!(kzg.verify_kzg_proof(
+            aggregate.get_commitment(),
+            aggregate.get_proof(),
+            aggregate.get_value(),
+            aggregate.get_z()
+        ));
+
+        // a changed blob, swapped commitments or a forged value fail
+        let mut other = blobs.clone();
+        other[1] = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[1..701]);
+        assert!(!aggregate.verify(&kzg, &other, &commitments).unwrap());
+        let mut swapped = commitments.clone();
+        swapped.swap(0, 2);
+        assert!(!aggregate.verify(&kzg, &blobs, &swapped).unwrap());
+        let mut forged = aggregate;
+        forged.value += Fr::from(1u64);
+        assert!(!forged.verify(&kzg, &blobs, &commitments).unwrap());
+
+        assert!(AggregatedBlobProof::new(&kzg, &[], &[]).is_err());
+        assert!(AggregatedBlobProof::new(&kzg, &blobs, &commitments[1..]).is_err());
+        assert!(aggregate.verify(&kzg, &blobs[1..], &commitments).is_err());
+    }
+}
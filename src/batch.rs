@@ -0,0 +1,215 @@
+//! Many blobs committed and proven together, verified with a single pairing
+//! check.
+//!
+//! Each blob is opened at a point derived from its commitment. The openings
+//! are reduced to `KzgAccumulator`s and folded with powers of a challenge
+//! hashed from the whole batch, so a verifier, e.g. a settlement contract,
+//! checks every opening with two pairings however many blobs there are.
+
+use crate::{
+    blob::Blob,
+    circuit::KzgAccumulator,
+    config::ChallengeHash,
+    consts::{BATCH_CHALLENGE_DOMAIN, BATCH_OPENING_DOMAIN},
+    errors::KzgError,
+    kzg::Kzg,
+    transcript::Transcript,
+};
+use ark_bn254::{Fr, G1Affine};
+
+/// The opening of one blob's commitment at the point derived from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlobOpening {
+    z: Fr,
+    value: Fr,
+    proof: G1Affine,
+}
+
+impl BlobOpening {
+    /// Returns the evaluation point.
+    pub fn get_z(&self) -> Fr {
+        self.z
+    }
+
+    /// Returns the evaluation of the blob polynomial at `z`.
+    pub fn get_value(&self) -> Fr {
+        self.value
+    }
+
+    /// Returns the KZG proof of the evaluation.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+}
+
+/// The commitments of a batch of blobs with an opening of each.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobBatch {
+    commitments: Vec<G1Affine>,
+    openings: Vec<BlobOpening>,
+}
+
+impl BlobBatch {
+    /// Pads each payload into a blob, commits to it, and opens it at the
+    /// point derived from its commitment with the challenge hash configured
+    /// on `kzg`.
+    pub fn commit(kzg: &Kzg, payloads: &[&[u8]]) -> Result<Self, KzgError> {
+        let hash = kzg.get_config().get_challenge_hash();
+        let mut commitments = Vec::with_capacity(payloads.len());
+        let mut openings = Vec::with_capacity(payloads.len());
+        for payload in payloads {
+            let polynomial = Blob::from_bytes_and_pad(payload)
+                .to_polynomial()
+                .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+            let commitment = kzg.commit(&polynomial)?;
+            let z = opening_point(hash, &commitment)?;
+            let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomial, z)?;
+            commitments.push(commitment);
+            openings.push(BlobOpening { z, value, proof });
+        }
+        Ok(BlobBatch {
+            commitments,
+            openings,
+        })
+    }
+
+    /// Returns the blob commitments, in payload order.
+    pub fn get_commitments(&self) -> &[G1Affine] {
+        &self.commitments
+    }
+
+    /// Returns the blob openings, in payload order.
+    pub fn get_openings(&self) -> &[BlobOpening] {
+        &self.openings
+    }
+
+    /// Returns the number of blobs in the batch.
+    pub fn len(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Checks if the batch has no blobs.
+    pub fn is_empty(&self) -> bool {
+        self.commitments.is_empty()
+    }
+
+    /// Returns the openings folded into one accumulator with the powers of
+    /// the batch challenge, derived with `hash`. Verifying it checks the
+    /// whole batch; the evaluation points must be checked separately, as
+    /// `verify` does.
+    pub fn aggregate(&self, hash: ChallengeHash) -> Result<KzgAccumulator, KzgError> {
+        let accumulators: Vec<KzgAccumulator> = self
+            .commitments
+            .iter()
+            .zip(&self.openings)
+            .map(|(commitment, opening)| {
+                KzgAccumulator::from_opening(commitment, &opening.proof, opening.z, opening.value)
+            })
+            .collect();
+        Ok(KzgAccumulator::fold(&accumulators, self.challenge(hash)?))
+    }
+
+    /// Checks every blob is opened at the point derived from its commitment
+    /// and that all openings hold, with one pairing check for the batch.
+    /// Challenges are derived with the hash configured on `kzg`.
+    pub fn verify(&self, kzg: &Kzg) -> Result<bool, KzgError> {
+        if self.commitments.len() != self.openings.len() {
+            return Ok(false);
+        }
+        let hash = kzg.get_config().get_challenge_hash();
+        for (commitment, opening) in self.commitments.iter().zip(&self.openings) {
+            if opening.z != opening_point(hash, commitment)? {
+                return Ok(false);
+            }
+        }
+        Ok(self.aggregate(hash)?.verify(kzg))
+    }
+
+    /// hashes every commitment and opening into the challenge the openings
+    /// are folded with
+    fn challenge(&self, hash: ChallengeHash) -> Result<Fr, KzgError> {
+        let mut transcript = Transcript::new(hash, BATCH_CHALLENGE_DOMAIN);
+        transcript.append_u64(self.commitments.len() as u64);
+        for (commitment, opening) in self.commitments.iter().zip(&self.openings) {
+            transcript.append_g1(commitment)?;
+            transcript.append_fr(&opening.z);
+            transcript.append_fr(&opening.value);
+            transcript.append_g1(&opening.proof)?;
+        }
+        transcript.challenge()
+    }
+}
+
+/// Derives the point a blob is opened at from its commitment with `hash`.
+pub fn opening_point(hash: ChallengeHash, commitment: &G1Affine) -> Result<Fr, KzgError> {
+    let mut transcript = Transcript::new(hash, BATCH_OPENING_DOMAIN);
+    transcript.append_g1(commitment)?;
+    transcript.challenge()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::KzgConfig, consts::GETTYSBURG_ADDRESS_BYTES};
+
+    #[test]
+    fn test_blob_batch() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let payloads: Vec<&[u8]> = vec![
+            &GETTYSBURG_ADDRESS_BYTES[..100],
+            &GETTYSBURG_ADDRESS_BYTES[..700],
+            GETTYSBURG_ADDRESS_BYTES,
+            &GETTYSBURG_ADDRESS_BYTES[50..60],
+        ];
+        let batch = BlobBatch::commit(&kzg, &payloads).unwrap();
+        assert_eq!(batch.len(), 4);
+        assert!(batch.verify(&kzg).unwrap());
+        for (commitment, opening) in batch.get_commitments().iter().zip(batch.get_openings()) {
+            assert!(kzg.verify_kzg_proof(
+                *commitment,
+                opening.get_proof(),
+                opening.get_value(),
+                opening.get_z()
+            ));
+        }
+        let empty = BlobBatch::commit(&kzg, &[]).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.verify(&kzg).unwrap());
+
+        // a wrong value, swapped commitments, or an opening at another point
+        // fail the batch
+        let mut bad = batch.clone();
+        bad.openings[2].value += Fr::from(1u64);
+        assert!(!bad.verify(&kzg).unwrap());
+        let mut bad = batch.clone();
+        bad.commitments.swap(0, 1);
+        assert!(!bad.verify(&kzg).unwrap());
+        let mut bad = batch.clone();
+        let polynomial = Blob::from_bytes_and_pad(payloads[3])
+            .to_polynomial()
+            .unwrap();
+        let z = Fr::from(5u64);
+        let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap();
+        bad.openings[3] = BlobOpening { z, value, proof };
+        assert!(bad
+            .aggregate(ChallengeHash::default())
+            .unwrap()
+            .verify(&kzg));
+        assert!(!bad.verify(&kzg).unwrap());
+
+        // a batch proven with one challenge hash fails under another
+        let mut keccak = kzg.clone();
+        keccak.set_config(KzgConfig::new().with_challenge_hash(ChallengeHash::Keccak256));
+        let keccak_batch = BlobBatch::commit(&keccak, &payloads).unwrap();
+        assert!(keccak_batch.verify(&keccak).unwrap());
+        assert!(!keccak_batch.verify(&kzg).unwrap());
+        assert!(!batch.verify(&keccak).unwrap());
+    }
+}
@@ -0,0 +1,213 @@
+//! Command line tool for committing to, proving and verifying blobs.
+//!
+//! Points are printed as hex of their compressed big-endian encoding, the one
+//! the SRS files use, and field elements as hex of their 32 big-endian bytes,
+//! all `0x`-prefixed.
+//! Every command prints a JSON object, and `verify` reads back the object
+//! printed by `prove`.
+
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use clap::{Args, Parser, Subcommand};
+use rust_kzg_bn254::{
+    blob::Blob,
+    encoding::{fr_from_bytes, fr_to_bytes, g1_from_bytes, g1_to_bytes},
+    helpers::g2_point_to_bytes_be,
+    kzg::Kzg,
+};
+use serde_json::{json, Value};
+use std::{fs, process::ExitCode};
+
+#[derive(Parser)]
+#[command(
+    name = "kzg-bn254",
+    about = "KZG commitments over BN254 for EigenDA blobs"
+)]
+struct Cli {
+    #[command(flatten)]
+    srs: SrsArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct SrsArgs {
+    /// G1 points of the SRS
+    #[arg(long, global = true, default_value = "resources/g1.point")]
+    g1: String,
+
+    /// G2 points of the SRS; the power of 2 table is used if empty
+    #[arg(long, global = true, default_value = "")]
+    g2: String,
+
+    /// G2 points at the powers of 2 of tau
+    #[arg(long, global = true, default_value = "resources/g2.point.powerOf2")]
+    g2_power_of_2: String,
+
+    /// order of the SRS
+    #[arg(long, global = true, default_value_t = 268435456)]
+    srs_order: u32,
+
+    /// number of points to load from the SRS files
+    #[arg(long, global = true, default_value_t = 131072)]
+    points: u32,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Pads the payload in FILE into a blob and commits to it
+    Commit { file: String },
+    /// Pads the payload in FILE into a blob and proves its evaluation at INDEX
+    Prove {
+        file: String,
+        #[arg(long)]
+        index: u64,
+    },
+    /// Verifies a proof printed by `prove`, read from FILE
+    Verify { file: String },
+    /// Prints the size of the SRS and checks that its G1 and G2 points agree
+    InspectSrs,
+    /// Converts the first LENGTH G1 points to the Lagrange basis and writes
+    /// them to OUT, for `Kzg::load_lagrange_srs`
+    LagrangeSrs {
+        out: String,
+        #[arg(long)]
+        length: usize,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(output) => {
+            println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            let failed = output.get("valid") == Some(&Value::Bool(false))
+                || output.get("consistent") == Some(&Value::Bool(false));
+            if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        },
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn run(cli: Cli) -> Result<Value, String> {
+    let mut kzg = Kzg::setup(
+        &cli.srs.g1,
+        &cli.srs.g2,
+        &cli.srs.g2_power_of_2,
+        cli.srs.srs_order,
+        cli.srs.points,
+    )
+    .map_err(|err| err.to_string())?;
+
+    match cli.command {
+        Command::Commit { file } => {
+            let blob = read_blob(&file)?;
+            let polynomial = blob.to_polynomial().map_err(|err| err.to_string())?;
+            let commitment = kzg.commit(&polynomial).map_err(|err| err.to_string())?;
+            Ok(json!({
+                "commitment": g1_to_hex(&commitment),
+                "length": polynomial.get_length_of_padded_blob_as_fr_vector(),
+            }))
+        },
+        Command::Prove { file, index } => {
+            let blob = read_blob(&file)?;
+            let polynomial = blob.to_polynomial().map_err(|err| err.to_string())?;
+            kzg.data_setup_custom(1, blob.len() as u64)
+                .map_err(|err| err.to_string())?;
+            let commitment = kzg.commit(&polynomial).map_err(|err| err.to_string())?;
+            let proof = kzg
+                .compute_kzg_proof_with_roots_of_unity(&polynomial, index)
+                .map_err(|err| err.to_string())?;
+            let value = polynomial
+                .get_at_index(index as usize)
+                .ok_or("index is out of range of the polynomial")?;
+            let z = kzg
+                .get_nth_root_of_unity(index as usize)
+                .ok_or("index is out of range of the polynomial")?;
+            Ok(json!({
+                "commitment": g1_to_hex(&commitment),
+                "index": index,
+                "value": fr_to_hex(value),
+                "z": fr_to_hex(z),
+                "proof": g1_to_hex(&proof),
+            }))
+        },
+        Command::Verify { file } => {
+            let input: Value = serde_json::from_str(
+                &fs::read_to_string(&file).map_err(|err| format!("{}: {}", file, err))?,
+            )
+            .map_err(|err| err.to_string())?;
+            let commitment = g1_from_hex(field(&input, "commitment")?)?;
+            let proof = g1_from_hex(field(&input, "proof")?)?;
+            let value = fr_from_hex(field(&input, "value")?)?;
+            let z = fr_from_hex(field(&input, "z")?)?;
+            Ok(json!({ "valid": kzg.verify_kzg_proof(commitment, proof, value, z) }))
+        },
+        Command::InspectSrs => {
+            let g1 = kzg.get_g1_points();
+            let g2 = kzg.get_g2_points();
+            // the power of 2 table starts at [tau]G2, the full table at G2
+            let g2_tau = if g2.len() > 28 { g2.get(1) } else { g2.first() }
+                .ok_or("the SRS has no G2 points")?;
+            let g1_tau = g1.get(1).ok_or("the SRS has fewer than 2 G1 points")?;
+            let consistent = g1[0] == G1Affine::generator()
+                && Bn254::pairing(g1_tau, G2Affine::generator())
+                    == Bn254::pairing(G1Affine::generator(), g2_tau);
+            Ok(json!({
+                "g1_points": g1.len(),
+                "g2_points": g2.len(),
+                "srs_order": cli.srs.srs_order,
+                "g1_tau": g1_to_hex(g1_tau),
+                "g2_tau": format!("0x{}", hex::encode(g2_point_to_bytes_be(g2_tau))),
+                "consistent": consistent,
+            }))
+        },
+        Command::LagrangeSrs { out, length } => {
+            kzg.precompute_lagrange_srs(length)
+                .map_err(|err| err.to_string())?;
+            kzg.write_lagrange_srs(&out)
+                .map_err(|err| err.to_string())?;
+            Ok(json!({ "length": length, "path": out }))
+        },
+    }
+}
+
+fn read_blob(file: &str) -> Result<Blob, String> {
+    let payload = fs::read(file).map_err(|err| format!("{}: {}", file, err))?;
+    Ok(Blob::from_bytes_and_pad(&payload))
+}
+
+fn field<'a>(input: &'a Value, name: &str) -> Result<&'a str, String> {
+    input
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("missing string field `{}`", name))
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>, String> {
+    hex::decode(input.strip_prefix("0x").unwrap_or(input)).map_err(|err| err.to_string())
+}
+
+fn g1_to_hex(point: &G1Affine) -> String {
+    format!("0x{}", hex::encode(g1_to_bytes(point)))
+}
+
+fn g1_from_hex(input: &str) -> Result<G1Affine, String> {
+    g1_from_bytes(&decode_hex(input)?).map_err(|err| err.to_string())
+}
+
+fn fr_to_hex(value: &Fr) -> String {
+    format!("0x{}", hex::encode(fr_to_bytes(value)))
+}
+
+fn fr_from_hex(input: &str) -> Result<Fr, String> {
+    fr_from_bytes(&decode_hex(input)?).map_err(|err| err.to_string())
+}
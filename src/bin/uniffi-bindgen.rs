@@ -0,0 +1,7 @@
+//! Generates the Kotlin and Swift bindings of the `mobile` feature, e.g.
+//! `cargo run --features mobile --bin uniffi-bindgen -- generate --library
+//! target/release/librust_kzg_bn254.so --language kotlin --out-dir out`.
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -1,4 +1,9 @@
-use crate::{errors::BlobError, helpers, polynomial::Polynomial};
+use crate::{consts::BYTES_PER_FIELD_ELEMENT, errors::BlobError, helpers, polynomial::Polynomial};
+#[cfg(feature = "std")]
+use crate::{errors::KzgError, kzg::Kzg};
+#[cfg(feature = "std")]
+use ark_bn254::G1Affine;
+use ark_std::{string::ToString, vec::Vec};
 
 /// A blob which is Eigen DA spec aligned.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -11,19 +16,17 @@ pub struct Blob {
 impl Blob {
     /// Creates a new `Blob` from the given data.
     pub fn new(blob_data: Vec<u8>, is_padded: bool) -> Self {
-        let length_after_padding;
-        if is_padded {
-            length_after_padding = blob_data.len();
-        }
-        else {
-            length_after_padding = 0;
+        let length_after_padding = if is_padded { blob_data.len() } else { 0 };
+        Blob {
+            blob_data,
+            is_padded,
+            length_after_padding,
         }
-        Blob { blob_data, is_padded, length_after_padding }
     }
 
     /// Creates a new `Blob` from the given data.
     pub fn is_padded(&self) -> bool {
-        return self.is_padded;
+        self.is_padded
     }
 
     /// Creates a new `Blob` from the provided byte slice and pads it according
@@ -48,6 +51,17 @@ impl Blob {
         self.blob_data.len()
     }
 
+    /// Checks if the blob data is empty.
+    pub fn is_empty(&self) -> bool {
+        self.blob_data.is_empty()
+    }
+
+    /// Returns the length of the blob data after padding, or 0 if the blob
+    /// is not padded.
+    pub fn get_length_after_padding(&self) -> usize {
+        self.length_after_padding
+    }
+
     /// Pads the blob data in-place if it is not already padded.
     pub fn pad_data(&mut self) -> Result<(), BlobError> {
         if self.is_padded {
@@ -85,6 +99,62 @@ impl Blob {
     }
 }
 
+/// Builds a padded blob from data appended piece by piece, packing each full
+/// 31 bytes into a symbol as it arrives, so producers never hold the raw
+/// payload next to its padded copy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlobBuilder {
+    padded: Vec<u8>,
+    pending: Vec<u8>,
+}
+
+impl BlobBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `bytes` to the blob.
+    pub fn append(&mut self, bytes: &[u8]) {
+        let symbol_size = BYTES_PER_FIELD_ELEMENT - 1;
+        let mut bytes = bytes;
+        while !bytes.is_empty() {
+            let take = (symbol_size - self.pending.len()).min(bytes.len());
+            self.pending.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if self.pending.len() == symbol_size {
+                self.padded.push(0x00);
+                self.padded.append(&mut self.pending);
+            }
+        }
+    }
+
+    /// Returns the number of bytes appended so far.
+    pub fn len(&self) -> usize {
+        self.padded.len() / BYTES_PER_FIELD_ELEMENT * (BYTES_PER_FIELD_ELEMENT - 1)
+            + self.pending.len()
+    }
+
+    /// Checks if nothing has been appended.
+    pub fn is_empty(&self) -> bool {
+        self.padded.is_empty() && self.pending.is_empty()
+    }
+
+    /// Pads the remaining bytes and returns the blob, the same as
+    /// `Blob::from_bytes_and_pad` of everything appended, with its
+    /// commitment.
+    #[cfg(feature = "std")]
+    pub fn finalize(mut self, kzg: &Kzg) -> Result<(Blob, G1Affine), KzgError> {
+        if !self.pending.is_empty() {
+            self.padded.push(0x00);
+            self.padded.append(&mut self.pending);
+        }
+        let blob = Blob::new(self.padded, true);
+        let commitment = kzg.blob_to_kzg_commitment(&blob)?;
+        Ok((blob, commitment))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,7 +180,7 @@ mod tests {
             vec![0, 104, 105],
             "testing adding padding"
         );
-        assert_eq!(blob.is_padded(), true, "has to be padded");
+        assert!(blob.is_padded(), "has to be padded");
 
         blob.remove_padding().unwrap();
         assert_eq!(
@@ -118,7 +188,7 @@ mod tests {
             vec![104, 105],
             "testing removing padding"
         );
-        assert_eq!(blob.is_padded(), false, "cannot be padded");
+        assert!(!blob.is_padded(), "cannot be padded");
 
         let result: Vec<u8> = vec![
             0, 70, 111, 117, 114, 115, 99, 111, 114, 101, 32, 97, 110, 100, 32, 115, 101, 118, 101,
@@ -207,11 +277,11 @@ mod tests {
 
         blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
         assert_eq!(blob.get_blob_data(), result, "testing adding padding");
-        assert_eq!(blob.is_padded(), true, "has to be padded");
+        assert!(blob.is_padded(), "has to be padded");
         assert_eq!(blob.get_length_after_padding(), 1515);
 
         blob.remove_padding().unwrap();
-        assert_eq!(blob.is_padded(), false, "cannot be padded");
+        assert!(!blob.is_padded(), "cannot be padded");
         assert_eq!(
             blob.get_blob_data(),
             GETTYSBURG_ADDRESS_BYTES,
@@ -227,9 +297,40 @@ mod tests {
 
         blob_raw.pad_data().unwrap();
         assert_eq!(blob_raw, blob_from, "testing adding padding");
-        assert_eq!(blob_raw.is_padded(), true, "has to be padded");
-        assert_eq!(blob_from.is_padded(), true, "has to be padded");
+        assert!(blob_raw.is_padded(), "has to be padded");
+        assert!(blob_from.is_padded(), "has to be padded");
     }
 
-}
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_blob_builder() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
 
+        // appends of every size, crossing symbol boundaries, pack the same
+        // as padding the whole payload at once
+        let mut builder = BlobBuilder::new();
+        assert!(builder.is_empty());
+        let mut rest = GETTYSBURG_ADDRESS_BYTES;
+        for size in [1, 30, 31, 32, 0, 100, 7].iter().cycle() {
+            if rest.is_empty() {
+                break;
+            }
+            let (head, tail) = rest.split_at((*size).min(rest.len()));
+            builder.append(head);
+            rest = tail;
+            assert_eq!(builder.len(), GETTYSBURG_ADDRESS_BYTES.len() - rest.len());
+        }
+        let (blob, commitment) = builder.finalize(&kzg).unwrap();
+        let expected = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        assert_eq!(blob, expected);
+        assert_eq!(commitment, kzg.blob_to_kzg_commitment(&expected).unwrap());
+    }
+}
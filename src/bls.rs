@@ -0,0 +1,108 @@
+//! BLS signatures over BN254 as EigenDA operators sign batches: signatures in
+//! G1, public keys in G2, and messages hashed to G1 with the try-and-increment
+//! `hashToG1` of the EigenDA `BN254` contract library.
+
+use crate::kzg::Kzg;
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+
+/// Hashes a 32-byte message to G1 exactly as the contracts do: starting from
+/// `x = message mod p`, the first `x` for which `beta = x^3 + 3` has the
+/// square root `y = beta^((p + 1) / 4)` gives the point `(x, y)`.
+pub fn hash_to_g1(message: &[u8; 32]) -> G1Affine {
+    let mut exponent = Fq::MODULUS;
+    exponent.add_with_carry(&Fq::one().into_bigint());
+    exponent.div2();
+    exponent.div2();
+
+    let mut x = Fq::from_be_bytes_mod_order(message);
+    loop {
+        let beta = x * x * x + Fq::from(3u64);
+        let y = beta.pow(exponent);
+        if y.square() == beta {
+            return G1Affine::new_unchecked(x, y);
+        }
+        x += Fq::one();
+    }
+}
+
+/// Signs `message` with `secret_key`.
+pub fn sign(secret_key: Fr, message: &[u8; 32]) -> G1Affine {
+    (hash_to_g1(message) * secret_key).into_affine()
+}
+
+/// Returns the G2 public key of `secret_key`.
+pub fn public_key(secret_key: Fr) -> G2Affine {
+    (G2Affine::generator() * secret_key).into_affine()
+}
+
+/// Sums signatures over the same message into one aggregate signature.
+pub fn aggregate_signatures(signatures: &[G1Affine]) -> G1Affine {
+    signatures
+        .iter()
+        .fold(G1Projective::zero(), |acc, signature| acc + signature)
+        .into_affine()
+}
+
+/// Sums public keys into the key the aggregate signature verifies under.
+pub fn aggregate_public_keys(public_keys: &[G2Affine]) -> G2Affine {
+    public_keys
+        .iter()
+        .fold(G2Projective::zero(), |acc, key| acc + key)
+        .into_affine()
+}
+
+/// Checks `e(signature, G2) = e(H(message), public_key)`.
+pub fn verify_signature(message: &[u8; 32], signature: &G1Affine, public_key: &G2Affine) -> bool {
+    !public_key.is_zero()
+        && Kzg::pairings_verify(
+            *signature,
+            G2Affine::generator(),
+            hash_to_g1(message),
+            *public_key,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_to_g1() {
+        // x = 0 has no point, so the search moves on to x = 1, where
+        // beta = 4 and (p + 1) / 4 picks the root 2
+        let point = hash_to_g1(&[0u8; 32]);
+        assert_eq!(point, G1Affine::new(Fq::from(1u64), Fq::from(2u64)));
+        assert_eq!(point, G1Affine::generator());
+
+        let point = hash_to_g1(&[7u8; 32]);
+        assert!(point.is_on_curve());
+        assert!(point.is_in_correct_subgroup_assuming_on_curve());
+        assert_ne!(point, hash_to_g1(&[8u8; 32]));
+    }
+
+    #[test]
+    fn test_aggregate_signature() {
+        let message = [42u8; 32];
+        let keys: Vec<Fr> = (1..=4u64).map(|i| Fr::from(i * 1000 + 7)).collect();
+        let signatures: Vec<G1Affine> = keys.iter().map(|k| sign(*k, &message)).collect();
+        let public_keys: Vec<G2Affine> = keys.iter().map(|k| public_key(*k)).collect();
+        assert!(verify_signature(&message, &signatures[0], &public_keys[0]));
+
+        let signature = aggregate_signatures(&signatures);
+        let apk = aggregate_public_keys(&public_keys);
+        assert!(verify_signature(&message, &signature, &apk));
+        assert!(!verify_signature(&[43u8; 32], &signature, &apk));
+        assert!(!verify_signature(
+            &message,
+            &signature,
+            &aggregate_public_keys(&public_keys[1..])
+        ));
+        assert!(!verify_signature(
+            &message,
+            &G1Affine::zero(),
+            &G2Affine::zero()
+        ));
+    }
+}
@@ -0,0 +1,335 @@
+//! A builder for `Kzg` setups.
+//!
+//! `Kzg::setup` takes its SRS files, order and number of points as
+//! positional arguments. `KzgBuilder` names them instead, adds the options
+//! that otherwise take calls after setup, and checks how the parameters
+//! relate to each other before any points are loaded.
+
+use crate::{config::KzgConfig, errors::KzgError, kzg::Kzg};
+use std::io::Read;
+
+/// How thoroughly `KzgBuilder::build` checks the SRS it loads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SrsValidation {
+    /// Each point is checked as it is decompressed, as `Kzg::setup` does.
+    #[default]
+    Points,
+    /// The points are also checked to be powers of one tau with
+    /// `Kzg::verify_srs`.
+    Full,
+}
+
+/// Where the builder reads a group's points from.
+enum PointSource {
+    Path(String),
+    Reader(Box<dyn Read + Send>),
+}
+
+/// Builds a `Kzg` setup from named options, see `Kzg::builder`.
+#[derive(Default)]
+pub struct KzgBuilder {
+    g1: Option<PointSource>,
+    g2: Option<PointSource>,
+    g2_power_of_2: Option<PointSource>,
+    srs_order: Option<u32>,
+    srs_points_to_load: Option<u32>,
+    config: Option<KzgConfig>,
+    validation: SrsValidation,
+    lagrange_srs_length: Option<usize>,
+    lagrange_srs_path: Option<String>,
+    g1_sha256: Option<[u8; 32]>,
+    g2_sha256: Option<[u8; 32]>,
+}
+
+impl Kzg {
+    /// Returns a builder for a setup.
+    pub fn builder() -> KzgBuilder {
+        KzgBuilder::new()
+    }
+}
+
+impl KzgBuilder {
+    /// Creates a builder with no options set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the G1 points from the file at `path`, like `g1.point`.
+    pub fn with_g1_path(mut self, path: &str) -> Self {
+        self.g1 = Some(PointSource::Path(path.to_string()));
+        self
+    }
+
+    /// Reads consecutive G2 powers from the file at `path`, like `g2.point`.
+    pub fn with_g2_path(mut self, path: &str) -> Self {
+        self.g2 = Some(PointSource::Path(path.to_string()));
+        self
+    }
+
+    /// Reads a G2 power of 2 table from the file at `path`, like
+    /// `g2.point.powerOf2`. Used when no consecutive G2 powers are given.
+    pub fn with_g2_power_of_2_path(mut self, path: &str) -> Self {
+        self.g2_power_of_2 = Some(PointSource::Path(path.to_string()));
+        self
+    }
+
+    /// Reads the G1 points from `reader`, in the format of `g1.point`.
+    pub fn with_g1_reader(mut self, reader: impl Read + Send + 'static) -> Self {
+        self.g1 = Some(PointSource::Reader(Box::new(reader)));
+        self
+    }
+
+    /// Reads consecutive G2 powers from `reader`, in the format of
+    /// `g2.point`.
+    pub fn with_g2_reader(mut self, reader: impl Read + Send + 'static) -> Self {
+        self.g2 = Some(PointSource::Reader(Box::new(reader)));
+        self
+    }
+
+    /// Reads a G2 power of 2 table from `reader`, in the format of
+    /// `g2.point.powerOf2`.
+    pub fn with_g2_power_of_2_reader(mut self, reader: impl Read + Send + 'static) -> Self {
+        self.g2_power_of_2 = Some(PointSource::Reader(Box::new(reader)));
+        self
+    }
+
+    /// Sets the order of the SRS, the number of G1 points it has in total.
+    /// Required.
+    pub fn with_srs_order(mut self, srs_order: u32) -> Self {
+        self.srs_order = Some(srs_order);
+        self
+    }
+
+    /// Loads only the first `srs_points_to_load` points instead of the
+    /// whole SRS order.
+    pub fn with_points_to_load(mut self, srs_points_to_load: u32) -> Self {
+        self.srs_points_to_load = Some(srs_points_to_load);
+        self
+    }
+
+    /// Loads the SRS and runs later work with `config`, e.g. on a given
+    /// number of threads or a caller-owned pool.
+    pub fn with_config(mut self, config: KzgConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Loads the SRS and runs later work on `num_threads` threads.
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.config = Some(
+            self.config
+                .unwrap_or_default()
+                .with_num_threads(num_threads),
+        );
+        self
+    }
+
+    /// Sets how thoroughly the loaded SRS is checked.
+    pub fn with_validation(mut self, validation: SrsValidation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Precomputes the Lagrange SRS of `length` points after loading, see
+    /// `Kzg::precompute_lagrange_srs`.
+    pub fn with_lagrange_srs(mut self, length: usize) -> Self {
+        self.lagrange_srs_length = Some(length);
+        self
+    }
+
+    /// Loads a Lagrange SRS written by `Kzg::write_lagrange_srs` from `path`
+    /// after loading, instead of computing one.
+    pub fn with_lagrange_srs_path(mut self, path: &str) -> Self {
+        self.lagrange_srs_path = Some(path.to_string());
+        self
+    }
+
+    /// Checks the G1 file against its SHA-256 digest before loading, see
+    /// `Kzg::setup_with_digests`.
+    pub fn with_g1_sha256(mut self, digest: [u8; 32]) -> Self {
+        self.g1_sha256 = Some(digest);
+        self
+    }
+
+    /// Checks the G2 file in use against its SHA-256 digest before loading.
+    pub fn with_g2_sha256(mut self, digest: [u8; 32]) -> Self {
+        self.g2_sha256 = Some(digest);
+        self
+    }
+
+    /// Checks the options and loads the setup.
+    pub fn build(self) -> Result<Kzg, KzgError> {
+        let srs_order = self
+            .srs_order
+            .ok_or_else(|| invalid("the SRS order is not set"))?;
+        let srs_points_to_load = self.srs_points_to_load.unwrap_or(srs_order);
+        if srs_points_to_load == 0 || srs_points_to_load > srs_order {
+            return Err(invalid(&format!(
+                "{srs_points_to_load} points to load is not within the SRS order {srs_order}"
+            )));
+        }
+        if let Some(length) = self.lagrange_srs_length {
+            if !length.is_power_of_two() || length > srs_points_to_load as usize {
+                return Err(invalid(&format!(
+                    "a Lagrange SRS of {length} points is not a power of 2 within the \
+                     {srs_points_to_load} points to load"
+                )));
+            }
+        }
+        if self.lagrange_srs_length.is_some() && self.lagrange_srs_path.is_some() {
+            return Err(invalid(
+                "a Lagrange SRS is both precomputed and loaded from a path",
+            ));
+        }
+        let g1 = self.g1.ok_or_else(|| invalid("no G1 points are given"))?;
+        let (g2, g2_is_power_of_2) = match (self.g2, self.g2_power_of_2) {
+            (Some(g2), _) => (g2, false),
+            (None, Some(g2)) => (g2, true),
+            (None, None) => return Err(invalid("no G2 points are given")),
+        };
+        let (g1_sha256, g2_sha256) = (self.g1_sha256, self.g2_sha256);
+
+        let load: Box<dyn FnOnce() -> Result<Kzg, KzgError> + Send> = match (g1, g2) {
+            (PointSource::Path(g1), PointSource::Path(g2)) => Box::new(move || {
+                let (g2_path, g2_power_of_2_path) = if g2_is_power_of_2 {
+                    ("", g2.as_str())
+                } else {
+                    (g2.as_str(), "")
+                };
+                Kzg::setup_with_digests(
+                    &g1,
+                    g2_path,
+                    g2_power_of_2_path,
+                    srs_order,
+                    srs_points_to_load,
+                    g1_sha256,
+                    g2_sha256,
+                )
+            }),
+            (PointSource::Reader(_), PointSource::Reader(_))
+                if g1_sha256.is_some() || g2_sha256.is_some() =>
+            {
+                return Err(invalid("digests are only checked for SRS paths"))
+            },
+            (PointSource::Reader(g1), PointSource::Reader(g2)) => Box::new(move || {
+                Kzg::setup_from_reader(g1, g2, g2_is_power_of_2, srs_order, srs_points_to_load)
+            }),
+            _ => {
+                return Err(invalid(
+                    "the G1 and G2 points must both come from paths or both from readers",
+                ))
+            },
+        };
+
+        let config = self.config.unwrap_or_default();
+        let validation = self.validation;
+        let lagrange_srs_length = self.lagrange_srs_length;
+        let lagrange_srs_path = self.lagrange_srs_path;
+        let mut kzg = config.install(move || {
+            let mut kzg = load()?;
+            if validation == SrsValidation::Full {
+                kzg.verify_srs()?;
+            }
+            if let Some(length) = lagrange_srs_length {
+                kzg.precompute_lagrange_srs(length)?;
+            }
+            if let Some(path) = lagrange_srs_path {
+                kzg.load_lagrange_srs(&path)?;
+            }
+            Ok::<_, KzgError>(kzg)
+        })??;
+        kzg.set_config(config);
+        Ok(kzg)
+    }
+}
+
+fn invalid(message: &str) -> KzgError {
+    KzgError::GenericError(format!("invalid setup options: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn test_kzg_builder() {
+        let kzg = Kzg::builder()
+            .with_g1_path("src/test-files/g1.point")
+            .with_g2_path("src/test-files/g2.point")
+            .with_srs_order(3000)
+            .with_points_to_load(2048)
+            .with_num_threads(2)
+            .with_validation(SrsValidation::Full)
+            .with_lagrange_srs(1024)
+            .build()
+            .unwrap();
+        let expected = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            2048,
+        )
+        .unwrap();
+        assert_eq!(kzg.get_g1_points(), expected.get_g1_points());
+        assert_eq!(kzg.get_g2_points(), expected.get_g2_points());
+        assert_eq!(kzg.get_lagrange_srs_length(), 1024);
+        assert_eq!(kzg.get_config().get_num_threads(), 2);
+
+        let path =
+            std::env::temp_dir().join(format!("builder-lagrange-srs-{}", std::process::id()));
+        let path = path.to_str().unwrap();
+        kzg.write_lagrange_srs(path).unwrap();
+        let loaded = Kzg::builder()
+            .with_g1_path("src/test-files/g1.point")
+            .with_g2_path("src/test-files/g2.point")
+            .with_srs_order(3000)
+            .with_points_to_load(2048)
+            .with_lagrange_srs_path(path)
+            .build()
+            .unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(loaded.get_lagrange_srs_length(), 1024);
+
+        let from_readers = Kzg::builder()
+            .with_g1_reader(File::open("src/test-files/g1.point").unwrap())
+            .with_g2_power_of_2_reader(File::open("src/test-files/g2.point.powerOf2").unwrap())
+            .with_srs_order(3000)
+            .build()
+            .unwrap();
+        assert_eq!(from_readers.get_g1_points().len(), 3000);
+
+        // parameters that don't fit together fail before loading
+        let base = || {
+            Kzg::builder()
+                .with_g1_path("src/test-files/missing.point")
+                .with_g2_path("src/test-files/missing.point")
+                .with_srs_order(3000)
+        };
+        for builder in [
+            Kzg::builder().with_g1_path("src/test-files/g1.point"),
+            base().with_points_to_load(3001),
+            base().with_points_to_load(0),
+            base().with_lagrange_srs(1000),
+            base().with_points_to_load(512).with_lagrange_srs(1024),
+            base()
+                .with_lagrange_srs(1024)
+                .with_lagrange_srs_path("src/test-files/missing.point"),
+            Kzg::builder()
+                .with_g1_path("src/test-files/g1.point")
+                .with_srs_order(3000),
+            base().with_g2_reader(File::open("src/test-files/g2.point").unwrap()),
+            Kzg::builder()
+                .with_g1_reader(File::open("src/test-files/g1.point").unwrap())
+                .with_g2_reader(File::open("src/test-files/g2.point").unwrap())
+                .with_srs_order(3000)
+                .with_g1_sha256([0; 32]),
+        ] {
+            let result = builder.build();
+            assert!(
+                matches!(result, Err(KzgError::GenericError(message)) if message.starts_with("invalid setup options"))
+            );
+        }
+    }
+}
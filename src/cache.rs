@@ -0,0 +1,244 @@
+use crate::{blob::Blob, errors::KzgError, kzg::Kzg};
+use ark_bn254::G1Affine;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+// blob digest and SRS fingerprint, plus the domain size and index for proofs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Commitment([u8; 32], [u8; 32]),
+    Proof([u8; 32], [u8; 32], usize, u64),
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    entries: HashMap<CacheKey, (G1Affine, u64)>,
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+}
+
+impl LruState {
+    fn get(&mut self, key: &CacheKey) -> Option<G1Affine> {
+        let tick = self.tick + 1;
+        let (value, last_used) = self.entries.get_mut(key)?;
+        self.recency.remove(last_used);
+        self.recency.insert(tick, *key);
+        *last_used = tick;
+        self.tick = tick;
+        Some(*value)
+    }
+
+    fn insert(&mut self, key: CacheKey, value: G1Affine, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((_, last_used)) = self.entries.insert(key, (value, self.tick)) {
+            self.recency.remove(&last_used);
+        }
+        self.recency.insert(self.tick, key);
+        while self.entries.len() > capacity {
+            let (_, oldest) = self.recency.pop_first().unwrap();
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+/// A bounded LRU cache of commitments and proofs keyed by the SHA-256 digest
+/// of the blob data and a fingerprint of the SRS they were computed with.
+///
+/// Dispersal clients that retry often recompute the commitment for the same
+/// blob many times; routing those calls through the cache makes repeats a
+/// hash plus a lookup. Proofs are additionally keyed by the size of the
+/// domain of roots of unity they open over and the index of the root, so a
+/// cache shared between `Kzg` instances loaded with different SRS files
+/// never returns a mismatched commitment or proof.
+#[derive(Debug)]
+pub struct CommitmentCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl CommitmentCache {
+    /// Creates a cache holding at most `capacity` commitments and proofs.
+    pub fn new(capacity: usize) -> Self {
+        CommitmentCache {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// Returns the maximum number of cached entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Checks if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all cached entries.
+    pub fn clear(&self) {
+        *self.state.lock().unwrap() = LruState::default();
+    }
+
+    /// Returns the commitment to `blob`, computing it with `kzg` on a miss.
+    pub fn blob_to_kzg_commitment(&self, kzg: &Kzg, blob: &Blob) -> Result<G1Affine, KzgError> {
+        let key = CacheKey::Commitment(Self::blob_digest(blob), kzg.srs_fingerprint());
+        self.get_or_insert_with(key, || kzg.blob_to_kzg_commitment(blob))
+    }
+
+    /// Returns the proof for `blob` at `index` over the roots of unity of
+    /// its length, computing it on a miss.
+    pub fn compute_kzg_proof(
+        &self,
+        kzg: &Kzg,
+        blob: &Blob,
+        index: u64,
+    ) -> Result<G1Affine, KzgError> {
+        let polynomial = blob
+            .to_polynomial()
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        let key = CacheKey::Proof(
+            Self::blob_digest(blob),
+            kzg.srs_fingerprint(),
+            polynomial.len(),
+            index,
+        );
+        self.get_or_insert_with(key, || {
+            kzg.compute_kzg_proof_with_roots_of_unity(&polynomial, index)
+        })
+    }
+
+    fn get_or_insert_with<F>(&self, key: CacheKey, compute: F) -> Result<G1Affine, KzgError>
+    where
+        F: FnOnce() -> Result<G1Affine, KzgError>,
+    {
+        if let Some(value) = self.state.lock().unwrap().get(&key) {
+            return Ok(value);
+        }
+        // compute outside the lock so concurrent misses don't serialize
+        let value = compute()?;
+        self.state.lock().unwrap().insert(key, value, self.capacity);
+        Ok(value)
+    }
+
+    fn blob_digest(blob: &Blob) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update([blob.is_padded() as u8]);
+        hasher.update(blob.get_blob_data());
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    fn setup_kzg() -> Kzg {
+        Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cache_returns_same_commitments_and_proofs() {
+        let kzg = setup_kzg();
+        let cache = CommitmentCache::new(8);
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+
+        let commitment = cache.blob_to_kzg_commitment(&kzg, &blob).unwrap();
+        assert_eq!(commitment, kzg.blob_to_kzg_commitment(&blob).unwrap());
+        assert_eq!(
+            cache.blob_to_kzg_commitment(&kzg, &blob).unwrap(),
+            commitment
+        );
+        assert_eq!(cache.len(), 1);
+
+        let proof = cache.compute_kzg_proof(&kzg, &blob, 5).unwrap();
+        let polynomial = blob.to_polynomial().unwrap();
+        assert_eq!(
+            proof,
+            kzg.compute_kzg_proof_with_roots_of_unity(&polynomial, 5)
+                .unwrap()
+        );
+        assert_eq!(cache.compute_kzg_proof(&kzg, &blob, 5).unwrap(), proof);
+        assert_eq!(cache.len(), 2);
+
+        assert!(cache.compute_kzg_proof(&kzg, &blob, u64::MAX).is_err());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let kzg = setup_kzg();
+        let cache = CommitmentCache::new(2);
+        let blobs: Vec<Blob> = ["a", "b", "c"]
+            .iter()
+            .map(|s| Blob::from_bytes_and_pad(s.as_bytes()))
+            .collect();
+
+        let _ = cache.blob_to_kzg_commitment(&kzg, &blobs[0]).unwrap();
+        let _ = cache.blob_to_kzg_commitment(&kzg, &blobs[1]).unwrap();
+        // touch "a" so "b" becomes the least recently used entry
+        let _ = cache.blob_to_kzg_commitment(&kzg, &blobs[0]).unwrap();
+        let _ = cache.blob_to_kzg_commitment(&kzg, &blobs[2]).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        let mut state = cache.state.lock().unwrap();
+        for (blob, cached) in blobs.iter().zip([true, false, true]) {
+            let key =
+                CacheKey::Commitment(CommitmentCache::blob_digest(blob), kzg.srs_fingerprint());
+            assert_eq!(state.get(&key).is_some(), cached);
+        }
+    }
+
+    #[test]
+    fn test_cache_keys_by_srs() {
+        let cache = CommitmentCache::new(8);
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        for seed in [b"one", b"two"] {
+            let kzg = Kzg::insecure_setup_from_seed(seed, 1024).unwrap();
+            assert_eq!(
+                cache.blob_to_kzg_commitment(&kzg, &blob).unwrap(),
+                kzg.blob_to_kzg_commitment(&blob).unwrap()
+            );
+            let polynomial = blob.to_polynomial().unwrap();
+            assert_eq!(
+                cache.compute_kzg_proof(&kzg, &blob, 3).unwrap(),
+                kzg.compute_kzg_proof_with_roots_of_unity(&polynomial, 3)
+                    .unwrap()
+            );
+        }
+        assert_eq!(cache.len(), 4);
+    }
+
+    #[test]
+    fn test_zero_capacity_cache_stores_nothing() {
+        let kzg = setup_kzg();
+        let cache = CommitmentCache::new(0);
+        let blob = Blob::from_bytes_and_pad("hi".as_bytes());
+        assert_eq!(
+            cache.blob_to_kzg_commitment(&kzg, &blob).unwrap(),
+            kzg.blob_to_kzg_commitment(&blob).unwrap()
+        );
+        assert!(cache.is_empty());
+    }
+}
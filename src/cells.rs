@@ -0,0 +1,264 @@
+//! Cells of a single extended blob, after the PeerDAS cell API of
+//! c-kzg-4844 but over BN254.
+//!
+//! A blob is extended by a factor of 2 and split into `CELLS_PER_EXT_BLOB`
+//! cells, each the evaluations over one coset of the extended domain, in the
+//! order of the EigenDA assignment rules. A cell's proof opens all of its
+//! evaluations against the blob commitment at once, so sampling nodes can
+//! check the cells they hold, and any half of the cells recovers the rest.
+//! Cells are `encoder::Chunk`s whose index is the cell index.
+
+use crate::{
+    blob::Blob,
+    consts::{BYTES_PER_FIELD_ELEMENT, CELL_BATCH_CHALLENGE_DOMAIN},
+    encoder::{Chunk, Encoder, EncodingParams},
+    errors::{EncoderError, KzgError},
+    kzg::Kzg,
+    polynomial::Polynomial,
+    transcript::Transcript,
+};
+use ark_bn254::{Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, Zero};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+
+/// Number of cells an extended blob is split into.
+pub const CELLS_PER_EXT_BLOB: usize = 128;
+
+/// Extends the padded `blob` and splits it into its cells, ordered by cell
+/// index.
+pub fn compute_cells(blob: &Blob) -> Result<Vec<Chunk>, EncoderError> {
+    encoder().encode(blob)
+}
+
+/// Returns the cells of the padded `blob` with the proof of each, computed
+/// together with FK20.
+pub fn compute_cells_and_kzg_proofs(
+    kzg: &Kzg,
+    blob: &Blob,
+) -> Result<(Vec<Chunk>, Vec<G1Affine>), EncoderError> {
+    let polynomial = blob
+        .to_polynomial()
+        .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+    cells_and_proofs(kzg, &polynomial)
+}
+
+/// Checks every cell against the commitment of its blob, given per cell, with
+/// one pairing check. The cells are folded with powers of a challenge hashed
+/// from all of them, and must all have the same length, i.e. come from blobs
+/// of the same extended size.
+pub fn verify_cell_kzg_proof_batch(
+    kzg: &Kzg,
+    commitments: &[G1Affine],
+    cells: &[Chunk],
+    proofs: &[G1Affine],
+) -> Result<bool, EncoderError> {
+    if commitments.len() != cells.len() || proofs.len() != cells.len() {
+        return Err(EncoderError::InvalidParametersError(
+            "commitments, cells and proofs differ in number".to_string(),
+        ));
+    }
+    if cells.is_empty() {
+        return Ok(true);
+    }
+    let params = params_for_cells(cells)?;
+    let cell_length = params.get_chunk_length();
+    let r = batch_challenge(kzg, commitments, cells, proofs)?;
+
+    // each cell holds if e(C - [I(tau)] + h^L * proof, G2) = e(proof, [tau^L]),
+    // for I its interpolation polynomial and h its coset's shift
+    let mut powers = Vec::with_capacity(cells.len());
+    let mut shifted_powers = Vec::with_capacity(cells.len());
+    let mut interpolation = vec![Fr::zero(); cell_length];
+    let mut power = Fr::one();
+    for cell in cells {
+        let shift = params.coset_shift(cell.get_index());
+        let coset = GeneralEvaluationDomain::<Fr>::new(cell_length)
+            .and_then(|domain| domain.get_coset(shift))
+            .ok_or_else(|| {
+                EncoderError::FftError("failed to construct coset for IFFT".to_string())
+            })?;
+        for (sum, coefficient) in interpolation
+            .iter_mut()
+            .zip(coset.ifft(cell.get_evaluations()))
+        {
+            *sum += power * coefficient;
+        }
+        powers.push(power);
+        shifted_powers.push(power * shift.pow([cell_length as u64]));
+        power *= r;
+    }
+    let msm = |bases: &[G1Affine], scalars: &[Fr]| {
+        G1Projective::msm(bases, scalars).map_err(|_| {
+            EncoderError::GenericError("bases and scalars differ in length".to_string())
+        })
+    };
+    let interpolation_g1 = kzg
+        .commit_coefficients(&interpolation)
+        .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+    let lhs = msm(commitments, &powers)? - interpolation_g1 + msm(proofs, &shifted_powers)?;
+    let rhs = msm(proofs, &powers)?;
+    let tau_power = kzg
+        .g2_tau_power_of_2(cell_length.trailing_zeros() as usize)
+        .ok_or_else(|| {
+            EncoderError::GenericError(
+                "G2 table does not hold the power of tau needed for the cells".to_string(),
+            )
+        })?;
+
+    Ok(Kzg::pairings_verify(
+        lhs.into_affine(),
+        G2Affine::generator(),
+        rhs.into_affine(),
+        tau_power,
+    ))
+}
+
+/// Recovers every cell of an extended blob and its proof from at least half
+/// of the cells, in any order.
+pub fn recover_cells_and_kzg_proofs(
+    kzg: &Kzg,
+    cells: &[Chunk],
+) -> Result<(Vec<Chunk>, Vec<G1Affine>), EncoderError> {
+    if cells.is_empty() {
+        return Err(EncoderError::InvalidParametersError(
+            "not enough cells to recover the blob".to_string(),
+        ));
+    }
+    let length = params_for_cells(cells)?.num_evaluations() / 2;
+    let polynomial = encoder().decode(cells, length * BYTES_PER_FIELD_ELEMENT)?;
+    cells_and_proofs(kzg, &polynomial)
+}
+
+fn encoder() -> Encoder {
+    Encoder::new(CELLS_PER_EXT_BLOB, 2).expect("cell encoding parameters are valid")
+}
+
+fn cells_and_proofs(
+    kzg: &Kzg,
+    polynomial: &Polynomial,
+) -> Result<(Vec<Chunk>, Vec<G1Affine>), EncoderError> {
+    let encoder = encoder();
+    Ok((
+        encoder.encode_polynomial(polynomial)?,
+        encoder.prove_chunks(kzg, polynomial)?,
+    ))
+}
+
+/// returns the layout of the extended blob the cells come from, which only
+/// depends on the cell length
+fn params_for_cells(cells: &[Chunk]) -> Result<EncodingParams, EncoderError> {
+    let cell_length = cells[0].len();
+    if !cell_length.is_power_of_two()
+        || cells
+            .iter()
+            .any(|cell| cell.len() != cell_length || cell.get_index() >= CELLS_PER_EXT_BLOB)
+    {
+        return Err(EncoderError::InvalidParametersError(
+            "cells do not match the encoding parameters".to_string(),
+        ));
+    }
+    let params = encoder().params(cell_length * CELLS_PER_EXT_BLOB / 2)?;
+    if params.get_chunk_length() != cell_length {
+        return Err(EncoderError::InvalidParametersError(
+            "cells do not match the encoding parameters".to_string(),
+        ));
+    }
+    Ok(params)
+}
+
+/// hashes every commitment, cell and proof into the challenge the cells are
+/// folded with
+fn batch_challenge(
+    kzg: &Kzg,
+    commitments: &[G1Affine],
+    cells: &[Chunk],
+    proofs: &[G1Affine],
+) -> Result<Fr, EncoderError> {
+    let to_encoder_error = |err: KzgError| EncoderError::GenericError(err.to_string());
+    let mut transcript = Transcript::new(
+        kzg.get_config().get_challenge_hash(),
+        CELL_BATCH_CHALLENGE_DOMAIN,
+    );
+    transcript.append_u64(cells.len() as u64);
+    for ((commitment, cell), proof) in commitments.iter().zip(cells).zip(proofs) {
+        transcript.append_g1(commitment).map_err(to_encoder_error)?;
+        transcript.append_u64(cell.get_index() as u64);
+        for evaluation in cell.get_evaluations() {
+            transcript.append_fr(evaluation);
+        }
+        transcript.append_g1(proof).map_err(to_encoder_error)?;
+    }
+    transcript.challenge().map_err(to_encoder_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    #[test]
+    fn test_cells() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let other = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..1200]);
+        let commitment = kzg.blob_to_kzg_commitment(&blob).unwrap();
+        let other_commitment = kzg.blob_to_kzg_commitment(&other).unwrap();
+
+        let (cells, proofs) = compute_cells_and_kzg_proofs(&kzg, &blob).unwrap();
+        assert_eq!(cells.len(), CELLS_PER_EXT_BLOB);
+        assert_eq!(cells, compute_cells(&blob).unwrap());
+        let (other_cells, other_proofs) = compute_cells_and_kzg_proofs(&kzg, &other).unwrap();
+
+        // cells of two blobs checked together
+        let mut commitments = vec![commitment; 10];
+        commitments.extend([other_commitment; 10]);
+        let batch: Vec<Chunk> = cells[..10]
+            .iter()
+            .chain(&other_cells[50..60])
+            .cloned()
+            .collect();
+        let mut batch_proofs = proofs[..10].to_vec();
+        batch_proofs.extend_from_slice(&other_proofs[50..60]);
+        assert!(verify_cell_kzg_proof_batch(&kzg, &commitments, &batch, &batch_proofs).unwrap());
+        assert!(verify_cell_kzg_proof_batch(&kzg, &[], &[], &[]).unwrap());
+
+        // a tampered cell, a swapped proof or the wrong commitment fail
+        let mut tampered = batch.clone();
+        let mut evaluations = tampered[3].get_evaluations().to_vec();
+        evaluations[0] += Fr::one();
+        tampered[3] = Chunk::new(3, evaluations);
+        assert!(
+            !verify_cell_kzg_proof_batch(&kzg, &commitments, &tampered, &batch_proofs).unwrap()
+        );
+        let mut swapped = batch_proofs.clone();
+        swapped.swap(0, 1);
+        assert!(!verify_cell_kzg_proof_batch(&kzg, &commitments, &batch, &swapped).unwrap());
+        commitments[15] = commitment;
+        assert!(!verify_cell_kzg_proof_batch(&kzg, &commitments, &batch, &batch_proofs).unwrap());
+        assert!(
+            verify_cell_kzg_proof_batch(&kzg, &commitments[1..], &batch, &batch_proofs).is_err()
+        );
+
+        // any half of the cells recovers all of them
+        let half: Vec<Chunk> = cells.iter().skip(1).step_by(2).cloned().collect();
+        assert_eq!(
+            recover_cells_and_kzg_proofs(&kzg, &half).unwrap(),
+            (cells.clone(), proofs.clone())
+        );
+        let first_half = other_cells[..CELLS_PER_EXT_BLOB / 2].to_vec();
+        assert_eq!(
+            recover_cells_and_kzg_proofs(&kzg, &first_half).unwrap(),
+            (other_cells, other_proofs)
+        );
+        assert!(recover_cells_and_kzg_proofs(&kzg, &half[1..]).is_err());
+        assert!(recover_cells_and_kzg_proofs(&kzg, &[]).is_err());
+    }
+}
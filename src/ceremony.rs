@@ -0,0 +1,249 @@
+//! A powers-of-tau ceremony over BN254.
+//!
+//! Each participant multiplies the `i`-th powers of the current SRS by `x^i`
+//! for a secret `x` derived from their entropy, so the SRS secret becomes
+//! `tau * x`, and publishes `[x]G2` as proof of the update. The final secret
+//! is unknown as long as one participant discarded their `x`. Anyone can
+//! check a transcript starting from the trivial SRS, where every point is a
+//! generator.
+
+use crate::{consts::CEREMONY_SECRET_DOMAIN, errors::KzgError, kzg::Kzg};
+use ark_bn254::{Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, PrimeField, UniformRand, Zero};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// Powers of a secret tau in G1 and G2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Srs {
+    g1_powers: Vec<G1Affine>,
+    g2_powers: Vec<G2Affine>,
+}
+
+impl Srs {
+    /// Wraps `[tau^i]G1` and `[tau^i]G2`. Needs at least 2 G1 and 2 G2 powers.
+    pub fn new(g1_powers: Vec<G1Affine>, g2_powers: Vec<G2Affine>) -> Result<Self, KzgError> {
+        if g1_powers.len() < 2 || g2_powers.len() < 2 {
+            return Err(KzgError::GenericError(
+                "an SRS needs at least 2 G1 and 2 G2 powers".to_string(),
+            ));
+        }
+        Ok(Srs {
+            g1_powers,
+            g2_powers,
+        })
+    }
+
+    /// Returns the SRS with tau = 1 that a ceremony starts from.
+    pub fn trivial(num_g1_powers: usize, num_g2_powers: usize) -> Result<Self, KzgError> {
+        Srs::new(
+            vec![G1Affine::generator(); num_g1_powers],
+            vec![G2Affine::generator(); num_g2_powers],
+        )
+    }
+
+    /// Returns the G1 powers of tau.
+    pub fn get_g1_powers(&self) -> &[G1Affine] {
+        &self.g1_powers
+    }
+
+    /// Returns the G2 powers of tau.
+    pub fn get_g2_powers(&self) -> &[G2Affine] {
+        &self.g2_powers
+    }
+
+    /// checks both sets of points are consecutive powers of one tau, by
+    /// comparing random combinations of each set against itself shifted by
+    /// one power
+    fn is_well_formed(&self) -> bool {
+        if self.g1_powers[0] != G1Affine::generator() || self.g2_powers[0] != G2Affine::generator()
+        {
+            return false;
+        }
+        let mut rng = rand::thread_rng();
+        let g1_weights: Vec<Fr> = (1..self.g1_powers.len())
+            .map(|_| Fr::rand(&mut rng))
+            .collect();
+        let g2_weights: Vec<Fr> = (1..self.g2_powers.len())
+            .map(|_| Fr::rand(&mut rng))
+            .collect();
+        let n1 = self.g1_powers.len();
+        let n2 = self.g2_powers.len();
+        let (Ok(g1_low), Ok(g1_high), Ok(g2_low), Ok(g2_high)) = (
+            G1Projective::msm(&self.g1_powers[..n1 - 1], &g1_weights),
+            G1Projective::msm(&self.g1_powers[1..], &g1_weights),
+            G2Projective::msm(&self.g2_powers[..n2 - 1], &g2_weights),
+            G2Projective::msm(&self.g2_powers[1..], &g2_weights),
+        ) else {
+            return false;
+        };
+
+        // e(sum r_i [tau^i]G1, [tau]G2) = e(sum r_i [tau^(i+1)]G1, G2), and
+        // the same for G2 with [tau]G1
+        Kzg::pairings_verify(
+            g1_low.into_affine(),
+            self.g2_powers[1],
+            g1_high.into_affine(),
+            G2Affine::generator(),
+        ) && Kzg::pairings_verify(
+            self.g1_powers[1],
+            g2_low.into_affine(),
+            G1Affine::generator(),
+            g2_high.into_affine(),
+        )
+    }
+}
+
+/// An SRS after one participant's update, with their proof of the update.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contribution {
+    srs: Srs,
+    public_key: G2Affine,
+}
+
+impl Contribution {
+    /// Pairs the updated `srs` with the participant's `[x]G2`.
+    pub fn new(srs: Srs, public_key: G2Affine) -> Self {
+        Contribution { srs, public_key }
+    }
+
+    /// Returns the updated SRS.
+    pub fn get_srs(&self) -> &Srs {
+        &self.srs
+    }
+
+    /// Returns `[x]G2` for the participant's secret `x`.
+    pub fn get_public_key(&self) -> G2Affine {
+        self.public_key
+    }
+}
+
+/// Updates `srs` with a secret derived from `entropy`, which should be at
+/// least 32 bytes of fresh randomness and is not kept.
+pub fn contribute(srs: &Srs, entropy: &[u8]) -> Result<Contribution, KzgError> {
+    if entropy.len() < 32 {
+        return Err(KzgError::GenericError(
+            "entropy must be at least 32 bytes".to_string(),
+        ));
+    }
+    let secret = Fr::from_be_bytes_mod_order(
+        &Sha256::new()
+            .chain_update(CEREMONY_SECRET_DOMAIN)
+            .chain_update(entropy)
+            .finalize(),
+    );
+    if secret.is_zero() {
+        return Err(KzgError::GenericError(
+            "entropy derives a zero secret".to_string(),
+        ));
+    }
+
+    let powers = |n: usize| {
+        let mut powers = Vec::with_capacity(n);
+        let mut power = Fr::ONE;
+        for _ in 0..n {
+            powers.push(power);
+            power *= secret;
+        }
+        powers
+    };
+    let g1: Vec<G1Projective> = srs
+        .g1_powers
+        .par_iter()
+        .zip(powers(srs.g1_powers.len()))
+        .map(|(point, power)| *point * power)
+        .collect();
+    let g2: Vec<G2Projective> = srs
+        .g2_powers
+        .par_iter()
+        .zip(powers(srs.g2_powers.len()))
+        .map(|(point, power)| *point * power)
+        .collect();
+
+    Ok(Contribution::new(
+        Srs::new(
+            G1Projective::normalize_batch(&g1),
+            G2Projective::normalize_batch(&g2),
+        )?,
+        (G2Affine::generator() * secret).into_affine(),
+    ))
+}
+
+/// Checks `contribution` is a well-formed update of `previous`: the new SRS
+/// holds powers of one tau, and that tau is the previous one times the secret
+/// behind the public key.
+pub fn verify_contribution(previous: &Srs, contribution: &Contribution) -> bool {
+    let srs = &contribution.srs;
+    srs.g1_powers.len() == previous.g1_powers.len()
+        && srs.g2_powers.len() == previous.g2_powers.len()
+        && !contribution.public_key.is_zero()
+        && Kzg::pairings_verify(
+            srs.g1_powers[1],
+            G2Affine::generator(),
+            previous.g1_powers[1],
+            contribution.public_key,
+        )
+        && srs.is_well_formed()
+}
+
+/// Checks every contribution of a ceremony in order, starting from the
+/// trivial SRS of the first contribution's size.
+pub fn verify_transcript(contributions: &[Contribution]) -> Result<bool, KzgError> {
+    let first = contributions
+        .first()
+        .ok_or_else(|| KzgError::GenericError("transcript has no contributions".to_string()))?;
+    let mut previous = Srs::trivial(first.srs.g1_powers.len(), first.srs.g2_powers.len())?;
+    for contribution in contributions {
+        if !verify_contribution(&previous, contribution) {
+            return Ok(false);
+        }
+        previous = contribution.srs.clone();
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ceremony_transcript() {
+        let start = Srs::trivial(16, 4).unwrap();
+        let first = contribute(&start, &[1u8; 32]).unwrap();
+        let second = contribute(first.get_srs(), &[2u8; 32]).unwrap();
+        assert!(verify_contribution(&start, &first));
+        assert!(verify_contribution(first.get_srs(), &second));
+        assert!(verify_transcript(&[first.clone(), second.clone()]).unwrap());
+        assert!(verify_transcript(&[]).is_err());
+        assert!(contribute(&start, &[1u8; 31]).is_err());
+
+        // the final tau is the product of the secrets
+        let secret = |entropy: &[u8]| {
+            Fr::from_be_bytes_mod_order(
+                &Sha256::new()
+                    .chain_update(CEREMONY_SECRET_DOMAIN)
+                    .chain_update(entropy)
+                    .finalize(),
+            )
+        };
+        let tau = secret(&[1u8; 32]) * secret(&[2u8; 32]);
+        assert_eq!(
+            second.get_srs().get_g1_powers()[5],
+            (G1Affine::generator() * tau.pow([5])).into_affine()
+        );
+
+        // skipping a contribution, a public key not matching the update, and
+        // a point that is not the next power all fail
+        assert!(!verify_transcript(&[second.clone()]).unwrap());
+        let wrong_key = Contribution::new(second.get_srs().clone(), first.get_public_key());
+        assert!(!verify_contribution(first.get_srs(), &wrong_key));
+        let mut g1_powers = second.get_srs().get_g1_powers().to_vec();
+        g1_powers[7] = g1_powers[8];
+        let tampered = Contribution::new(
+            Srs::new(g1_powers, second.get_srs().get_g2_powers().to_vec()).unwrap(),
+            second.get_public_key(),
+        );
+        assert!(!verify_transcript(&[first, tampered]).unwrap());
+    }
+}
@@ -0,0 +1,491 @@
+use crate::{
+    bls,
+    consts::CERT_CHALLENGE_DOMAIN,
+    encoder::Encoder,
+    encoding::g1_to_bytes,
+    errors::CertError,
+    helpers::g2_point_to_bytes_be,
+    kzg::Kzg,
+    merkle::{g1_to_abi_words, MerkleProof},
+    retrieve::Frame,
+};
+use ark_bn254::{Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::PrimeField;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// The operators' attestation that a blob is part of a signed batch: the
+/// batch's Merkle root, the blob header's inclusion proof under it, and the
+/// aggregate BLS signature over the root with the aggregate public key of the
+/// signers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchAttestation {
+    batch_root: [u8; 32],
+    inclusion_proof: MerkleProof,
+    signature: G1Affine,
+    apk: G2Affine,
+}
+
+impl BatchAttestation {
+    /// Creates an attestation of `batch_root` signed with `signature` under
+    /// the aggregate public key `apk`.
+    pub fn new(
+        batch_root: [u8; 32],
+        inclusion_proof: MerkleProof,
+        signature: G1Affine,
+        apk: G2Affine,
+    ) -> Self {
+        BatchAttestation {
+            batch_root,
+            inclusion_proof,
+            signature,
+            apk,
+        }
+    }
+
+    /// Returns the Merkle root of the batch's blob headers.
+    pub fn get_batch_root(&self) -> [u8; 32] {
+        self.batch_root
+    }
+
+    /// Returns the proof of the blob header's inclusion in the batch.
+    pub fn get_inclusion_proof(&self) -> &MerkleProof {
+        &self.inclusion_proof
+    }
+
+    /// Returns the aggregate signature over the batch root.
+    pub fn get_signature(&self) -> G1Affine {
+        self.signature
+    }
+
+    /// Returns the aggregate G2 public key of the signers.
+    pub fn get_apk(&self) -> G2Affine {
+        self.apk
+    }
+}
+
+/// The commitments a DA batch carries for one blob, with optionally some of
+/// its chunks to spot-check and the batch attestation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlobCert {
+    commitment: G1Affine,
+    length_commitment: G2Affine,
+    length_proof: G2Affine,
+    length: usize,
+    frames: Vec<Frame>,
+    attestation: Option<BatchAttestation>,
+}
+
+impl BlobCert {
+    /// Creates a certificate for a polynomial of `length` field elements.
+    /// `frames` may be empty to only check the commitments.
+    pub fn new(
+        commitment: G1Affine,
+        length_commitment: G2Affine,
+        length_proof: G2Affine,
+        length: usize,
+        frames: Vec<Frame>,
+    ) -> Self {
+        BlobCert {
+            commitment,
+            length_commitment,
+            length_proof,
+            length,
+            frames,
+            attestation: None,
+        }
+    }
+
+    /// Attaches the batch attestation, checked by `verify_batch` along with
+    /// the commitments.
+    pub fn with_attestation(mut self, attestation: BatchAttestation) -> Self {
+        self.attestation = Some(attestation);
+        self
+    }
+
+    /// Returns the commitment to the blob's polynomial.
+    pub fn get_commitment(&self) -> G1Affine {
+        self.commitment
+    }
+
+    /// Returns the commitment to the blob's polynomial in G2.
+    pub fn get_length_commitment(&self) -> G2Affine {
+        self.length_commitment
+    }
+
+    /// Returns the proof of the polynomial's length.
+    pub fn get_length_proof(&self) -> G2Affine {
+        self.length_proof
+    }
+
+    /// Returns the number of field elements the polynomial is over.
+    pub fn get_length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the sampled chunks and their proofs.
+    pub fn get_frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Returns the batch attestation, if any.
+    pub fn get_attestation(&self) -> Option<&BatchAttestation> {
+        self.attestation.as_ref()
+    }
+
+    /// Returns the blob header as it is hashed into the batch's Merkle tree:
+    /// the commitment's coordinates followed by the length, each as a
+    /// big-endian 32-byte word.
+    pub fn header_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(96);
+        bytes.extend_from_slice(&g1_to_abi_words(&self.commitment));
+        bytes.extend_from_slice(&[0u8; 24]);
+        bytes.extend_from_slice(&(self.length as u64).to_be_bytes());
+        bytes
+    }
+
+    /// Checks the header is included in the attested batch and the aggregate
+    /// signature over the batch root holds. Fails if there is no attestation.
+    pub fn verify_attestation(&self) -> Result<(), CertError> {
+        let attestation = self
+            .attestation
+            .as_ref()
+            .ok_or_else(|| CertError::GenericError("certificate has no attestation".to_string()))?;
+        if !attestation
+            .inclusion_proof
+            .verify(&attestation.batch_root, &self.header_bytes())
+        {
+            return Err(CertError::InvalidInclusionProofError);
+        }
+        if !bls::verify_signature(
+            &attestation.batch_root,
+            &attestation.signature,
+            &attestation.apk,
+        ) {
+            return Err(CertError::InvalidSignatureError);
+        }
+        Ok(())
+    }
+}
+
+/// Validates every certificate of a batch, returning one result per
+/// certificate in input order.
+///
+/// For each certificate, the commitment must match the length commitment and
+/// the length proof must hold, the attestation must hold if there is one, and
+/// every sampled frame must open against the commitment under `encoder`. The commitment and length checks of the whole
+/// batch are folded into one multi-pairing with powers of a challenge hashed
+/// from all certificates; only when it fails are the certificates checked one
+/// by one to find the culprits. Frames are checked in parallel on the thread
+/// pool configured on `kzg`.
+pub fn verify_batch(
+    kzg: &Kzg,
+    encoder: &Encoder,
+    certs: &[BlobCert],
+) -> Vec<Result<(), CertError>> {
+    let run = || {
+        let mut results: Vec<Result<(), CertError>> = vec![Ok(()); certs.len()];
+        let mut challenges = vec![G1Affine::zero(); certs.len()];
+        for (i, cert) in certs.iter().enumerate() {
+            if !cert.length.is_power_of_two() {
+                results[i] = Err(CertError::GenericError(
+                    "length is not a power of 2".to_string(),
+                ));
+                continue;
+            }
+            match kzg.length_challenge(cert.length) {
+                Ok(challenge) => challenges[i] = challenge,
+                Err(err) => results[i] = Err(CertError::GenericError(err.to_string())),
+            }
+        }
+
+        let batch: Vec<usize> = (0..certs.len()).filter(|&i| results[i].is_ok()).collect();
+        let batch_valid =
+            verify_commitments_batched(certs, &challenges, &batch, batch_challenge(certs, &batch));
+        if !batch_valid {
+            let individual: Vec<(usize, Result<(), CertError>)> = batch
+                .par_iter()
+                .map(|&i| (i, verify_commitments(&certs[i], challenges[i])))
+                .collect();
+            for (i, result) in individual {
+                results[i] = result;
+            }
+        }
+
+        results
+            .into_par_iter()
+            .zip(certs)
+            .map(|(result, cert)| {
+                result?;
+                if cert.attestation.is_some() {
+                    cert.verify_attestation()?;
+                }
+                cert.frames.par_iter().try_for_each(|frame| {
+                    match encoder.verify_chunk(
+                        kzg,
+                        &cert.commitment,
+                        cert.length,
+                        frame.get_chunk(),
+                        &frame.get_proof(),
+                    ) {
+                        Ok(true) => Ok(()),
+                        Ok(false) => Err(CertError::InvalidChunkProofError(
+                            frame.get_chunk().get_index(),
+                        )),
+                        Err(err) => Err(CertError::GenericError(err.to_string())),
+                    }
+                })
+            })
+            .collect()
+    };
+    kzg.get_config()
+        .install(run)
+        .unwrap_or_else(|err| vec![Err(CertError::GenericError(err.to_string())); certs.len()])
+}
+
+/// checks a single certificate's commitment and length proof
+fn verify_commitments(cert: &BlobCert, challenge: G1Affine) -> Result<(), CertError> {
+    if !Kzg::pairings_verify(
+        cert.commitment,
+        G2Affine::generator(),
+        G1Affine::generator(),
+        cert.length_commitment,
+    ) {
+        return Err(CertError::InvalidCommitmentError);
+    }
+    if !Kzg::pairings_verify(
+        challenge,
+        cert.length_commitment,
+        G1Affine::generator(),
+        cert.length_proof,
+    ) {
+        return Err(CertError::InvalidLengthProofError);
+    }
+    Ok(())
+}
+
+/// With `a_i = r^(2i)` and `b_i = r^(2i+1)`, checks
+/// `e(sum a_i C_i, G2) * e(G1, -sum (a_i L_i + b_i P_i)) * prod e(b_i X_i, L_i) = 1`,
+/// which holds for all `e(C_i, G2) = e(G1, L_i)` and `e(X_i, L_i) = e(G1, P_i)`
+/// at once, except with negligible probability
+fn verify_commitments_batched(
+    certs: &[BlobCert],
+    challenges: &[G1Affine],
+    batch: &[usize],
+    r: Fr,
+) -> bool {
+    if batch.is_empty() {
+        return true;
+    }
+    let mut commitment_weights = Vec::with_capacity(batch.len());
+    let mut proof_weights = Vec::with_capacity(batch.len());
+    let mut power = Fr::from(1u64);
+    for _ in batch {
+        commitment_weights.push(power);
+        proof_weights.push(power * r);
+        power *= r * r;
+    }
+
+    let commitments: Vec<G1Affine> = batch.iter().map(|&i| certs[i].commitment).collect();
+    let g2_bases: Vec<G2Affine> = batch
+        .iter()
+        .flat_map(|&i| [certs[i].length_commitment, certs[i].length_proof])
+        .collect();
+    let g2_scalars: Vec<Fr> = commitment_weights
+        .iter()
+        .zip(&proof_weights)
+        .flat_map(|(a, b)| [*a, *b])
+        .collect();
+    let (Ok(lhs), Ok(rhs)) = (
+        G1Projective::msm(&commitments, &commitment_weights),
+        G2Projective::msm(&g2_bases, &g2_scalars),
+    ) else {
+        return false;
+    };
+
+    let mut g1_points = vec![lhs.into_affine(), G1Affine::generator()];
+    let mut g2_points = vec![G2Affine::generator(), (-rhs).into_affine()];
+    let weighted_challenges: Vec<G1Projective> = batch
+        .iter()
+        .zip(&proof_weights)
+        .map(|(&i, b)| challenges[i] * b)
+        .collect();
+    g1_points.extend(G1Projective::normalize_batch(&weighted_challenges));
+    g2_points.extend(batch.iter().map(|&i| certs[i].length_commitment));
+    Kzg::multi_pairing(&g1_points, &g2_points)
+}
+
+/// hashes the commitments and lengths of the batched certificates into the
+/// challenge their checks are combined with
+fn batch_challenge(certs: &[BlobCert], batch: &[usize]) -> Fr {
+    let mut hasher = Sha256::new();
+    hasher.update(CERT_CHALLENGE_DOMAIN);
+    hasher.update((batch.len() as u64).to_be_bytes());
+    for &i in batch {
+        let cert = &certs[i];
+        hasher.update(g1_to_bytes(&cert.commitment));
+        hasher.update(g2_point_to_bytes_be(&cert.length_commitment));
+        hasher.update(g2_point_to_bytes_be(&cert.length_proof));
+        hasher.update((cert.length as u64).to_be_bytes());
+    }
+    Fr::from_be_bytes_mod_order(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES, encoder::Chunk, merkle::MerkleTree,
+        polynomial::Polynomial,
+    };
+
+    fn cert(kzg: &Kzg, encoder: &Encoder, polynomial: &Polynomial) -> BlobCert {
+        let chunks = encoder.encode_polynomial(polynomial).unwrap();
+        let proofs = encoder.prove_chunks(kzg, polynomial).unwrap();
+        BlobCert::new(
+            kzg.commit(polynomial).unwrap(),
+            kzg.compute_length_commitment(polynomial).unwrap(),
+            kzg.compute_length_proof(polynomial, polynomial.len())
+                .unwrap(),
+            polynomial.len(),
+            chunks
+                .into_iter()
+                .zip(proofs)
+                .step_by(3)
+                .map(|(c, p)| Frame::new(c, p))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let encoder = Encoder::new(8, 2).unwrap();
+        let certs: Vec<BlobCert> = [100, 700, GETTYSBURG_ADDRESS_BYTES.len()]
+            .iter()
+            .map(|&n| {
+                let polynomial = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..n])
+                    .to_polynomial()
+                    .unwrap();
+                cert(&kzg, &encoder, &polynomial)
+            })
+            .collect();
+        assert!(verify_batch(&kzg, &encoder, &certs)
+            .iter()
+            .all(Result::is_ok));
+        assert!(verify_batch(&kzg, &encoder, &[]).is_empty());
+
+        let mut bad = certs.clone();
+        // a length proof for a longer polynomial than claimed
+        bad[0].length = certs[0].length / 2;
+        bad[1].commitment = certs[2].commitment;
+        let frame = &certs[2].frames[1];
+        let tampered = Chunk::new(
+            frame.get_chunk().get_index(),
+            frame
+                .get_chunk()
+                .get_evaluations()
+                .iter()
+                .map(|e| *e + Fr::from(1u64))
+                .collect(),
+        );
+        bad[2].frames[1] = Frame::new(tampered, frame.get_proof());
+        let results = verify_batch(&kzg, &encoder, &bad);
+        assert_eq!(results[0], Err(CertError::InvalidLengthProofError));
+        assert_eq!(results[1], Err(CertError::InvalidCommitmentError));
+        assert_eq!(
+            results[2],
+            Err(CertError::InvalidChunkProofError(
+                certs[2].frames[1].get_chunk().get_index()
+            ))
+        );
+
+        let mut unsupported = certs[..1].to_vec();
+        unsupported[0].length = 3;
+        assert!(matches!(
+            verify_batch(&kzg, &encoder, &unsupported)[0],
+            Err(CertError::GenericError(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_attestation() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let encoder = Encoder::new(4, 2).unwrap();
+        let certs: Vec<BlobCert> = [100, 300, 500]
+            .iter()
+            .map(|&n| {
+                let polynomial = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..n])
+                    .to_polynomial()
+                    .unwrap();
+                cert(&kzg, &encoder, &polynomial)
+            })
+            .collect();
+        let headers: Vec<Vec<u8>> = certs.iter().map(BlobCert::header_bytes).collect();
+        let tree = MerkleTree::new(&headers);
+        let root = tree.get_root();
+        let keys = [Fr::from(11u64), Fr::from(12u64), Fr::from(13u64)];
+        let signature = bls::aggregate_signatures(&keys.map(|k| bls::sign(k, &root)));
+        let apk = bls::aggregate_public_keys(&keys.map(bls::public_key));
+
+        let attested: Vec<BlobCert> = certs
+            .iter()
+            .enumerate()
+            .map(|(i, cert)| {
+                cert.clone().with_attestation(BatchAttestation::new(
+                    root,
+                    tree.prove(i).unwrap(),
+                    signature,
+                    apk,
+                ))
+            })
+            .collect();
+        assert!(attested.iter().all(|c| c.verify_attestation().is_ok()));
+        assert!(verify_batch(&kzg, &encoder, &attested)
+            .iter()
+            .all(Result::is_ok));
+        assert!(matches!(
+            certs[0].verify_attestation(),
+            Err(CertError::GenericError(_))
+        ));
+
+        // a proof for another header, and a signature missing a signer, fail
+        let mut bad = attested.clone();
+        bad[0].attestation = Some(BatchAttestation::new(
+            root,
+            tree.prove(1).unwrap(),
+            signature,
+            apk,
+        ));
+        bad[1].attestation = Some(BatchAttestation::new(
+            root,
+            tree.prove(1).unwrap(),
+            signature,
+            bls::aggregate_public_keys(
+                &keys[1..]
+                    .iter()
+                    .map(|k| bls::public_key(*k))
+                    .collect::<Vec<_>>(),
+            ),
+        ));
+        let results = verify_batch(&kzg, &encoder, &bad);
+        assert_eq!(results[0], Err(CertError::InvalidInclusionProofError));
+        assert_eq!(results[1], Err(CertError::InvalidSignatureError));
+        assert_eq!(results[2], Ok(()));
+    }
+}
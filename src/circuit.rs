@@ -0,0 +1,172 @@
+//! Public inputs for verifying KZG openings inside a circuit over the BN254
+//! scalar field.
+//!
+//! Evaluation points and values are native to such a circuit, but the
+//! coordinates of G1 points live in the base field and are split into
+//! `NUM_LIMBS` little-endian limbs of `LIMB_BITS` bits each, the layout
+//! non-native BN254 gadgets commonly use. Openings are reduced to a
+//! `KzgAccumulator`, a pair of G1 points whose pairing check against the fixed
+//! G2 points of the SRS the circuit defers to its verifier, so the circuit
+//! itself never computes a pairing.
+
+use crate::kzg::Kzg;
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, PrimeField};
+
+/// Number of bits in each limb of a base field element.
+pub const LIMB_BITS: usize = 88;
+/// Number of limbs a base field element is split into.
+pub const NUM_LIMBS: usize = 3;
+
+/// A deferred check `e(lhs, G2) = e(rhs, [tau]G2)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KzgAccumulator {
+    lhs: G1Affine,
+    rhs: G1Affine,
+}
+
+impl KzgAccumulator {
+    /// Reduces the claim that the polynomial committed to by `commitment`
+    /// evaluates to `value` at `z`, as shown by `proof`.
+    ///
+    /// `e(C - [y], G2) = e(proof, [tau - z])` is rearranged into
+    /// `e(C - [y] + z * proof, G2) = e(proof, [tau])`, which only involves
+    /// the fixed G2 points.
+    pub fn from_opening(commitment: &G1Affine, proof: &G1Affine, z: Fr, value: Fr) -> Self {
+        let lhs = commitment.into_group() - G1Affine::generator() * value + *proof * z;
+        KzgAccumulator {
+            lhs: lhs.into_affine(),
+            rhs: *proof,
+        }
+    }
+
+    /// Folds `accumulators` into one with the powers `1, r, r^2, ...` of a
+    /// random `r`, which holds exactly when all of them do, except with
+    /// negligible probability.
+    pub fn fold(accumulators: &[KzgAccumulator], r: Fr) -> Self {
+        let mut powers = Vec::with_capacity(accumulators.len());
+        let mut power = Fr::from(1u64);
+        for _ in accumulators {
+            powers.push(power);
+            power *= r;
+        }
+        let lhs: Vec<G1Affine> = accumulators.iter().map(|a| a.lhs).collect();
+        let rhs: Vec<G1Affine> = accumulators.iter().map(|a| a.rhs).collect();
+        let msm = |bases: &[G1Affine]| {
+            G1Projective::msm(bases, &powers)
+                .expect("bases and scalars have the same length")
+                .into_affine()
+        };
+        KzgAccumulator {
+            lhs: msm(&lhs),
+            rhs: msm(&rhs),
+        }
+    }
+
+    /// Returns the point paired with G2.
+    pub fn get_lhs(&self) -> G1Affine {
+        self.lhs
+    }
+
+    /// Returns the point paired with `[tau]G2`.
+    pub fn get_rhs(&self) -> G1Affine {
+        self.rhs
+    }
+
+    /// Runs the deferred pairing check against the SRS of `kzg`.
+    pub fn verify(&self, kzg: &Kzg) -> bool {
+        kzg.verify_pairing(self.lhs, self.rhs)
+    }
+
+    /// Returns the limbs of `lhs.x`, `lhs.y`, `rhs.x` and `rhs.y`, in that
+    /// order.
+    pub fn to_limbs(&self) -> Vec<Fr> {
+        [self.lhs, self.rhs].iter().flat_map(g1_to_limbs).collect()
+    }
+}
+
+/// Returns the public inputs of an opening: the limbs of the commitment's
+/// and the proof's coordinates, then `z` and `value`.
+pub fn opening_public_inputs(commitment: &G1Affine, proof: &G1Affine, z: Fr, value: Fr) -> Vec<Fr> {
+    let mut inputs: Vec<Fr> = [commitment, proof]
+        .into_iter()
+        .flat_map(g1_to_limbs)
+        .collect();
+    inputs.extend([z, value]);
+    inputs
+}
+
+/// Splits `value` into `NUM_LIMBS` little-endian limbs of `LIMB_BITS` bits.
+pub fn fq_to_limbs(value: &Fq) -> [Fr; NUM_LIMBS] {
+    let bits = value.into_bigint().to_bits_le();
+    let mut limbs = [Fr::from(0u64); NUM_LIMBS];
+    for (limb, chunk) in limbs.iter_mut().zip(bits.chunks(LIMB_BITS)) {
+        *limb = Fr::from_bigint(<Fr as PrimeField>::BigInt::from_bits_le(chunk))
+            .expect("limbs are smaller than the scalar field");
+    }
+    limbs
+}
+
+/// returns the limbs of the coordinates of `point`, with the identity's
+/// coordinates taken as zero
+pub(crate) fn g1_to_limbs(point: &G1Affine) -> Vec<Fr> {
+    let zero = Fq::from(0u64);
+    let (x, y) = point.xy().unwrap_or((&zero, &zero));
+    fq_to_limbs(x).into_iter().chain(fq_to_limbs(y)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_fq_to_limbs() {
+        let value = -Fq::from(1u64);
+        let limbs = fq_to_limbs(&value);
+        let recombined = limbs.iter().rev().fold(BigUint::from(0u64), |acc, limb| {
+            (acc << LIMB_BITS) + BigUint::from(limb.into_bigint())
+        });
+        assert_eq!(recombined, BigUint::from(value.into_bigint()));
+        assert!(limbs
+            .iter()
+            .all(|limb| limb.into_bigint().num_bits() as usize <= LIMB_BITS));
+    }
+
+    #[test]
+    fn test_accumulator_from_opening() {
+        let mut kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = blob.to_polynomial().unwrap();
+        kzg.data_setup_custom(1, blob.len().try_into().unwrap())
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let proof = kzg
+            .compute_kzg_proof_with_roots_of_unity(&polynomial, 7)
+            .unwrap();
+        let z = *kzg.get_nth_root_of_unity(7).unwrap();
+        let value = *polynomial.get_at_index(7).unwrap();
+
+        let accumulator = KzgAccumulator::from_opening(&commitment, &proof, z, value);
+        assert!(accumulator.verify(&kzg));
+        assert_eq!(accumulator.to_limbs().len(), 4 * NUM_LIMBS);
+        assert!(
+            !KzgAccumulator::from_opening(&commitment, &proof, z, value + Fr::from(1u64))
+                .verify(&kzg)
+        );
+
+        let inputs = opening_public_inputs(&commitment, &proof, z, value);
+        assert_eq!(inputs.len(), 4 * NUM_LIMBS + 2);
+        assert_eq!(&inputs[..NUM_LIMBS], &fq_to_limbs(&commitment.x));
+        assert_eq!(&inputs[4 * NUM_LIMBS..], &[z, value]);
+    }
+}
@@ -0,0 +1,644 @@
+use crate::{
+    consts::{
+        BYTES_PER_FIELD_ELEMENT, SIZE_OF_G1_AFFINE_COMPRESSED, SIZE_OF_G1_AFFINE_UNCOMPRESSED,
+    },
+    encoding::{g1_from_bytes, g1_from_uncompressed_bytes, g1_to_bytes, g1_to_uncompressed_bytes},
+    errors::KzgError,
+    evm::g2_to_abi_words,
+    glv, helpers,
+    kzg::Kzg,
+    merkle::{g1_to_abi_words, keccak256},
+    polynomial::Polynomial,
+    workspace::KzgWorkspace,
+};
+use ark_bn254::{Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_std::Zero;
+use std::fmt;
+
+/// A commitment to a polynomial in evaluation form, along with the number of
+/// field elements it commits to.
+///
+/// Commitments are linear in the polynomial's evaluations, so changing a
+/// single evaluation from `old` to `new` moves the commitment by
+/// `(new - old) * L_index(tau)`. `update` applies that delta directly instead
+/// of recommitting the whole polynomial, which is what append/patch-style blob
+/// builders need.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KzgCommitment {
+    point: G1Affine,
+    length: usize,
+}
+
+impl KzgCommitment {
+    /// Wraps a commitment to a polynomial of `length` field elements.
+    pub fn new(point: G1Affine, length: usize) -> Self {
+        KzgCommitment { point, length }
+    }
+
+    /// Commits to `polynomial` with `kzg`.
+    pub fn from_polynomial(polynomial: &Polynomial, kzg: &Kzg) -> Result<Self, KzgError> {
+        Ok(KzgCommitment::new(
+            kzg.commit(polynomial)?,
+            polynomial.len(),
+        ))
+    }
+
+    /// Returns the commitment as a G1 point.
+    pub fn get_point(&self) -> G1Affine {
+        self.point
+    }
+
+    /// Returns the number of field elements the commitment is over.
+    pub fn get_length(&self) -> usize {
+        self.length
+    }
+
+    /// Decodes a commitment to a polynomial of `length` field elements from
+    /// its 32-byte compressed or 64-byte uncompressed point, checking the
+    /// point is on the curve and in the subgroup.
+    pub fn from_bytes(bytes: &[u8], length: usize) -> Result<Self, KzgError> {
+        Ok(KzgCommitment::new(decode_point(bytes)?, length))
+    }
+
+    /// Returns the compressed 32-byte encoding of the point.
+    pub fn to_bytes(&self) -> [u8; SIZE_OF_G1_AFFINE_COMPRESSED] {
+        g1_to_bytes(&self.point)
+    }
+
+    /// Returns the uncompressed 64-byte encoding of the point.
+    pub fn to_uncompressed_bytes(&self) -> [u8; SIZE_OF_G1_AFFINE_UNCOMPRESSED] {
+        g1_to_uncompressed_bytes(&self.point)
+    }
+
+    /// Updates the commitment after the evaluation at `index` changed from
+    /// `old_value` to `new_value`.
+    ///
+    /// Costs a single MSM over `length` points to derive the Lagrange basis
+    /// point; use `update_with_workspace` when applying many updates.
+    pub fn update(
+        &mut self,
+        index: u64,
+        old_value: Fr,
+        new_value: Fr,
+        kzg: &Kzg,
+    ) -> Result<(), KzgError> {
+        let index = self.check_index(index)?;
+        let basis = kzg.lagrange_basis_point(self.length, index)?;
+        self.apply_delta(basis, new_value - old_value);
+        Ok(())
+    }
+
+    /// Same as `update`, but reads the Lagrange basis point from the bases
+    /// cached in `workspace`, so repeated updates cost one scalar
+    /// multiplication each once the bases are built.
+    pub fn update_with_workspace(
+        &mut self,
+        index: u64,
+        old_value: Fr,
+        new_value: Fr,
+        kzg: &Kzg,
+        workspace: &mut KzgWorkspace,
+    ) -> Result<(), KzgError> {
+        let index = self.check_index(index)?;
+        kzg.fill_lagrange_bases(self.length, workspace)?;
+        let basis = workspace.g1_lagrange[index];
+        self.apply_delta(basis, new_value - old_value);
+        Ok(())
+    }
+
+    /// Returns the commitment to the sum of the two polynomials, which must
+    /// have the same length.
+    pub fn add(&self, other: &Self) -> Result<Self, KzgError> {
+        self.check_length(other)?;
+        Ok(KzgCommitment::new(
+            (self.point + other.point).into_affine(),
+            self.length,
+        ))
+    }
+
+    /// Returns the commitment to the difference of the two polynomials, which
+    /// must have the same length.
+    pub fn sub(&self, other: &Self) -> Result<Self, KzgError> {
+        self.check_length(other)?;
+        Ok(KzgCommitment::new(
+            (self.point - other.point).into_affine(),
+            self.length,
+        ))
+    }
+
+    /// Returns the commitment to the polynomial multiplied by `scalar`.
+    pub fn scale(&self, scalar: Fr) -> Self {
+        KzgCommitment::new(glv::mul(&self.point, &scalar).into_affine(), self.length)
+    }
+
+    fn check_length(&self, other: &Self) -> Result<(), KzgError> {
+        if self.length != other.length {
+            return Err(KzgError::GenericError(
+                "commitments are over polynomials of different lengths".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the key EigenDA contracts identify the blob by.
+    ///
+    /// As in the Go `BlobHeader.BlobKey`, this is
+    /// `keccak256(abi.encode(keccak256(abi.encode(header)), paymentMetadataHash))`,
+    /// where `header` is the tuple `(uint16 version, bytes quorumNumbers,
+    /// (G1Point commitment, G2Point lengthCommitment, G2Point lengthProof,
+    /// uint32 length))` and `length` is this commitment's number of field
+    /// elements.
+    pub fn to_blob_key(&self, header: &BlobKeyHeader) -> [u8; 32] {
+        // the tuple is dynamic because of the quorum numbers, so it is
+        // encoded behind an offset, with the numbers after its 13 head words
+        let mut encoded = Vec::with_capacity(32 * 16 + header.quorum_numbers.len());
+        encoded.extend_from_slice(&abi_word(32));
+        encoded.extend_from_slice(&abi_word(header.version.into()));
+        encoded.extend_from_slice(&abi_word(13 * 32));
+        encoded.extend_from_slice(&g1_to_abi_words(&self.point));
+        encoded.extend_from_slice(&g2_to_abi_words(&header.length_commitment));
+        encoded.extend_from_slice(&g2_to_abi_words(&header.length_proof));
+        encoded.extend_from_slice(&abi_word(self.length as u64));
+        encoded.extend_from_slice(&abi_word(header.quorum_numbers.len() as u64));
+        encoded.extend_from_slice(&header.quorum_numbers);
+        encoded.resize(encoded.len().next_multiple_of(32), 0);
+
+        let mut key_preimage = [0u8; 64];
+        key_preimage[..32].copy_from_slice(&keccak256(&encoded));
+        key_preimage[32..].copy_from_slice(&header.payment_metadata_hash);
+        keccak256(&key_preimage)
+    }
+
+    fn check_index(&self, index: u64) -> Result<usize, KzgError> {
+        usize::try_from(index)
+            .ok()
+            .filter(|&i| i < self.length)
+            .ok_or_else(|| {
+                KzgError::GenericError("index is out of range of the polynomial".to_string())
+            })
+    }
+
+    fn apply_delta(&mut self, basis: G1Affine, delta: Fr) {
+        let updated: G1Projective = self.point.into_group() + glv::mul(&basis, &delta);
+        self.point = updated.into_affine();
+    }
+}
+
+impl From<KzgCommitment> for G1Affine {
+    fn from(commitment: KzgCommitment) -> Self {
+        commitment.point
+    }
+}
+
+/// Formats the compressed point as `0x`-prefixed hex.
+impl fmt::Display for KzgCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, &self.to_bytes())
+    }
+}
+
+/// The blob header fields besides the commitment that go into an EigenDA
+/// blob key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobKeyHeader {
+    /// The blob version the blob was dispersed with.
+    pub version: u16,
+    /// The quorums the blob is dispersed to, one byte each.
+    pub quorum_numbers: Vec<u8>,
+    /// The commitment to the blob's length, in G2.
+    pub length_commitment: G2Affine,
+    /// The proof of the blob's length, in G2.
+    pub length_proof: G2Affine,
+    /// The keccak256 hash of the ABI-encoded payment metadata.
+    pub payment_metadata_hash: [u8; 32],
+}
+
+/// Encodes `value` as a 32-byte big-endian ABI word.
+fn abi_word(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// An opening proof along with the point it opens at.
+///
+/// Proofs at the same point are linear in the polynomial like commitments,
+/// so the proof for a linear combination of polynomials is the same
+/// combination of their proofs, opening to the same combination of values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KzgProof {
+    point: G1Affine,
+    z: Fr,
+}
+
+impl KzgProof {
+    /// Wraps a proof of an evaluation at `z`.
+    pub fn new(point: G1Affine, z: Fr) -> Self {
+        KzgProof { point, z }
+    }
+
+    /// Returns the proof as a G1 point.
+    pub fn get_point(&self) -> G1Affine {
+        self.point
+    }
+
+    /// Returns the point the proof opens at.
+    pub fn get_z(&self) -> Fr {
+        self.z
+    }
+
+    /// Decodes a proof of an evaluation at `z` from its 32-byte compressed or
+    /// 64-byte uncompressed point, checking the point is on the curve and in
+    /// the subgroup.
+    pub fn from_bytes(bytes: &[u8], z: Fr) -> Result<Self, KzgError> {
+        Ok(KzgProof::new(decode_point(bytes)?, z))
+    }
+
+    /// Returns the compressed 32-byte encoding of the point.
+    pub fn to_bytes(&self) -> [u8; SIZE_OF_G1_AFFINE_COMPRESSED] {
+        g1_to_bytes(&self.point)
+    }
+
+    /// Returns the uncompressed 64-byte encoding of the point.
+    pub fn to_uncompressed_bytes(&self) -> [u8; SIZE_OF_G1_AFFINE_UNCOMPRESSED] {
+        g1_to_uncompressed_bytes(&self.point)
+    }
+
+    /// Returns the proof for the sum of the two polynomials, which must be
+    /// opened at the same point.
+    pub fn add(&self, other: &Self) -> Result<Self, KzgError> {
+        self.check_z(other)?;
+        Ok(KzgProof::new(
+            (self.point + other.point).into_affine(),
+            self.z,
+        ))
+    }
+
+    /// Returns the proof for the difference of the two polynomials, which
+    /// must be opened at the same point.
+    pub fn sub(&self, other: &Self) -> Result<Self, KzgError> {
+        self.check_z(other)?;
+        Ok(KzgProof::new(
+            (self.point - other.point).into_affine(),
+            self.z,
+        ))
+    }
+
+    /// Returns the proof for the polynomial multiplied by `scalar`.
+    pub fn scale(&self, scalar: Fr) -> Self {
+        KzgProof::new(glv::mul(&self.point, &scalar).into_affine(), self.z)
+    }
+
+    fn check_z(&self, other: &Self) -> Result<(), KzgError> {
+        if self.z != other.z {
+            return Err(KzgError::GenericError(
+                "proofs open at different points".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl From<KzgProof> for G1Affine {
+    fn from(proof: KzgProof) -> Self {
+        proof.point
+    }
+}
+
+/// Formats the compressed point as `0x`-prefixed hex.
+impl fmt::Display for KzgProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, &self.to_bytes())
+    }
+}
+
+fn decode_point(bytes: &[u8]) -> Result<G1Affine, KzgError> {
+    if bytes.len() == SIZE_OF_G1_AFFINE_UNCOMPRESSED {
+        g1_from_uncompressed_bytes(bytes)
+    } else {
+        g1_from_bytes(bytes)
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    f.write_str("0x")?;
+    bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+}
+
+/// Commits to a byte payload fed in pieces, like a hasher.
+///
+/// Bytes are packed into 31-byte symbols as in `Blob::from_bytes_and_pad`,
+/// and each `update` folds its complete symbols into the running MSM, so the
+/// payload is never held in memory as a whole. The domain has to be fixed up
+/// front since the Lagrange bases depend on it; finalizing over the same
+/// length as the padded blob gives the same commitment as `commit_bytes`.
+#[derive(Clone, Debug)]
+pub struct CommitmentBuilder {
+    bases: Vec<G1Affine>,
+    accumulator: G1Projective,
+    next_index: usize,
+    pending: Vec<u8>,
+}
+
+impl CommitmentBuilder {
+    /// Starts a commitment over `length` field elements, which must be a
+    /// power of 2 no longer than the SRS.
+    pub fn new(kzg: &Kzg, length: usize) -> Result<Self, KzgError> {
+        Ok(CommitmentBuilder {
+            bases: kzg.g1_ifft(length)?,
+            accumulator: G1Projective::zero(),
+            next_index: 0,
+            pending: Vec::with_capacity(BYTES_PER_FIELD_ELEMENT),
+        })
+    }
+
+    /// Feeds `bytes` into the commitment. Fails without consuming anything if
+    /// the payload would no longer fit in the domain.
+    pub fn update(&mut self, bytes: &[u8]) -> Result<(), KzgError> {
+        let symbol_size = BYTES_PER_FIELD_ELEMENT - 1;
+        let total = self.pending.len() + bytes.len();
+        if self.next_index + (total + symbol_size - 1) / symbol_size > self.bases.len() {
+            return Err(KzgError::CommitError(
+                "payload is longer than the commitment domain".to_string(),
+            ));
+        }
+
+        let split = (symbol_size - self.pending.len()).min(bytes.len());
+        self.pending.extend_from_slice(&bytes[..split]);
+        let mut scalars = Vec::with_capacity(total / symbol_size);
+        if self.pending.len() == symbol_size {
+            scalars.push(Self::symbol_to_fr(&self.pending));
+            self.pending.clear();
+        }
+        let mut symbols = bytes[split..].chunks_exact(symbol_size);
+        scalars.extend(symbols.by_ref().map(Self::symbol_to_fr));
+        self.pending.extend_from_slice(symbols.remainder());
+
+        let end = self.next_index + scalars.len();
+        let partial = glv::msm(&self.bases[self.next_index..end], &scalars, None)
+            .map_err(|err| KzgError::CommitError(err.to_string()))?;
+        self.accumulator += partial;
+        self.next_index = end;
+        Ok(())
+    }
+
+    /// Pads the remaining bytes and returns the commitment.
+    pub fn finalize(mut self) -> KzgCommitment {
+        if !self.pending.is_empty() {
+            let last = Self::symbol_to_fr(&self.pending);
+            self.accumulator += glv::mul(&self.bases[self.next_index], &last);
+        }
+        KzgCommitment::new(self.accumulator.into_affine(), self.bases.len())
+    }
+
+    /// Converts up to 31 bytes to a field element the way `to_fr_array` reads
+    /// a padded symbol: a zero byte, the data, then zeros up to 32 bytes.
+    fn symbol_to_fr(symbol: &[u8]) -> Fr {
+        let mut padded = [0u8; BYTES_PER_FIELD_ELEMENT];
+        padded[1..=symbol.len()].copy_from_slice(symbol);
+        helpers::set_bytes_canonical(&padded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+    use ark_std::UniformRand;
+
+    fn setup_kzg() -> Kzg {
+        Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_update_matches_recommitting() {
+        let kzg = setup_kzg();
+        let mut rng = ark_std::test_rng();
+        // cover both the small-blob fast path and the MSM path
+        for bytes in ["hi".as_bytes(), GETTYSBURG_ADDRESS_BYTES] {
+            let blob = Blob::from_bytes_and_pad(bytes);
+            let polynomial = blob.to_polynomial().unwrap();
+            let mut commitment = KzgCommitment::from_polynomial(&polynomial, &kzg).unwrap();
+            let mut commitment_ws = commitment;
+            let mut workspace = KzgWorkspace::new();
+
+            let mut evals = polynomial.to_vec();
+            for index in [0, evals.len() - 1, evals.len() / 3] {
+                let new_value = Fr::rand(&mut rng);
+                commitment
+                    .update(index as u64, evals[index], new_value, &kzg)
+                    .unwrap();
+                commitment_ws
+                    .update_with_workspace(
+                        index as u64,
+                        evals[index],
+                        new_value,
+                        &kzg,
+                        &mut workspace,
+                    )
+                    .unwrap();
+                evals[index] = new_value;
+            }
+
+            let expected = kzg.commit(&Polynomial::new(&evals, blob.len()).unwrap());
+            assert_eq!(commitment.get_point(), expected.unwrap());
+            assert_eq!(commitment_ws, commitment);
+        }
+    }
+
+    #[test]
+    fn test_homomorphic_operations() {
+        let kzg = setup_kzg();
+        let mut rng = ark_std::test_rng();
+        let p = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let q = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[100..1400])
+            .to_polynomial()
+            .unwrap();
+        assert_eq!(p.len(), q.len());
+        let scalar = Fr::rand(&mut rng);
+        let combine = |f: &dyn Fn(Fr, Fr) -> Fr| {
+            let evals: Vec<Fr> = p
+                .as_slice()
+                .iter()
+                .zip(q.as_slice())
+                .map(|(a, b)| f(*a, *b))
+                .collect();
+            Polynomial::new(&evals, p.len() * 32).unwrap()
+        };
+        let sum = combine(&|a, b| a + b);
+        let difference = combine(&|a, b| a - b);
+        let scaled = combine(&|a, _| a * scalar);
+
+        let p_commitment = KzgCommitment::from_polynomial(&p, &kzg).unwrap();
+        let q_commitment = KzgCommitment::from_polynomial(&q, &kzg).unwrap();
+        assert_eq!(
+            p_commitment.add(&q_commitment).unwrap(),
+            KzgCommitment::from_polynomial(&sum, &kzg).unwrap()
+        );
+        assert_eq!(
+            p_commitment.sub(&q_commitment).unwrap(),
+            KzgCommitment::from_polynomial(&difference, &kzg).unwrap()
+        );
+        assert_eq!(
+            p_commitment.scale(scalar),
+            KzgCommitment::from_polynomial(&scaled, &kzg).unwrap()
+        );
+        let short = KzgCommitment::new(q_commitment.get_point(), q.len() / 2);
+        assert!(p_commitment.add(&short).is_err());
+        assert!(p_commitment.sub(&short).is_err());
+
+        let z = Fr::rand(&mut rng);
+        let (p_proof, p_value) = kzg.compute_kzg_proof_at_point(&p, z).unwrap();
+        let (q_proof, q_value) = kzg.compute_kzg_proof_at_point(&q, z).unwrap();
+        let (p_proof, q_proof) = (KzgProof::new(p_proof, z), KzgProof::new(q_proof, z));
+        for (commitment, proof, value) in [
+            (
+                p_commitment.add(&q_commitment).unwrap(),
+                p_proof.add(&q_proof).unwrap(),
+                p_value + q_value,
+            ),
+            (
+                p_commitment.sub(&q_commitment).unwrap(),
+                p_proof.sub(&q_proof).unwrap(),
+                p_value - q_value,
+            ),
+            (
+                p_commitment.scale(scalar),
+                p_proof.scale(scalar),
+                p_value * scalar,
+            ),
+        ] {
+            assert!(kzg.verify_kzg_proof(commitment.into(), proof.into(), value, z));
+        }
+        let elsewhere = KzgProof::new(q_proof.get_point(), z + Fr::from(1u64));
+        assert!(p_proof.add(&elsewhere).is_err());
+        assert!(p_proof.sub(&elsewhere).is_err());
+    }
+
+    #[test]
+    fn test_byte_encodings() {
+        let kzg = setup_kzg();
+        let commitment = kzg.commit_bytes(GETTYSBURG_ADDRESS_BYTES).unwrap();
+        let length = commitment.get_length();
+        let compressed = commitment.to_bytes();
+        assert_eq!(
+            KzgCommitment::from_bytes(&compressed, length).unwrap(),
+            commitment
+        );
+        assert_eq!(
+            KzgCommitment::from_bytes(&commitment.to_uncompressed_bytes(), length).unwrap(),
+            commitment
+        );
+        assert!(KzgCommitment::from_bytes(&compressed[1..], length).is_err());
+
+        let display = commitment.to_string();
+        assert_eq!(display.len(), 2 + 2 * SIZE_OF_G1_AFFINE_COMPRESSED);
+        assert!(display.starts_with("0x"));
+        assert_eq!(display[2..4], format!("{:02x}", compressed[0]));
+
+        let z = Fr::from(7u64);
+        let proof = KzgProof::new(commitment.get_point(), z);
+        let decoded = KzgProof::from_bytes(&proof.to_uncompressed_bytes(), z).unwrap();
+        assert_eq!(decoded, proof);
+        assert_eq!(proof.to_string(), display);
+
+        // an x coordinate with no point on the curve is rejected
+        let mut uncompressed = proof.to_uncompressed_bytes();
+        uncompressed[0] ^= 1;
+        assert!(KzgProof::from_bytes(&uncompressed, z).is_err());
+    }
+
+    #[test]
+    fn test_commitment_builder_matches_commit_bytes() {
+        let kzg = setup_kzg();
+        let bytes = GETTYSBURG_ADDRESS_BYTES;
+        let expected = kzg.commit_bytes(bytes).unwrap();
+        let length = expected.get_length();
+
+        for piece_size in [1, 7, 31, 62, 100, bytes.len()] {
+            let mut builder = kzg.commitment_builder(length).unwrap();
+            for piece in bytes.chunks(piece_size) {
+                builder.update(piece).unwrap();
+            }
+            assert_eq!(builder.finalize(), expected);
+        }
+
+        let mut builder = kzg.commitment_builder(4).unwrap();
+        builder.update(&[1u8; 31 * 3 + 1]).unwrap();
+        assert!(builder.update(&[1u8; 31]).is_err());
+        assert!(builder.update(&[1u8; 30]).is_ok());
+        assert!(kzg.commitment_builder(3).is_err());
+    }
+
+    #[test]
+    fn test_update_rejects_out_of_range_index() {
+        let kzg = setup_kzg();
+        let polynomial = Blob::from_bytes_and_pad("hi".as_bytes())
+            .to_polynomial()
+            .unwrap();
+        let mut commitment = KzgCommitment::from_polynomial(&polynomial, &kzg).unwrap();
+        let length = commitment.get_length() as u64;
+        assert!(commitment
+            .update(length, Fr::from(1u64), Fr::from(2u64), &kzg)
+            .is_err());
+        assert!(commitment
+            .update_with_workspace(
+                length,
+                Fr::from(1u64),
+                Fr::from(2u64),
+                &kzg,
+                &mut KzgWorkspace::new()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_blob_key() {
+        let kzg = setup_kzg();
+        let header = BlobKeyHeader {
+            version: 0,
+            quorum_numbers: vec![0, 1],
+            length_commitment: G2Affine::identity(),
+            length_proof: G2Affine::identity(),
+            payment_metadata_hash: [7u8; 32],
+        };
+
+        // with identity points, the encoding is just the offsets and integers
+        let commitment = KzgCommitment::new(G1Affine::identity(), 4);
+        let mut encoded = vec![0u8; 32 * 16];
+        encoded[31] = 0x20;
+        encoded[32 * 2 + 30] = 0x01;
+        encoded[32 * 2 + 31] = 0xa0;
+        encoded[32 * 13 + 31] = 4;
+        encoded[32 * 14 + 31] = 2;
+        encoded[32 * 15 + 1] = 1;
+        let mut preimage = keccak256(&encoded).to_vec();
+        preimage.extend_from_slice(&[7u8; 32]);
+        assert_eq!(commitment.to_blob_key(&header), keccak256(&preimage));
+
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let commitment = kzg.commit_blob(&blob).unwrap();
+        let key = commitment.to_blob_key(&header);
+        let mut other = header.clone();
+        other.quorum_numbers.push(2);
+        assert_ne!(commitment.to_blob_key(&other), key);
+        let mut other = header.clone();
+        other.payment_metadata_hash[0] = 0;
+        assert_ne!(commitment.to_blob_key(&other), key);
+        let mut other = header;
+        other.length_proof = G2Affine::generator();
+        assert_ne!(commitment.to_blob_key(&other), key);
+        let shorter = KzgCommitment::new(commitment.get_point(), commitment.get_length() - 1);
+        assert_ne!(shorter.to_blob_key(&other), commitment.to_blob_key(&other));
+    }
+}
@@ -0,0 +1,353 @@
+use crate::{
+    consts::{
+        FFT_SEQUENTIAL_CUTOFF, MSM_MIN_CHUNK_LEN, MSM_SEQUENTIAL_CUTOFF,
+        SMALL_BLOB_FAST_PATH_THRESHOLD,
+    },
+    errors::KzgError,
+};
+use rayon::ThreadPool;
+use std::{
+    cell::OnceCell,
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+thread_local! {
+    // single-threaded pool for work below the sequential cutoffs, so
+    // third-party code that splits work by `rayon::current_num_threads`
+    // doesn't fan out
+    static SEQUENTIAL_POOL: OnceCell<Option<ThreadPool>> = OnceCell::new();
+}
+
+/// The hash Fiat-Shamir challenges are derived with, see `Transcript`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChallengeHash {
+    /// SHA-256 over the serialized transcript.
+    #[default]
+    Sha256,
+    /// Keccak-256 over the serialized transcript, cheap to re-derive on the
+    /// EVM.
+    Keccak256,
+    /// Circom-compatible Poseidon over BN254 scalars, cheap to re-derive in a
+    /// circuit.
+    #[cfg(feature = "poseidon")]
+    Poseidon,
+}
+
+/// Runtime configuration shared by the parallel operations of a `Kzg`
+/// instance.
+///
+/// Parallel work runs on rayon's global pool unless a thread count or a
+/// pool is set. A pool for a thread count is built on first use and shared by
+/// clones of the config. Besides choosing the thread pool, the config holds
+/// the thresholds that decide when work is split across threads. The best values depend on the
+/// machine; `benches/bench_work_split.rs` sweeps them so they can be tuned
+/// per deployment.
+#[derive(Clone)]
+pub struct KzgConfig {
+    num_threads: Option<usize>,
+    thread_pool: Option<Arc<ThreadPool>>,
+    // the pool of `num_threads` threads, built by the first `install`
+    num_threads_pool: Arc<OnceLock<ThreadPool>>,
+    small_blob_threshold: usize,
+    msm_sequential_cutoff: usize,
+    msm_min_chunk_len: usize,
+    fft_sequential_cutoff: usize,
+    challenge_hash: ChallengeHash,
+}
+
+impl KzgConfig {
+    /// Creates a config that runs parallel work on rayon's global pool, with
+    /// one thread per CPU unless `RAYON_NUM_THREADS` says otherwise.
+    pub fn new() -> Self {
+        KzgConfig {
+            num_threads: None,
+            thread_pool: None,
+            num_threads_pool: Arc::default(),
+            small_blob_threshold: SMALL_BLOB_FAST_PATH_THRESHOLD,
+            msm_sequential_cutoff: MSM_SEQUENTIAL_CUTOFF,
+            msm_min_chunk_len: MSM_MIN_CHUNK_LEN,
+            fft_sequential_cutoff: FFT_SEQUENTIAL_CUTOFF,
+            challenge_hash: ChallengeHash::default(),
+        }
+    }
+
+    /// Runs parallel work on a pool with the given number of threads instead
+    /// of the global pool. The pool is built once, on first use.
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = Some(num_threads);
+        self.num_threads_pool = Arc::default();
+        self
+    }
+
+    /// Runs parallel work on a caller-owned pool, so the library doesn't
+    /// spawn threads of its own.
+    pub fn with_thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /// Commits and proofs for polynomials of up to this many field elements
+    /// skip the G1 IFFT and the thread pool and use a direct single-threaded
+    /// path.
+    pub fn with_small_blob_threshold(mut self, small_blob_threshold: usize) -> Self {
+        self.small_blob_threshold = small_blob_threshold;
+        self
+    }
+
+    /// MSMs over fewer points than `msm_sequential_cutoff` run on the calling
+    /// thread.
+    pub fn with_msm_sequential_cutoff(mut self, msm_sequential_cutoff: usize) -> Self {
+        self.msm_sequential_cutoff = msm_sequential_cutoff;
+        self
+    }
+
+    /// Each parallel MSM task processes at least `msm_min_chunk_len` scalars.
+    pub fn with_msm_min_chunk_len(mut self, msm_min_chunk_len: usize) -> Self {
+        self.msm_min_chunk_len = msm_min_chunk_len.max(1);
+        self
+    }
+
+    /// FFTs shorter than `fft_sequential_cutoff` run single-threaded. Has no
+    /// effect with a custom thread pool, since the library doesn't spawn
+    /// threads of its own then.
+    pub fn with_fft_sequential_cutoff(mut self, fft_sequential_cutoff: usize) -> Self {
+        self.fft_sequential_cutoff = fft_sequential_cutoff;
+        self
+    }
+
+    /// Derives the challenges of batched verifications with `challenge_hash`
+    /// instead of SHA-256. Provers and verifiers must agree on it.
+    pub fn with_challenge_hash(mut self, challenge_hash: ChallengeHash) -> Self {
+        self.challenge_hash = challenge_hash;
+        self
+    }
+
+    /// Returns the largest polynomial length that takes the small-blob path.
+    pub fn get_small_blob_threshold(&self) -> usize {
+        self.small_blob_threshold
+    }
+
+    /// Returns the number of points below which MSMs run on the calling thread.
+    pub fn get_msm_sequential_cutoff(&self) -> usize {
+        self.msm_sequential_cutoff
+    }
+
+    /// Returns the minimum number of scalars per parallel MSM task.
+    pub fn get_msm_min_chunk_len(&self) -> usize {
+        self.msm_min_chunk_len
+    }
+
+    /// Returns the length below which FFTs run single-threaded.
+    pub fn get_fft_sequential_cutoff(&self) -> usize {
+        self.fft_sequential_cutoff
+    }
+
+    /// Returns the hash challenges are derived with.
+    pub fn get_challenge_hash(&self) -> ChallengeHash {
+        self.challenge_hash
+    }
+
+    /// Returns the number of threads parallel work runs on.
+    pub fn get_num_threads(&self) -> usize {
+        match (&self.thread_pool, self.num_threads) {
+            (Some(pool), _) => pool.current_num_threads(),
+            (None, Some(num_threads)) => num_threads,
+            (None, None) => rayon::current_num_threads(),
+        }
+    }
+
+    /// Executes `op` inside the configured thread pool. Work that is already
+    /// running inside a rayon pool stays there rather than moving to another.
+    pub(crate) fn install<OP, R>(&self, op: OP) -> Result<R, KzgError>
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        if let Some(pool) = &self.thread_pool {
+            return Ok(pool.install(op));
+        }
+        let Some(num_threads) = self.num_threads else {
+            return Ok(op());
+        };
+        if rayon::current_thread_index().is_some() {
+            return Ok(op());
+        }
+        if self.num_threads_pool.get().is_none() {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .map_err(|err| KzgError::GenericError(err.to_string()))?;
+            // a concurrent first call may have won, then this pool is dropped
+            let _ = self.num_threads_pool.set(pool);
+        }
+        Ok(self.num_threads_pool.get().unwrap().install(op))
+    }
+
+    /// Executes an FFT of `length` points, single-threaded below the
+    /// sequential cutoff and on the configured thread pool otherwise. FFTs
+    /// nested in other parallel work follow the enclosing pool.
+    pub(crate) fn install_fft<OP, R>(&self, length: usize, op: OP) -> Result<R, KzgError>
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        if length >= self.fft_sequential_cutoff
+            || self.thread_pool.is_some()
+            || rayon::current_thread_index().is_some()
+        {
+            return self.install(op);
+        }
+        SEQUENTIAL_POOL.with(|pool| {
+            let pool =
+                pool.get_or_init(|| rayon::ThreadPoolBuilder::new().num_threads(1).build().ok());
+            match pool {
+                Some(pool) => Ok(pool.install(op)),
+                None => Err(KzgError::GenericError(
+                    "failed to build the sequential thread pool".to_string(),
+                )),
+            }
+        })
+    }
+
+    /// Returns the minimum chunk length for an MSM over `num_points` points,
+    /// or None if it should run on the calling thread.
+    pub(crate) fn msm_chunking(&self, num_points: usize) -> Option<usize> {
+        if num_points < self.msm_sequential_cutoff {
+            None
+        } else {
+            Some(self.msm_min_chunk_len)
+        }
+    }
+}
+
+impl Default for KzgConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for KzgConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KzgConfig")
+            .field("num_threads", &self.get_num_threads())
+            .field("custom_thread_pool", &self.thread_pool.is_some())
+            .field("small_blob_threshold", &self.small_blob_threshold)
+            .field("msm_sequential_cutoff", &self.msm_sequential_cutoff)
+            .field("msm_min_chunk_len", &self.msm_min_chunk_len)
+            .field("fft_sequential_cutoff", &self.fft_sequential_cutoff)
+            .field("challenge_hash", &self.challenge_hash)
+            .finish()
+    }
+}
+
+impl PartialEq for KzgConfig {
+    fn eq(&self, other: &Self) -> bool {
+        let same_pool = match (&self.thread_pool, &other.thread_pool) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_pool
+            && self.num_threads == other.num_threads
+            && self.small_blob_threshold == other.small_blob_threshold
+            && self.msm_sequential_cutoff == other.msm_sequential_cutoff
+            && self.msm_min_chunk_len == other.msm_min_chunk_len
+            && self.fft_sequential_cutoff == other.fft_sequential_cutoff
+            && self.challenge_hash == other.challenge_hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_uses_num_threads() {
+        let config = KzgConfig::new().with_num_threads(3);
+        assert_eq!(config.get_num_threads(), 3);
+        let threads = config.install(rayon::current_num_threads).unwrap();
+        assert_eq!(threads, 3);
+    }
+
+    #[test]
+    fn test_install_builds_pool_once() {
+        let config = KzgConfig::new().with_num_threads(2);
+        assert_eq!(config.install(rayon::current_num_threads).unwrap(), 2);
+        let pool: *const ThreadPool = config.num_threads_pool.get().unwrap();
+
+        // later installs, also through clones, reuse the same pool
+        let clone = config.clone();
+        for config in [&config, &clone] {
+            assert_eq!(config.install(rayon::current_num_threads).unwrap(), 2);
+            assert!(std::ptr::eq(config.num_threads_pool.get().unwrap(), pool));
+        }
+
+        // without a thread count work runs on the global pool
+        let config = KzgConfig::new();
+        assert_eq!(
+            config.install(rayon::current_num_threads).unwrap(),
+            rayon::current_num_threads()
+        );
+        assert!(config.num_threads_pool.get().is_none());
+    }
+
+    #[test]
+    fn test_install_uses_custom_thread_pool() {
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let config = KzgConfig::new().with_thread_pool(pool.clone());
+        assert_eq!(config.get_num_threads(), 2);
+        assert_eq!(config, KzgConfig::new().with_thread_pool(pool));
+        assert_ne!(config, KzgConfig::new());
+
+        // nested installs stay on the caller's pool
+        let threads = config
+            .install(|| config.install(rayon::current_num_threads).unwrap())
+            .unwrap();
+        assert_eq!(threads, 2);
+    }
+
+    #[test]
+    fn test_install_fft_respects_sequential_cutoff() {
+        let config = KzgConfig::new()
+            .with_num_threads(3)
+            .with_fft_sequential_cutoff(64);
+        assert_eq!(
+            config.install_fft(63, rayon::current_num_threads).unwrap(),
+            1
+        );
+        assert_eq!(
+            config.install_fft(64, rayon::current_num_threads).unwrap(),
+            3
+        );
+
+        // a custom pool is always used, so no threads are spawned
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let config = config.with_thread_pool(pool);
+        assert_eq!(
+            config.install_fft(1, rayon::current_num_threads).unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_msm_chunking() {
+        let config = KzgConfig::new()
+            .with_msm_sequential_cutoff(100)
+            .with_msm_min_chunk_len(0);
+        assert_eq!(config.get_msm_min_chunk_len(), 1);
+        assert_eq!(config.msm_chunking(99), None);
+        assert_eq!(config.msm_chunking(100), Some(1));
+        assert_ne!(config, KzgConfig::new());
+    }
+}
@@ -1,4 +1,85 @@
 pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
 pub const SIZE_OF_G1_AFFINE_COMPRESSED: usize = 32; // in bytes
+pub const SIZE_OF_G1_AFFINE_UNCOMPRESSED: usize = 64; // in bytes
 pub const SIZE_OF_G2_AFFINE_COMPRESSED: usize = 64; // in bytes
+
+// Polynomials up to this many field elements (4KB of blob data) are committed
+// and proven with a direct inverse DFT and naive MSM instead of the G1 IFFT.
+pub const SMALL_BLOB_FAST_PATH_THRESHOLD: usize = 128;
+// MSMs over fewer points than this run on the calling thread by default.
+pub const MSM_SEQUENTIAL_CUTOFF: usize = 1 << 8;
+// Default minimum number of scalars each parallel MSM task processes.
+pub const MSM_MIN_CHUNK_LEN: usize = 1 << 9;
+// FFTs shorter than this run single-threaded by default.
+pub const FFT_SEQUENTIAL_CUTOFF: usize = 1 << 10;
+// SRS files are read and parsed this many bytes at a time during setup.
+pub const SRS_READ_WINDOW_BYTES: usize = 1 << 20;
+// Number of consecutive powers `Kzg::verify_srs` checks with pairings.
+pub const SRS_VERIFICATION_SAMPLES: usize = 64;
+// Primitive roots of unity of each power of 2 order, indexed by log2 of the
+// order up to the two-adicity of Fr, as in the EigenDA Go code.
+pub const PRIMITIVE_ROOTS_OF_UNITY: [&str; 29] = [
+    "1",
+    "21888242871839275222246405745257275088548364400416034343698204186575808495616",
+    "21888242871839275217838484774961031246007050428528088939761107053157389710902",
+    "19540430494807482326159819597004422086093766032135589407132600596362845576832",
+    "14940766826517323942636479241147756311199852622225275649687664389641784935947",
+    "4419234939496763621076330863786513495701855246241724391626358375488475697872",
+    "9088801421649573101014283686030284801466796108869023335878462724291607593530",
+    "10359452186428527605436343203440067497552205259388878191021578220384701716497",
+    "3478517300119284901893091970156912948790432420133812234316178878452092729974",
+    "6837567842312086091520287814181175430087169027974246751610506942214842701774",
+    "3161067157621608152362653341354432744960400845131437947728257924963983317266",
+    "1120550406532664055539694724667294622065367841900378087843176726913374367458",
+    "4158865282786404163413953114870269622875596290766033564087307867933865333818",
+    "197302210312744933010843010704445784068657690384188106020011018676818793232",
+    "20619701001583904760601357484951574588621083236087856586626117568842480512645",
+    "20402931748843538985151001264530049874871572933694634836567070693966133783803",
+    "421743594562400382753388642386256516545992082196004333756405989743524594615",
+    "12650941915662020058015862023665998998969191525479888727406889100124684769509",
+    "11699596668367776675346610687704220591435078791727316319397053191800576917728",
+    "15549849457946371566896172786938980432421851627449396898353380550861104573629",
+    "17220337697351015657950521176323262483320249231368149235373741788599650842711",
+    "13536764371732269273912573961853310557438878140379554347802702086337840854307",
+    "12143866164239048021030917283424216263377309185099704096317235600302831912062",
+    "934650972362265999028062457054462628285482693704334323590406443310927365533",
+    "5709868443893258075976348696661355716898495876243883251619397131511003808859",
+    "19200870435978225707111062059747084165650991997241425080699860725083300967194",
+    "7419588552507395652481651088034484897579724952953562618697845598160172257810",
+    "2082940218526944230311718225077035922214683169814847712455127909555749686340",
+    "19103219067921713944291392827692070036145651957329286315305642004821462161904",
+];
+// Number of domains `Domain::new` keeps cached beyond those still in use,
+// the least recently used is dropped first.
+pub const DOMAIN_CACHE_CAPACITY: usize = 8;
+// Domain separation tags for the hashes behind data-availability sampling.
+pub const SAMPLE_INDICES_DOMAIN: &[u8] = b"RUST_KZG_BN254_SAMPLE_INDICES_V2";
+pub const SAMPLE_SEED_INDICES_DOMAIN: &[u8] = b"RUST_KZG_BN254_SAMPLE_SEED_INDICES_V1";
+pub const SAMPLE_CHALLENGE_DOMAIN: &[u8] = b"RUST_KZG_BN254_SAMPLE_CHALLENGE_V1";
+// Domain separation tag for the challenge batching blob certificate checks.
+pub const CERT_CHALLENGE_DOMAIN: &[u8] = b"RUST_KZG_BN254_CERT_CHALLENGE_V1";
+// Domain separation tag for deriving a ceremony participant's secret.
+pub const CEREMONY_SECRET_DOMAIN: &[u8] = b"RUST_KZG_BN254_CEREMONY_SECRET_V1";
+// Domain separation tag for deriving the tau of an insecure test SRS.
+pub const INSECURE_SRS_TAU_DOMAIN: &[u8] = b"RUST_KZG_BN254_INSECURE_SRS_TAU_V1";
+// Domain separation tag for the point a proof of equivalence opens at.
+pub const EQUIVALENCE_CHALLENGE_DOMAIN: &[u8] = b"RUST_KZG_BN254_EQUIVALENCE_CHALLENGE_V1";
+// Domain separation tags for the points blobs of a batch open at and the
+// challenge folding their openings.
+pub const BATCH_OPENING_DOMAIN: &[u8] = b"RUST_KZG_BN254_BATCH_OPENING_V1";
+pub const BATCH_CHALLENGE_DOMAIN: &[u8] = b"RUST_KZG_BN254_BATCH_CHALLENGE_V1";
+// c-kzg-4844's domain separation tag for the point a blob proof opens at.
+pub const BLOB_CHALLENGE_DOMAIN: &[u8] = b"FSBLOBVERIFY_V1_";
+// Domain separation tag for the challenge folding the openings of a batch of
+// blob proofs.
+pub const BLOB_BATCH_CHALLENGE_DOMAIN: &[u8] = b"RUST_KZG_BN254_BLOB_BATCH_CHALLENGE_V1";
+// Domain separation tag for the challenge combining polynomials opened at one
+// point.
+pub const AGGREGATION_CHALLENGE_DOMAIN: &[u8] = b"RUST_KZG_BN254_AGGREGATION_CHALLENGE_V1";
+// Domain separation tag for the point an aggregated blob proof opens at.
+pub const AGGREGATED_OPENING_DOMAIN: &[u8] = b"RUST_KZG_BN254_AGGREGATED_OPENING_V1";
+// Domain separation tag for the challenge folding a batch of cell proofs.
+#[cfg(feature = "peerdas")]
+pub const CELL_BATCH_CHALLENGE_DOMAIN: &[u8] = b"RUST_KZG_BN254_CELL_BATCH_CHALLENGE_V1";
+#[cfg(test)]
 pub const GETTYSBURG_ADDRESS_BYTES: &[u8] = "Fourscore and seven years ago our fathers brought forth, on this continent, a new nation, conceived in liberty, and dedicated to the proposition that all men are created equal. Now we are engaged in a great civil war, testing whether that nation, or any nation so conceived, and so dedicated, can long endure. We are met on a great battle-field of that war. We have come to dedicate a portion of that field, as a final resting-place for those who here gave their lives, that that nation might live. It is altogether fitting and proper that we should do this. But, in a larger sense, we cannot dedicate, we cannot consecrate—we cannot hallow—this ground. The brave men, living and dead, who struggled here, have consecrated it far above our poor power to add or detract. The world will little note, nor long remember what we say here, but it can never forget what they did here. It is for us the living, rather, to be dedicated here to the unfinished work which they who fought here have thus far so nobly advanced. It is rather for us to be here dedicated to the great task remaining before us—that from these honored dead we take increased devotion to that cause for which they here gave the last full measure of devotion—that we here highly resolve that these dead shall not have died in vain—that this nation, under God, shall have a new birth of freedom, and that government of the people, by the people, for the people, shall not perish from the earth.".as_bytes();
@@ -0,0 +1,144 @@
+use crate::{
+    blob::Blob,
+    encoder::{Chunk, Encoder, EncodingParams},
+    errors::EncoderError,
+    kzg::Kzg,
+    retrieve::BlobHeader,
+};
+use ark_bn254::{G1Affine, G2Affine};
+
+/// Everything a client sends the disperser for one payload: the padded blob,
+/// its commitments, and its chunks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreparedBlob {
+    blob: Blob,
+    commitment: G1Affine,
+    length_commitment: G2Affine,
+    length_proof: G2Affine,
+    length: usize,
+    params: EncodingParams,
+    chunks: Vec<Chunk>,
+}
+
+impl PreparedBlob {
+    /// Returns the padded blob.
+    pub fn get_blob(&self) -> &Blob {
+        &self.blob
+    }
+
+    /// Returns the commitment to the blob's polynomial.
+    pub fn get_commitment(&self) -> G1Affine {
+        self.commitment
+    }
+
+    /// Returns the commitment to the blob's polynomial in G2.
+    pub fn get_length_commitment(&self) -> G2Affine {
+        self.length_commitment
+    }
+
+    /// Returns the proof that the polynomial has at most `get_length`
+    /// coefficients.
+    pub fn get_length_proof(&self) -> G2Affine {
+        self.length_proof
+    }
+
+    /// Returns the number of field elements the polynomial is over, a power
+    /// of 2.
+    pub fn get_length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the header retrievers check the chunks against.
+    pub fn get_header(&self) -> BlobHeader {
+        BlobHeader::new(self.commitment, self.blob.len())
+    }
+
+    /// Returns the shape of the encoding the chunks follow.
+    pub fn get_params(&self) -> EncodingParams {
+        self.params
+    }
+
+    /// Returns the chunks, ordered by chunk index.
+    pub fn get_chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+}
+
+/// Pads `payload` into a blob, commits to it, proves its length and splits
+/// its extension into chunks with `encoder`.
+///
+/// The length commitment and proof need `kzg` to hold the full G2 table up to
+/// its SRS order, as operators' setups do.
+pub fn prepare(kzg: &Kzg, encoder: &Encoder, payload: &[u8]) -> Result<PreparedBlob, EncoderError> {
+    let blob = Blob::from_bytes_and_pad(payload);
+    let polynomial = blob
+        .to_polynomial()
+        .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+    let length = polynomial.len();
+
+    let commitment = kzg
+        .commit(&polynomial)
+        .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+    let length_commitment = kzg
+        .compute_length_commitment(&polynomial)
+        .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+    let length_proof = kzg
+        .compute_length_proof(&polynomial, polynomial.len())
+        .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+
+    Ok(PreparedBlob {
+        params: encoder.params(length)?,
+        chunks: encoder.encode_polynomial(&polynomial)?,
+        blob,
+        commitment,
+        length_commitment,
+        length_proof,
+        length,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    #[test]
+    fn test_prepare() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let encoder = Encoder::new(8, 2).unwrap();
+        let prepared = prepare(&kzg, &encoder, GETTYSBURG_ADDRESS_BYTES).unwrap();
+
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = blob.to_polynomial().unwrap();
+        assert_eq!(prepared.get_blob(), &blob);
+        assert_eq!(prepared.get_length(), polynomial.len());
+        assert_eq!(prepared.get_commitment(), kzg.commit(&polynomial).unwrap());
+        assert!(kzg
+            .verify_length_proof(
+                prepared.get_length_commitment(),
+                prepared.get_length_proof(),
+                prepared.get_length()
+            )
+            .unwrap());
+
+        assert_eq!(prepared.get_chunks().len(), 8);
+        assert_eq!(
+            encoder
+                .decode(prepared.get_chunks(), blob.len())
+                .unwrap()
+                .to_vec(),
+            polynomial.to_vec()
+        );
+        assert_eq!(
+            prepared.get_params().num_evaluations(),
+            polynomial.len() * 2
+        );
+    }
+}
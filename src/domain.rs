@@ -0,0 +1,249 @@
+//! Evaluation domains over the roots of unity of the BN254 scalar field.
+//!
+//! A `Domain` holds what proving and encoding need to know about the
+//! `size`-th roots of unity: their generator, the roots and their inverses,
+//! the bit-reversal permutation, and FFTs over them. The most recently used
+//! domains are cached per size and their tables are computed on first use,
+//! so `Kzg`, the encoder and `Polynomial` share one copy instead of expanding
+//! the roots on every call.
+
+use crate::{consts::DOMAIN_CACHE_CAPACITY, errors::KzgError, helpers};
+use ark_bn254::Fr;
+use ark_ff::Field;
+use ark_poly::{domain::DomainCoeff, EvaluationDomain, GeneralEvaluationDomain};
+use std::{
+    fmt,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+/// The log2 of the largest domain, the two-adicity of the scalar field.
+pub const MAX_LOG2_DOMAIN_SIZE: u32 = 28;
+
+/// The `size`-th roots of unity, for `size` a power of 2, see the module
+/// docs.
+pub struct Domain {
+    size: usize,
+    generator: Fr,
+    size_inv: Fr,
+    evaluation_domain: GeneralEvaluationDomain<Fr>,
+    roots_of_unity: OnceLock<Vec<Fr>>,
+    inverse_roots_of_unity: OnceLock<Vec<Fr>>,
+    bit_reversal: OnceLock<Vec<usize>>,
+}
+
+impl Domain {
+    /// Returns the domain of `size` roots of unity from the process-wide
+    /// cache, creating it if it isn't cached. Besides the domains still in
+    /// use, the cache keeps the `DOMAIN_CACHE_CAPACITY` most recently used
+    /// ones. `size` must be a power of 2 up to `2^MAX_LOG2_DOMAIN_SIZE`.
+    pub fn new(size: usize) -> Result<Arc<Domain>, KzgError> {
+        // least recently used first
+        static DOMAINS: OnceLock<Mutex<Vec<Arc<Domain>>>> = OnceLock::new();
+
+        let generator = helpers::primitive_root_of_unity(size).ok_or_else(|| {
+            KzgError::FftError(format!(
+                "no domain of {size} roots of unity, the size must be a power of 2 up to \
+                 2^{MAX_LOG2_DOMAIN_SIZE}"
+            ))
+        })?;
+        let mut domains = DOMAINS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(position) = domains.iter().position(|domain| domain.size == size) {
+            let domain = domains.remove(position);
+            domains.push(domain.clone());
+            return Ok(domain);
+        }
+        let evaluation_domain = GeneralEvaluationDomain::<Fr>::new(size)
+            .ok_or_else(|| KzgError::FftError("failed to construct domain for FFT".to_string()))?;
+        let domain = Arc::new(Domain {
+            size,
+            generator,
+            size_inv: Fr::from(size as u64).inverse().unwrap(),
+            evaluation_domain,
+            roots_of_unity: OnceLock::new(),
+            inverse_roots_of_unity: OnceLock::new(),
+            bit_reversal: OnceLock::new(),
+        });
+        domains.push(domain.clone());
+        // domains held elsewhere cost nothing extra to keep, so only evict
+        // unused ones
+        while domains.len() > DOMAIN_CACHE_CAPACITY {
+            match domains
+                .iter()
+                .position(|domain| Arc::strong_count(domain) == 1)
+            {
+                Some(position) => drop(domains.remove(position)),
+                None => break,
+            }
+        }
+        Ok(domain)
+    }
+
+    /// Returns the number of roots of unity.
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the primitive `size`-th root of unity the roots are powers of.
+    pub fn get_generator(&self) -> Fr {
+        self.generator
+    }
+
+    /// Returns the inverse of the size in the field, the scale of an inverse
+    /// FFT.
+    pub fn get_size_inv(&self) -> Fr {
+        self.size_inv
+    }
+
+    /// Returns the roots of unity `[1, w, w^2, ..., w^(size-1)]`.
+    pub fn get_roots_of_unity(&self) -> &[Fr] {
+        self.roots_of_unity.get_or_init(|| {
+            helpers::roots_of_unity(self.size).expect("domain sizes are checked on creation")
+        })
+    }
+
+    /// Returns the inverses of the roots of unity, `[1, w^-1, ..., w^-(size-1)]`,
+    /// the twiddles of an inverse FFT.
+    pub fn get_inverse_roots_of_unity(&self) -> &[Fr] {
+        self.inverse_roots_of_unity.get_or_init(|| {
+            // w^-i = w^(size - i)
+            let roots = self.get_roots_of_unity();
+            (0..self.size)
+                .map(|i| roots[(self.size - i) % self.size])
+                .collect()
+        })
+    }
+
+    /// Returns the bit-reversal permutation of the domain, which maps each
+    /// index to the index with its `log2(size)` bits reversed.
+    pub fn get_bit_reversal(&self) -> &[usize] {
+        self.bit_reversal
+            .get_or_init(|| (0..self.size).map(|i| reverse_bits(i, self.size)).collect())
+    }
+
+    /// Returns the `index`-th root of unity, `w^index`, without computing the
+    /// table of roots.
+    pub fn element(&self, index: usize) -> Fr {
+        match self.roots_of_unity.get() {
+            Some(roots) => roots[index % self.size],
+            None => self.generator.pow([(index % self.size) as u64]),
+        }
+    }
+
+    /// Returns the arkworks domain the FFTs run on, e.g. to take a coset of
+    /// it.
+    pub fn get_evaluation_domain(&self) -> &GeneralEvaluationDomain<Fr> {
+        &self.evaluation_domain
+    }
+
+    /// Evaluates the polynomial with `coefficients` over the roots of unity.
+    /// Fewer coefficients than the size are padded with zeros.
+    pub fn fft<T: DomainCoeff<Fr>>(&self, coefficients: &[T]) -> Vec<T> {
+        self.evaluation_domain.fft(coefficients)
+    }
+
+    /// Interpolates the coefficients of the polynomial with `evaluations`
+    /// over the roots of unity.
+    pub fn ifft<T: DomainCoeff<Fr>>(&self, evaluations: &[T]) -> Vec<T> {
+        self.evaluation_domain.ifft(evaluations)
+    }
+
+    /// Like `fft`, in place.
+    pub fn fft_in_place<T: DomainCoeff<Fr>>(&self, coefficients: &mut Vec<T>) {
+        self.evaluation_domain.fft_in_place(coefficients)
+    }
+
+    /// Like `ifft`, in place.
+    pub fn ifft_in_place<T: DomainCoeff<Fr>>(&self, evaluations: &mut Vec<T>) {
+        self.evaluation_domain.ifft_in_place(evaluations)
+    }
+}
+
+impl fmt::Debug for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Domain")
+            .field("size", &self.size)
+            .field("generator", &self.generator)
+            .finish()
+    }
+}
+
+/// reverses the low `log2(size)` bits of `value`, for `size` a power of 2
+pub(crate) fn reverse_bits(value: usize, size: usize) -> usize {
+    if size <= 1 {
+        return 0;
+    }
+    value.reverse_bits() >> (usize::BITS - size.trailing_zeros())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::{One, UniformRand, Zero};
+
+    #[test]
+    fn test_domain() {
+        for log2 in [0, 1, 5, 10] {
+            let size = 1 << log2;
+            let domain = Domain::new(size).unwrap();
+            assert!(Arc::ptr_eq(&domain, &Domain::new(size).unwrap()));
+            assert_eq!(domain.get_size(), size);
+            assert_eq!(domain.get_size_inv() * Fr::from(size as u64), Fr::one());
+
+            // the roots match arkworks', which the FFTs run over
+            let arkworks = GeneralEvaluationDomain::<Fr>::new(size).unwrap();
+            assert_eq!(domain.get_generator(), arkworks.group_gen());
+            let roots = domain.get_roots_of_unity();
+            assert_eq!(roots, arkworks.elements().collect::<Vec<_>>());
+            for (i, (root, inverse)) in roots
+                .iter()
+                .zip(domain.get_inverse_roots_of_unity())
+                .enumerate()
+            {
+                assert_eq!(*root * inverse, Fr::one());
+                assert_eq!(domain.element(i), *root);
+            }
+
+            let bit_reversal = domain.get_bit_reversal();
+            for i in 0..size {
+                assert_eq!(bit_reversal[bit_reversal[i]], i);
+            }
+
+            let mut rng = ark_std::test_rng();
+            let coefficients: Vec<Fr> = (0..size).map(|_| Fr::rand(&mut rng)).collect();
+            let evaluations = domain.fft(&coefficients);
+            for (root, evaluation) in roots.iter().zip(&evaluations) {
+                let expected = coefficients
+                    .iter()
+                    .rev()
+                    .fold(Fr::zero(), |acc, c| acc * root + c);
+                assert_eq!(*evaluation, expected);
+            }
+            assert_eq!(domain.ifft(&evaluations), coefficients);
+        }
+        let polynomial = crate::polynomial::Polynomial::new(&vec![Fr::one(); 5], 160).unwrap();
+        assert!(Arc::ptr_eq(
+            &polynomial.domain().unwrap(),
+            &Domain::new(8).unwrap()
+        ));
+        assert_eq!(
+            Domain::new(8).unwrap().get_bit_reversal(),
+            [0, 4, 2, 6, 1, 5, 3, 7]
+        );
+
+        for size in [0, 3, 1 << 29] {
+            assert!(Domain::new(size).is_err());
+        }
+
+        // unused domains are evicted once more recent ones fill the cache
+        let unused = Arc::downgrade(&Domain::new(1 << 27).unwrap());
+        let held = Domain::new(1 << 26).unwrap();
+        for log2 in 0..26 {
+            Domain::new(1 << log2).unwrap();
+        }
+        assert!(unused.upgrade().is_none());
+        assert!(Arc::ptr_eq(&held, &Domain::new(1 << 26).unwrap()));
+    }
+}
@@ -0,0 +1,183 @@
+//! The commit, prove and verify path without the standard library.
+//!
+//! `Kzg` loads its SRS from files and spreads work over thread pools, neither
+//! of which exists inside embedded verifiers or zkVM guests. `EmbeddedKzg`
+//! holds an SRS handed to it as points or bytes and only needs `alloc`, so it
+//! builds with `default-features = false`. Its commitments and proofs are the
+//! same as `Kzg`'s for the same SRS, and the polynomial division behind them
+//! is the one `Kzg` uses, from `helpers`. Its group operations and pairings go
+//! through the `Bn254Ops` of `accel`, so zkVM guests can run them on their
+//! precompiles.
+
+use crate::{
+    accel::{self, ArkworksOps, Bn254Ops},
+    consts::SIZE_OF_G1_AFFINE_COMPRESSED,
+    errors::KzgError,
+    helpers,
+    polynomial::Polynomial,
+};
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_std::{format, marker::PhantomData, string::ToString, vec::Vec};
+
+/// A KZG setup over an SRS already in memory, running its BN254 operations
+/// with `B`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmbeddedKzg<B = ArkworksOps> {
+    g1: Vec<G1Affine>,
+    g2_tau: G2Affine,
+    ops: PhantomData<B>,
+}
+
+impl EmbeddedKzg {
+    /// Creates a setup from the G1 powers of tau and `[tau]G2`.
+    pub fn new(g1: Vec<G1Affine>, g2_tau: G2Affine) -> Self {
+        EmbeddedKzg {
+            g1,
+            g2_tau,
+            ops: PhantomData,
+        }
+    }
+
+    /// Creates a setup from the concatenated 32-byte compressed G1 points and
+    /// the 64-byte compressed `[tau]G2`, in the big-endian encoding of the
+    /// SRS files. The points are read in place, without copying the bytes.
+    pub fn from_bytes(g1_bytes: &[u8], g2_tau_bytes: &[u8]) -> Result<Self, KzgError> {
+        if g1_bytes.len() % SIZE_OF_G1_AFFINE_COMPRESSED != 0 {
+            return Err(KzgError::SerializationError(format!(
+                "G1 points are {} bytes, not a multiple of {SIZE_OF_G1_AFFINE_COMPRESSED}",
+                g1_bytes.len()
+            )));
+        }
+        let g1 = g1_bytes
+            .chunks_exact(SIZE_OF_G1_AFFINE_COMPRESSED)
+            .map(|chunk| {
+                helpers::read_g1_point_from_bytes_be(chunk)
+                    .map_err(|err| KzgError::SerializationError(err.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2_tau = helpers::read_g2_point_from_bytes_be(g2_tau_bytes)
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        Ok(EmbeddedKzg::new(g1, g2_tau))
+    }
+}
+
+impl<B: Bn254Ops> EmbeddedKzg<B> {
+    /// Returns the same setup running its BN254 operations with `C`.
+    pub fn with_ops<C: Bn254Ops>(self) -> EmbeddedKzg<C> {
+        EmbeddedKzg {
+            g1: self.g1,
+            g2_tau: self.g2_tau,
+            ops: PhantomData,
+        }
+    }
+
+    /// Returns the G1 powers of tau.
+    pub fn get_g1_points(&self) -> &[G1Affine] {
+        &self.g1
+    }
+
+    /// Returns `[tau]G2`.
+    pub fn get_g2_tau(&self) -> G2Affine {
+        self.g2_tau
+    }
+
+    /// Commits to `polynomial`, given in evaluation form.
+    pub fn commit(&self, polynomial: &Polynomial) -> Result<G1Affine, KzgError> {
+        let coefficients = self.coefficients(polynomial)?;
+        self.msm(&coefficients)
+    }
+
+    /// Proves the evaluation of `polynomial` at `z`, returning the proof and
+    /// the value.
+    pub fn compute_proof_at_point(
+        &self,
+        polynomial: &Polynomial,
+        z: Fr,
+    ) -> Result<(G1Affine, Fr), KzgError> {
+        let coefficients = self.coefficients(polynomial)?;
+        // the remainder of the division by (X - z) is the value at z
+        let (quotient, value) = helpers::divide_by_linear(&coefficients, z);
+        Ok((self.msm(&quotient)?, value))
+    }
+
+    /// Checks `proof` opens `commitment` to `value` at `z`.
+    pub fn verify_proof(&self, commitment: G1Affine, proof: G1Affine, value: Fr, z: Fr) -> bool {
+        let commit_minus_value = commitment.into_group() - accel::g1_generator_mul::<B>(&value);
+        let x_minus_z = self.g2_tau.into_group() - accel::g2_generator_mul::<B>(&z);
+        B::pairing_check(
+            &[commit_minus_value.into_affine(), -proof],
+            &[G2Affine::generator(), x_minus_z.into_affine()],
+        )
+    }
+
+    /// interpolates the evaluations into monomial coefficients, checking the
+    /// SRS is long enough to commit to them
+    fn coefficients(&self, polynomial: &Polynomial) -> Result<Vec<Fr>, KzgError> {
+        if polynomial.len() > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+        let domain = GeneralEvaluationDomain::<Fr>::new(polynomial.len())
+            .ok_or_else(|| KzgError::FftError("failed to construct domain for IFFT".to_string()))?;
+        Ok(domain.ifft(polynomial.as_slice()))
+    }
+
+    fn msm(&self, scalars: &[Fr]) -> Result<G1Affine, KzgError> {
+        let bases = self
+            .g1
+            .get(..scalars.len())
+            .ok_or_else(|| KzgError::CommitError("more scalars than G1 points".to_string()))?;
+        Ok(B::g1_msm(bases, scalars).into_affine())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES, kzg::Kzg};
+
+    #[test]
+    fn test_embedded_kzg() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let g2_tau = kzg.get_g2_points()[1];
+        let embedded = EmbeddedKzg::new(kzg.get_g1_points(), g2_tau);
+
+        // the SRS files hold the same points as bytes
+        let g1_bytes = std::fs::read("src/test-files/g1.point").unwrap();
+        let g2_bytes = std::fs::read("src/test-files/g2.point").unwrap();
+        let from_bytes =
+            EmbeddedKzg::from_bytes(&g1_bytes[..32 * 3000], &g2_bytes[64..128]).unwrap();
+        assert_eq!(from_bytes, embedded);
+        assert!(EmbeddedKzg::from_bytes(&g1_bytes[..33], &g2_bytes[64..128]).is_err());
+
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let commitment = embedded.commit(&polynomial).unwrap();
+        assert_eq!(commitment, kzg.commit(&polynomial).unwrap());
+
+        let z = Fr::from(1234u64);
+        let (proof, value) = embedded.compute_proof_at_point(&polynomial, z).unwrap();
+        assert_eq!(
+            (proof, value),
+            kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap()
+        );
+        assert!(embedded.verify_proof(commitment, proof, value, z));
+        assert!(kzg.verify_kzg_proof(commitment, proof, value, z));
+        assert!(!embedded.verify_proof(commitment, proof, value + Fr::from(1u64), z));
+        assert!(!embedded.verify_proof(commitment, proof, value, z + Fr::from(1u64)));
+
+        let short = EmbeddedKzg::new(embedded.get_g1_points()[..4].to_vec(), g2_tau);
+        assert!(short.commit(&polynomial).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! A 3000-point SRS compiled into the crate.
+//!
+//! The `embedded-srs` feature bakes the SRS files under `src/test-files` into
+//! the binary with `include_bytes!`, adding about 288KB, so examples, tests
+//! and wasm demos can set up without reading files or fetching the SRS. The
+//! points are the first 3000 powers of the EigenDA SRS, enough for blobs of
+//! up to 96000 bytes. Production deployments should still load a full SRS.
+
+#[cfg(feature = "std")]
+use crate::kzg::Kzg;
+use crate::{embedded::EmbeddedKzg, errors::KzgError};
+
+/// The compressed G1 points, in the format of `g1.point`.
+pub const EMBEDDED_G1_POINTS: &[u8] = include_bytes!("test-files/g1.point");
+/// The compressed G2 points, in the format of `g2.point`.
+pub const EMBEDDED_G2_POINTS: &[u8] = include_bytes!("test-files/g2.point");
+/// The number of G1 and G2 points embedded.
+pub const EMBEDDED_SRS_POINTS: u32 = 3000;
+
+impl EmbeddedKzg {
+    /// Creates a setup from the embedded SRS.
+    pub fn from_embedded_srs() -> Result<Self, KzgError> {
+        // [tau]G2 is the second G2 point
+        EmbeddedKzg::from_bytes(EMBEDDED_G1_POINTS, &EMBEDDED_G2_POINTS[64..128])
+    }
+}
+
+#[cfg(feature = "std")]
+impl Kzg {
+    /// Creates a setup from the embedded SRS, the same as
+    /// `Kzg::setup("g1.point", "g2.point", "", 3000, 3000)` over the test
+    /// files.
+    pub fn setup_embedded() -> Result<Self, KzgError> {
+        Kzg::setup_from_bytes(
+            EMBEDDED_G1_POINTS,
+            EMBEDDED_G2_POINTS,
+            false,
+            EMBEDDED_SRS_POINTS,
+            EMBEDDED_SRS_POINTS,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+
+    #[test]
+    fn test_setup_embedded() {
+        let kzg = Kzg::setup_embedded().unwrap();
+        let from_files = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            3000,
+        )
+        .unwrap();
+        assert_eq!(kzg.get_g1_points(), from_files.get_g1_points());
+        assert_eq!(kzg.get_g2_points(), from_files.get_g2_points());
+        assert_eq!(kzg.verifier(), from_files.verifier());
+
+        let embedded = EmbeddedKzg::from_embedded_srs().unwrap();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        assert_eq!(
+            embedded.commit(&polynomial).unwrap(),
+            kzg.commit(&polynomial).unwrap()
+        );
+    }
+}
@@ -0,0 +1,725 @@
+use crate::{
+    blob::Blob,
+    consts::BYTES_PER_FIELD_ELEMENT,
+    domain::{reverse_bits, Domain},
+    errors::EncoderError,
+    kzg::Kzg,
+    polynomial::Polynomial,
+};
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{batch_inversion, FftField, Field};
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain};
+use ark_std::{One, Zero};
+use std::sync::Arc;
+
+/// The shape of an encoded blob: `num_chunks` chunks of `chunk_length`
+/// evaluations each, over a domain of `num_chunks * chunk_length` roots of
+/// unity.
+///
+/// Follows the EigenDA assignment rules: chunk `i` holds the evaluations over
+/// the coset `w^j * <w^num_chunks>`, where `w` generates the extended domain
+/// and `j` is `i` with its `log2(num_chunks)` bits reversed. This is the same
+/// as bit-reversing the extended evaluations, cutting them into contiguous
+/// chunks, and putting each chunk back into natural coset order, which is what
+/// the Go encoder does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncodingParams {
+    num_chunks: usize,
+    chunk_length: usize,
+}
+
+impl EncodingParams {
+    /// Returns the number of chunks.
+    pub fn get_num_chunks(&self) -> usize {
+        self.num_chunks
+    }
+
+    /// Returns the number of evaluations in each chunk.
+    pub fn get_chunk_length(&self) -> usize {
+        self.chunk_length
+    }
+
+    /// Returns the size of the extended evaluation domain.
+    pub fn num_evaluations(&self) -> usize {
+        self.num_chunks * self.chunk_length
+    }
+
+    /// Returns the power of the extended domain's generator that shifts the
+    /// coset of chunk `chunk_index`.
+    pub fn coset_index(&self, chunk_index: usize) -> usize {
+        reverse_bits(chunk_index, self.num_chunks)
+    }
+
+    /// Returns the shift `h` of the coset `h * <w^num_chunks>` holding the
+    /// evaluations of chunk `chunk_index`.
+    pub fn coset_shift(&self, chunk_index: usize) -> Fr {
+        self.domain_generator()
+            .pow([self.coset_index(chunk_index) as u64])
+    }
+
+    /// Returns the points chunk `chunk_index` is evaluated at, in the order of
+    /// its evaluations.
+    pub fn evaluation_points(&self, chunk_index: usize) -> Vec<Fr> {
+        let step = self.domain_generator().pow([self.num_chunks as u64]);
+        let mut point = self.coset_shift(chunk_index);
+        (0..self.chunk_length)
+            .map(|_| {
+                let current = point;
+                point *= step;
+                current
+            })
+            .collect()
+    }
+
+    fn domain_generator(&self) -> Fr {
+        Domain::new(self.num_evaluations())
+            .expect("encoding params are checked against the two-adicity of Fr")
+            .get_generator()
+    }
+}
+
+/// One chunk of an encoded blob, as assigned to a DA node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chunk {
+    index: usize,
+    evaluations: Vec<Fr>,
+}
+
+impl Chunk {
+    /// Creates a chunk from evaluations received for chunk `index`.
+    pub fn new(index: usize, evaluations: Vec<Fr>) -> Self {
+        Chunk { index, evaluations }
+    }
+
+    /// Returns the position of the chunk in the encoded blob.
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the chunk's evaluations, in the order given by
+    /// `EncodingParams::evaluation_points`.
+    pub fn get_evaluations(&self) -> &[Fr] {
+        &self.evaluations
+    }
+
+    /// Returns the number of evaluations in the chunk.
+    pub fn len(&self) -> usize {
+        self.evaluations.len()
+    }
+
+    /// Checks if the chunk has no evaluations.
+    pub fn is_empty(&self) -> bool {
+        self.evaluations.is_empty()
+    }
+}
+
+/// Reed-Solomon encoder that extends blobs by a fixed coding rate and splits
+/// the result into chunks for dispersal.
+///
+/// The extension is systematic: the polynomial's evaluations over its own
+/// domain reappear unchanged at every `coding_rate`-th point of the extended
+/// domain, so the encoded data commits to the same polynomial as the blob.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Encoder {
+    num_chunks: usize,
+    coding_rate: usize,
+}
+
+impl Encoder {
+    /// Creates an encoder producing `num_chunks` chunks, rounded up to a power
+    /// of 2, from blobs extended by `coding_rate`, which must be a power of 2.
+    pub fn new(num_chunks: usize, coding_rate: usize) -> Result<Self, EncoderError> {
+        if num_chunks == 0 {
+            return Err(EncoderError::InvalidParametersError(
+                "number of chunks must be positive".to_string(),
+            ));
+        }
+        if !coding_rate.is_power_of_two() {
+            return Err(EncoderError::InvalidParametersError(
+                "coding rate must be a power of 2".to_string(),
+            ));
+        }
+        Ok(Encoder {
+            num_chunks: num_chunks.next_power_of_two(),
+            coding_rate,
+        })
+    }
+
+    /// Returns the number of chunks each blob is split into.
+    pub fn get_num_chunks(&self) -> usize {
+        self.num_chunks
+    }
+
+    /// Returns the factor blobs are extended by.
+    pub fn get_coding_rate(&self) -> usize {
+        self.coding_rate
+    }
+
+    /// Returns the encoding of a polynomial of `length` field elements.
+    ///
+    /// The extended domain holds at least `coding_rate` times the padded
+    /// length, and is grown further if needed to give every chunk at least one
+    /// evaluation.
+    pub fn params(&self, length: usize) -> Result<EncodingParams, EncoderError> {
+        let num_evaluations = length
+            .next_power_of_two()
+            .checked_mul(self.coding_rate)
+            .filter(|&n| n.trailing_zeros() <= Fr::TWO_ADICITY)
+            .ok_or_else(|| {
+                EncoderError::InvalidParametersError(
+                    "extended domain is larger than the field supports".to_string(),
+                )
+            })?
+            .max(self.num_chunks);
+        if num_evaluations.trailing_zeros() > Fr::TWO_ADICITY {
+            return Err(EncoderError::InvalidParametersError(
+                "number of chunks is larger than the field supports".to_string(),
+            ));
+        }
+        Ok(EncodingParams {
+            num_chunks: self.num_chunks,
+            chunk_length: num_evaluations / self.num_chunks,
+        })
+    }
+
+    /// Extends the evaluations of `polynomial` to the domain of
+    /// `params(polynomial.len())`, returning them in natural order.
+    pub fn extend(&self, polynomial: &Polynomial) -> Result<Vec<Fr>, EncoderError> {
+        let params = self.params(polynomial.len())?;
+        let extended_domain = fft_domain(params.num_evaluations())?;
+
+        let mut evaluations = Self::coefficients(polynomial)?;
+        extended_domain.fft_in_place(&mut evaluations);
+        Ok(evaluations)
+    }
+
+    /// Converts the padded `blob` to its polynomial and encodes it.
+    pub fn encode(&self, blob: &Blob) -> Result<Vec<Chunk>, EncoderError> {
+        let polynomial = blob
+            .to_polynomial()
+            .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+        self.encode_polynomial(&polynomial)
+    }
+
+    /// Extends `polynomial` and splits the result into chunks, ordered by
+    /// chunk index.
+    pub fn encode_polynomial(&self, polynomial: &Polynomial) -> Result<Vec<Chunk>, EncoderError> {
+        let params = self.params(polynomial.len())?;
+        let extended = self.extend(polynomial)?;
+        Ok((0..params.num_chunks)
+            .map(|index| {
+                let offset = params.coset_index(index);
+                Chunk {
+                    index,
+                    evaluations: extended[offset..]
+                        .iter()
+                        .step_by(params.num_chunks)
+                        .copied()
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+
+    /// Computes one multireveal proof per chunk of `polynomial`, ordered by
+    /// chunk index.
+    ///
+    /// The proof for a chunk over the coset `h * <w^num_chunks>` of size `L` is
+    /// a commitment to the quotient of the polynomial by the coset's vanishing
+    /// polynomial `X^L - h^L`, which opens all of the chunk's evaluations at
+    /// once. All of them are computed together with the FK20 multi-proof
+    /// algorithm of `Kzg::compute_coset_proofs`, in O(n log n) group
+    /// operations instead of an MSM per chunk.
+    pub fn prove_chunks(
+        &self,
+        kzg: &Kzg,
+        polynomial: &Polynomial,
+    ) -> Result<Vec<G1Affine>, EncoderError> {
+        let params = self.params(polynomial.len())?;
+        let proofs = kzg
+            .compute_coset_proofs(polynomial, params.chunk_length, params.num_chunks)
+            .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+        Ok((0..params.num_chunks)
+            .map(|index| proofs[params.coset_index(index)])
+            .collect())
+    }
+
+    /// Checks `proof` for `chunk` of a polynomial of `length` field elements
+    /// against the polynomial's `commitment`.
+    ///
+    /// Verifies `e(C - [I(tau)], G2) = e(proof, [tau^L - h^L]G2)`, with `I` the
+    /// polynomial interpolating the chunk over its coset `h * <w^num_chunks>`
+    /// of size `L`. Needs `[tau^L]G2` in the G2 table of `kzg`.
+    pub fn verify_chunk(
+        &self,
+        kzg: &Kzg,
+        commitment: &G1Affine,
+        length: usize,
+        chunk: &Chunk,
+        proof: &G1Affine,
+    ) -> Result<bool, EncoderError> {
+        let params = self.params(length)?;
+        let chunk_length = params.chunk_length;
+        if chunk.index >= params.num_chunks || chunk.len() != chunk_length {
+            return Err(EncoderError::InvalidParametersError(
+                "chunk does not match the encoding parameters".to_string(),
+            ));
+        }
+
+        let shift = params.coset_shift(chunk.index);
+        let coset = fft_domain(chunk_length)?
+            .get_evaluation_domain()
+            .get_coset(shift)
+            .ok_or_else(|| {
+                EncoderError::FftError("failed to construct coset for IFFT".to_string())
+            })?;
+        let interpolation = kzg
+            .commit_coefficients(&coset.ifft(&chunk.evaluations))
+            .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+        let tau_power = kzg
+            .g2_tau_power_of_2(chunk_length.trailing_zeros() as usize)
+            .ok_or_else(|| {
+                EncoderError::GenericError(
+                    "G2 table does not hold the power of tau needed for the chunk".to_string(),
+                )
+            })?;
+        let vanishing =
+            tau_power.into_group() - G2Affine::generator() * shift.pow([chunk_length as u64]);
+
+        Ok(Kzg::pairings_verify(
+            (*commitment - interpolation).into_affine(),
+            G2Affine::generator(),
+            *proof,
+            vanishing.into_affine(),
+        ))
+    }
+
+    /// Recovers the polynomial of a blob of `length_of_padded_blob` bytes from
+    /// any subset of its chunks that together hold at least as many
+    /// evaluations as the polynomial has.
+    ///
+    /// With `E` the extended evaluations, zeroed where chunks are missing, and
+    /// `Z` the polynomial vanishing on the missing cosets, `E * Z` agrees with
+    /// `P * Z` on the whole domain and has low enough degree to be
+    /// interpolated from it. `P` is then `(P * Z) / Z`, divided pointwise over
+    /// a coset of the domain where `Z` has no roots. Fails if the chunks are
+    /// malformed, too few, or inconsistent with any polynomial of the
+    /// expected degree.
+    pub fn decode(
+        &self,
+        chunks: &[Chunk],
+        length_of_padded_blob: usize,
+    ) -> Result<Polynomial, EncoderError> {
+        let num_elements = length_of_padded_blob.div_ceil(BYTES_PER_FIELD_ELEMENT);
+        if num_elements == 0 {
+            return Err(EncoderError::InvalidParametersError(
+                "blob length must be positive".to_string(),
+            ));
+        }
+        let length = num_elements.next_power_of_two();
+        let params = self.params(length)?;
+        let num_chunks = params.num_chunks;
+        let chunk_length = params.chunk_length;
+        let num_evaluations = params.num_evaluations();
+
+        // scatter the received evaluations, marking which cosets are present
+        let mut evaluations = vec![Fr::zero(); num_evaluations];
+        let mut received = vec![false; num_chunks];
+        for chunk in chunks {
+            if chunk.index >= num_chunks || chunk.len() != chunk_length {
+                return Err(EncoderError::InvalidParametersError(
+                    "chunk does not match the encoding parameters".to_string(),
+                ));
+            }
+            let offset = params.coset_index(chunk.index);
+            if received[offset] {
+                return Err(EncoderError::InvalidParametersError(
+                    "chunk index is repeated".to_string(),
+                ));
+            }
+            received[offset] = true;
+            for (t, eval) in chunk.evaluations.iter().enumerate() {
+                evaluations[offset + t * num_chunks] = *eval;
+            }
+        }
+        let num_received = received.iter().filter(|&&r| r).count();
+        if num_received * chunk_length < length {
+            return Err(EncoderError::InvalidParametersError(
+                "not enough chunks to recover the polynomial".to_string(),
+            ));
+        }
+
+        let domain = fft_domain(num_evaluations)?;
+        let shifted_domain = domain
+            .get_evaluation_domain()
+            .get_coset(Fr::GENERATOR)
+            .ok_or_else(|| {
+                EncoderError::FftError("failed to construct coset for FFT".to_string())
+            })?;
+
+        // Z(X) = z(X^L) with z(Y) the product of (Y - c) over the missing
+        // cosets' c = h^L, which are num_chunks-th roots of unity. Both on the
+        // domain and on its shift, x^L only depends on the position modulo
+        // num_chunks, so z is evaluated at num_chunks points per domain.
+        let missing_roots: Vec<Fr> = (0..num_chunks)
+            .filter(|&offset| !received[offset])
+            .map(|offset| domain.element(offset * chunk_length))
+            .collect();
+        let vanishing = |y: Fr| missing_roots.iter().map(|c| y - c).product::<Fr>();
+        let z_on_domain: Vec<Fr> = (0..num_chunks)
+            .map(|s| vanishing(domain.element(s * chunk_length)))
+            .collect();
+        let shift_power = Fr::GENERATOR.pow([chunk_length as u64]);
+        let mut z_on_shifted: Vec<Fr> = (0..num_chunks)
+            .map(|s| vanishing(shift_power * domain.element(s * chunk_length)))
+            .collect();
+
+        for (i, eval) in evaluations.iter_mut().enumerate() {
+            *eval *= z_on_domain[i % num_chunks];
+        }
+        domain.ifft_in_place(&mut evaluations);
+        shifted_domain.fft_in_place(&mut evaluations);
+        batch_inversion(&mut z_on_shifted);
+        for (i, eval) in evaluations.iter_mut().enumerate() {
+            *eval *= z_on_shifted[i % num_chunks];
+        }
+        shifted_domain.ifft_in_place(&mut evaluations);
+
+        if evaluations[length..].iter().any(|c| !c.is_zero()) {
+            return Err(EncoderError::GenericError(
+                "chunks are not consistent with a polynomial of the blob's length".to_string(),
+            ));
+        }
+        evaluations.truncate(length);
+        fft_domain(length)?.fft_in_place(&mut evaluations);
+        if evaluations[num_elements..].iter().any(|e| !e.is_zero()) {
+            return Err(EncoderError::GenericError(
+                "chunks are not consistent with a polynomial of the blob's length".to_string(),
+            ));
+        }
+        evaluations.truncate(num_elements);
+
+        Polynomial::new(&evaluations, length_of_padded_blob)
+            .map_err(|err| EncoderError::GenericError(err.to_string()))
+    }
+
+    /// converts the evaluations of `polynomial` to monomial coefficients
+    fn coefficients(polynomial: &Polynomial) -> Result<Vec<Fr>, EncoderError> {
+        Ok(fft_domain(polynomial.len())?.ifft(polynomial.as_slice()))
+    }
+}
+
+/// Recovers the evaluations over all `domain_size` roots of unity of a
+/// polynomial of degree below `domain_size / 2`, i.e. one extended at coding
+/// rate 2, from at least half of them, given as `(index, evaluation)` pairs.
+/// Returns the evaluations in natural order.
+///
+/// With `E` the evaluations, zeroed where they are missing, and `Z` the
+/// polynomial vanishing on the missing roots, `E * Z` agrees with `P * Z` on
+/// the whole domain, where `P` is the polynomial sought, and has degree below
+/// `domain_size`, so it is interpolated from the domain. `P` is then
+/// `(P * Z) / Z`, divided pointwise over a coset of the domain where `Z` has
+/// no roots. Fails if an index is out of range or repeated, too few
+/// evaluations are known, or they don't fit a polynomial of low enough
+/// degree.
+pub fn recover_polynomial(
+    known_evals: &[(usize, Fr)],
+    domain_size: usize,
+) -> Result<Vec<Fr>, EncoderError> {
+    if !domain_size.is_power_of_two() || domain_size.trailing_zeros() > Fr::TWO_ADICITY {
+        return Err(EncoderError::InvalidParametersError(
+            "domain size must be a power of 2 the field supports".to_string(),
+        ));
+    }
+    let mut evaluations = vec![Fr::zero(); domain_size];
+    let mut known = vec![false; domain_size];
+    for &(index, eval) in known_evals {
+        if index >= domain_size || known[index] {
+            return Err(EncoderError::InvalidParametersError(
+                "evaluation index is out of range or repeated".to_string(),
+            ));
+        }
+        known[index] = true;
+        evaluations[index] = eval;
+    }
+    if 2 * known_evals.len() < domain_size {
+        return Err(EncoderError::InvalidParametersError(
+            "not enough evaluations to recover the polynomial".to_string(),
+        ));
+    }
+
+    let domain = fft_domain(domain_size)?;
+    let shifted_domain = domain
+        .get_evaluation_domain()
+        .get_coset(Fr::GENERATOR)
+        .ok_or_else(|| EncoderError::FftError("failed to construct coset for FFT".to_string()))?;
+
+    let missing_roots: Vec<Fr> = (0..domain_size)
+        .filter(|&i| !known[i])
+        .map(|i| domain.element(i))
+        .collect();
+    let mut vanishing = vanishing_polynomial(&missing_roots).coeffs;
+    vanishing.resize(domain_size, Fr::zero());
+    let mut z_on_shifted = shifted_domain.fft(&vanishing);
+    domain.fft_in_place(&mut vanishing);
+
+    for (eval, z) in evaluations.iter_mut().zip(&vanishing) {
+        *eval *= z;
+    }
+    domain.ifft_in_place(&mut evaluations);
+    shifted_domain.fft_in_place(&mut evaluations);
+    batch_inversion(&mut z_on_shifted);
+    for (eval, z_inv) in evaluations.iter_mut().zip(&z_on_shifted) {
+        *eval *= z_inv;
+    }
+    shifted_domain.ifft_in_place(&mut evaluations);
+
+    if evaluations[domain_size / 2..].iter().any(|c| !c.is_zero()) {
+        return Err(EncoderError::GenericError(
+            "evaluations are not consistent with a polynomial of half the domain's size"
+                .to_string(),
+        ));
+    }
+    domain.fft_in_place(&mut evaluations);
+    Ok(evaluations)
+}
+
+/// returns the product of `X - root` over `roots`, multiplying halves with
+/// FFTs
+fn vanishing_polynomial(roots: &[Fr]) -> DensePolynomial<Fr> {
+    match roots {
+        [] => DensePolynomial::from_coefficients_vec(vec![Fr::one()]),
+        [root] => DensePolynomial::from_coefficients_vec(vec![-*root, Fr::one()]),
+        _ => {
+            let (left, right) = roots.split_at(roots.len() / 2);
+            &vanishing_polynomial(left) * &vanishing_polynomial(right)
+        },
+    }
+}
+
+/// returns the cached domain of `size` roots of unity
+fn fft_domain(size: usize) -> Result<Arc<Domain>, EncoderError> {
+    Domain::new(size).map_err(|err| EncoderError::FftError(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+    use ark_bn254::{Bn254, G1Projective};
+    use ark_ec::{pairing::Pairing, VariableBaseMSM};
+    use ark_poly::{GeneralEvaluationDomain, Polynomial as _};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_encoder_rejects_invalid_parameters() {
+        assert!(Encoder::new(0, 2).is_err());
+        assert!(Encoder::new(8, 3).is_err());
+        assert!(Encoder::new(8, 0).is_err());
+        assert_eq!(Encoder::new(5, 2).unwrap().get_num_chunks(), 8);
+
+        let encoder = Encoder::new(1, 1 << 20).unwrap();
+        assert!(encoder.params(1 << 8).is_ok());
+        assert!(encoder.params(1 << 9).is_err());
+        assert!(Encoder::new(1 << 29, 1).unwrap().params(1).is_err());
+    }
+
+    #[test]
+    fn test_extension_is_systematic() {
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        for coding_rate in [1, 2, 4] {
+            let encoder = Encoder::new(4, coding_rate).unwrap();
+            let extended = encoder.extend(&polynomial).unwrap();
+            assert_eq!(extended.len(), polynomial.len() * coding_rate);
+            for (i, eval) in polynomial.as_slice().iter().enumerate() {
+                assert_eq!(extended[i * coding_rate], *eval);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chunks_follow_assignment_rules() {
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let domain = GeneralEvaluationDomain::<Fr>::new(polynomial.len()).unwrap();
+        let coefficients =
+            DensePolynomial::from_coefficients_vec(domain.ifft(polynomial.as_slice()));
+
+        // more chunks than extended evaluations too, so chunks get one each
+        for num_chunks in [1, 8, 64, 2048] {
+            let encoder = Encoder::new(num_chunks, 2).unwrap();
+            let params = encoder.params(polynomial.len()).unwrap();
+            let chunks = encoder.encode_polynomial(&polynomial).unwrap();
+            assert_eq!(chunks.len(), num_chunks);
+
+            // the Go encoder bit-reverses the extended evaluations, slices
+            // them into contiguous chunks and re-reverses each chunk
+            let n = params.num_evaluations();
+            let l = params.get_chunk_length();
+            let generator = Fr::get_root_of_unity(n as u64).unwrap();
+            let mut seen = HashSet::new();
+            for (i, chunk) in chunks.iter().enumerate() {
+                assert_eq!(chunk.get_index(), i);
+                assert_eq!(chunk.len(), l);
+                let points = params.evaluation_points(i);
+                for (t, (point, eval)) in points.iter().zip(chunk.get_evaluations()).enumerate() {
+                    let position = reverse_bits(i * l + reverse_bits(t, l), n);
+                    assert_eq!(*point, generator.pow([position as u64]));
+                    assert_eq!(*eval, coefficients.evaluate(point));
+                    assert!(seen.insert(position));
+                }
+            }
+            assert_eq!(seen.len(), n);
+        }
+    }
+
+    #[test]
+    fn test_encode_requires_padded_blob() {
+        let encoder = Encoder::new(4, 2).unwrap();
+        let mut blob = Blob::from_bytes_and_pad("hi".as_bytes());
+        assert!(encoder.encode(&blob).is_ok());
+        blob.remove_padding().unwrap();
+        assert!(encoder.encode(&blob).is_err());
+    }
+
+    #[test]
+    fn test_prove_chunks() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let g1 = kzg.get_g1_points();
+        let g2 = kzg.get_g2_points();
+
+        // chunks longer than the polynomial get the identity as their proof
+        for num_chunks in [1, 2, 8, 64] {
+            let encoder = Encoder::new(num_chunks, 2).unwrap();
+            let params = encoder.params(polynomial.len()).unwrap();
+            let chunks = encoder.encode_polynomial(&polynomial).unwrap();
+            let proofs = encoder.prove_chunks(&kzg, &polynomial).unwrap();
+            assert_eq!(proofs.len(), chunks.len());
+
+            let l = params.get_chunk_length();
+            for (chunk, proof) in chunks.iter().zip(&proofs) {
+                // e(C - [I(tau)], G2) = e(proof, [tau^L - h^L]G2)
+                let shift = params.coset_shift(chunk.get_index());
+                let coset = GeneralEvaluationDomain::<Fr>::new(l)
+                    .unwrap()
+                    .get_coset(shift)
+                    .unwrap();
+                let interpolation = coset.ifft(chunk.get_evaluations());
+                let interpolation_g1 = G1Projective::msm(&g1[..l], &interpolation).unwrap();
+                let vanishing_g2 =
+                    g2[l].into_group() - G2Affine::generator() * shift.pow([l as u64]);
+                assert_eq!(
+                    Bn254::pairing(
+                        commitment.into_group() - interpolation_g1,
+                        G2Affine::generator()
+                    ),
+                    Bn254::pairing(proof, vanishing_g2.into_affine())
+                );
+                assert!(encoder
+                    .verify_chunk(&kzg, &commitment, polynomial.len(), chunk, proof)
+                    .unwrap());
+            }
+
+            if num_chunks > 2 {
+                assert!(!encoder
+                    .verify_chunk(&kzg, &commitment, polynomial.len(), &chunks[0], &proofs[1])
+                    .unwrap());
+                let mut tampered = chunks[1].clone();
+                tampered.evaluations[0] += Fr::from(1u64);
+                assert!(!encoder
+                    .verify_chunk(&kzg, &commitment, polynomial.len(), &tampered, &proofs[1])
+                    .unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_recover_polynomial() {
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let extended = Encoder::new(1, 2).unwrap().extend(&polynomial).unwrap();
+        let domain_size = extended.len();
+
+        // every other evaluation, the first half, and a scattered half
+        let subsets: [Vec<usize>; 3] = [
+            (0..domain_size).step_by(2).collect(),
+            (0..domain_size / 2).collect(),
+            (0..domain_size).filter(|i| (i * 7) % 4 < 2).collect(),
+        ];
+        for subset in subsets {
+            let known: Vec<(usize, Fr)> = subset.iter().map(|&i| (i, extended[i])).collect();
+            assert_eq!(recover_polynomial(&known, domain_size).unwrap(), extended);
+        }
+        let all: Vec<(usize, Fr)> = extended.iter().copied().enumerate().collect();
+        assert_eq!(recover_polynomial(&all, domain_size).unwrap(), extended);
+
+        // too few, repeated, out of range or tampered evaluations fail
+        let mut known: Vec<(usize, Fr)> = all[..domain_size / 2].to_vec();
+        assert!(recover_polynomial(&known[1..], domain_size).is_err());
+        known.push(known[0]);
+        assert!(recover_polynomial(&known, domain_size).is_err());
+        known.pop();
+        known.push((domain_size, Fr::zero()));
+        assert!(recover_polynomial(&known, domain_size).is_err());
+        known.pop();
+        known.push((domain_size - 1, extended[domain_size - 1] + Fr::from(1u64)));
+        assert!(recover_polynomial(&known, domain_size).is_err());
+        assert!(recover_polynomial(&all, 3).is_err());
+    }
+
+    #[test]
+    fn test_decode_from_subset_of_chunks() {
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = blob.to_polynomial().unwrap();
+        let encoder = Encoder::new(16, 4).unwrap();
+        let chunks = encoder.encode(&blob).unwrap();
+
+        // a quarter of the chunks is exactly enough at coding rate 4
+        for subset in [
+            chunks.clone(),
+            chunks.iter().step_by(4).cloned().collect(),
+            chunks[12..].to_vec(),
+            chunks.iter().rev().skip(3).step_by(3).cloned().collect(),
+        ] {
+            let decoded = encoder.decode(&subset, blob.len()).unwrap();
+            assert_eq!(decoded, polynomial);
+            assert_eq!(decoded.to_bytes_be(), polynomial.to_bytes_be());
+        }
+
+        assert!(encoder.decode(&chunks[13..], blob.len()).is_err());
+        let repeated = vec![
+            chunks[0].clone(),
+            chunks[0].clone(),
+            chunks[1].clone(),
+            chunks[2].clone(),
+        ];
+        assert!(encoder.decode(&repeated, blob.len()).is_err());
+        assert!(encoder
+            .decode(
+                &[Chunk::new(16, chunks[0].get_evaluations().to_vec())],
+                blob.len()
+            )
+            .is_err());
+
+        // with redundancy to spare, corrupted evaluations are detected
+        let mut corrupted = chunks[8..].to_vec();
+        corrupted[0].evaluations[0] += Fr::from(1u64);
+        assert!(encoder.decode(&corrupted, blob.len()).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Canonical byte encodings of points and field elements.
+//!
+//! Everything the crate hashes, serializes or hands across a language
+//! boundary is built from these, so the bytes are the same on every target:
+//! a G1 point is encoded as gnark and the EigenDA SRS files encode it, its
+//! 32-byte big-endian x coordinate with the top bits flagging which square
+//! root y is, and a field element is its 32-byte big-endian integer. Lengths
+//! and indices are written as big-endian `u64`s, never as `usize`, whose
+//! width depends on the target. Decoding rejects anything that does not
+//! encode back to the same bytes.
+
+use crate::{
+    consts::{SIZE_OF_G1_AFFINE_COMPRESSED, SIZE_OF_G1_AFFINE_UNCOMPRESSED},
+    errors::KzgError,
+    helpers,
+};
+use ark_bn254::{Fq, Fr, G1Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::{format, string::ToString};
+
+/// Number of bytes of an encoded field element.
+pub const FR_ENCODED_SIZE: usize = 32;
+
+/// Encodes a G1 point in the compressed big-endian encoding of the SRS
+/// files.
+pub fn g1_to_bytes(point: &G1Affine) -> [u8; SIZE_OF_G1_AFFINE_COMPRESSED] {
+    helpers::g1_point_to_bytes_be(point)
+}
+
+/// Decodes a compressed big-endian G1 point, checking it is on the curve and
+/// in the subgroup.
+pub fn g1_from_bytes(bytes: &[u8]) -> Result<G1Affine, KzgError> {
+    if bytes.len() != SIZE_OF_G1_AFFINE_COMPRESSED {
+        return Err(KzgError::SerializationError(format!(
+            "G1 point is {} bytes, expected {}",
+            bytes.len(),
+            SIZE_OF_G1_AFFINE_COMPRESSED
+        )));
+    }
+    let point = helpers::read_g1_point_from_bytes_be(bytes)
+        .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+    if g1_to_bytes(&point) != bytes {
+        return Err(KzgError::SerializationError(
+            "G1 point is not canonical".to_string(),
+        ));
+    }
+    Ok(point)
+}
+
+/// Encodes a G1 point as its big-endian x and y coordinates, which skips the
+/// square root when decoding. The identity is all zeros.
+pub fn g1_to_uncompressed_bytes(point: &G1Affine) -> [u8; SIZE_OF_G1_AFFINE_UNCOMPRESSED] {
+    let mut bytes = [0u8; SIZE_OF_G1_AFFINE_UNCOMPRESSED];
+    if let Some((x, y)) = point.xy() {
+        bytes[..32].copy_from_slice(&x.into_bigint().to_bytes_be());
+        bytes[32..].copy_from_slice(&y.into_bigint().to_bytes_be());
+    }
+    bytes
+}
+
+/// Decodes an uncompressed big-endian G1 point, checking it is on the curve
+/// and in the subgroup.
+pub fn g1_from_uncompressed_bytes(bytes: &[u8]) -> Result<G1Affine, KzgError> {
+    if bytes.len() != SIZE_OF_G1_AFFINE_UNCOMPRESSED {
+        return Err(KzgError::SerializationError(format!(
+            "G1 point is {} bytes, expected {}",
+            bytes.len(),
+            SIZE_OF_G1_AFFINE_UNCOMPRESSED
+        )));
+    }
+    let point = if bytes.iter().all(|byte| *byte == 0) {
+        G1Affine::zero()
+    } else {
+        let x = Fq::from_be_bytes_mod_order(&bytes[..32]);
+        let y = Fq::from_be_bytes_mod_order(&bytes[32..]);
+        let point = G1Affine::new_unchecked(x, y);
+        if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(KzgError::SerializationError(
+                "G1 point is not on the curve".to_string(),
+            ));
+        }
+        point
+    };
+    if g1_to_uncompressed_bytes(&point) != bytes {
+        return Err(KzgError::SerializationError(
+            "G1 point is not canonical".to_string(),
+        ));
+    }
+    Ok(point)
+}
+
+/// Encodes a field element as its big-endian integer.
+pub fn fr_to_bytes(value: &Fr) -> [u8; FR_ENCODED_SIZE] {
+    let mut bytes = [0u8; FR_ENCODED_SIZE];
+    bytes.copy_from_slice(&value.into_bigint().to_bytes_be());
+    bytes
+}
+
+/// Decodes a big-endian field element, rejecting values not below the
+/// modulus.
+pub fn fr_from_bytes(bytes: &[u8]) -> Result<Fr, KzgError> {
+    let value = Fr::from_be_bytes_mod_order(bytes);
+    if bytes.len() != FR_ENCODED_SIZE || fr_to_bytes(&value) != bytes {
+        return Err(KzgError::SerializationError(
+            "field element is not canonical".to_string(),
+        ));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES, embedded::EmbeddedKzg};
+    use ark_std::vec::Vec;
+    use hex_literal::hex;
+
+    // The SRS and the expected outputs are compiled in, so this runs
+    // unchanged on every target CI cross-compiles to, including
+    // `--no-default-features` and wasm, and pins the bytes across them.
+    const G1_POINTS: &[u8] = include_bytes!("test-files/g1.point");
+    const G2_POINTS: &[u8] = include_bytes!("test-files/g2.point");
+    const KNOWN_COMMITMENT: [u8; 32] =
+        hex!("868bf472ebc0e26c297f8a9257c3f42a38af1e4612b60f6ac64d57dc272d50b1");
+    const KNOWN_PROOF: [u8; 32] =
+        hex!("aae43e591834a505c93149def7a9b232a82232e4bc0238c224f90310c35fdf64");
+    const KNOWN_VALUE: [u8; 32] =
+        hex!("170a24994f5f080213c497f201eac32446e698a7a2dc6e47da6085dd0b370912");
+
+    #[test]
+    fn test_known_answers() {
+        let kzg = EmbeddedKzg::from_bytes(G1_POINTS, &G2_POINTS[64..128]).unwrap();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let z = Fr::from(0x1234_5678_9abc_def0u64);
+        let (proof, value) = kzg.compute_proof_at_point(&polynomial, z).unwrap();
+        assert_eq!(g1_to_bytes(&commitment), KNOWN_COMMITMENT);
+        assert_eq!(g1_to_bytes(&proof), KNOWN_PROOF);
+        assert_eq!(fr_to_bytes(&value), KNOWN_VALUE);
+        assert!(kzg.verify_proof(commitment, proof, value, z));
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let point = EmbeddedKzg::from_bytes(&G1_POINTS[32..64], &G2_POINTS[64..128])
+            .unwrap()
+            .get_g1_points()[0];
+        let bytes = g1_to_bytes(&point);
+        assert_eq!(g1_from_bytes(&bytes).unwrap(), point);
+        let mut long: Vec<u8> = bytes.to_vec();
+        long.push(0);
+        assert!(g1_from_bytes(&long).is_err());
+        assert!(g1_from_bytes(&bytes[..31]).is_err());
+        // points are encoded as the SRS files encode them
+        assert_eq!(bytes, G1_POINTS[32..64]);
+        // the flags must name the square root y is
+        let mut flags = bytes;
+        flags[0] &= 0b0011_1111;
+        assert!(g1_from_bytes(&flags).is_err());
+        assert_eq!(
+            g1_from_bytes(&g1_to_bytes(&G1Affine::zero())).unwrap(),
+            G1Affine::zero()
+        );
+
+        let bytes = g1_to_uncompressed_bytes(&point);
+        assert_eq!(g1_from_uncompressed_bytes(&bytes).unwrap(), point);
+        assert!(g1_from_uncompressed_bytes(&bytes[..32]).is_err());
+        let mut off_curve = bytes;
+        off_curve[63] ^= 1;
+        assert!(g1_from_uncompressed_bytes(&off_curve).is_err());
+        assert_eq!(
+            g1_from_uncompressed_bytes(&[0; 64]).unwrap(),
+            G1Affine::zero()
+        );
+
+        let value = -Fr::from(1u64);
+        let bytes = fr_to_bytes(&value);
+        assert_eq!(fr_from_bytes(&bytes).unwrap(), value);
+        // the modulus itself reduces to zero and is rejected
+        let mut modulus = bytes;
+        modulus[31] += 1;
+        assert!(fr_from_bytes(&modulus).is_err());
+        assert!(fr_from_bytes(&bytes[1..]).is_err());
+        assert_eq!(fr_to_bytes(&Fr::from(1u64))[31], 1);
+    }
+}
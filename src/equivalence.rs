@@ -0,0 +1,164 @@
+//! Proof of equivalence between a blob's KZG commitment and its keccak256
+//! hash.
+//!
+//! Both representations are evaluated at a point derived from the hash and
+//! the commitment. The KZG side is shown with an opening proof; the hash side
+//! is checked by whoever holds the data behind the hash, e.g. a contract or a
+//! circuit evaluating the blob polynomial at the same point. Since the point
+//! is fixed only after both representations are, agreeing there binds the
+//! hash to the commitment, without opening every element.
+
+use crate::{
+    blob::Blob,
+    consts::EQUIVALENCE_CHALLENGE_DOMAIN,
+    errors::KzgError,
+    kzg::Kzg,
+    merkle::{g1_to_abi_words, keccak256},
+    polynomial::Polynomial,
+};
+use ark_bn254::{Fr, G1Affine};
+use ark_ff::PrimeField;
+
+/// The opening of a blob's commitment at the point derived from its hash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EquivalenceProof {
+    hash: [u8; 32],
+    z: Fr,
+    value: Fr,
+    proof: G1Affine,
+}
+
+impl EquivalenceProof {
+    /// Returns the keccak256 hash of the padded blob.
+    pub fn get_hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// Returns the evaluation point derived from the hash and commitment.
+    pub fn get_z(&self) -> Fr {
+        self.z
+    }
+
+    /// Returns the evaluation of the blob polynomial at `z`.
+    pub fn get_value(&self) -> Fr {
+        self.value
+    }
+
+    /// Returns the KZG proof of the evaluation.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+}
+
+/// Derives the evaluation point as
+/// `keccak256(domain || hash || commitment.x || commitment.y)` reduced into
+/// the scalar field, with the commitment encoded as the EVM encodes a
+/// `G1Point`, so contracts can recompute it.
+pub fn challenge(hash: &[u8; 32], commitment: &G1Affine) -> Fr {
+    let mut input = Vec::with_capacity(EQUIVALENCE_CHALLENGE_DOMAIN.len() + 96);
+    input.extend_from_slice(EQUIVALENCE_CHALLENGE_DOMAIN);
+    input.extend_from_slice(hash);
+    input.extend_from_slice(&g1_to_abi_words(commitment));
+    Fr::from_be_bytes_mod_order(&keccak256(&input))
+}
+
+/// Commits to the blob padded from `payload` and proves its commitment is
+/// equivalent to the keccak256 hash of the padded blob.
+pub fn prove_equivalence(
+    kzg: &Kzg,
+    payload: &[u8],
+) -> Result<(G1Affine, EquivalenceProof), KzgError> {
+    prove_blob_equivalence(kzg, &Blob::from_bytes_and_pad(payload))
+}
+
+/// Commits to the padded `blob` and proves its commitment is equivalent to
+/// the keccak256 hash of its data.
+pub fn prove_blob_equivalence(
+    kzg: &Kzg,
+    blob: &Blob,
+) -> Result<(G1Affine, EquivalenceProof), KzgError> {
+    if !blob.is_padded() {
+        return Err(KzgError::GenericError(
+            "blob must be padded to prove equivalence".to_string(),
+        ));
+    }
+    let polynomial = blob
+        .to_polynomial()
+        .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+    let commitment = kzg.commit(&polynomial)?;
+    let hash = keccak256(&blob.get_blob_data());
+    let z = challenge(&hash, &commitment);
+    let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomial, z)?;
+    Ok((
+        commitment,
+        EquivalenceProof {
+            hash,
+            z,
+            value,
+            proof,
+        },
+    ))
+}
+
+/// Checks the KZG side of `proof`: that its point is derived from its hash
+/// and `commitment`, and that the commitment opens to its value there.
+pub fn verify_commitment_side(kzg: &Kzg, commitment: &G1Affine, proof: &EquivalenceProof) -> bool {
+    proof.z == challenge(&proof.hash, commitment)
+        && kzg.verify_kzg_proof(*commitment, proof.proof, proof.value, proof.z)
+}
+
+/// Checks both sides of `proof` against the padded `blob`: its hash, its
+/// evaluation at the derived point, and the opening of `commitment`.
+pub fn verify_equivalence(
+    kzg: &Kzg,
+    commitment: &G1Affine,
+    blob: &Blob,
+    proof: &EquivalenceProof,
+) -> Result<bool, KzgError> {
+    let polynomial: Polynomial = blob
+        .to_polynomial()
+        .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+    Ok(keccak256(&blob.get_blob_data()) == proof.hash
+        && Kzg::evaluate_polynomial_in_evaluation_form(&polynomial, proof.z) == proof.value
+        && verify_commitment_side(kzg, commitment, proof))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    #[test]
+    fn test_equivalence_proof() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let (commitment, proof) = prove_equivalence(&kzg, GETTYSBURG_ADDRESS_BYTES).unwrap();
+        assert_eq!(proof.get_hash(), keccak256(&blob.get_blob_data()));
+        assert!(verify_commitment_side(&kzg, &commitment, &proof));
+        assert!(verify_equivalence(&kzg, &commitment, &blob, &proof).unwrap());
+        assert_eq!(
+            prove_blob_equivalence(&kzg, &blob).unwrap(),
+            (commitment, proof)
+        );
+        let raw = Blob::new(GETTYSBURG_ADDRESS_BYTES.to_vec(), false);
+        assert!(prove_blob_equivalence(&kzg, &raw).is_err());
+
+        // a different blob, or a proof for another commitment, fails
+        let other = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[1..]);
+        assert!(!verify_equivalence(&kzg, &commitment, &other, &proof).unwrap());
+        let (other_commitment, other_proof) =
+            prove_equivalence(&kzg, &GETTYSBURG_ADDRESS_BYTES[1..]).unwrap();
+        assert!(!verify_commitment_side(&kzg, &commitment, &other_proof));
+        let mut wrong_value = proof;
+        wrong_value.value += Fr::from(1u64);
+        assert!(!verify_commitment_side(&kzg, &commitment, &wrong_value));
+        assert!(verify_equivalence(&kzg, &other_commitment, &other, &other_proof).unwrap());
+    }
+}
@@ -1,4 +1,7 @@
-use std::{error::Error, fmt};
+use ark_std::string::String;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum BlobError {
@@ -17,6 +20,7 @@ impl fmt::Display for BlobError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for BlobError {}
 
 #[derive(Clone, Debug, PartialEq)]
@@ -38,6 +42,7 @@ impl fmt::Display for PolynomialError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for PolynomialError {}
 
 #[derive(Clone, Debug, PartialEq)]
@@ -45,6 +50,7 @@ pub enum KzgError {
     CommitError(String),
     SerializationError(String),
     FftError(String),
+    InvalidProofError(usize),
     GenericError(String),
 }
 
@@ -54,13 +60,81 @@ impl fmt::Display for KzgError {
             KzgError::CommitError(ref msg) => write!(f, "Commitment error: {}", msg),
             KzgError::SerializationError(ref msg) => write!(f, "Serialization error: {}", msg),
             KzgError::FftError(ref msg) => write!(f, "FFT error: {}", msg),
+            KzgError::InvalidProofError(index) => write!(f, "invalid proof for blob {}", index),
             KzgError::GenericError(ref msg) => write!(f, "Generic error: {}", msg),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for KzgError {}
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum EncoderError {
+    InvalidParametersError(String),
+    InvalidChunkProofError(usize),
+    InvalidSegmentError(usize),
+    FftError(String),
+    GenericError(String),
+}
+
+impl fmt::Display for EncoderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            EncoderError::InvalidParametersError(ref msg) => {
+                write!(f, "invalid encoding parameters: {}", msg)
+            },
+            EncoderError::InvalidChunkProofError(index) => {
+                write!(f, "invalid proof for chunk {}", index)
+            },
+            EncoderError::InvalidSegmentError(index) => {
+                write!(f, "segment {} does not match the manifest", index)
+            },
+            EncoderError::FftError(ref msg) => write!(f, "FFT error: {}", msg),
+            EncoderError::GenericError(ref msg) => write!(f, "generic error: {}", msg),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for EncoderError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CertError {
+    InvalidCommitmentError,
+    InvalidLengthProofError,
+    InvalidChunkProofError(usize),
+    InvalidAssignmentError(String),
+    InvalidInclusionProofError,
+    InvalidSignatureError,
+    GenericError(String),
+}
+
+impl fmt::Display for CertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            CertError::InvalidCommitmentError => {
+                write!(f, "commitment does not match the length commitment")
+            },
+            CertError::InvalidLengthProofError => write!(f, "invalid length proof"),
+            CertError::InvalidChunkProofError(index) => {
+                write!(f, "invalid proof for chunk {}", index)
+            },
+            CertError::InvalidAssignmentError(ref msg) => {
+                write!(f, "chunks do not match the assignment: {}", msg)
+            },
+            CertError::InvalidInclusionProofError => {
+                write!(f, "blob header is not included in the batch")
+            },
+            CertError::InvalidSignatureError => write!(f, "invalid batch signature"),
+            CertError::GenericError(ref msg) => write!(f, "generic error: {}", msg),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CertError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,6 +193,12 @@ mod tests {
         assert_eq!(format!("{}", error), format!("FFT error: {}", msg));
     }
 
+    #[test]
+    fn test_kzg_error_invalid_proof() {
+        let error = KzgError::InvalidProofError(3);
+        assert_eq!(format!("{}", error), "invalid proof for blob 3");
+    }
+
     #[test]
     fn test_kzg_error_generic() {
         let msg = String::from("test generic error");
@@ -168,4 +248,61 @@ mod tests {
         assert_eq!(error1, error3);
         assert_ne!(error1, error2);
     }
+
+    #[test]
+    fn test_encoder_error_display() {
+        let msg = String::from("test error");
+        assert_eq!(
+            format!("{}", EncoderError::InvalidParametersError(msg.clone())),
+            format!("invalid encoding parameters: {}", msg)
+        );
+        assert_eq!(
+            format!("{}", EncoderError::InvalidChunkProofError(3)),
+            "invalid proof for chunk 3"
+        );
+        assert_eq!(
+            format!("{}", EncoderError::InvalidSegmentError(2)),
+            "segment 2 does not match the manifest"
+        );
+        assert_eq!(
+            format!("{}", EncoderError::FftError(msg.clone())),
+            format!("FFT error: {}", msg)
+        );
+        assert_eq!(
+            format!("{}", EncoderError::GenericError(msg.clone())),
+            format!("generic error: {}", msg)
+        );
+    }
+
+    #[test]
+    fn test_cert_error_display() {
+        assert_eq!(
+            format!("{}", CertError::InvalidCommitmentError),
+            "commitment does not match the length commitment"
+        );
+        assert_eq!(
+            format!("{}", CertError::InvalidLengthProofError),
+            "invalid length proof"
+        );
+        assert_eq!(
+            format!("{}", CertError::InvalidChunkProofError(2)),
+            "invalid proof for chunk 2"
+        );
+        assert_eq!(
+            format!("{}", CertError::InvalidAssignmentError("error".to_string())),
+            "chunks do not match the assignment: error"
+        );
+        assert_eq!(
+            format!("{}", CertError::InvalidInclusionProofError),
+            "blob header is not included in the batch"
+        );
+        assert_eq!(
+            format!("{}", CertError::InvalidSignatureError),
+            "invalid batch signature"
+        );
+        assert_eq!(
+            format!("{}", CertError::GenericError("error".to_string())),
+            "generic error: error"
+        );
+    }
 }
@@ -0,0 +1,105 @@
+//! Calldata for on-chain KZG verification contracts.
+//!
+//! `evm` builds the raw input of the `ecPairing` precompile. Verification
+//! contracts instead take the commitment, proof and opening as ABI-encoded
+//! arguments and assemble that input themselves. Points use the EIP-196/197
+//! layout of 32-byte big-endian words, and, following the usual convention,
+//! the proof is passed negated so the contract can hand it to `ecPairing` as
+//! is, checking `e(C - [y]G1, G2) * e(-pi, [tau - z]G2) = 1` without negating
+//! on-chain.
+
+use crate::merkle::keccak256;
+pub use crate::{evm::g2_to_abi_words, merkle::g1_to_abi_words};
+use ark_bn254::{Fr, G1Affine};
+use ark_ff::{BigInteger, PrimeField};
+
+/// Signature of the verification function `verify_kzg_proof_calldata`
+/// encodes a call to: the commitment and negated proof as `BN254.G1Point`s,
+/// then the value and the point it is opened at.
+pub const VERIFY_KZG_PROOF_SIGNATURE: &str =
+    "verifyKzgProof((uint256,uint256),(uint256,uint256),uint256,uint256)";
+
+/// Returns the 4-byte selector of the function with `signature`, the first
+/// bytes of its keccak256 hash.
+pub fn function_selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Encodes `value` as a 32-byte big-endian `uint256` word.
+pub fn fr_to_abi_word(value: &Fr) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word.copy_from_slice(&value.into_bigint().to_bytes_be());
+    word
+}
+
+/// Encodes `-point` as a `BN254.G1Point`, the form contracts take proofs in.
+pub fn negated_g1_to_abi_words(point: &G1Affine) -> [u8; 64] {
+    g1_to_abi_words(&-*point)
+}
+
+/// Returns the ABI-encoded arguments of `VERIFY_KZG_PROOF_SIGNATURE` for
+/// `proof` opening `commitment` to `value` at `z`.
+pub fn kzg_proof_arguments(commitment: &G1Affine, proof: &G1Affine, value: Fr, z: Fr) -> Vec<u8> {
+    let mut arguments = Vec::with_capacity(192);
+    arguments.extend_from_slice(&g1_to_abi_words(commitment));
+    arguments.extend_from_slice(&negated_g1_to_abi_words(proof));
+    arguments.extend_from_slice(&fr_to_abi_word(&value));
+    arguments.extend_from_slice(&fr_to_abi_word(&z));
+    arguments
+}
+
+/// Returns the calldata of a `verifyKzgProof` call checking `proof` opens
+/// `commitment` to `value` at `z`.
+pub fn verify_kzg_proof_calldata(
+    commitment: &G1Affine,
+    proof: &G1Affine,
+    value: Fr,
+    z: Fr,
+) -> Vec<u8> {
+    let mut calldata = function_selector(VERIFY_KZG_PROOF_SIGNATURE).to_vec();
+    calldata.extend_from_slice(&kzg_proof_arguments(commitment, proof, value, z));
+    calldata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES, evm, kzg::Kzg};
+    use ark_ec::AffineRepr;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_verify_kzg_proof_calldata() {
+        assert_eq!(
+            function_selector("transfer(address,uint256)"),
+            hex!("a9059cbb")
+        );
+        assert_eq!(fr_to_abi_word(&Fr::from(258u64))[30..], [1, 2]);
+        assert_eq!(negated_g1_to_abi_words(&G1Affine::zero()), [0u8; 64]);
+
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let z = Fr::from(7u64);
+        let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap();
+
+        let calldata = verify_kzg_proof_calldata(&commitment, &proof, value, z);
+        assert_eq!(calldata.len(), 4 + 192);
+        assert_eq!(calldata[..4], function_selector(VERIFY_KZG_PROOF_SIGNATURE));
+        assert_eq!(calldata[4..68], g1_to_abi_words(&commitment));
+        assert_eq!(calldata[164..], fr_to_abi_word(&z));
+        // the negated proof is the G1 point of the second ecPairing pair
+        let pairing = evm::kzg_proof_calldata(&kzg, commitment, proof, value, z);
+        assert_eq!(calldata[68..132], pairing[evm::PAIRING_PAIR_SIZE..][..64]);
+    }
+}
@@ -0,0 +1,160 @@
+//! Calldata for the EVM's BN254 pairing precompile.
+//!
+//! `ecPairing`, at address 0x08, takes pairs of a G1 and a G2 point and
+//! returns whether the product of their pairings is the identity (EIP-197).
+//! A contract checking a KZG opening forwards exactly these bytes, so
+//! encoding them here keeps on-chain verification in step with
+//! `Kzg::verify_kzg_proof`. With the `revm` feature, the tests run the
+//! calldata through revm's precompile on randomized inputs and check it
+//! agrees with native verification.
+
+use crate::{kzg::Kzg, merkle::g1_to_abi_words};
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+
+/// Number of bytes of one G1 and G2 pair of `ecPairing` calldata.
+pub const PAIRING_PAIR_SIZE: usize = 192;
+
+/// Encodes `point` as the contract's `BN254.G2Point`: the imaginary then the
+/// real part of `X`, followed by those of `Y`, as 32-byte big-endian words,
+/// all zero for the identity.
+pub fn g2_to_abi_words(point: &G2Affine) -> [u8; 128] {
+    let mut words = [0u8; 128];
+    if let Some((x, y)) = point.xy() {
+        for (word, coordinate) in words.chunks_exact_mut(32).zip([x.c1, x.c0, y.c1, y.c0]) {
+            word.copy_from_slice(&coordinate.into_bigint().to_bytes_be());
+        }
+    }
+    words
+}
+
+/// Returns the `ecPairing` calldata checking `e(a1, a2) = e(b1, b2)`, the
+/// check `Kzg::pairings_verify` makes, as `e(a1, a2) * e(-b1, b2) = 1`.
+pub fn pairing_calldata(a1: &G1Affine, a2: &G2Affine, b1: &G1Affine, b2: &G2Affine) -> Vec<u8> {
+    let mut calldata = Vec::with_capacity(2 * PAIRING_PAIR_SIZE);
+    for (g1, g2) in [(*a1, a2), (-*b1, b2)] {
+        calldata.extend_from_slice(&g1_to_abi_words(&g1));
+        calldata.extend_from_slice(&g2_to_abi_words(g2));
+    }
+    calldata
+}
+
+/// Returns the `ecPairing` calldata checking `proof` opens `commitment` to
+/// `value` at `z`, over the same points `Kzg::verify_kzg_proof` pairs.
+pub fn kzg_proof_calldata(
+    kzg: &Kzg,
+    commitment: G1Affine,
+    proof: G1Affine,
+    value: Fr,
+    z: Fr,
+) -> Vec<u8> {
+    let (a1, a2, b1, b2) = kzg.kzg_proof_pairing_inputs(commitment, proof, value, z);
+    pairing_calldata(&a1, &a2, &b1, &b2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_abi_encodings() {
+        // the G2 generator as EIP-197 lists it
+        let generator = g2_to_abi_words(&G2Affine::generator());
+        assert_eq!(
+            generator[..64],
+            hex!(
+                "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"
+                "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"
+            )
+        );
+        assert_eq!(
+            generator[64..],
+            hex!(
+                "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"
+                "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+            )
+        );
+        assert_eq!(g2_to_abi_words(&G2Affine::zero()), [0u8; 128]);
+
+        let g1 = G1Affine::generator();
+        let calldata = pairing_calldata(&g1, &G2Affine::generator(), &g1, &G2Affine::generator());
+        assert_eq!(calldata.len(), 2 * PAIRING_PAIR_SIZE);
+        assert_eq!(calldata[..64], g1_to_abi_words(&g1));
+        assert_eq!(calldata[PAIRING_PAIR_SIZE..][..64], g1_to_abi_words(&-g1));
+    }
+
+    #[cfg(feature = "revm")]
+    #[test]
+    fn test_revm_differential() {
+        use crate::blob::Blob;
+        use ark_ec::CurveGroup;
+        use ark_std::UniformRand;
+        use rand::Rng;
+        use revm_precompile::{Precompile, Precompiles};
+
+        // runs the calldata through revm's ecPairing
+        fn ec_pairing(calldata: &[u8]) -> bool {
+            let mut address = [0u8; 20];
+            address[19] = 8;
+            let Some(Precompile::Standard(run)) = Precompiles::istanbul().get(&address) else {
+                panic!("ecPairing is missing");
+            };
+            let (_, output) = run(calldata, u64::MAX).unwrap();
+            let mut one = [0u8; 32];
+            one[31] = 1;
+            output == one
+        }
+
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let mut rng = rand::thread_rng();
+        for _ in 0..16 {
+            let payload: Vec<u8> = (0..rng.gen_range(1..1000)).map(|_| rng.gen()).collect();
+            let polynomial = Blob::from_bytes_and_pad(&payload).to_polynomial().unwrap();
+            let commitment = kzg.commit(&polynomial).unwrap();
+            let z = Fr::rand(&mut rng);
+            let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap();
+            assert!(ec_pairing(&kzg_proof_calldata(
+                &kzg, commitment, proof, value, z
+            )));
+            let other = G1Affine::rand(&mut rng);
+            for (commitment, proof, value, z) in [
+                (commitment, proof, value, z),
+                (commitment, proof, value + Fr::from(1u64), z),
+                (commitment, proof, value, z + Fr::from(1u64)),
+                (commitment, other, value, z),
+                (other, proof, value, z),
+            ] {
+                assert_eq!(
+                    ec_pairing(&kzg_proof_calldata(&kzg, commitment, proof, value, z)),
+                    kzg.verify_kzg_proof(commitment, proof, value, z)
+                );
+            }
+
+            // e([a]G1, [b]G2) = e([ab]G1, G2), and not for a different product
+            let (a, b) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+            let a_g1 = (G1Affine::generator() * a).into_affine();
+            let b_g2 = (G2Affine::generator() * b).into_affine();
+            for product in [a * b, a * b + Fr::from(1u64)] {
+                let ab_g1 = (G1Affine::generator() * product).into_affine();
+                assert_eq!(
+                    ec_pairing(&pairing_calldata(
+                        &a_g1,
+                        &b_g2,
+                        &ab_g1,
+                        &G2Affine::generator()
+                    )),
+                    Kzg::pairings_verify(a_g1, b_g2, ab_g1, G2Affine::generator())
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! C ABI for calling the prover in-process from Go, C++, Java and others.
+//!
+//! Every function takes plain byte buffers with their lengths and returns a
+//! status code, `KZG_OK` on success. Results are written to caller-owned
+//! buffers of fixed size: points are 32-byte gnark compressed big-endian
+//! encodings, with the flag bits in the top byte, and field elements are 32
+//! big-endian bytes, as in the proving service. Payloads are padded into blobs as
+//! `Blob::from_bytes_and_pad` does. Panics are caught at the boundary and
+//! reported as `KZG_ERROR`. The matching header is
+//! `include/rust_kzg_bn254.h`, generated with cbindgen.
+
+use crate::{
+    blob::Blob,
+    embedded::EmbeddedKzg,
+    encoding::{fr_from_bytes, fr_to_bytes, g1_from_bytes, g1_to_bytes},
+    polynomial::Polynomial,
+};
+use ark_bn254::{Fr, G1Affine};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use std::{panic, slice};
+
+/// Number of bytes of a serialized point or field element.
+pub const KZG_BYTES: usize = 32;
+
+/// The call succeeded.
+pub const KZG_OK: i32 = 0;
+/// A required pointer was null.
+pub const KZG_NULL_POINTER: i32 = 1;
+/// An argument could not be decoded or is out of range.
+pub const KZG_INVALID_INPUT: i32 = 2;
+/// The computation failed.
+pub const KZG_ERROR: i32 = 3;
+
+/// An SRS loaded by `kzg_setup_from_bytes`, freed with `kzg_free`.
+pub struct KzgHandle {
+    inner: EmbeddedKzg,
+}
+
+/// Loads the SRS from the concatenated 32-byte G1 points and the 64-byte
+/// `[tau]G2`, in the big-endian encoding of the SRS files, and stores a new
+/// handle in `out`.
+///
+/// # Safety
+///
+/// The buffers must be valid for their lengths and `out` valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_setup_from_bytes(
+    g1: *const u8,
+    g1_len: usize,
+    g2_tau: *const u8,
+    g2_tau_len: usize,
+    out: *mut *mut KzgHandle,
+) -> i32 {
+    if g1.is_null() || g2_tau.is_null() || out.is_null() {
+        return KZG_NULL_POINTER;
+    }
+    let g1 = slice::from_raw_parts(g1, g1_len);
+    let g2_tau = slice::from_raw_parts(g2_tau, g2_tau_len);
+    guard(|| {
+        let inner = EmbeddedKzg::from_bytes(g1, g2_tau).map_err(|_| KZG_INVALID_INPUT)?;
+        *out = Box::into_raw(Box::new(KzgHandle { inner }));
+        Ok(())
+    })
+}
+
+/// Frees a handle from `kzg_setup_from_bytes`. Null is ignored.
+///
+/// # Safety
+///
+/// `handle` must come from `kzg_setup_from_bytes` and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_free(handle: *mut KzgHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Pads the payload into a blob and writes its commitment to
+/// `commitment_out`.
+///
+/// # Safety
+///
+/// `handle` must be live, `payload` valid for `payload_len` bytes and
+/// `commitment_out` valid for `KZG_BYTES` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_commit(
+    handle: *const KzgHandle,
+    payload: *const u8,
+    payload_len: usize,
+    commitment_out: *mut u8,
+) -> i32 {
+    if handle.is_null() || payload.is_null() || commitment_out.is_null() {
+        return KZG_NULL_POINTER;
+    }
+    let kzg = &(*handle).inner;
+    let payload = slice::from_raw_parts(payload, payload_len);
+    let commitment_out = slice::from_raw_parts_mut(commitment_out, KZG_BYTES);
+    guard(|| {
+        let commitment = kzg
+            .commit(&to_polynomial(payload)?)
+            .map_err(|_| KZG_ERROR)?;
+        commitment_out.copy_from_slice(&g1_to_bytes(&commitment));
+        Ok(())
+    })
+}
+
+/// Pads the payload into a blob and opens it at its `index`-th field
+/// element, writing the proof, the value and the root of unity it is
+/// opened at.
+///
+/// # Safety
+///
+/// `handle` must be live, `payload` valid for `payload_len` bytes and each
+/// output valid for `KZG_BYTES` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_prove(
+    handle: *const KzgHandle,
+    payload: *const u8,
+    payload_len: usize,
+    index: u64,
+    proof_out: *mut u8,
+    value_out: *mut u8,
+    z_out: *mut u8,
+) -> i32 {
+    if handle.is_null()
+        || payload.is_null()
+        || proof_out.is_null()
+        || value_out.is_null()
+        || z_out.is_null()
+    {
+        return KZG_NULL_POINTER;
+    }
+    let kzg = &(*handle).inner;
+    let payload = slice::from_raw_parts(payload, payload_len);
+    let proof_out = slice::from_raw_parts_mut(proof_out, KZG_BYTES);
+    let value_out = slice::from_raw_parts_mut(value_out, KZG_BYTES);
+    let z_out = slice::from_raw_parts_mut(z_out, KZG_BYTES);
+    guard(|| {
+        let polynomial = to_polynomial(payload)?;
+        let index = usize::try_from(index)
+            .ok()
+            .filter(|&i| i < polynomial.len())
+            .ok_or(KZG_INVALID_INPUT)?;
+        let z = GeneralEvaluationDomain::<Fr>::new(polynomial.len())
+            .ok_or(KZG_INVALID_INPUT)?
+            .element(index);
+        let (proof, value) = kzg
+            .compute_proof_at_point(&polynomial, z)
+            .map_err(|_| KZG_ERROR)?;
+        proof_out.copy_from_slice(&g1_to_bytes(&proof));
+        value_out.copy_from_slice(&fr_to_bytes(&value));
+        z_out.copy_from_slice(&fr_to_bytes(&z));
+        Ok(())
+    })
+}
+
+/// Checks the proof opens the commitment to the value at `z`, writing 1 to
+/// `valid_out` if it does and 0 otherwise.
+///
+/// # Safety
+///
+/// `handle` must be live, each input valid for `KZG_BYTES` bytes and
+/// `valid_out` valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_verify(
+    handle: *const KzgHandle,
+    commitment: *const u8,
+    proof: *const u8,
+    value: *const u8,
+    z: *const u8,
+    valid_out: *mut u8,
+) -> i32 {
+    if handle.is_null()
+        || commitment.is_null()
+        || proof.is_null()
+        || value.is_null()
+        || z.is_null()
+        || valid_out.is_null()
+    {
+        return KZG_NULL_POINTER;
+    }
+    let kzg = &(*handle).inner;
+    let commitment = slice::from_raw_parts(commitment, KZG_BYTES);
+    let proof = slice::from_raw_parts(proof, KZG_BYTES);
+    let value = slice::from_raw_parts(value, KZG_BYTES);
+    let z = slice::from_raw_parts(z, KZG_BYTES);
+    guard(|| {
+        let valid = kzg.verify_proof(
+            read_point(commitment)?,
+            read_point(proof)?,
+            read_fr(value)?,
+            read_fr(z)?,
+        );
+        *valid_out = valid as u8;
+        Ok(())
+    })
+}
+
+/// runs `f`, turning its error or a panic into a status code
+fn guard(f: impl FnOnce() -> Result<(), i32>) -> i32 {
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(Ok(())) => KZG_OK,
+        Ok(Err(code)) => code,
+        Err(_) => KZG_ERROR,
+    }
+}
+
+fn to_polynomial(payload: &[u8]) -> Result<Polynomial, i32> {
+    Blob::from_bytes_and_pad(payload)
+        .to_polynomial()
+        .map_err(|_| KZG_INVALID_INPUT)
+}
+
+fn read_point(bytes: &[u8]) -> Result<G1Affine, i32> {
+    g1_from_bytes(bytes).map_err(|_| KZG_INVALID_INPUT)
+}
+
+fn read_fr(bytes: &[u8]) -> Result<Fr, i32> {
+    fr_from_bytes(bytes).map_err(|_| KZG_INVALID_INPUT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+    use std::ptr;
+
+    #[test]
+    fn test_ffi() {
+        let g1 = std::fs::read("src/test-files/g1.point").unwrap();
+        let g2 = std::fs::read("src/test-files/g2.point").unwrap();
+        let g1 = &g1[..32 * 3000];
+        let g2_tau = &g2[64..128];
+        let payload = GETTYSBURG_ADDRESS_BYTES;
+        unsafe {
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                kzg_setup_from_bytes(g1.as_ptr(), g1.len(), g2_tau.as_ptr(), 63, &mut handle),
+                KZG_INVALID_INPUT
+            );
+            assert_eq!(
+                kzg_setup_from_bytes(
+                    g1.as_ptr(),
+                    g1.len(),
+                    g2_tau.as_ptr(),
+                    g2_tau.len(),
+                    &mut handle
+                ),
+                KZG_OK
+            );
+
+            let mut commitment = [0u8; KZG_BYTES];
+            assert_eq!(
+                kzg_commit(
+                    handle,
+                    payload.as_ptr(),
+                    payload.len(),
+                    commitment.as_mut_ptr()
+                ),
+                KZG_OK
+            );
+            let (mut proof, mut value, mut z) =
+                ([0u8; KZG_BYTES], [0u8; KZG_BYTES], [0u8; KZG_BYTES]);
+            let prove = |index, proof: &mut [u8; 32], value: &mut [u8; 32], z: &mut [u8; 32]| {
+                kzg_prove(
+                    handle,
+                    payload.as_ptr(),
+                    payload.len(),
+                    index,
+                    proof.as_mut_ptr(),
+                    value.as_mut_ptr(),
+                    z.as_mut_ptr(),
+                )
+            };
+            assert_eq!(prove(9, &mut proof, &mut value, &mut z), KZG_OK);
+            assert_eq!(
+                prove(1 << 20, &mut proof, &mut value, &mut z),
+                KZG_INVALID_INPUT
+            );
+
+            let mut valid = 2u8;
+            let verify = |value: &[u8; 32], valid: &mut u8| {
+                kzg_verify(
+                    handle,
+                    commitment.as_ptr(),
+                    proof.as_ptr(),
+                    value.as_ptr(),
+                    z.as_ptr(),
+                    valid,
+                )
+            };
+            assert_eq!(verify(&value, &mut valid), KZG_OK);
+            assert_eq!(valid, 1);
+            let mut wrong = value;
+            wrong[31] ^= 1;
+            assert_eq!(verify(&wrong, &mut valid), KZG_OK);
+            assert_eq!(valid, 0);
+            assert_eq!(verify(&[0xff; 32], &mut valid), KZG_INVALID_INPUT);
+
+            assert_eq!(
+                kzg_commit(
+                    ptr::null(),
+                    payload.as_ptr(),
+                    payload.len(),
+                    commitment.as_mut_ptr()
+                ),
+                KZG_NULL_POINTER
+            );
+            kzg_free(handle);
+            kzg_free(ptr::null_mut());
+        }
+    }
+}
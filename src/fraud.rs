@@ -0,0 +1,227 @@
+//! Evidence of an invalid opening, for dispute resolution.
+//!
+//! When a served sample fails verification, the sampler keeps the commitment,
+//! the index, the claimed value and the proof. That is everything a dispute
+//! resolver needs to re-run the failing pairing check itself. If the sampler
+//! also holds the data, the evaluation it expected can be attached.
+
+use crate::{
+    encoding::{fr_from_bytes, fr_to_bytes, g1_from_bytes, g1_to_bytes},
+    errors::KzgError,
+    kzg::Kzg,
+    sampling::{self, Sample},
+};
+use ark_bn254::{Fr, G1Affine};
+
+/// Length of the serialized evidence without an expected value.
+const EVIDENCE_SIZE: usize = 32 + 8 + 8 + 32 + 32 + 1;
+
+/// A claimed evaluation of a committed polynomial whose proof does not hold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FraudEvidence {
+    commitment: G1Affine,
+    length: u64,
+    index: u64,
+    provided_value: Fr,
+    expected_value: Option<Fr>,
+    proof: G1Affine,
+}
+
+impl FraudEvidence {
+    /// Records `sample` of a polynomial of `length` field elements committed
+    /// to by `commitment`.
+    pub fn new(commitment: G1Affine, length: u64, sample: &Sample) -> Self {
+        FraudEvidence {
+            commitment,
+            length,
+            index: sample.get_index(),
+            provided_value: sample.get_value(),
+            expected_value: None,
+            proof: sample.get_proof(),
+        }
+    }
+
+    /// Attaches the evaluation the data actually has at the index.
+    pub fn with_expected_value(mut self, expected_value: Fr) -> Self {
+        self.expected_value = Some(expected_value);
+        self
+    }
+
+    /// Returns the commitment the sample was checked against.
+    pub fn get_commitment(&self) -> G1Affine {
+        self.commitment
+    }
+
+    /// Returns the number of field elements of the committed polynomial.
+    pub fn get_length(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns the index of the offending sample.
+    pub fn get_index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the evaluation the server claimed.
+    pub fn get_provided_value(&self) -> Fr {
+        self.provided_value
+    }
+
+    /// Returns the evaluation the data has, if known.
+    pub fn get_expected_value(&self) -> Option<Fr> {
+        self.expected_value
+    }
+
+    /// Returns the proof the server sent.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+
+    /// Checks the evidence proves fraud: the proof of the provided value
+    /// does not hold at the index, and the expected value, if any, differs
+    /// from the provided one.
+    pub fn verify(&self, kzg: &Kzg) -> Result<bool, KzgError> {
+        if !self.length.is_power_of_two() || self.index >= self.length {
+            return Err(KzgError::GenericError(
+                "index is out of range of the polynomial".to_string(),
+            ));
+        }
+        let z = Kzg::root_of_unity(self.length as usize, self.index as usize);
+        Ok(
+            !kzg.verify_kzg_proof(self.commitment, self.proof, self.provided_value, z)
+                && self.expected_value != Some(self.provided_value),
+        )
+    }
+
+    /// Serializes the evidence as the compressed commitment, the length and
+    /// index as big-endian u64s, the provided value as a big-endian 32-byte
+    /// integer, the compressed proof, then a flag byte followed by the
+    /// expected value if it is 1.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, KzgError> {
+        let mut bytes = Vec::with_capacity(EVIDENCE_SIZE + 32);
+        bytes.extend_from_slice(&g1_to_bytes(&self.commitment));
+        bytes.extend_from_slice(&self.length.to_be_bytes());
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&fr_to_bytes(&self.provided_value));
+        bytes.extend_from_slice(&g1_to_bytes(&self.proof));
+        match self.expected_value {
+            Some(value) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&fr_to_bytes(&value));
+            },
+            None => bytes.push(0),
+        }
+        Ok(bytes)
+    }
+
+    /// Parses evidence serialized by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KzgError> {
+        let invalid = || KzgError::SerializationError("invalid fraud evidence".to_string());
+        if bytes.len() != EVIDENCE_SIZE && bytes.len() != EVIDENCE_SIZE + 32 {
+            return Err(invalid());
+        }
+        let u64_at = |offset: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[offset..offset + 8]);
+            u64::from_be_bytes(word)
+        };
+        let expected_value = match (bytes[EVIDENCE_SIZE - 1], bytes.len() - EVIDENCE_SIZE) {
+            (0, 0) => None,
+            (1, 32) => Some(fr_from_bytes(&bytes[EVIDENCE_SIZE..])?),
+            _ => return Err(invalid()),
+        };
+        Ok(FraudEvidence {
+            commitment: g1_from_bytes(&bytes[..32])?,
+            length: u64_at(32),
+            index: u64_at(40),
+            provided_value: fr_from_bytes(&bytes[48..80])?,
+            expected_value,
+            proof: g1_from_bytes(&bytes[80..112])?,
+        })
+    }
+}
+
+/// Verifies `samples` like `sampling::verify_samples`, and on failure checks
+/// them one by one to return evidence against the first invalid sample.
+/// Returns `None` if all samples hold.
+pub fn verify_samples_with_evidence(
+    kzg: &Kzg,
+    commitment: &G1Affine,
+    samples: &[Sample],
+    length: usize,
+) -> Result<Option<FraudEvidence>, KzgError> {
+    if sampling::verify_samples(kzg, commitment, samples, length)? {
+        return Ok(None);
+    }
+    for sample in samples {
+        let evidence = FraudEvidence::new(*commitment, length as u64, sample);
+        if evidence.verify(kzg)? {
+            return Ok(Some(evidence));
+        }
+    }
+    Err(KzgError::GenericError(
+        "batch failed but every sample holds on its own".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+
+    #[test]
+    fn test_fraud_evidence() {
+        let mut kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = blob.to_polynomial().unwrap();
+        kzg.data_setup_custom(1, blob.len().try_into().unwrap())
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let length = polynomial.len();
+        let indices = sampling::sample_indices(&commitment, b"seed", 8, length).unwrap();
+        let mut samples = sampling::open_samples(&kzg, &polynomial, &indices).unwrap();
+        assert_eq!(
+            verify_samples_with_evidence(&kzg, &commitment, &samples, length).unwrap(),
+            None
+        );
+
+        let honest = samples[5];
+        samples[5] = Sample::new(
+            honest.get_index(),
+            honest.get_value() + Fr::from(1u64),
+            honest.get_proof(),
+        );
+        let evidence = verify_samples_with_evidence(&kzg, &commitment, &samples, length)
+            .unwrap()
+            .unwrap();
+        assert_eq!(evidence.get_index(), honest.get_index());
+        assert_eq!(evidence.get_provided_value(), samples[5].get_value());
+        assert!(evidence.verify(&kzg).unwrap());
+
+        // the data holder attaches the value it expected, and the evidence
+        // survives serialization
+        let evidence = evidence.with_expected_value(honest.get_value());
+        assert!(evidence.verify(&kzg).unwrap());
+        let bytes = evidence.to_bytes().unwrap();
+        assert_eq!(bytes.len(), EVIDENCE_SIZE + 32);
+        assert_eq!(FraudEvidence::from_bytes(&bytes).unwrap(), evidence);
+        assert!(FraudEvidence::from_bytes(&bytes[1..]).is_err());
+        let mut bad_flag = bytes.clone();
+        bad_flag[EVIDENCE_SIZE - 1] = 0;
+        assert!(FraudEvidence::from_bytes(&bad_flag).is_err());
+
+        // an honest sample is not evidence
+        let framed = FraudEvidence::new(commitment, length as u64, &honest);
+        assert!(!framed.verify(&kzg).unwrap());
+        assert!(FraudEvidence::new(commitment, 3, &honest)
+            .verify(&kzg)
+            .is_err());
+    }
+}
@@ -0,0 +1,378 @@
+//! GLV scalar multiplication for BN254 G1.
+//!
+//! BN254 G1 has the endomorphism `phi(x, y) = (beta * x, y) = lambda * (x, y)`
+//! where `beta` and `lambda` are cube roots of unity in Fq and Fr. Any scalar
+//! `k` splits into `k1 + lambda * k2` with `|k1|, |k2| < 2^128`, so `k * P`
+//! becomes `k1 * P + k2 * phi(P)`, which halves the number of doublings. The
+//! MSM doubles the number of bases instead and runs a signed-digit Pippenger
+//! over 128-bit scalars.
+
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective};
+use ark_ec::{AffineRepr, Group, VariableBaseMSM};
+use ark_ff::{MontFp, PrimeField, Zero};
+use ark_std::ops::Neg;
+use rayon::prelude::*;
+
+/// Cube root of unity in Fq, `phi(x, y) = (BETA * x, y)`.
+const BETA: Fq =
+    MontFp!("21888242871839275220042445260109153167277707414472061641714758635765020556616");
+
+/// Cube root of unity in Fr with `phi(P) = LAMBDA * P`.
+const LAMBDA: Fr =
+    MontFp!("21888242871839275217838484774961031246154997185409878258781734729429964517155");
+
+// Short basis of the lattice {(a, b) : a + b * LAMBDA = 0 mod r}, as
+// v1 = (A1, -B1) and v2 = (A2, B2). Only the b components are needed since k1
+// is recovered as k - LAMBDA * k2.
+const B1: u128 = 9931322734385697763;
+const B2: u128 = 147946756881789319010696353538189108491;
+
+// round(2^256 * B2 / r) and round(2^256 * B1 / r), little-endian limbs, used
+// to estimate the Babai rounding coefficients with a multiply and shift.
+const G1: [u64; 3] = [0x5398fd0300ff6565, 0x4ccef014a773d2d2, 0x2];
+const G2: [u64; 3] = [0xd91d232ec7e0b3d7, 0x2, 0x0];
+
+/// Window width of the wNAF used for single scalar multiplications.
+const WNAF_WINDOW: usize = 4;
+
+/// applies the endomorphism, `phi(P) = LAMBDA * P`
+pub fn endomorphism(point: &G1Affine) -> G1Affine {
+    if point.is_zero() {
+        return *point;
+    }
+    G1Affine::new_unchecked(point.x * BETA, point.y)
+}
+
+/// splits `scalar` into `k1 + LAMBDA * k2` and returns each half as a sign
+/// (true if negative) and magnitude, or None if a half doesn't fit in 128 bits
+pub fn decompose(scalar: &Fr) -> Option<((bool, u128), (bool, u128))> {
+    let k = scalar.into_bigint().0;
+    let c1 = mul_shift_256(&k, &G1);
+    let c2 = mul_shift_256(&k, &G2);
+
+    // k2 = -(c1 * -B1 + c2 * B2), k1 = k - LAMBDA * k2
+    let k2 = Fr::from(c1) * Fr::from(B1) - Fr::from(c2) * Fr::from(B2);
+    let k1 = *scalar - LAMBDA * k2;
+    Some((to_signed_u128(&k1)?, to_signed_u128(&k2)?))
+}
+
+/// computes `scalar * base` with the GLV decomposition and interleaved wNAF
+pub fn mul(base: &G1Affine, scalar: &Fr) -> G1Projective {
+    let Some(((neg1, k1), (neg2, k2))) = decompose(scalar) else {
+        return base.mul_bigint(scalar.into_bigint());
+    };
+    let p1 = conditional_neg(*base, neg1);
+    let p2 = conditional_neg(endomorphism(base), neg2);
+
+    let table1 = odd_multiples(p1);
+    let table2 = odd_multiples(p2);
+    let naf1 = wnaf(k1);
+    let naf2 = wnaf(k2);
+
+    let mut acc = G1Projective::zero();
+    for i in (0..naf1.len().max(naf2.len())).rev() {
+        acc.double_in_place();
+        for (naf, table) in [(&naf1, &table1), (&naf2, &table2)] {
+            match naf.get(i).copied().unwrap_or(0) {
+                0 => {},
+                d if d > 0 => acc += table[(d / 2) as usize],
+                d => acc -= table[(-d / 2) as usize],
+            }
+        }
+    }
+    acc
+}
+
+/// multi-scalar multiplication over the GLV split scalars, with the same
+/// contract as `G1Projective::msm`: returns the length of the shorter input
+/// as the error if the lengths differ. Work is split into parallel tasks of
+/// at least `min_chunk_len` scalars, or runs on the calling thread if None.
+pub fn msm(
+    bases: &[G1Affine],
+    scalars: &[Fr],
+    min_chunk_len: Option<usize>,
+) -> Result<G1Projective, usize> {
+    if bases.len() != scalars.len() {
+        return Err(bases.len().min(scalars.len()));
+    }
+
+    let decomposed: Option<Vec<_>> = match min_chunk_len {
+        Some(min_len) => scalars
+            .par_iter()
+            .with_min_len(min_len)
+            .map(decompose)
+            .collect(),
+        None => scalars.iter().map(decompose).collect(),
+    };
+    let Some(decomposed) = decomposed else {
+        return G1Projective::msm(bases, scalars);
+    };
+
+    let mut points = Vec::with_capacity(2 * bases.len());
+    let mut magnitudes = Vec::with_capacity(2 * bases.len());
+    for (base, ((neg1, k1), (neg2, k2))) in bases.iter().zip(decomposed) {
+        points.push(conditional_neg(*base, neg1));
+        magnitudes.push(k1);
+        points.push(conditional_neg(endomorphism(base), neg2));
+        magnitudes.push(k2);
+    }
+    Ok(pippenger(&points, &magnitudes, min_chunk_len.is_some()))
+}
+
+/// signed-digit bucket method over scalars of at most 128 bits, with the
+/// windows summed in parallel if `parallel` is set
+fn pippenger(points: &[G1Affine], scalars: &[u128], parallel: bool) -> G1Projective {
+    let num_bits = scalars
+        .iter()
+        .map(|s| 128 - s.leading_zeros() as usize)
+        .max()
+        .unwrap_or(0);
+    if num_bits == 0 {
+        return G1Projective::zero();
+    }
+
+    let c = window_size(points.len(), num_bits);
+    let num_windows = num_windows(num_bits, c);
+    // digits are stored window-major so each window scans a contiguous run
+    let mut digits = vec![0i32; num_windows * scalars.len()];
+    for (i, scalar) in scalars.iter().enumerate() {
+        write_signed_digits(*scalar, c, |w, digit| digits[w * scalars.len() + i] = digit);
+    }
+
+    let window_sum = |window_digits: &[i32]| {
+        let mut buckets = vec![G1Projective::zero(); 1 << (c - 1)];
+        for (point, &digit) in points.iter().zip(window_digits) {
+            match digit {
+                0 => {},
+                d if d > 0 => buckets[(d - 1) as usize] += point,
+                d => buckets[(-d - 1) as usize] -= point,
+            }
+        }
+        // sum_i (i + 1) * buckets[i] with a running sum
+        let mut running_sum = G1Projective::zero();
+        let mut window_sum = G1Projective::zero();
+        for bucket in buckets.into_iter().rev() {
+            running_sum += bucket;
+            window_sum += running_sum;
+        }
+        window_sum
+    };
+    let window_sums: Vec<G1Projective> = if parallel {
+        digits.par_chunks(scalars.len()).map(window_sum).collect()
+    } else {
+        digits.chunks(scalars.len()).map(window_sum).collect()
+    };
+
+    let mut total = G1Projective::zero();
+    for window_sum in window_sums.into_iter().rev() {
+        for _ in 0..c {
+            total.double_in_place();
+        }
+        total += window_sum;
+    }
+    total
+}
+
+/// picks the window width minimizing the bucket additions plus the bucket
+/// reduction cost, `num_windows * (num_points + 2^c)`
+fn window_size(num_points: usize, num_bits: usize) -> usize {
+    (2..=20)
+        .min_by_key(|&c| num_windows(num_bits, c) * (num_points + (1 << c)))
+        .unwrap()
+}
+
+/// the windows cover at least two bits more than the scalars, so the top
+/// window's digit plus an incoming carry stays below 2^(c-1) and never
+/// carries out
+fn num_windows(num_bits: usize, c: usize) -> usize {
+    (num_bits + 1) / c + 1
+}
+
+/// recodes `scalar` into digits in [-2^(c-1), 2^(c-1)), passing each
+/// window index and digit to `write`
+fn write_signed_digits(scalar: u128, c: usize, mut write: impl FnMut(usize, i32)) {
+    let radix = 1i64 << c;
+    let num_windows = num_windows(128 - scalar.leading_zeros() as usize, c);
+    let mut carry = 0i64;
+    for w in 0..num_windows {
+        let shift = w * c;
+        let window = if shift < 128 {
+            ((scalar >> shift) as i64) & (radix - 1)
+        } else {
+            0
+        };
+        let mut digit = window + carry;
+        carry = 0;
+        if digit >= radix / 2 {
+            digit -= radix;
+            carry = 1;
+        }
+        write(w, digit as i32);
+    }
+}
+
+/// width-`WNAF_WINDOW` non-adjacent form, least significant digit first
+fn wnaf(mut scalar: u128) -> Vec<i8> {
+    let radix = 1i32 << WNAF_WINDOW;
+    let mut naf = Vec::with_capacity(129);
+    while scalar > 0 {
+        if scalar & 1 == 1 {
+            let mut digit = (scalar % radix as u128) as i32;
+            if digit >= radix / 2 {
+                digit -= radix;
+            }
+            if digit > 0 {
+                scalar -= digit as u128;
+            } else {
+                scalar += (-digit) as u128;
+            }
+            naf.push(digit as i8);
+        } else {
+            naf.push(0);
+        }
+        scalar >>= 1;
+    }
+    naf
+}
+
+/// returns [P, 3P, 5P, ...] up to the largest odd wNAF digit
+fn odd_multiples(point: G1Affine) -> Vec<G1Projective> {
+    let double = point.into_group().double();
+    let mut table = Vec::with_capacity(1 << (WNAF_WINDOW - 2));
+    table.push(point.into_group());
+    for i in 1..(1 << (WNAF_WINDOW - 2)) {
+        table.push(table[i - 1] + double);
+    }
+    table
+}
+
+fn conditional_neg(point: G1Affine, negate: bool) -> G1Affine {
+    if negate {
+        point.neg()
+    } else {
+        point
+    }
+}
+
+/// returns the sign and magnitude of `value` viewed as an integer in
+/// (-r/2, r/2), if the magnitude fits in 128 bits
+fn to_signed_u128(value: &Fr) -> Option<(bool, u128)> {
+    let limbs = value.into_bigint().0;
+    if limbs[2] == 0 && limbs[3] == 0 {
+        return Some((false, limbs[0] as u128 | (limbs[1] as u128) << 64));
+    }
+    let limbs = value.neg().into_bigint().0;
+    if limbs[2] == 0 && limbs[3] == 0 {
+        return Some((true, limbs[0] as u128 | (limbs[1] as u128) << 64));
+    }
+    None
+}
+
+/// computes `(k * g) >> 256` for a 256-bit `k` and 192-bit `g`
+fn mul_shift_256(k: &[u64; 4], g: &[u64; 3]) -> u128 {
+    let mut product = [0u64; 7];
+    for (i, &ki) in k.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &gj) in g.iter().enumerate() {
+            let t = ki as u128 * gj as u128 + product[i + j] as u128 + carry;
+            product[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        product[i + g.len()] = carry as u64;
+    }
+    product[4] as u128 | (product[5] as u128) << 64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::CurveGroup;
+    use ark_std::{One, UniformRand};
+
+    fn edge_scalars() -> Vec<Fr> {
+        vec![
+            Fr::zero(),
+            Fr::one(),
+            -Fr::one(),
+            LAMBDA,
+            -LAMBDA,
+            LAMBDA * LAMBDA,
+            Fr::from(u128::MAX),
+            -Fr::from(u128::MAX),
+        ]
+    }
+
+    #[test]
+    fn test_endomorphism_is_lambda_mul() {
+        let mut rng = ark_std::test_rng();
+        let point = G1Projective::rand(&mut rng).into_affine();
+        assert_eq!(
+            endomorphism(&point),
+            (point.into_group() * LAMBDA).into_affine()
+        );
+        assert!(endomorphism(&G1Affine::zero()).is_zero());
+    }
+
+    #[test]
+    fn test_decompose_recombines_to_scalar() {
+        let mut rng = ark_std::test_rng();
+        let mut scalars = edge_scalars();
+        scalars.extend((0..1000).map(|_| Fr::rand(&mut rng)));
+        for scalar in scalars {
+            let ((neg1, k1), (neg2, k2)) = decompose(&scalar).unwrap();
+            let k1 = if neg1 { -Fr::from(k1) } else { Fr::from(k1) };
+            let k2 = if neg2 { -Fr::from(k2) } else { Fr::from(k2) };
+            assert_eq!(k1 + LAMBDA * k2, scalar);
+        }
+    }
+
+    #[test]
+    fn test_mul_matches_arkworks() {
+        let mut rng = ark_std::test_rng();
+        let point = G1Projective::rand(&mut rng).into_affine();
+        let mut scalars = edge_scalars();
+        scalars.extend((0..50).map(|_| Fr::rand(&mut rng)));
+        for scalar in scalars {
+            assert_eq!(mul(&point, &scalar), point * scalar);
+        }
+        assert!(mul(&G1Affine::zero(), &Fr::rand(&mut rng)).is_zero());
+    }
+
+    #[test]
+    fn test_msm_matches_arkworks() {
+        let mut rng = ark_std::test_rng();
+        for size in [0, 1, 7, 33, 300] {
+            let bases: Vec<G1Affine> = (0..size)
+                .map(|_| G1Projective::rand(&mut rng).into_affine())
+                .collect();
+            let mut scalars: Vec<Fr> = (0..size).map(|_| Fr::rand(&mut rng)).collect();
+            for (scalar, edge) in scalars.iter_mut().zip(edge_scalars()) {
+                *scalar = edge;
+            }
+            assert_eq!(
+                msm(&bases, &scalars, Some(1)).unwrap(),
+                G1Projective::msm(&bases, &scalars).unwrap()
+            );
+        }
+        let bases = vec![G1Affine::generator(); 3];
+        assert_eq!(msm(&bases, &[Fr::one(); 2], None), Err(2));
+    }
+
+    #[test]
+    fn test_pippenger_carries() {
+        let mut rng = ark_std::test_rng();
+        // all-ones scalars carry out of every window, including the top one
+        for size in [1, 2, 4, 100, 200] {
+            let points: Vec<G1Affine> = (0..size)
+                .map(|_| G1Projective::rand(&mut rng).into_affine())
+                .collect();
+            for scalar in [u128::MAX, u128::MAX >> 1, (1u128 << 127) - 2] {
+                let scalars = vec![scalar; size];
+                let expected: G1Projective =
+                    points.iter().map(|point| *point * Fr::from(scalar)).sum();
+                assert_eq!(pippenger(&points, &scalars, true), expected);
+                assert_eq!(pippenger(&points, &scalars, false), expected);
+            }
+        }
+    }
+}
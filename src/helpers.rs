@@ -1,18 +1,23 @@
 use ark_bn254::{Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
 use ark_ec::AffineRepr;
-use ark_ff::{sbb, BigInt, BigInteger, Field, LegendreSymbol, PrimeField};
-use ark_std::{str::FromStr, vec::Vec, One, Zero};
+use ark_ff::{batch_inversion, sbb, BigInt, BigInteger, Field, LegendreSymbol, PrimeField};
+use ark_std::{str::FromStr, vec, vec::Vec, One, Zero};
+use core::cmp;
+#[cfg(feature = "std")]
 use crossbeam_channel::Receiver;
-use std::cmp;
 
+#[cfg(feature = "std")]
+use crate::traits::ReadPointFromBytes;
 use crate::{
     arith,
-    consts::{BYTES_PER_FIELD_ELEMENT, SIZE_OF_G1_AFFINE_COMPRESSED, SIZE_OF_G2_AFFINE_COMPRESSED},
-    traits::ReadPointFromBytes,
+    consts::{
+        BYTES_PER_FIELD_ELEMENT, PRIMITIVE_ROOTS_OF_UNITY, SIZE_OF_G1_AFFINE_COMPRESSED,
+        SIZE_OF_G2_AFFINE_COMPRESSED,
+    },
 };
 
-pub fn blob_to_polynomial(blob: &Vec<u8>) -> Vec<Fr> {
-    to_fr_array(&blob)
+pub fn blob_to_polynomial(blob: &[u8]) -> Vec<Fr> {
+    to_fr_array(blob)
 }
 
 pub fn set_bytes_canonical_manual(data: &[u8]) -> Fr {
@@ -83,7 +88,7 @@ pub fn remove_empty_byte_from_padded_bytes(data: &[u8]) -> Vec<u8> {
 }
 
 pub fn set_bytes_canonical(data: &[u8]) -> Fr {
-    return Fr::from_be_bytes_mod_order(&data);
+    Fr::from_be_bytes_mod_order(data)
 }
 
 fn get_num_element(data_len: usize, symbol_size: usize) -> usize {
@@ -94,15 +99,15 @@ pub fn to_fr_array(data: &[u8]) -> Vec<Fr> {
     let num_ele = get_num_element(data.len(), BYTES_PER_FIELD_ELEMENT);
     let mut eles = vec![Fr::zero(); num_ele]; // Initialize with zero elements
 
-    for i in 0..num_ele {
+    for (i, ele) in eles.iter_mut().enumerate() {
         let start = i * BYTES_PER_FIELD_ELEMENT;
         let end = (i + 1) * BYTES_PER_FIELD_ELEMENT;
         if end > data.len() {
             let mut padded = vec![0u8; BYTES_PER_FIELD_ELEMENT];
             padded[..data.len() - start].copy_from_slice(&data[start..]);
-            eles[i] = set_bytes_canonical(&padded);
+            *ele = set_bytes_canonical(&padded);
         } else {
-            eles[i] = set_bytes_canonical(&data[start..end]);
+            *ele = set_bytes_canonical(&data[start..end]);
         }
     }
     eles
@@ -113,8 +118,8 @@ pub fn to_byte_array(data_fr: &[Fr], max_data_size: usize) -> Vec<u8> {
     let data_size = cmp::min(n * BYTES_PER_FIELD_ELEMENT, max_data_size);
     let mut data = vec![0u8; data_size];
 
-    for i in 0..n {
-        let v: Vec<u8> = data_fr[i].into_bigint().to_bytes_be();
+    for (i, fr) in data_fr.iter().enumerate() {
+        let v: Vec<u8> = fr.into_bigint().to_bytes_be();
 
         let start = i * BYTES_PER_FIELD_ELEMENT;
         let end = (i + 1) * BYTES_PER_FIELD_ELEMENT;
@@ -136,24 +141,19 @@ pub fn is_zeroed(first_byte: u8, buf: Vec<u8>) -> bool {
         return false;
     }
 
-    for i in 0..buf.len() {
-        if buf[i] != 0 {
-            return false;
-        }
-    }
-    true
+    buf.iter().all(|&byte| byte == 0)
 }
 
 pub fn str_vec_to_fr_vec(input: Vec<&str>) -> Result<Vec<Fr>, &str> {
     let mut output: Vec<Fr> = Vec::<Fr>::with_capacity(input.len());
 
-    for i in 0..input.len() {
-        if input[i] == "-1" {
+    for item in input {
+        if item == "-1" {
             let mut test = Fr::one();
             test.neg_in_place();
             output.push(test);
         } else {
-            let fr_data = Fr::from_str(input[i]).expect("could not load string to Fr");
+            let fr_data = Fr::from_str(item).expect("could not load string to Fr");
             output.push(fr_data);
         }
     }
@@ -161,6 +161,82 @@ pub fn str_vec_to_fr_vec(input: Vec<&str>) -> Result<Vec<Fr>, &str> {
     Ok(output)
 }
 
+/// Returns the primitive root of unity of `order`, the generator of the
+/// `order`-th roots of unity that blobs are committed, proven and encoded
+/// over. `order` must be a power of 2 up to 2^28, the two-adicity of `Fr`.
+pub fn primitive_root_of_unity(order: usize) -> Option<Fr> {
+    if !order.is_power_of_two() {
+        return None;
+    }
+    PRIMITIVE_ROOTS_OF_UNITY
+        .get(order.trailing_zeros() as usize)
+        .map(|root| Fr::from_str(root).unwrap())
+}
+
+/// Returns the `n` roots of unity `[1, w, w^2, ..., w^(n-1)]`, where `w` is
+/// `primitive_root_of_unity(n)`, or None if there is no primitive root of
+/// order `n`.
+pub fn roots_of_unity(n: usize) -> Option<Vec<Fr>> {
+    let generator = primitive_root_of_unity(n)?;
+    let mut roots = Vec::with_capacity(n);
+    let mut root = Fr::one();
+    for _ in 0..n {
+        roots.push(root);
+        root *= generator;
+    }
+    Some(roots)
+}
+
+/// Evaluates the polynomial given by `evaluations` over the roots of unity of
+/// their length at an arbitrary point `z`, without interpolating it first.
+/// Uses the barycentric formula, so verifiers can compute `p(z)` from
+/// evaluation-form data such as a blob. Returns None if the length is not a
+/// power of 2 up to 2^28.
+pub fn evaluate_polynomial_in_evaluation_form(evaluations: &[Fr], z: Fr) -> Option<Fr> {
+    let roots_of_unity = roots_of_unity(evaluations.len())?;
+    Some(evaluate_over_roots_of_unity(
+        evaluations,
+        &roots_of_unity,
+        z,
+    ))
+}
+
+/// evaluates at `z` the polynomial with `evaluations` over `roots_of_unity`,
+/// with `p(z) = (z^n - 1) / n * sum e_i w_i / (z - w_i)`, or the evaluation
+/// itself if `z` is one of the roots
+pub(crate) fn evaluate_over_roots_of_unity(evaluations: &[Fr], roots_of_unity: &[Fr], z: Fr) -> Fr {
+    if let Some(i) = roots_of_unity.iter().position(|root| *root == z) {
+        return evaluations[i];
+    }
+
+    let mut denominators: Vec<Fr> = roots_of_unity.iter().map(|root| z - root).collect();
+    batch_inversion(&mut denominators);
+    let sum: Fr = evaluations
+        .iter()
+        .zip(roots_of_unity)
+        .zip(&denominators)
+        .map(|((eval, root), inv)| *eval * root * inv)
+        .sum();
+    let n = Fr::from(evaluations.len() as u64);
+    (z.pow([evaluations.len() as u64]) - Fr::one()) * n.inverse().unwrap() * sum
+}
+
+/// divides the polynomial with `coefficients`, lowest degree first, by
+/// (X - z) with synthetic division, returning the quotient and the remainder,
+/// which is the value of the polynomial at `z`
+pub(crate) fn divide_by_linear(coefficients: &[Fr], z: Fr) -> (Vec<Fr>, Fr) {
+    let mut quotient = vec![Fr::zero(); coefficients.len().saturating_sub(1)];
+    let mut acc = Fr::zero();
+    for i in (1..coefficients.len()).rev() {
+        acc = coefficients[i] + z * acc;
+        quotient[i - 1] = acc;
+    }
+    let remainder = coefficients
+        .first()
+        .map_or(Fr::zero(), |constant| *constant + z * acc);
+    (quotient, remainder)
+}
+
 pub fn lexicographically_largest(z: &Fq) -> bool {
     // This can be determined by checking to see if the element is
     // larger than (p - 1) // 2. If we subtract by ((p - 1) // 2) + 1
@@ -190,7 +266,7 @@ pub fn lexicographically_largest(z: &Fq) -> bool {
     borrow == 0
 }
 
-pub fn read_g2_point_from_bytes_be(g2_bytes_be: &Vec<u8>) -> Result<G2Affine, &str> {
+pub fn read_g2_point_from_bytes_be(g2_bytes_be: &[u8]) -> Result<G2Affine, &str> {
     if g2_bytes_be.len() != SIZE_OF_G2_AFFINE_COMPRESSED {
         return Err("not enough bytes for g2 point");
     }
@@ -232,22 +308,18 @@ pub fn read_g2_point_from_bytes_be(g2_bytes_be: &Vec<u8>) -> Result<G2Affine, &s
 
     let mut y_sqrt = added_result.sqrt().ok_or("no square root found").unwrap();
 
-    let mut lexicographical_check_result = false;
-
-    if y_sqrt.c1.0.is_zero() {
-        lexicographical_check_result = lexicographically_largest(&y_sqrt.c0);
+    let lexicographical_check_result = if y_sqrt.c1.0.is_zero() {
+        lexicographically_largest(&y_sqrt.c0)
     } else {
-        lexicographical_check_result = lexicographically_largest(&y_sqrt.c1);
-    }
+        lexicographically_largest(&y_sqrt.c1)
+    };
 
     if lexicographical_check_result {
         if m_data == m_compressed_smallest {
             y_sqrt.neg_in_place();
         }
-    } else {
-        if m_data == m_compressed_largest {
-            y_sqrt.neg_in_place();
-        }
+    } else if m_data == m_compressed_largest {
+        y_sqrt.neg_in_place();
     }
 
     let point = G2Affine::new_unchecked(x, y_sqrt);
@@ -259,7 +331,7 @@ pub fn read_g2_point_from_bytes_be(g2_bytes_be: &Vec<u8>) -> Result<G2Affine, &s
     Ok(point)
 }
 
-pub fn read_g1_point_from_bytes_be(g1_bytes_be: &Vec<u8>) -> Result<G1Affine, &str> {
+pub fn read_g1_point_from_bytes_be(g1_bytes_be: &[u8]) -> Result<G1Affine, &str> {
     if g1_bytes_be.len() != SIZE_OF_G1_AFFINE_COMPRESSED {
         return Err("not enough bytes for g1 point");
     }
@@ -283,16 +355,14 @@ pub fn read_g1_point_from_bytes_be(g1_bytes_be: &Vec<u8>) -> Result<G1Affine, &s
     x_bytes[0] &= !m_mask;
     let x = Fq::from_be_bytes_mod_order(&x_bytes);
     let y_squared = x * x * x + Fq::from(3);
-    let mut y_sqrt = y_squared.sqrt().ok_or("no item1").unwrap();
+    let mut y_sqrt = y_squared.sqrt().ok_or("point is not on the curve")?;
 
     if lexicographically_largest(&y_sqrt) {
         if m_data == m_compressed_smallest {
             y_sqrt.neg_in_place();
         }
-    } else {
-        if m_data == m_compressed_largest {
-            y_sqrt.neg_in_place();
-        }
+    } else if m_data == m_compressed_largest {
+        y_sqrt.neg_in_place();
     }
     let point = G1Affine::new_unchecked(x, y_sqrt);
     if !point.is_in_correct_subgroup_assuming_on_curve()
@@ -303,15 +373,54 @@ pub fn read_g1_point_from_bytes_be(g1_bytes_be: &Vec<u8>) -> Result<G1Affine, &s
     Ok(point)
 }
 
+/// Encodes `point` as `read_g1_point_from_bytes_be` reads it: the big-endian
+/// x coordinate with its top bits flagging which square root y is.
+pub fn g1_point_to_bytes_be(point: &G1Affine) -> [u8; SIZE_OF_G1_AFFINE_COMPRESSED] {
+    let mut bytes = [0u8; SIZE_OF_G1_AFFINE_COMPRESSED];
+    let Some((x, y)) = point.xy() else {
+        bytes[0] = 0b01 << 6;
+        return bytes;
+    };
+    bytes.copy_from_slice(&x.into_bigint().to_bytes_be());
+    bytes[0] |= if lexicographically_largest(y) {
+        0b11 << 6
+    } else {
+        0b10 << 6
+    };
+    bytes
+}
+
+/// Encodes `point` as `read_g2_point_from_bytes_be` reads it: the big-endian
+/// imaginary then real part of x, with the top bits flagging which square
+/// root y is.
+pub fn g2_point_to_bytes_be(point: &G2Affine) -> [u8; SIZE_OF_G2_AFFINE_COMPRESSED] {
+    let mut bytes = [0u8; SIZE_OF_G2_AFFINE_COMPRESSED];
+    let Some((x, y)) = point.xy() else {
+        bytes[0] = 0b01 << 6;
+        return bytes;
+    };
+    let half_size = SIZE_OF_G2_AFFINE_COMPRESSED / 2;
+    bytes[..half_size].copy_from_slice(&x.c1.into_bigint().to_bytes_be());
+    bytes[half_size..].copy_from_slice(&x.c0.into_bigint().to_bytes_be());
+    let largest = if y.c1.is_zero() {
+        lexicographically_largest(&y.c0)
+    } else {
+        lexicographically_largest(&y.c1)
+    };
+    bytes[0] |= if largest { 0b11 << 6 } else { 0b10 << 6 };
+    bytes
+}
+
+#[cfg(feature = "std")]
 pub fn process_chunks<T>(receiver: Receiver<(Vec<u8>, usize)>) -> Vec<(T, usize)>
 where
     T: ReadPointFromBytes,
 {
     receiver
         .iter()
-        .filter_map(
+        .map(
             |(chunk, position)| match T::read_point_from_bytes_be(&chunk) {
-                Ok(point) => Some((point, position)),
+                Ok(point) => (point, position),
                 Err(err) => panic!("{}", err),
             },
         )
@@ -326,8 +435,8 @@ fn get_b_twist_curve_coeff() -> Fq2 {
     let mut twist_curve_coeff = Fq2::new(twist_c0, twist_c1);
     twist_curve_coeff = *twist_curve_coeff.inverse_in_place().unwrap();
 
-    twist_curve_coeff.c0 = twist_curve_coeff.c0 * Fq::from(3);
-    twist_curve_coeff.c1 = twist_curve_coeff.c1 * Fq::from(3);
+    twist_curve_coeff.c0 *= Fq::from(3);
+    twist_curve_coeff.c1 *= Fq::from(3);
     twist_curve_coeff
 }
 
@@ -369,6 +478,7 @@ pub fn is_on_curve_g2(g2: &G2Projective) -> bool {
     left == right
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn test_g1_is_on_curve() {
     use ark_ff::UniformRand;
@@ -377,13 +487,14 @@ fn test_g1_is_on_curve() {
     let rng = &mut thread_rng();
     for _ in 0..1000 {
         let point = G1Affine::rand(rng);
-        assert_eq!(is_on_curve_g1(&G1Projective::from(point)), true);
+        assert!(is_on_curve_g1(&G1Projective::from(point)));
         let mut not_on_curve = point;
         not_on_curve.x += Fq::one();
-        assert_eq!(is_on_curve_g1(&G1Projective::from(not_on_curve)), false);
+        assert!(!is_on_curve_g1(&G1Projective::from(not_on_curve)));
     }
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn test_g2_is_on_curve() {
     use ark_ff::UniformRand;
@@ -392,14 +503,15 @@ fn test_g2_is_on_curve() {
     let rng = &mut thread_rng();
     for _ in 0..1000 {
         let point = G2Affine::rand(rng);
-        assert_eq!(is_on_curve_g2(&G2Projective::from(point)), true);
+        assert!(is_on_curve_g2(&G2Projective::from(point)));
         let mut not_on_curve = point;
         not_on_curve.x += Fq2::one();
-        assert_eq!(is_on_curve_g2(&G2Projective::from(not_on_curve)), false);
+        assert!(!is_on_curve_g2(&G2Projective::from(not_on_curve)));
     }
 }
 // Loads data from files. This data was generated by gnark and is DA compatible.
 // Tests deserialization of data and equivalence.
+#[cfg(feature = "std")]
 #[test]
 fn test_blob_to_polynomial() {
     use ark_serialize::Read;
@@ -410,7 +522,7 @@ fn test_blob_to_polynomial() {
 
     let file = File::open("src/test-files/blobs.txt").unwrap();
     let mut reader = BufReader::new(file);
-    let mut buffer = vec![0u8; SIZE_OF_G1_AFFINE_COMPRESSED];
+    let mut buffer = [0u8; SIZE_OF_G1_AFFINE_COMPRESSED];
     let mut read_fr_from_bytes: Vec<Fr> = vec![];
     let mut fr_from_str_vec: Vec<Fr> = vec![];
 
@@ -458,13 +570,13 @@ fn test_to_fr_array() {
         .as_slice(),
     );
     let data_fr = to_fr_array(&converted);
-    let result = to_byte_array(&data_fr, converted.len().try_into().unwrap());
+    let result = to_byte_array(&data_fr, converted.len());
     assert_eq!(converted, result, "should be deserialized properly");
 
     let ga_converted = convert_by_padding_empty_byte(GETTYSBURG_ADDRESS_BYTES);
     let ga_converted_fr = to_fr_array(&ga_converted);
     assert_eq!(
-        to_byte_array(&ga_converted_fr, ga_converted.len().try_into().unwrap()),
+        to_byte_array(&ga_converted_fr, ga_converted.len()),
         ga_converted
     );
 }
@@ -667,4 +779,27 @@ mod tests {
         let result = read_g2_point_from_bytes_be(&binding);
         assert_eq!(result, Err("not enough bytes for g2 point"));
     }
+
+    #[test]
+    fn test_roots_of_unity() {
+        for log2 in 0..=28 {
+            let order = 1usize << log2;
+            let root = primitive_root_of_unity(order).unwrap();
+            // a primitive root of order n has order exactly n
+            assert!(root.pow([order as u64]).is_one());
+            assert_eq!(log2 == 0, root.pow([order as u64 / 2]).is_one());
+        }
+        assert_eq!(primitive_root_of_unity(2), Some(-Fr::one()));
+        for order in [0, 3, 1 << 29] {
+            assert_eq!(primitive_root_of_unity(order), None);
+            assert_eq!(roots_of_unity(order), None);
+        }
+
+        let roots = roots_of_unity(8).unwrap();
+        assert_eq!(roots.len(), 8);
+        assert_eq!(roots[0], Fr::one());
+        assert_eq!(roots[1], primitive_root_of_unity(8).unwrap());
+        assert_eq!(roots[4], -Fr::one());
+        assert_eq!(roots[7] * roots[1], Fr::one());
+    }
 }
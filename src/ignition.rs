@@ -0,0 +1,307 @@
+//! Loading the SRS from the Aztec Ignition transcripts.
+//!
+//! Ignition split its BN254 powers of tau over numbered files,
+//! `transcript00.dat` onwards. Each starts with a manifest of big-endian
+//! `u32`s: the transcript's number, the number of transcripts, the total
+//! number of G1 and G2 points, the numbers of points in this transcript and
+//! the index of its first point. The G1 points `[tau^i]G1` for `i >= 1`
+//! follow, then the G2 points, which only the first transcript has, and a
+//! checksum. Coordinates are four 64-bit limbs, least significant first,
+//! each big-endian. The generator isn't stored, so it is put back in front
+//! of the G1 points.
+//!
+//! The manifests are checked to chain together and to match the file
+//! sizes, and every point is checked to be on the curve. The BLAKE2b
+//! checksums are not verified.
+
+use crate::{
+    errors::KzgError,
+    keys::{ProvingKey, VerifyingKey},
+    kzg::Kzg,
+    ptau::read_points,
+};
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger256, PrimeField};
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+const MANIFEST_SIZE: u64 = 28;
+const G1_POINT_SIZE: usize = 64;
+const G2_POINT_SIZE: usize = 128;
+const CHECKSUM_SIZE: u64 = 64;
+
+/// The header of a transcript file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Manifest {
+    transcript_number: u32,
+    total_transcripts: u32,
+    total_g1_points: u32,
+    total_g2_points: u32,
+    num_g1_points: u32,
+    num_g2_points: u32,
+    start_from: u32,
+}
+
+impl Manifest {
+    fn read<R: Read>(reader: &mut R) -> Result<Self, KzgError> {
+        let mut next = || -> Result<u32, KzgError> {
+            let mut bytes = [0u8; 4];
+            reader
+                .read_exact(&mut bytes)
+                .map_err(|e| KzgError::SerializationError(e.to_string()))?;
+            Ok(u32::from_be_bytes(bytes))
+        };
+        // fields are read in the order they are written
+        Ok(Manifest {
+            transcript_number: next()?,
+            total_transcripts: next()?,
+            total_g1_points: next()?,
+            total_g2_points: next()?,
+            num_g1_points: next()?,
+            num_g2_points: next()?,
+            start_from: next()?,
+        })
+    }
+
+    fn file_size(&self) -> u64 {
+        MANIFEST_SIZE
+            + self.num_g1_points as u64 * G1_POINT_SIZE as u64
+            + self.num_g2_points as u64 * G2_POINT_SIZE as u64
+            + CHECKSUM_SIZE
+    }
+}
+
+/// The powers of tau read from the Ignition transcripts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IgnitionSrs {
+    g1: Vec<G1Affine>,
+    g2_tau: G2Affine,
+    total_g1_points: u32,
+}
+
+impl IgnitionSrs {
+    /// Returns the G1 powers of tau that were read, starting at the
+    /// generator.
+    pub fn get_g1_points(&self) -> &[G1Affine] {
+        &self.g1
+    }
+
+    /// Returns `[tau]G2`.
+    pub fn get_g2_tau(&self) -> G2Affine {
+        self.g2_tau
+    }
+
+    /// Returns the number of G1 powers of tau over all transcripts, not
+    /// counting the generator.
+    pub fn get_total_g1_points(&self) -> u32 {
+        self.total_g1_points
+    }
+}
+
+/// Returns the path of transcript `number` in `dir`.
+pub fn transcript_path(dir: &Path, number: u32) -> std::path::PathBuf {
+    dir.join(format!("transcript{number:02}.dat"))
+}
+
+/// Reads the first `num_points` G1 powers of tau, including the generator,
+/// and `[tau]G2` from the transcripts in `dir`, reading only as many
+/// transcripts as needed.
+pub fn read_ignition_transcripts(dir: &Path, num_points: usize) -> Result<IgnitionSrs, KzgError> {
+    let mut g1 = Vec::with_capacity(num_points.max(1));
+    g1.push(G1Affine::generator());
+    let mut g2_tau = None;
+    let mut first: Option<Manifest> = None;
+
+    let mut number = 0;
+    while g1.len() < num_points || g2_tau.is_none() {
+        let path = transcript_path(dir, number);
+        let file = File::open(&path)
+            .map_err(|e| KzgError::GenericError(format!("{}: {e}", path.display())))?;
+        let file_len = file
+            .metadata()
+            .map_err(|e| KzgError::GenericError(e.to_string()))?
+            .len();
+        let mut reader = BufReader::new(file);
+        let manifest = Manifest::read(&mut reader)?;
+        let expected = *first.get_or_insert(manifest);
+
+        if manifest.transcript_number != number
+            || manifest.total_transcripts != expected.total_transcripts
+            || manifest.total_g1_points != expected.total_g1_points
+            || manifest.total_g2_points != expected.total_g2_points
+            || manifest.start_from as usize != g1.len() - 1
+            || manifest.file_size() != file_len
+        {
+            return Err(KzgError::SerializationError(format!(
+                "{} does not continue the transcripts before it",
+                path.display()
+            )));
+        }
+        if number == 0 && manifest.num_g2_points == 0 {
+            return Err(KzgError::SerializationError(
+                "the first transcript has no G2 points".to_string(),
+            ));
+        }
+        if num_points as u64 > expected.total_g1_points as u64 + 1 {
+            return Err(KzgError::GenericError(format!(
+                "ignition transcripts hold {} G1 points, not {num_points}",
+                expected.total_g1_points as u64 + 1
+            )));
+        }
+
+        let count = (manifest.num_g1_points as usize).min(num_points.saturating_sub(g1.len()));
+        g1.extend(read_points(&mut reader, count, G1_POINT_SIZE, |bytes| {
+            Ok(G1Affine::new_unchecked(
+                read_fq(&bytes[..32])?,
+                read_fq(&bytes[32..])?,
+            ))
+        })?);
+        if g2_tau.is_none() {
+            // skip the rest of the G1 points to the G2 points
+            let skipped = (manifest.num_g1_points as usize - count) * G1_POINT_SIZE;
+            std::io::copy(
+                &mut (&mut reader).take(skipped as u64),
+                &mut std::io::sink(),
+            )
+            .map_err(|e| KzgError::SerializationError(e.to_string()))?;
+            let g2 = read_points(&mut reader, 1, G2_POINT_SIZE, |bytes| {
+                let x = Fq2::new(read_fq(&bytes[..32])?, read_fq(&bytes[32..64])?);
+                let y = Fq2::new(read_fq(&bytes[64..96])?, read_fq(&bytes[96..])?);
+                Ok(G2Affine::new_unchecked(x, y))
+            })?;
+            g2_tau = Some(g2[0]);
+        }
+        number += 1;
+    }
+
+    Ok(IgnitionSrs {
+        g1,
+        g2_tau: g2_tau.expect("the first transcript holds G2 points"),
+        total_g1_points: first.map_or(0, |manifest| manifest.total_g1_points),
+    })
+}
+
+impl Kzg {
+    /// Creates a setup from the first `srs_points_to_load` powers of tau of
+    /// the Ignition transcripts in `transcript_dir`, with an SRS order of
+    /// all the G1 points the transcripts hold. Ignition only has `[tau]G2`,
+    /// so the G2 points are a power of 2 table of that one point.
+    pub fn setup_from_ignition(
+        transcript_dir: &str,
+        srs_points_to_load: u32,
+    ) -> Result<Self, KzgError> {
+        let srs =
+            read_ignition_transcripts(Path::new(transcript_dir), srs_points_to_load as usize)?;
+        Self::from_keys(
+            ProvingKey::new(srs.g1, srs.total_g1_points as u64 + 1)?,
+            VerifyingKey::from_power_of_2_table(vec![srs.g2_tau])?,
+        )
+    }
+}
+
+/// Reads a base field element stored as four big-endian limbs, least
+/// significant first.
+fn read_fq(bytes: &[u8]) -> Result<Fq, KzgError> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("chunks are 8 bytes"));
+    }
+    Fq::from_bigint(BigInteger256::new(limbs)).ok_or_else(|| {
+        KzgError::SerializationError("transcript coordinate is not a field element".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+
+    fn write_fq(bytes: &mut Vec<u8>, value: &Fq) {
+        for limb in value.into_bigint().0 {
+            bytes.extend_from_slice(&limb.to_be_bytes());
+        }
+    }
+
+    // splits `g1[1..]` over transcripts of `per_transcript` points
+    fn write_transcripts(dir: &Path, g1: &[G1Affine], g2_tau: &G2Affine, per_transcript: usize) {
+        let chunks: Vec<_> = g1[1..].chunks(per_transcript).collect();
+        for (number, chunk) in chunks.iter().enumerate() {
+            let num_g2_points = u32::from(number == 0);
+            let manifest = [
+                number as u32,
+                chunks.len() as u32,
+                (g1.len() - 1) as u32,
+                1,
+                chunk.len() as u32,
+                num_g2_points,
+                (number * per_transcript) as u32,
+            ];
+            let mut bytes: Vec<u8> = manifest
+                .iter()
+                .flat_map(|word| word.to_be_bytes())
+                .collect();
+            for point in chunk.iter() {
+                let (x, y) = point.xy().unwrap();
+                write_fq(&mut bytes, x);
+                write_fq(&mut bytes, y);
+            }
+            if number == 0 {
+                let (x, y) = g2_tau.xy().unwrap();
+                for coordinate in [x.c0, x.c1, y.c0, y.c1] {
+                    write_fq(&mut bytes, &coordinate);
+                }
+            }
+            bytes.extend_from_slice(&[0u8; CHECKSUM_SIZE as usize]);
+            std::fs::write(transcript_path(dir, number as u32), bytes).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_read_ignition_transcripts() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let dir = std::env::temp_dir().join("rust-kzg-bn254-test-ignition");
+        std::fs::create_dir_all(&dir).unwrap();
+        let g1 = kzg.get_g1_points();
+        write_transcripts(&dir, &g1[..2001], &kzg.verifier().get_g2_tau(), 1000);
+
+        // the first transcript alone covers this, the second is read for more
+        for num_points in [10, 1500, 2001] {
+            let srs = read_ignition_transcripts(&dir, num_points).unwrap();
+            assert_eq!(srs.get_g1_points(), &g1[..num_points]);
+            assert_eq!(srs.get_total_g1_points(), 2000);
+        }
+        assert!(read_ignition_transcripts(&dir, 2002).is_err());
+
+        let from_ignition = Kzg::setup_from_ignition(dir.to_str().unwrap(), 2000).unwrap();
+        assert_eq!(from_ignition.verifier(), kzg.verifier());
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let commitment = from_ignition.blob_to_kzg_commitment(&blob).unwrap();
+        assert_eq!(commitment, kzg.blob_to_kzg_commitment(&blob).unwrap());
+        let proof = from_ignition
+            .compute_blob_kzg_proof(&blob, &commitment)
+            .unwrap();
+        assert!(kzg
+            .verify_blob_kzg_proof(&blob, &commitment, &proof)
+            .unwrap());
+
+        // a second transcript that doesn't pick up where the first ended
+        let second = transcript_path(&dir, 1);
+        let mut bytes = std::fs::read(&second).unwrap();
+        bytes[27] ^= 1;
+        std::fs::write(&second, bytes).unwrap();
+        assert!(read_ignition_transcripts(&dir, 1500).is_err());
+        assert!(read_ignition_transcripts(&dir, 1000).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
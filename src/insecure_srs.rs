@@ -0,0 +1,117 @@
+//! Deterministic SRS for tests. NOT FOR PRODUCTION USE.
+//!
+//! `Kzg::insecure_setup_from_seed` derives tau from a seed and computes its
+//! powers in memory, so CI and downstream crates can test against an SRS of
+//! any size without shipping point files. Anyone who knows the seed knows
+//! tau and can forge proofs for any commitment, so these setups must never
+//! verify anything that matters. `Kzg::export_srs` writes the points to
+//! SRS files, for tools that only read files.
+
+use crate::{
+    consts::INSECURE_SRS_TAU_DOMAIN,
+    errors::KzgError,
+    keys::{ProvingKey, VerifyingKey},
+    kzg::Kzg,
+};
+use ark_bn254::{Fr, G1Projective, G2Projective};
+use ark_ec::CurveGroup;
+use ark_ff::{Field, PrimeField, Zero};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// Returns the tau `Kzg::insecure_setup_from_seed` derives from `seed`.
+pub fn insecure_tau_from_seed(seed: &[u8]) -> Fr {
+    Fr::from_be_bytes_mod_order(
+        &Sha256::new()
+            .chain_update(INSECURE_SRS_TAU_DOMAIN)
+            .chain_update(seed)
+            .finalize(),
+    )
+}
+
+/// Returns `[tau^i]G` for `i < n`.
+fn powers_of_tau<G: CurveGroup<ScalarField = Fr>>(tau: Fr, n: usize) -> Vec<G::Affine> {
+    let mut powers = Vec::with_capacity(n);
+    let mut power = Fr::ONE;
+    for _ in 0..n {
+        powers.push(power);
+        power *= tau;
+    }
+    let points: Vec<G> = powers
+        .into_par_iter()
+        .map(|power| G::generator() * power)
+        .collect();
+    G::normalize_batch(&points)
+}
+
+impl Kzg {
+    /// INSECURE: creates a setup of `n` G1 and G2 powers of a tau derived
+    /// from `seed`, with an SRS order of `n`. The same seed always gives the
+    /// same SRS, and knowing it is enough to forge proofs, so this is only
+    /// for tests.
+    pub fn insecure_setup_from_seed(seed: &[u8], n: u32) -> Result<Self, KzgError> {
+        if n < 2 {
+            return Err(KzgError::GenericError(
+                "an insecure SRS needs at least 2 points".to_string(),
+            ));
+        }
+        let tau = insecure_tau_from_seed(seed);
+        if tau.is_zero() {
+            return Err(KzgError::GenericError(
+                "seed derives a zero tau".to_string(),
+            ));
+        }
+        Self::from_keys(
+            ProvingKey::new(powers_of_tau::<G1Projective>(tau, n as usize), n.into())?,
+            VerifyingKey::from_powers(powers_of_tau::<G2Projective>(tau, n as usize))?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+    use ark_bn254::G1Affine;
+    use ark_ec::AffineRepr;
+
+    #[test]
+    fn test_insecure_setup_from_seed() {
+        let kzg = Kzg::insecure_setup_from_seed(b"test", 1024).unwrap();
+        assert_eq!(kzg, Kzg::insecure_setup_from_seed(b"test", 1024).unwrap());
+        assert_ne!(
+            kzg.get_g1_points(),
+            Kzg::insecure_setup_from_seed(b"other", 1024)
+                .unwrap()
+                .get_g1_points()
+        );
+        let tau = insecure_tau_from_seed(b"test");
+        assert_eq!(
+            kzg.get_g1_points()[1],
+            (G1Affine::generator() * tau).into_affine()
+        );
+        kzg.verify_srs().unwrap();
+
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let commitment = kzg.blob_to_kzg_commitment(&blob).unwrap();
+        let proof = kzg.compute_blob_kzg_proof(&blob, &commitment).unwrap();
+        assert!(kzg
+            .verify_blob_kzg_proof(&blob, &commitment, &proof)
+            .unwrap());
+
+        // the written files load back into the same setup
+        let dir = std::env::temp_dir().join("rust-kzg-bn254-test-insecure-srs");
+        std::fs::create_dir_all(&dir).unwrap();
+        let g1_path = dir.join("g1.point");
+        let g2_path = dir.join("g2.point");
+        let (g1_path, g2_path) = (g1_path.to_str().unwrap(), g2_path.to_str().unwrap());
+        let mut g1 = kzg.get_g1_points();
+        g1.push(G1Affine::zero());
+        Kzg::write_g1_points(g1_path, &g1).unwrap();
+        Kzg::write_g2_points(g2_path, &kzg.get_g2_points()).unwrap();
+        let loaded = Kzg::setup(g1_path, g2_path, "", 1025, 1025).unwrap();
+        assert_eq!(loaded.get_g1_points(), g1);
+        assert_eq!(loaded.get_g2_points(), kzg.get_g2_points());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+//! The SRS split into what provers and verifiers each need.
+//!
+//! A `ProvingKey` holds the G1 powers of tau that commitments and proofs are
+//! built from, and a `VerifyingKey` the G2 points their checks pair against.
+//! Both serialize with `CanonicalSerialize`, so a prover ships only the G1
+//! points and a verifier only a few G2 points, in a compact binary form that
+//! loads without the parsing of the SRS files. `Kzg::from_keys` puts them
+//! back together.
+
+use crate::{errors::KzgError, verifier::KzgVerifier};
+use ark_bn254::{G1Affine, G2Affine};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+
+/// The G1 powers of tau, along with the order of the SRS they were taken
+/// from.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ProvingKey {
+    g1: Vec<G1Affine>,
+    srs_order: u64,
+}
+
+impl ProvingKey {
+    /// Creates a proving key from the first G1 powers of tau of an SRS of
+    /// `srs_order` points.
+    pub fn new(g1: Vec<G1Affine>, srs_order: u64) -> Result<Self, KzgError> {
+        if g1.len() as u64 > srs_order {
+            return Err(KzgError::GenericError(
+                "number of points to load is more than the srs order".to_string(),
+            ));
+        }
+        Ok(ProvingKey { g1, srs_order })
+    }
+
+    /// Returns the G1 powers of tau.
+    pub fn get_g1_points(&self) -> &[G1Affine] {
+        &self.g1
+    }
+
+    /// Returns the order of the SRS.
+    pub fn get_srs_order(&self) -> u64 {
+        self.srs_order
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<G1Affine>, u64) {
+        (self.g1, self.srs_order)
+    }
+}
+
+/// G2 points of the SRS, either consecutive powers of tau or the powers
+/// `tau^(2^i)` of a power of 2 table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey {
+    g2: Vec<G2Affine>,
+    power_of_2: bool,
+}
+
+impl VerifyingKey {
+    /// Creates a verifying key from consecutive G2 powers of tau, starting at
+    /// the generator.
+    pub fn from_powers(g2: Vec<G2Affine>) -> Result<Self, KzgError> {
+        Self::new(g2, false)
+    }
+
+    /// Creates a verifying key from a power of 2 table, starting at
+    /// `[tau]G2`.
+    pub fn from_power_of_2_table(g2: Vec<G2Affine>) -> Result<Self, KzgError> {
+        Self::new(g2, true)
+    }
+
+    pub(crate) fn new(g2: Vec<G2Affine>, power_of_2: bool) -> Result<Self, KzgError> {
+        let key = VerifyingKey { g2, power_of_2 };
+        if !key.holds_g2_tau() {
+            return Err(KzgError::GenericError(
+                "verifying key does not hold [tau]G2".to_string(),
+            ));
+        }
+        Ok(key)
+    }
+
+    fn tau_index(&self) -> usize {
+        if self.power_of_2 {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn holds_g2_tau(&self) -> bool {
+        self.tau_index() < self.g2.len()
+    }
+
+    /// Returns the G2 points.
+    pub fn get_g2_points(&self) -> &[G2Affine] {
+        &self.g2
+    }
+
+    /// Returns whether the points are a power of 2 table.
+    pub fn is_power_of_2_table(&self) -> bool {
+        self.power_of_2
+    }
+
+    /// Returns `[tau]G2`.
+    pub fn get_g2_tau(&self) -> G2Affine {
+        self.g2[self.tau_index()]
+    }
+
+    /// Returns a verifier over `[tau]G2`, deriving challenges with SHA-256.
+    pub fn verifier(&self) -> KzgVerifier {
+        KzgVerifier::new(self.get_g2_tau())
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<G2Affine>, bool) {
+        (self.g2, self.power_of_2)
+    }
+}
+
+impl CanonicalSerialize for VerifyingKey {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.power_of_2.serialize_with_mode(&mut writer, compress)?;
+        self.g2.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.power_of_2.serialized_size(compress) + self.g2.serialized_size(compress)
+    }
+}
+
+impl Valid for VerifyingKey {
+    // unlike a derived check, also rejects keys without [tau]G2, which
+    // `get_g2_tau` relies on
+    fn check(&self) -> Result<(), SerializationError> {
+        if !self.holds_g2_tau() {
+            return Err(SerializationError::InvalidData);
+        }
+        self.g2.check()
+    }
+}
+
+impl CanonicalDeserialize for VerifyingKey {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let key = VerifyingKey {
+            power_of_2: bool::deserialize_with_mode(&mut reader, compress, validate)?,
+            g2: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+        // the points were checked as they were read
+        if validate == Validate::Yes && !key.holds_g2_tau() {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES, kzg::Kzg};
+
+    #[test]
+    fn test_keys_round_trip() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let proving_key = kzg.proving_key();
+        let verifying_key = kzg.verifying_key();
+        assert_eq!(verifying_key.verifier(), kzg.verifier());
+
+        let mut pk_bytes = Vec::new();
+        proving_key.serialize_compressed(&mut pk_bytes).unwrap();
+        assert_eq!(pk_bytes.len(), proving_key.compressed_size());
+        let mut vk_bytes = Vec::new();
+        verifying_key.serialize_uncompressed(&mut vk_bytes).unwrap();
+        let proving_key = ProvingKey::deserialize_compressed(&pk_bytes[..]).unwrap();
+        let verifying_key = VerifyingKey::deserialize_uncompressed(&vk_bytes[..]).unwrap();
+        assert!(ProvingKey::deserialize_compressed(&pk_bytes[..pk_bytes.len() - 1]).is_err());
+
+        let rebuilt = Kzg::from_keys(proving_key, verifying_key).unwrap();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let commitment = rebuilt.blob_to_kzg_commitment(&blob).unwrap();
+        assert_eq!(commitment, kzg.blob_to_kzg_commitment(&blob).unwrap());
+        let proof = rebuilt.compute_blob_kzg_proof(&blob, &commitment).unwrap();
+        assert!(kzg
+            .verify_blob_kzg_proof(&blob, &commitment, &proof)
+            .unwrap());
+
+        // a released G2 table leaves a one-point power of 2 table
+        let mut released = rebuilt.clone();
+        released.release_g2_points();
+        let verifying_key = released.verifying_key();
+        assert!(verifying_key.is_power_of_2_table());
+        assert_eq!(verifying_key.get_g2_points().len(), 1);
+        assert_eq!(verifying_key.verifier(), kzg.verifier());
+        assert!(released.reload_g2_points().is_err());
+
+        assert!(VerifyingKey::from_powers(vec![G2Affine::default()]).is_err());
+        assert!(VerifyingKey::from_power_of_2_table(vec![]).is_err());
+        // [false, empty table] decodes but fails validation
+        let mut empty = Vec::new();
+        (false, Vec::<G2Affine>::new())
+            .serialize_compressed(&mut empty)
+            .unwrap();
+        assert!(VerifyingKey::deserialize_compressed(&empty[..]).is_err());
+        assert!(ProvingKey::new(vec![G1Affine::default(); 2], 1).is_err());
+    }
+}
@@ -1,27 +1,94 @@
 use crate::{
-    blob::Blob, consts::BYTES_PER_FIELD_ELEMENT, errors::KzgError, helpers, polynomial::Polynomial,
+    blob::Blob,
+    commitment::{CommitmentBuilder, KzgCommitment},
+    config::KzgConfig,
+    consts::{
+        BYTES_PER_FIELD_ELEMENT, SIZE_OF_G1_AFFINE_COMPRESSED, SIZE_OF_G2_AFFINE_COMPRESSED,
+        SRS_READ_WINDOW_BYTES, SRS_VERIFICATION_SAMPLES,
+    },
+    domain::Domain,
+    encoding::{g1_from_bytes, g1_to_bytes},
+    errors::KzgError,
+    glv, helpers,
+    keys::{ProvingKey, VerifyingKey},
+    openings::BlobOpenings,
+    polynomial::Polynomial,
+    srs_cache,
     traits::ReadPointFromBytes,
+    verifier::{KzgVerifier, OpeningClaim},
+    workspace::KzgWorkspace,
 };
-use ark_bn254::{g1::G1Affine, Bn254, Fr, G1Projective, G2Affine};
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
-use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_bn254::{g1::G1Affine, Bn254, Fr, G1Projective, G2Affine, G2Projective};
+use ark_ec::{
+    pairing::Pairing,
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{batch_inversion, Field};
 use ark_serialize::Read;
 use ark_std::{
     ops::{Div, Mul},
-    str::FromStr,
-    One, Zero,
+    One, UniformRand, Zero,
 };
-use crossbeam_channel::{bounded, Sender};
 use num_traits::ToPrimitive;
-use std::{fs::File, io, io::BufReader};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
 
+/// A KZG setup. The SRS points are shared between clones, so cloning a setup
+/// is cheap and many workers can hold one loaded SRS. Changing a clone's
+/// points, e.g. with `release_g2_points` or `precompute_lagrange_srs`, only
+/// replaces them in that clone. Committing, proving and verifying take
+/// `&self` and derive the roots of unity of each polynomial, so threads can
+/// share one setup behind an `Arc` without a lock.
 #[derive(Debug, PartialEq, Clone)]
 pub struct Kzg {
-    g1: Vec<G1Affine>,
-    g2: Vec<G2Affine>,
+    g1: Arc<Vec<G1Affine>>,
+    g1_lagrange: Arc<Vec<G1Affine>>,
+    g2: Arc<Vec<G2Affine>>,
     params: Params,
     srs_order: u64,
     expanded_roots_of_unity: Vec<Fr>,
+    config: KzgConfig,
+    g2_source: G2Source,
+    g2_released: bool,
+    // the cache file and key of a setup made by `setup_with_cache`
+    srs_cache: Option<(PathBuf, [u8; 32])>,
+}
+
+// the arguments of `Kzg::setup_shared`
+type SharedSetupKey = (String, String, String, u32, u32);
+
+// a setup loaded by `Kzg::setup_shared`, behind its own lock so a load only
+// blocks callers waiting for the same setup
+type SharedSetup = Arc<Mutex<Option<Kzg>>>;
+
+static SHARED_SETUPS: OnceLock<Mutex<HashMap<SharedSetupKey, SharedSetup>>> = OnceLock::new();
+
+/// Environment variable naming the directory `Kzg::setup` caches parsed SRS
+/// points in, see `Kzg::setup_with_cache`.
+pub const SRS_CACHE_DIR_VAR: &str = "KZG_SRS_CACHE_DIR";
+
+/// Called by `Kzg::setup_with_progress` after each window of SRS points it
+/// parses, with the number of G1 and G2 points read so far and the number it
+/// reads in total.
+pub type ProgressCallback<'a> = &'a dyn Fn(usize, usize);
+
+/// Where the G2 points were loaded from, so they can be reloaded after being
+/// released.
+#[derive(Debug, PartialEq, Clone)]
+enum G2Source {
+    Points { path: String, points_to_load: u32 },
+    PowerOf2 { path: String },
+    // handed over in memory, from a `VerifyingKey` or a reader, so there is
+    // nothing to reload from
+    Memory { power_of_2: bool },
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -33,12 +100,50 @@ struct Params {
 }
 
 impl Kzg {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(srs_order = srs_order, srs_points_to_load = srs_points_to_load)
+        )
+    )]
     pub fn setup(
         path_to_g1_points: &str,
         path_to_g2_points: &str,
         g2_power_of2_path: &str,
         srs_order: u32,
         srs_points_to_load: u32,
+    ) -> Result<Self, KzgError> {
+        if let Some(cache_dir) = std::env::var_os(SRS_CACHE_DIR_VAR).filter(|dir| !dir.is_empty()) {
+            return Self::setup_with_cache(
+                path_to_g1_points,
+                path_to_g2_points,
+                g2_power_of2_path,
+                srs_order,
+                srs_points_to_load,
+                &cache_dir.to_string_lossy(),
+            );
+        }
+        Self::setup_with_progress(
+            path_to_g1_points,
+            path_to_g2_points,
+            g2_power_of2_path,
+            srs_order,
+            srs_points_to_load,
+            &|_, _| {},
+        )
+    }
+
+    /// Creates a setup like `setup`, calling `progress` as the SRS files are
+    /// parsed, so long setups can report how far along they are.
+    pub fn setup_with_progress(
+        path_to_g1_points: &str,
+        path_to_g2_points: &str,
+        g2_power_of2_path: &str,
+        srs_order: u32,
+        srs_points_to_load: u32,
+        progress: ProgressCallback,
     ) -> Result<Self, KzgError> {
         if srs_points_to_load > srs_order {
             return Err(KzgError::GenericError(
@@ -46,55 +151,676 @@ impl Kzg {
             ));
         }
 
-        let g1_points =
-            Self::parallel_read_g1_points(path_to_g1_points.to_owned(), srs_points_to_load)
-                .map_err(|e| KzgError::SerializationError(e.to_string()))?;
-        let mut g2_points: Vec<G2Affine> = vec![];
+        let g2_source =
+            Self::g2_file_source(path_to_g2_points, g2_power_of2_path, srs_points_to_load)?;
+
+        // count the points of both files first, so progress covers the whole
+        // setup
+        let g1_total = Self::points_in_file(
+            path_to_g1_points,
+            SIZE_OF_G1_AFFINE_COMPRESSED,
+            srs_points_to_load as usize,
+        )?;
+        let total = g1_total
+            + match &g2_source {
+                G2Source::Points {
+                    path,
+                    points_to_load,
+                } => Self::points_in_file(
+                    path,
+                    SIZE_OF_G2_AFFINE_COMPRESSED,
+                    *points_to_load as usize,
+                )?,
+                G2Source::PowerOf2 { path } => {
+                    Self::points_in_file(path, SIZE_OF_G2_AFFINE_COMPRESSED, usize::MAX)?
+                },
+                G2Source::Memory { .. } => 0,
+            };
+        let g1_points = Self::read_points_in_windows(
+            path_to_g1_points,
+            SIZE_OF_G1_AFFINE_COMPRESSED,
+            srs_points_to_load as usize,
+            SRS_READ_WINDOW_BYTES / SIZE_OF_G1_AFFINE_COMPRESSED,
+            &|read| progress(read, total),
+        )
+        .map_err(|e| KzgError::SerializationError(e.to_string()))?;
+        let g2_points = Self::read_g2_source(&g2_source, &|read| progress(g1_total + read, total))?;
+
+        Ok(Self::from_srs(
+            g1_points,
+            g2_points,
+            g2_source,
+            srs_order.into(),
+        ))
+    }
+
+    /// Creates a setup like `setup`, loading the parsed points from a cache in
+    /// `cache_dir` when one was written for the same SRS files and
+    /// parameters, and parsing the files and writing the cache otherwise.
+    /// A Lagrange SRS precomputed afterwards is added to the cache with
+    /// `write_srs_cache`, and loaded along with the points. `setup` does the
+    /// same when the `KZG_SRS_CACHE_DIR` environment variable names a
+    /// directory.
+    pub fn setup_with_cache(
+        path_to_g1_points: &str,
+        path_to_g2_points: &str,
+        g2_power_of2_path: &str,
+        srs_order: u32,
+        srs_points_to_load: u32,
+        cache_dir: &str,
+    ) -> Result<Self, KzgError> {
+        let key = srs_cache::cache_key(
+            &[path_to_g1_points, path_to_g2_points, g2_power_of2_path],
+            &[srs_order.into(), srs_points_to_load.into()],
+        )?;
+        let path = srs_cache::cache_path(Path::new(cache_dir), &key);
+        if let Some(cached) = srs_cache::read_cache(&path, &key) {
+            let g2_source =
+                Self::g2_file_source(path_to_g2_points, g2_power_of2_path, srs_points_to_load)?;
+            let mut kzg = Self::from_srs(cached.g1, cached.g2, g2_source, srs_order.into());
+            kzg.g1_lagrange = Arc::new(cached.g1_lagrange);
+            kzg.srs_cache = Some((path, key));
+            return Ok(kzg);
+        }
+
+        let mut kzg = Self::setup_with_progress(
+            path_to_g1_points,
+            path_to_g2_points,
+            g2_power_of2_path,
+            srs_order,
+            srs_points_to_load,
+            &|_, _| {},
+        )?;
+        kzg.srs_cache = Some((path, key));
+        kzg.write_srs_cache()?;
+        Ok(kzg)
+    }
+
+    /// Creates a setup like `setup` after checking the SHA-256 digests of the
+    /// SRS files, so corrupted or tampered files fail with an error naming
+    /// the file instead of loading. `g1_sha256` is checked against the G1
+    /// file and `g2_sha256` against the G2 file in use, `path_to_g2_points`
+    /// or else `g2_power_of2_path`. A digest of `None` is not checked. The
+    /// files are hashed whole before any points are parsed, and the points
+    /// are always parsed from the checked files, never loaded from the SRS
+    /// cache.
+    pub fn setup_with_digests(
+        path_to_g1_points: &str,
+        path_to_g2_points: &str,
+        g2_power_of2_path: &str,
+        srs_order: u32,
+        srs_points_to_load: u32,
+        g1_sha256: Option<[u8; 32]>,
+        g2_sha256: Option<[u8; 32]>,
+    ) -> Result<Self, KzgError> {
+        if let Some(expected) = g1_sha256 {
+            Self::check_file_sha256(path_to_g1_points, &expected)?;
+        }
+        if let Some(expected) = g2_sha256 {
+            let g2_path = if path_to_g2_points.is_empty() {
+                g2_power_of2_path
+            } else {
+                path_to_g2_points
+            };
+            Self::check_file_sha256(g2_path, &expected)?;
+        }
+        Self::setup_with_progress(
+            path_to_g1_points,
+            path_to_g2_points,
+            g2_power_of2_path,
+            srs_order,
+            srs_points_to_load,
+            &|_, _| {},
+        )
+    }
+
+    fn check_file_sha256(path: &str, expected: &[u8; 32]) -> Result<(), KzgError> {
+        let digest = srs_cache::file_sha256(path)?;
+        if digest != *expected {
+            let hex = |bytes: &[u8; 32]| {
+                let mut hex = String::with_capacity(64);
+                for byte in bytes {
+                    hex.push_str(&format!("{byte:02x}"));
+                }
+                hex
+            };
+            return Err(KzgError::GenericError(format!(
+                "SRS file {path} has SHA-256 digest {}, expected {}",
+                hex(&digest),
+                hex(expected)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Writes the points, and the Lagrange SRS if one was precomputed, to the
+    /// cache of a setup made by `setup_with_cache`.
+    pub fn write_srs_cache(&self) -> Result<(), KzgError> {
+        let Some((path, key)) = &self.srs_cache else {
+            return Err(KzgError::GenericError(
+                "setup was not made with an SRS cache".to_string(),
+            ));
+        };
+        if self.g2_released {
+            return Err(KzgError::GenericError(
+                "G2 points must be loaded to write the SRS cache".to_string(),
+            ));
+        }
+        srs_cache::write_cache(path, key, &self.g1, &self.g2, &self.g1_lagrange)
+    }
+
+    fn g2_file_source(
+        path_to_g2_points: &str,
+        g2_power_of2_path: &str,
+        srs_points_to_load: u32,
+    ) -> Result<G2Source, KzgError> {
         if !path_to_g2_points.is_empty() {
-            g2_points =
-                Self::parallel_read_g2_points(path_to_g2_points.to_owned(), srs_points_to_load)
-                    .map_err(|e| KzgError::SerializationError(e.to_string()))?;
+            Ok(G2Source::Points {
+                path: path_to_g2_points.to_owned(),
+                points_to_load: srs_points_to_load,
+            })
         } else if !g2_power_of2_path.is_empty() {
-            g2_points = Self::read_g2_point_on_power_of_2(&g2_power_of2_path)?;
+            Ok(G2Source::PowerOf2 {
+                path: g2_power_of2_path.to_owned(),
+            })
         } else {
-            return Err(KzgError::GenericError(
+            Err(KzgError::GenericError(
                 "both g2 point files are empty, need the proper file specified".to_string(),
+            ))
+        }
+    }
+
+    /// Creates a setup like `setup` with exactly the first `n` G1 powers of
+    /// tau, e.g. the points needed for the largest blob committed to. Where
+    /// `setup` loads whatever part of `srs_points_to_load` a file holds, this
+    /// fails if the G1 file, or the file of consecutive G2 points if one is
+    /// given, holds fewer than `n` points.
+    pub fn setup_truncated(
+        path_to_g1_points: &str,
+        path_to_g2_points: &str,
+        g2_power_of2_path: &str,
+        srs_order: u32,
+        n: u32,
+    ) -> Result<Self, KzgError> {
+        let mut files = vec![(path_to_g1_points, SIZE_OF_G1_AFFINE_COMPRESSED, "G1")];
+        if !path_to_g2_points.is_empty() {
+            files.push((path_to_g2_points, SIZE_OF_G2_AFFINE_COMPRESSED, "G2"));
+        }
+        for (path, point_size, group) in files {
+            let available = Self::points_in_file(path, point_size, n as usize)?;
+            if available < n as usize {
+                return Err(KzgError::GenericError(format!(
+                    "{path} holds {available} {group} points, fewer than the {n} requested"
+                )));
+            }
+        }
+        Self::setup(
+            path_to_g1_points,
+            path_to_g2_points,
+            g2_power_of2_path,
+            srs_order,
+            n,
+        )
+    }
+
+    /// Creates a setup like `setup` from G1 points split over several shard
+    /// files, concatenated in the order given. The shards are read in
+    /// parallel, and only those covering the first `srs_points_to_load`
+    /// points are read. Every shard but the last must hold whole points, and
+    /// the shards are checked to be contiguous: the first starts at the
+    /// generator and each one starts at the power of tau following the last
+    /// point of the one before, so missing, repeated or reordered shards
+    /// fail.
+    pub fn setup_sharded(
+        g1_shard_paths: &[&str],
+        path_to_g2_points: &str,
+        g2_power_of2_path: &str,
+        srs_order: u32,
+        srs_points_to_load: u32,
+    ) -> Result<Self, KzgError> {
+        if srs_points_to_load > srs_order {
+            return Err(KzgError::GenericError(
+                "number of points to load is more than the srs order".to_string(),
+            ));
+        }
+        if g1_shard_paths.is_empty() {
+            return Err(KzgError::GenericError("no G1 shards given".to_string()));
+        }
+
+        // the points each shard holds, and how many of them are loaded
+        let mut shards = Vec::with_capacity(g1_shard_paths.len());
+        let mut remaining = srs_points_to_load as usize;
+        for (i, path) in g1_shard_paths.iter().enumerate() {
+            let len = std::fs::metadata(path)
+                .map_err(|e| KzgError::GenericError(format!("{path}: {e}")))?
+                .len() as usize;
+            let is_last = i + 1 == g1_shard_paths.len();
+            if len == 0 || (!is_last && len % SIZE_OF_G1_AFFINE_COMPRESSED != 0) {
+                return Err(KzgError::GenericError(format!(
+                    "G1 shard {path} of {len} bytes does not hold whole points"
+                )));
+            }
+            let to_load = remaining.min(len / SIZE_OF_G1_AFFINE_COMPRESSED);
+            remaining -= to_load;
+            if to_load > 0 {
+                shards.push((*path, to_load));
+            }
+        }
+
+        if shards.is_empty() {
+            return Err(KzgError::GenericError("no G1 points to load".to_string()));
+        }
+
+        // each shard is parsed straight into its part of the G1 points
+        let mut g1_points = vec![G1Affine::zero(); srs_points_to_load as usize - remaining];
+        let mut parts = Vec::with_capacity(shards.len());
+        let mut rest = g1_points.as_mut_slice();
+        for (path, to_load) in &shards {
+            let (part, tail) = rest.split_at_mut(*to_load);
+            parts.push((*path, part));
+            rest = tail;
+        }
+        let g2_source =
+            Self::g2_file_source(path_to_g2_points, g2_power_of2_path, srs_points_to_load)?;
+        // like the other setups, this runs on the caller's pool, which
+        // `KzgBuilder` sets to the one of its config
+        let (g1_read, g2_points) = rayon::join(
+            || {
+                parts
+                    .into_par_iter()
+                    .try_for_each(|(path, part)| Self::read_points_into(path, part))
+            },
+            || Self::read_g2_source(&g2_source, &|_| {}),
+        );
+        g1_read?;
+        let g2_points = g2_points?;
+
+        if g1_points[0] != G1Affine::generator() {
+            return Err(KzgError::GenericError(format!(
+                "G1 shard {} does not start at the generator",
+                shards[0].0
+            )));
+        }
+        let kzg = Self::from_srs(g1_points, g2_points, g2_source, srs_order.into());
+        let g2_tau = kzg.g2_tau();
+        let mut start = 0;
+        for pair in shards.windows(2) {
+            start += pair[0].1;
+            let (last, first) = (kzg.g1[start - 1], kzg.g1[start]);
+            if !Self::pairings_verify(last, g2_tau, first, G2Affine::generator()) {
+                return Err(KzgError::GenericError(format!(
+                    "G1 shard {} does not continue the shard before it",
+                    pair[1].0
+                )));
+            }
+        }
+        Ok(kzg)
+    }
+
+    /// Returns a setup like `setup` from a process-wide cache, loading it on
+    /// the first call with these arguments. Later calls return a clone
+    /// sharing the loaded points, so every worker can call this instead of
+    /// loading its own copy of the SRS. Calls wait while another call loads
+    /// the same setup, and failed loads aren't cached. Setups are keyed by
+    /// their paths and stay loaded for the life of the process, or until
+    /// `clear_shared_setups` is called, e.g. after replacing an SRS file.
+    pub fn setup_shared(
+        path_to_g1_points: &str,
+        path_to_g2_points: &str,
+        g2_power_of2_path: &str,
+        srs_order: u32,
+        srs_points_to_load: u32,
+    ) -> Result<Self, KzgError> {
+        let key = (
+            path_to_g1_points.to_string(),
+            path_to_g2_points.to_string(),
+            g2_power_of2_path.to_string(),
+            srs_order,
+            srs_points_to_load,
+        );
+        let entry = SHARED_SETUPS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(key)
+            .or_default()
+            .clone();
+        // OnceLock::get_or_try_init isn't stable, so an entry is a lock
+        // around the setup, held while loading it
+        let mut setup = entry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(kzg) = setup.as_ref() {
+            return Ok(kzg.clone());
+        }
+        let kzg = Self::setup(
+            path_to_g1_points,
+            path_to_g2_points,
+            g2_power_of2_path,
+            srs_order,
+            srs_points_to_load,
+        )?;
+        *setup = Some(kzg.clone());
+        Ok(kzg)
+    }
+
+    /// Drops the setups cached by `setup_shared`, so the next call loads the
+    /// SRS files again. Clones already returned keep their points.
+    pub fn clear_shared_setups() {
+        if let Some(setups) = SHARED_SETUPS.get() {
+            setups
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clear();
+        }
+    }
+
+    /// Creates a setup like `setup`, reading the G1 points from `g1_reader`
+    /// and the G2 points from `g2_reader`, in the formats of the SRS files.
+    /// `g2_reader` holds consecutive powers of tau like `g2.point`, or, if
+    /// `g2_is_power_of_2`, a table like `g2.point.powerOf2`. Readers are read
+    /// no further than the points needed, and a reader ending early leaves
+    /// fewer points, as a shorter file would. The G2 points cannot be
+    /// reloaded once released.
+    pub fn setup_from_reader<R: Read, S: Read>(
+        g1_reader: R,
+        g2_reader: S,
+        g2_is_power_of_2: bool,
+        srs_order: u32,
+        srs_points_to_load: u32,
+    ) -> Result<Self, KzgError> {
+        if srs_points_to_load > srs_order {
+            return Err(KzgError::GenericError(
+                "number of points to load is more than the srs order".to_string(),
             ));
         }
 
-        Ok(Self {
-            g1: g1_points,
-            g2: g2_points,
+        let g1_points = Self::read_points_from_reader(
+            g1_reader,
+            SIZE_OF_G1_AFFINE_COMPRESSED,
+            srs_points_to_load as usize,
+            SRS_READ_WINDOW_BYTES / SIZE_OF_G1_AFFINE_COMPRESSED,
+            Vec::new(),
+            &|_| {},
+        )?;
+        let g2_points_to_load = if g2_is_power_of_2 {
+            usize::MAX
+        } else {
+            srs_points_to_load as usize
+        };
+        let g2_points = Self::read_points_from_reader(
+            g2_reader,
+            SIZE_OF_G2_AFFINE_COMPRESSED,
+            g2_points_to_load,
+            SRS_READ_WINDOW_BYTES / SIZE_OF_G2_AFFINE_COMPRESSED,
+            Vec::new(),
+            &|_| {},
+        )?;
+        // a power of 2 table starts at [tau]G2
+        let (g2_points, power_of_2) = VerifyingKey::new(g2_points, g2_is_power_of_2)?.into_parts();
+
+        Ok(Self::from_srs(
+            g1_points,
+            g2_points,
+            G2Source::Memory { power_of_2 },
+            srs_order.into(),
+        ))
+    }
+
+    /// Creates a setup like `setup_from_reader` from the contents of the SRS
+    /// files.
+    pub fn setup_from_bytes(
+        g1_bytes: &[u8],
+        g2_bytes: &[u8],
+        g2_is_power_of_2: bool,
+        srs_order: u32,
+        srs_points_to_load: u32,
+    ) -> Result<Self, KzgError> {
+        Self::setup_from_reader(
+            g1_bytes,
+            g2_bytes,
+            g2_is_power_of_2,
+            srs_order,
+            srs_points_to_load,
+        )
+    }
+
+    fn from_srs(g1: Vec<G1Affine>, g2: Vec<G2Affine>, g2_source: G2Source, srs_order: u64) -> Self {
+        Self {
+            g1: Arc::new(g1),
+            g1_lagrange: Arc::default(),
+            g2: Arc::new(g2),
             params: Params {
                 chunk_length: 0,
                 num_chunks: 0,
                 max_fft_width: 0,
                 completed_setup: false,
             },
-            srs_order: srs_order.into(),
+            srs_order,
             expanded_roots_of_unity: vec![],
+            config: KzgConfig::default(),
+            g2_source,
+            g2_released: false,
+            srs_cache: None,
+        }
+    }
+
+    fn read_g2_source(
+        g2_source: &G2Source,
+        progress: &dyn Fn(usize),
+    ) -> Result<Vec<G2Affine>, KzgError> {
+        match g2_source {
+            G2Source::Points {
+                path,
+                points_to_load,
+            } => Self::read_points_in_windows(
+                path,
+                SIZE_OF_G2_AFFINE_COMPRESSED,
+                *points_to_load as usize,
+                SRS_READ_WINDOW_BYTES / SIZE_OF_G2_AFFINE_COMPRESSED,
+                progress,
+            )
+            .map_err(|e| KzgError::SerializationError(e.to_string())),
+            G2Source::PowerOf2 { path } => Self::read_points_in_windows(
+                path,
+                SIZE_OF_G2_AFFINE_COMPRESSED,
+                usize::MAX,
+                SRS_READ_WINDOW_BYTES / SIZE_OF_G2_AFFINE_COMPRESSED,
+                progress,
+            ),
+            G2Source::Memory { .. } => Err(KzgError::GenericError(
+                "G2 points handed over in memory cannot be reloaded".to_string(),
+            )),
+        }
+    }
+
+    /// Creates a setup from a proving key and a verifying key, e.g. after
+    /// deserializing them. The G2 points cannot be reloaded once released.
+    pub fn from_keys(
+        proving_key: ProvingKey,
+        verifying_key: VerifyingKey,
+    ) -> Result<Self, KzgError> {
+        let (g1, srs_order) = proving_key.into_parts();
+        let (g2, power_of_2) = verifying_key.into_parts();
+        // keys deserialized without validation haven't been checked yet
+        let (g1, srs_order) = ProvingKey::new(g1, srs_order)?.into_parts();
+        let (g2, power_of_2) = VerifyingKey::new(g2, power_of_2)?.into_parts();
+
+        Ok(Self::from_srs(
+            g1,
+            g2,
+            G2Source::Memory { power_of_2 },
+            srs_order,
+        ))
+    }
+
+    /// Returns a copy of the G1 points as a proving key.
+    pub fn proving_key(&self) -> ProvingKey {
+        ProvingKey::new(self.g1.to_vec(), self.srs_order)
+            .expect("setup loads at most srs_order points")
+    }
+
+    /// Returns a copy of the G2 points as a verifying key. After
+    /// `release_g2_points` this only holds `[tau]G2`.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey::new(self.g2.to_vec(), !self.g2_is_consecutive())
+            .expect("a loaded G2 table holds [tau]G2")
+    }
+
+    /// Drops the G2 table from memory, keeping only the [tau]G2 point that
+    /// `verify_kzg_proof` needs. Useful for prover-only deployments, where
+    /// commits and proofs never touch G2.
+    pub fn release_g2_points(&mut self) {
+        if self.g2_released {
+            return;
+        }
+        self.g2 = Arc::new(vec![self.g2_tau()]);
+        self.g2_released = true;
+    }
+
+    /// Reloads the full G2 table from the file it was originally loaded from,
+    /// after a call to `release_g2_points`.
+    pub fn reload_g2_points(&mut self) -> Result<(), KzgError> {
+        if !self.g2_released {
+            return Ok(());
+        }
+        self.g2 = Arc::new(Self::read_g2_source(&self.g2_source, &|_| {})?);
+        self.g2_released = false;
+        Ok(())
+    }
+
+    /// Returns whether the full G2 table is resident in memory.
+    pub fn is_g2_loaded(&self) -> bool {
+        !self.g2_released
+    }
+
+    /// Checks the loaded SRS for corrupted points: every point must be on the
+    /// curve, in the prime order subgroup and not the identity, the powers
+    /// must start at the generators, and `SRS_VERIFICATION_SAMPLES` randomly
+    /// sampled consecutive G1 powers must satisfy
+    /// `e([tau^i]G1, [tau]G2) = e([tau^(i+1)]G1, G2)`. The G2 powers are
+    /// sampled the same way against `[tau]G1`, and a power of 2 table is
+    /// checked against the G1 powers it shares exponents with.
+    pub fn verify_srs(&self) -> Result<(), KzgError> {
+        self.config.install(|| self.check_srs())?
+    }
+
+    /// the checks of `verify_srs`, run on the configured thread pool
+    fn check_srs(&self) -> Result<(), KzgError> {
+        if self.g1.len() < 2 || self.g2.is_empty() {
+            return Err(KzgError::GenericError(
+                "verifying the SRS needs at least 2 G1 points and a G2 point".to_string(),
+            ));
+        }
+        if let Some(i) = Self::find_invalid_point(&self.g1) {
+            return Err(KzgError::GenericError(format!(
+                "G1 point {i} of the SRS is not a valid point"
+            )));
+        }
+        if let Some(i) = Self::find_invalid_point(&self.g2) {
+            return Err(KzgError::GenericError(format!(
+                "G2 point {i} of the SRS is not a valid point"
+            )));
+        }
+        let consecutive = self.g2_is_consecutive();
+        if self.g1[0] != G1Affine::generator()
+            || (consecutive && self.g2[0] != G2Affine::generator())
+        {
+            return Err(KzgError::GenericError(
+                "the SRS powers don't start at the generator".to_string(),
+            ));
+        }
+
+        let mut rng = rand::thread_rng();
+        let (g1_low, g1_high) = Self::sample_consecutive_powers(&self.g1, &mut rng);
+        // e(sum r_i [tau^i]G1, [tau]G2) = e(sum r_i [tau^(i+1)]G1, G2)
+        if !Self::pairings_verify(g1_low, self.g2_tau(), g1_high, G2Affine::generator()) {
+            return Err(KzgError::GenericError(
+                "the G1 points of the SRS are not consecutive powers of tau".to_string(),
+            ));
+        }
+        let g2_consistent = if consecutive {
+            let (g2_low, g2_high) = Self::sample_consecutive_powers(&self.g2, &mut rng);
+            Self::pairings_verify(self.g1[1], g2_low, G1Affine::generator(), g2_high)
+        } else {
+            // [tau^(2^k)]G2 against [tau^(2^k)]G1, for the k the G1 points reach
+            self.g2
+                .iter()
+                .enumerate()
+                .skip(1)
+                .take_while(|(k, _)| {
+                    1usize
+                        .checked_shl(*k as u32)
+                        .is_some_and(|i| i < self.g1.len())
+                })
+                .all(|(k, point)| {
+                    Self::pairings_verify(
+                        self.g1[1 << k],
+                        G2Affine::generator(),
+                        G1Affine::generator(),
+                        *point,
+                    )
+                })
+        };
+        if !g2_consistent {
+            return Err(KzgError::GenericError(
+                "the G2 points of the SRS are not powers of the same tau".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// returns the index of the first point that is off the curve, outside
+    /// the prime order subgroup or the identity
+    fn find_invalid_point<C: SWCurveConfig>(points: &[Affine<C>]) -> Option<usize> {
+        points.par_iter().position_first(|point| {
+            point.is_zero()
+                || !point.is_on_curve()
+                || !point.is_in_correct_subgroup_assuming_on_curve()
         })
     }
 
-    pub fn read_g2_point_on_power_of_2(g2_power_of2_path: &str) -> Result<Vec<G2Affine>, KzgError> {
-        let mut file = File::open(g2_power_of2_path).unwrap();
-
-        // Calculate the start position in bytes and seek to that position
-        // Read in 64-byte chunks
-        let mut chunks = Vec::new();
-        let mut buffer = [0u8; 64];
-        loop {
-            let bytes_read = file.read(&mut buffer).unwrap();
-            if bytes_read == 0 {
-                break; // End of file reached
-            }
-            chunks
-                .push(G2Affine::read_point_from_bytes_be(&buffer[..bytes_read].to_vec()).unwrap());
+    /// returns random combinations of sampled powers and of the powers one
+    /// above them, `sum r_i points[i]` and `sum r_i points[i + 1]`
+    fn sample_consecutive_powers<P: AffineRepr<ScalarField = Fr>>(
+        points: &[P],
+        rng: &mut impl rand::Rng,
+    ) -> (P, P) {
+        let (mut low, mut high) = (P::Group::zero(), P::Group::zero());
+        let samples = SRS_VERIFICATION_SAMPLES.min(points.len() - 1);
+        for _ in 0..samples {
+            let i = rng.gen_range(0..points.len() - 1);
+            let weight = Fr::rand(rng);
+            low += points[i] * weight;
+            high += points[i + 1] * weight;
         }
-        Ok(chunks)
+        (low.into_affine(), high.into_affine())
+    }
+
+    /// Replaces the runtime configuration, e.g. to run all parallel work on a
+    /// caller-owned rayon thread pool.
+    pub fn set_config(&mut self, config: KzgConfig) {
+        self.config = config;
+    }
+
+    /// Returns the runtime configuration.
+    pub fn get_config(&self) -> &KzgConfig {
+        &self.config
+    }
+
+    pub fn read_g2_point_on_power_of_2(g2_power_of2_path: &str) -> Result<Vec<G2Affine>, KzgError> {
+        Self::read_points_in_windows(
+            g2_power_of2_path,
+            SIZE_OF_G2_AFFINE_COMPRESSED,
+            usize::MAX,
+            SRS_READ_WINDOW_BYTES / SIZE_OF_G2_AFFINE_COMPRESSED,
+            &|_| {},
+        )
     }
 
     /// data_setup_custom is a helper function
+    /// that sets up the parameters and roots of unity for a blob of
+    /// `padded_input_data_size` bytes, see `data_setup_mins`.
     pub fn data_setup_custom(
         &mut self,
         num_of_nodes: u64,
@@ -107,7 +833,19 @@ impl Kzg {
         self.data_setup_mins(min_num_chunks, num_of_nodes)
     }
 
-    /// data_setup_mins sets up the environment per the blob data
+    /// data_setup_mins sets up the environment per the blob data.
+    /// Committing and proving no longer depend on it: proofs over roots of
+    /// unity take the roots from each polynomial's length, see
+    /// `roots_of_unity`. Only `get_expanded_roots_of_unity` and
+    /// `get_nth_root_of_unity` return what it sets up.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(min_chunk_length = min_chunk_length, min_num_chunks = min_num_chunks)
+        )
+    )]
     pub fn data_setup_mins(
         &mut self,
         min_chunk_length: u64,
@@ -145,20 +883,34 @@ impl Kzg {
             ));
         }
 
-        let primitive_roots_of_unity = Self::get_primitive_roots_of_unity();
-        let found_root_of_unity = primitive_roots_of_unity
-            .get(log2_of_evals.to_usize().unwrap())
-            .unwrap();
-        let mut expanded_roots_of_unity = Self::expand_root_of_unity(found_root_of_unity);
-        expanded_roots_of_unity.truncate(expanded_roots_of_unity.len() - 1);
+        let expanded_roots_of_unity = Domain::new(1 << log2_of_evals)?
+            .get_roots_of_unity()
+            .to_vec();
 
         params.completed_setup = true;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            chunk_length = params.chunk_length,
+            num_chunks = params.num_chunks,
+            max_fft_width = params.max_fft_width,
+            "data setup complete"
+        );
         self.params = params;
         self.expanded_roots_of_unity = expanded_roots_of_unity;
 
         Ok(())
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(length_of_data_after_padding = length_of_data_after_padding)
+        )
+    )]
+    /// Sets up the roots of unity for `length_of_data_after_padding` bytes,
+    /// like `data_setup_mins`, which proving doesn't depend on either.
     pub fn calculate_roots_of_unity(
         &mut self,
         length_of_data_after_padding: u64,
@@ -184,12 +936,9 @@ impl Kzg {
             ));
         }
 
-        let primitive_roots_of_unity = Self::get_primitive_roots_of_unity();
-        let found_root_of_unity = primitive_roots_of_unity
-            .get(log2_of_evals.to_usize().unwrap())
-            .unwrap();
-        let mut expanded_roots_of_unity = Self::expand_root_of_unity(found_root_of_unity);
-        expanded_roots_of_unity.truncate(expanded_roots_of_unity.len() - 1);
+        let expanded_roots_of_unity = Domain::new(1 << log2_of_evals)?
+            .get_roots_of_unity()
+            .to_vec();
 
         self.params.completed_setup = true;
         self.expanded_roots_of_unity = expanded_roots_of_unity;
@@ -197,103 +946,172 @@ impl Kzg {
         Ok(())
     }
 
-    // helper function to debug
-    pub fn get_expanded_roots_of_unity(&self) -> &Vec<Fr> { // Adjust the return type as necessary
-            &self.expanded_roots_of_unity
+    /// Returns the roots of unity set up by the last `data_setup_*` call.
+    /// Proofs are over `roots_of_unity(polynomial.len())`, which these only
+    /// match when the setup was for a blob of that many field elements.
+    pub fn get_expanded_roots_of_unity(&self) -> &Vec<Fr> {
+        &self.expanded_roots_of_unity
     }
 
-    /// helper function to get the 
+    /// Returns the `i`-th root of unity set up by the last `data_setup_*`
+    /// call, see `get_expanded_roots_of_unity`.
     pub fn get_nth_root_of_unity(&self, i: usize) -> Option<&Fr> {
         self.expanded_roots_of_unity.get(i)
     }
 
-    /// function to expand the roots based on the configuration
-    fn expand_root_of_unity(root_of_unity: &Fr) -> Vec<Fr> {
-        let mut roots = vec![Fr::one()]; // Initialize with 1
-        roots.push(*root_of_unity); // Add the root of unity
+    /// Returns the roots of unity a polynomial of `length` evaluations is
+    /// proven over, the same ones `data_setup_custom` sets up for a blob of
+    /// that many field elements. `length` must be a power of 2. See `Domain`
+    /// to share them instead of copying.
+    pub fn roots_of_unity(length: usize) -> Result<Vec<Fr>, KzgError> {
+        Ok(Domain::new(length)?.get_roots_of_unity().to_vec())
+    }
+
+    /// helper function to get g1 points
+    pub fn get_g1_points(&self) -> Vec<G1Affine> {
+        self.g1.to_vec()
+    }
 
-        let mut i = 1;
-        while !roots[i].is_one() {
-            // Continue until the element cycles back to one
-            let this = &roots[i];
-            i += 1;
-            roots.push(this * root_of_unity); // Push the next power of the root
-                                              // of unity
+    /// Returns a digest identifying the loaded SRS, for keying values
+    /// computed with it. The powers of a valid SRS are determined by
+    /// `tau * G1`, so this hashes that point and the first Lagrange basis
+    /// point with the number of points loaded and the SRS order.
+    pub(crate) fn srs_fingerprint(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.srs_order.to_be_bytes());
+        for points in [&self.g1, &self.g1_lagrange] {
+            hasher.update((points.len() as u64).to_be_bytes());
+            if let Some(point) = points.get(1).or(points.first()) {
+                hasher.update(helpers::g1_point_to_bytes_be(point));
+            }
         }
-        roots
+        hasher.finalize().into()
     }
 
-    /// refer to DA code for more context
-    fn get_primitive_roots_of_unity() -> Vec<Fr> {
-        let data: [&str; 29] = [
-            "1",
-            "21888242871839275222246405745257275088548364400416034343698204186575808495616",
-            "21888242871839275217838484774961031246007050428528088939761107053157389710902",
-            "19540430494807482326159819597004422086093766032135589407132600596362845576832",
-            "14940766826517323942636479241147756311199852622225275649687664389641784935947",
-            "4419234939496763621076330863786513495701855246241724391626358375488475697872",
-            "9088801421649573101014283686030284801466796108869023335878462724291607593530",
-            "10359452186428527605436343203440067497552205259388878191021578220384701716497",
-            "3478517300119284901893091970156912948790432420133812234316178878452092729974",
-            "6837567842312086091520287814181175430087169027974246751610506942214842701774",
-            "3161067157621608152362653341354432744960400845131437947728257924963983317266",
-            "1120550406532664055539694724667294622065367841900378087843176726913374367458",
-            "4158865282786404163413953114870269622875596290766033564087307867933865333818",
-            "197302210312744933010843010704445784068657690384188106020011018676818793232",
-            "20619701001583904760601357484951574588621083236087856586626117568842480512645",
-            "20402931748843538985151001264530049874871572933694634836567070693966133783803",
-            "421743594562400382753388642386256516545992082196004333756405989743524594615",
-            "12650941915662020058015862023665998998969191525479888727406889100124684769509",
-            "11699596668367776675346610687704220591435078791727316319397053191800576917728",
-            "15549849457946371566896172786938980432421851627449396898353380550861104573629",
-            "17220337697351015657950521176323262483320249231368149235373741788599650842711",
-            "13536764371732269273912573961853310557438878140379554347802702086337840854307",
-            "12143866164239048021030917283424216263377309185099704096317235600302831912062",
-            "934650972362265999028062457054462628285482693704334323590406443310927365533",
-            "5709868443893258075976348696661355716898495876243883251619397131511003808859",
-            "19200870435978225707111062059747084165650991997241425080699860725083300967194",
-            "7419588552507395652481651088034484897579724952953562618697845598160172257810",
-            "2082940218526944230311718225077035922214683169814847712455127909555749686340",
-            "19103219067921713944291392827692070036145651957329286315305642004821462161904",
-        ];
-        data.iter()
-            .map(|each| Fr::from_str(each).unwrap())
-            .collect()
+    /// reads up to `num_points` points of `point_size` bytes from the start of
+    /// the file. The file is read `window_points` points at a time, each window
+    /// is parsed in parallel directly into storage preallocated for the final
+    /// points, so peak memory stays close to the size of the points
+    /// themselves. `progress` is called with the number of points read after
+    /// each window.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(file = file_path, num_points = num_points)
+        )
+    )]
+    fn read_points_in_windows<T: ReadPointFromBytes>(
+        file_path: &str,
+        point_size: usize,
+        num_points: usize,
+        window_points: usize,
+        progress: &dyn Fn(usize),
+    ) -> Result<Vec<T>, KzgError> {
+        let num_points = Self::points_in_file(file_path, point_size, num_points)?;
+        let file = File::open(file_path).map_err(|e| KzgError::GenericError(e.to_string()))?;
+        Self::read_points_from_reader(
+            file,
+            point_size,
+            num_points,
+            window_points,
+            Vec::with_capacity(num_points),
+            progress,
+        )
     }
 
-    /// helper function to get g1 points
-    pub fn get_g1_points(&self) -> Vec<G1Affine> {
-        self.g1.to_vec()
+    /// reads the first `points.len()` G1 points of the file into `points`,
+    /// a window at a time
+    fn read_points_into(file_path: &str, points: &mut [G1Affine]) -> Result<(), KzgError> {
+        let mut file = File::open(file_path).map_err(|e| KzgError::GenericError(e.to_string()))?;
+        let window_points = SRS_READ_WINDOW_BYTES / SIZE_OF_G1_AFFINE_COMPRESSED;
+        let mut window = vec![0u8; window_points * SIZE_OF_G1_AFFINE_COMPRESSED];
+        for part in points.chunks_mut(window_points) {
+            let bytes = &mut window[..part.len() * SIZE_OF_G1_AFFINE_COMPRESSED];
+            if Self::fill_window(&mut file, bytes)? < bytes.len() {
+                return Err(KzgError::GenericError(format!(
+                    "{file_path} ended before {} points",
+                    points.len()
+                )));
+            }
+            part.par_iter_mut()
+                .zip(bytes.par_chunks_exact(SIZE_OF_G1_AFFINE_COMPRESSED))
+                .try_for_each(|(point, chunk)| {
+                    *point = G1Affine::read_point_from_bytes_be(chunk)?;
+                    Ok(())
+                })
+                .map_err(|e: std::io::Error| KzgError::SerializationError(e.to_string()))?;
+        }
+        Ok(())
     }
 
-    /// read files in chunks with specified length
-    fn read_file_chunks(
+    /// returns how many of the first `num_points` points of `point_size`
+    /// bytes the file holds
+    fn points_in_file(
         file_path: &str,
-        sender: Sender<(Vec<u8>, usize)>,
         point_size: usize,
-        num_points: u32,
-    ) -> io::Result<()> {
-        let file = File::open(file_path)?;
-        let mut reader = BufReader::new(file);
-        let mut position = 0;
-        let mut buffer = vec![0u8; point_size];
-
-        let mut i = 0;
-        while let Ok(bytes_read) = reader.read(&mut buffer) {
-            if bytes_read == 0 {
+        num_points: usize,
+    ) -> Result<usize, KzgError> {
+        let file_len = std::fs::metadata(file_path)
+            .map_err(|e| KzgError::GenericError(e.to_string()))?
+            .len();
+        Ok(num_points.min((file_len / point_size as u64) as usize))
+    }
+
+    /// reads up to `num_points` points of `point_size` bytes from `reader`
+    /// into `points`, `window_points` at a time, stopping early at the end of
+    /// the input. Trailing bytes short of a whole point are ignored.
+    /// `progress` is called with the number of points read after each window.
+    /// Points are parsed on the caller's thread pool, see `setup_sharded`.
+    fn read_points_from_reader<T: ReadPointFromBytes, R: Read>(
+        mut reader: R,
+        point_size: usize,
+        num_points: usize,
+        window_points: usize,
+        mut points: Vec<T>,
+        progress: &dyn Fn(usize),
+    ) -> Result<Vec<T>, KzgError> {
+        let window_points = window_points.clamp(1, num_points.max(1));
+        let mut window = vec![0u8; window_points * point_size];
+        while points.len() < num_points {
+            let requested = window_points.min(num_points - points.len());
+            let filled = Self::fill_window(&mut reader, &mut window[..requested * point_size])?;
+            let count = filled / point_size;
+            let bytes = &window[..count * point_size];
+            let start = points.len();
+            points.resize(start + count, T::zero());
+            points[start..]
+                .par_iter_mut()
+                .zip(bytes.par_chunks_exact(point_size))
+                .try_for_each(|(point, chunk)| {
+                    *point = T::read_point_from_bytes_be(chunk)?;
+                    Ok(())
+                })
+                .map_err(|e: std::io::Error| KzgError::SerializationError(e.to_string()))?;
+            progress(points.len());
+            if count < requested {
                 break;
             }
-            sender
-                .send((buffer[..bytes_read].to_vec(), position))
-                .unwrap();
-            position += bytes_read;
-            buffer.resize(point_size, 0); // Ensure the buffer is always the correct size
-            i += 1;
-            if num_points == i {
-                break;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(points = points.len(), "read SRS points");
+        Ok(points)
+    }
+
+    /// reads into `window` until it is full or the input ends, returning the
+    /// number of bytes read
+    fn fill_window<R: Read>(reader: &mut R, window: &mut [u8]) -> Result<usize, KzgError> {
+        let mut filled = 0;
+        while filled < window.len() {
+            match reader.read(&mut window[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {},
+                Err(e) => return Err(KzgError::GenericError(e.to_string())),
             }
         }
-        Ok(())
+        Ok(filled)
     }
 
     /// read G2 points in parallel
@@ -301,44 +1119,13 @@ impl Kzg {
         file_path: String,
         srs_points_to_load: u32,
     ) -> Result<Vec<G2Affine>, KzgError> {
-        let (sender, receiver) = bounded::<(Vec<u8>, usize)>(1000);
-
-        // Spawning the reader thread
-        let reader_thread = std::thread::spawn(
-            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                Self::read_file_chunks(&file_path, sender, 64, srs_points_to_load)
-                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })
-            },
-        );
-
-        let num_workers = num_cpus::get();
-
-        let workers: Vec<_> = (0..num_workers)
-            .map(|_| {
-                let receiver = receiver.clone();
-                std::thread::spawn(move || helpers::process_chunks::<G2Affine>(receiver))
-            })
-            .collect();
-
-        // Wait for the reader thread to finish
-        match reader_thread.join() {
-            Ok(result) => match result {
-                Ok(_) => {},
-                Err(e) => return Err(KzgError::GenericError(e.to_string())),
-            },
-            Err(_) => return Err(KzgError::GenericError("Thread panicked".to_string())),
-        }
-
-        // Collect and sort results
-        let mut all_points = Vec::new();
-        for worker in workers {
-            let points = worker.join().expect("Worker thread panicked");
-            all_points.extend(points);
-        }
-
-        // Sort by original position to maintain order
-        all_points.sort_by_key(|&(_, position)| position);
-        Ok(all_points.iter().map(|(point, _)| *point).collect())
+        Self::read_points_in_windows(
+            &file_path,
+            SIZE_OF_G2_AFFINE_COMPRESSED,
+            srs_points_to_load as usize,
+            SRS_READ_WINDOW_BYTES / SIZE_OF_G2_AFFINE_COMPRESSED,
+            &|_| {},
+        )
     }
 
     /// read G1 points in parallel
@@ -346,128 +1133,1054 @@ impl Kzg {
         file_path: String,
         srs_points_to_load: u32,
     ) -> Result<Vec<G1Affine>, KzgError> {
-        let (sender, receiver) = bounded::<(Vec<u8>, usize)>(1000);
+        Self::read_points_in_windows(
+            &file_path,
+            SIZE_OF_G1_AFFINE_COMPRESSED,
+            srs_points_to_load as usize,
+            SRS_READ_WINDOW_BYTES / SIZE_OF_G1_AFFINE_COMPRESSED,
+            &|_| {},
+        )
+    }
 
-        // Spawning the reader thread
-        let reader_thread = std::thread::spawn(
-            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                Self::read_file_chunks(&file_path, sender, 32, srs_points_to_load)
-                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })
-            },
-        );
+    /// obtain copy of g2 points
+    pub fn get_g2_points(&self) -> Vec<G2Affine> {
+        self.g2.to_vec()
+    }
+
+    /// Writes `points` to `path` in the format of `g1.point`.
+    pub fn write_g1_points(path: &str, points: &[G1Affine]) -> Result<(), KzgError> {
+        let mut bytes = Vec::with_capacity(points.len() * SIZE_OF_G1_AFFINE_COMPRESSED);
+        for point in points {
+            bytes.extend_from_slice(&helpers::g1_point_to_bytes_be(point));
+        }
+        std::fs::write(path, bytes).map_err(|e| KzgError::GenericError(e.to_string()))
+    }
+
+    /// Writes `points` to `path` in the format of `g2.point`, or of
+    /// `g2.point.powerOf2` for a power of 2 table.
+    pub fn write_g2_points(path: &str, points: &[G2Affine]) -> Result<(), KzgError> {
+        let mut bytes = Vec::with_capacity(points.len() * SIZE_OF_G2_AFFINE_COMPRESSED);
+        for point in points {
+            bytes.extend_from_slice(&helpers::g2_point_to_bytes_be(point));
+        }
+        std::fs::write(path, bytes).map_err(|e| KzgError::GenericError(e.to_string()))
+    }
+
+    /// Writes the loaded points to `dir` in the layouts of the EigenDA SRS
+    /// files: the G1 points to `g1.point` and the G2 power of 2 table to
+    /// `g2.point.powerOf2`, along with the G2 points to `g2.point` when
+    /// consecutive powers are loaded. Loading the files back gives the same
+    /// setup, so this converts SRS from other formats or cuts a large SRS
+    /// down to the points loaded.
+    pub fn export_srs(&self, dir: &str) -> Result<(), KzgError> {
+        if self.g2_released {
+            return Err(KzgError::GenericError(
+                "the G2 points have been released".to_string(),
+            ));
+        }
+        let dir = Path::new(dir);
+        std::fs::create_dir_all(dir).map_err(|e| KzgError::GenericError(e.to_string()))?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+
+        Self::write_g1_points(&path("g1.point"), &self.g1)?;
+        let power_of_2_table: Vec<G2Affine> = if self.g2_is_consecutive() {
+            Self::write_g2_points(&path("g2.point"), &self.g2)?;
+            (0..)
+                .map_while(|log2| self.g2_tau_power_of_2(log2))
+                .collect()
+        } else {
+            self.g2.to_vec()
+        };
+        Self::write_g2_points(&path("g2.point.powerOf2"), &power_of_2_table)
+    }
+
+    /// commit the actual polynomial with the values setup
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(length = polynomial.len()))
+    )]
+    pub fn commit(&self, polynomial: &Polynomial) -> Result<G1Affine, KzgError> {
+        if polynomial.len() > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+
+        // A precomputed Lagrange SRS for this length makes the commitment a
+        // single MSM
+        if polynomial.len() == self.g1_lagrange.len() {
+            return self.g1_msm(
+                &self.g1_lagrange,
+                polynomial.as_slice(),
+                KzgError::CommitError,
+            );
+        }
+
+        // Small blobs skip the G1 IFFT and the thread pool entirely
+        if polynomial.len() <= self.config.get_small_blob_threshold() {
+            let domain = Domain::new(polynomial.len())?;
+            let coeffs = Self::naive_ifft(&polynomial.to_vec(), domain.get_roots_of_unity());
+            return Ok(Self::naive_msm(&self.g1[..coeffs.len()], &coeffs));
+        }
+
+        let bases = self.g1_ifft(polynomial.len())?;
+        self.g1_msm(&bases, polynomial.as_slice(), KzgError::CommitError)
+    }
+
+    pub fn commit_to_evaluation_polynomial(
+        &self,
+        polynomial: &Polynomial,
+    ) -> Result<G1Affine, KzgError> {
+        if polynomial.len() > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+
+        self.g1_msm(
+            &self.g1[..polynomial.len()],
+            polynomial.as_slice(),
+            KzgError::CommitError,
+        )
+    }
+
+    /// 4844 compatible helper function
+    pub fn blob_to_kzg_commitment(&self, blob: &Blob) -> Result<G1Affine, KzgError> {
+        let polynomial = blob
+            .to_polynomial()
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        let commitment = self.commit(&polynomial)?;
+        Ok(commitment)
+    }
+
+    /// Commits to `blob`, padding a copy first if it is not padded yet.
+    pub fn commit_blob(&self, blob: &Blob) -> Result<KzgCommitment, KzgError> {
+        let polynomial = if blob.is_padded() {
+            blob.to_polynomial()
+        } else {
+            let mut padded = blob.clone();
+            padded.pad_data().and_then(|_| padded.to_polynomial())
+        }
+        .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        KzgCommitment::from_polynomial(&polynomial, self)
+    }
+
+    /// Pads `bytes` into a blob and commits to it.
+    pub fn commit_bytes(&self, bytes: &[u8]) -> Result<KzgCommitment, KzgError> {
+        self.commit_blob(&Blob::from_bytes_and_pad(bytes))
+    }
+
+    /// Starts a streaming commitment over `length` field elements; see
+    /// `CommitmentBuilder`.
+    pub fn commitment_builder(&self, length: usize) -> Result<CommitmentBuilder, KzgError> {
+        CommitmentBuilder::new(self, length)
+    }
+
+    /// Proves the blob's evaluation at the challenge derived from the blob
+    /// and `commitment`, like c-kzg-4844's `compute_blob_kzg_proof`.
+    pub fn compute_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment: &G1Affine,
+    ) -> Result<G1Affine, KzgError> {
+        let polynomial = blob
+            .to_polynomial()
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        let z = self.verifier().blob_challenge(&polynomial, commitment)?;
+        let (proof, _) = self.compute_kzg_proof_at_point(&polynomial, z)?;
+        Ok(proof)
+    }
+
+    /// Checks a proof from `compute_blob_kzg_proof`, re-deriving the
+    /// challenge and the blob's evaluation there, like c-kzg-4844's
+    /// `verify_blob_kzg_proof`.
+    pub fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment: &G1Affine,
+        proof: &G1Affine,
+    ) -> Result<bool, KzgError> {
+        self.verifier()
+            .verify_blob_kzg_proof(blob, commitment, proof)
+    }
+
+    /// Same as `verify_blob_kzg_proof`, over the padded blob data as returned
+    /// by `Blob::get_blob_data` and the compressed commitment and proof.
+    /// Points that are not on the curve or not in the subgroup are errors
+    /// rather than failed checks.
+    pub fn verify_from_bytes(
+        &self,
+        blob_bytes: &[u8],
+        commitment_bytes: &[u8],
+        proof_bytes: &[u8],
+    ) -> Result<bool, KzgError> {
+        self.verifier()
+            .verify_from_bytes(blob_bytes, commitment_bytes, proof_bytes)
+    }
+
+    /// Checks many proofs from `compute_blob_kzg_proof` with a single
+    /// pairing check, folding the openings with powers of a challenge hashed
+    /// from all of them. Returns one result per blob, in input order. Blobs
+    /// are only checked one by one when the combined check fails, to find
+    /// which proofs are invalid.
+    pub fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments: &[G1Affine],
+        proofs: &[G1Affine],
+    ) -> Result<Vec<Result<(), KzgError>>, KzgError> {
+        self.verifier()
+            .verify_blob_kzg_proof_batch(blobs, commitments, proofs)
+    }
+
+    /// Derives the point `compute_blob_kzg_proof` opens the blob at, so a
+    /// prover and verifier agree on it from the blob and commitment alone.
+    ///
+    /// The challenge is the configured hash (SHA-256 by default, or
+    /// keccak256) of, in order:
+    /// - the `FSBLOBVERIFY_V1_` domain separation tag
+    /// - the number of field elements in the blob as 16 big-endian bytes
+    /// - each field element as 32 big-endian bytes
+    /// - the compressed commitment
+    ///
+    /// reduced modulo the scalar field order from big-endian bytes, as
+    /// c-kzg-4844 derives it.
+    pub fn compute_challenge(&self, blob: &Blob, commitment: &G1Affine) -> Result<Fr, KzgError> {
+        self.verifier().compute_challenge(blob, commitment)
+    }
+
+    /// helper function to work with the library and the env of the kzg instance.
+    /// Proves over the roots of unity of the polynomial's length, whatever
+    /// `data_setup_*` set up.
+    pub fn compute_kzg_proof_with_roots_of_unity(
+        &self,
+        polynomial: &Polynomial,
+        index: u64,
+    ) -> Result<G1Affine, KzgError> {
+        let domain = Domain::new(polynomial.len())?;
+        self.compute_kzg_proof(polynomial, index, domain.get_roots_of_unity())
+    }
 
-        let num_workers = num_cpus::get();
+    /// function to compute the kzg proof given the values.
+    /// Proves the evaluation at the root of unity at `index`; use
+    /// `compute_kzg_proof_at_point` to open at any other point. It used to
+    /// fail unless a `data_setup_*` function had run, and now needs no setup.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(length = polynomial.len(), index = index)
+        )
+    )]
+    pub fn compute_kzg_proof(
+        &self,
+        polynomial: &Polynomial,
+        index: u64,
+        root_of_unities: &[Fr],
+    ) -> Result<G1Affine, KzgError> {
+        if polynomial.len() != root_of_unities.len() {
+            return Err(KzgError::GenericError(
+                "inconsistent length between blob and root of unities".to_string(),
+            ));
+        }
 
-        let workers: Vec<_> = (0..num_workers)
-            .map(|_| {
-                let receiver = receiver.clone();
-                std::thread::spawn(move || helpers::process_chunks::<G1Affine>(receiver))
+        let eval_fr = polynomial.to_vec();
+        let mut poly_shift: Vec<Fr> = Vec::with_capacity(eval_fr.len());
+        let usized_index = if let Some(x) = index.to_usize() {
+            x
+        } else {
+            return Err(KzgError::SerializationError(
+                "index couldn't be converted to usize".to_string(),
+            ));
+        };
+        if usized_index >= eval_fr.len() {
+            return Err(KzgError::GenericError(
+                "index is out of range of the polynomial".to_string(),
+            ));
+        }
+
+        let value_fr = eval_fr[usized_index];
+        let z_fr = root_of_unities[usized_index];
+
+        for fr in eval_fr.iter() {
+            poly_shift.push(*fr - value_fr);
+        }
+
+        let mut denom_poly = Vec::<Fr>::with_capacity(root_of_unities.len());
+        for root in root_of_unities.iter().take(eval_fr.len()) {
+            denom_poly.push(*root - z_fr);
+        }
+
+        let mut quotient_poly = Vec::<Fr>::with_capacity(root_of_unities.len());
+
+        for i in 0..root_of_unities.len() {
+            if denom_poly[i].is_zero() {
+                quotient_poly.push(Self::compute_quotient_eval_on_domain(
+                    z_fr,
+                    &eval_fr,
+                    value_fr,
+                    root_of_unities,
+                ));
+            } else {
+                quotient_poly.push(poly_shift[i].div(denom_poly[i]));
+            }
+        }
+
+        if polynomial.len() <= self.config.get_small_blob_threshold() {
+            let quotient_coeffs = Self::naive_ifft(&quotient_poly, root_of_unities);
+            return Ok(Self::naive_msm(
+                &self.g1[..quotient_coeffs.len()],
+                &quotient_coeffs,
+            ));
+        }
+
+        let g1_lagrange = self.g1_ifft(polynomial.len())?;
+        self.g1_msm(&g1_lagrange, &quotient_poly, KzgError::SerializationError)
+    }
+
+    /// evaluates `polynomial`, given by its evaluations over the roots of
+    /// unity of its length, at an arbitrary point `z` with the barycentric
+    /// formula `p(z) = (z^n - 1) / n * sum e_i w_i / (z - w_i)`
+    pub fn evaluate_polynomial_in_evaluation_form(polynomial: &Polynomial, z: Fr) -> Fr {
+        let domain = Domain::new(polynomial.len()).expect("polynomials have a power of 2 length");
+        helpers::evaluate_over_roots_of_unity(polynomial.as_slice(), domain.get_roots_of_unity(), z)
+    }
+
+    /// Evaluates the polynomial of `blob` at an arbitrary point `z` with the
+    /// barycentric formula, the value a blob proof opens to at `z`. See
+    /// `helpers::evaluate_polynomial_in_evaluation_form` for evaluations that
+    /// are not in a blob.
+    pub fn evaluate_blob_in_evaluation_form(blob: &Blob, z: Fr) -> Result<Fr, KzgError> {
+        let polynomial = blob
+            .to_polynomial()
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        Ok(Self::evaluate_polynomial_in_evaluation_form(&polynomial, z))
+    }
+
+    /// proves the evaluation of `polynomial` at an arbitrary point `z`, which
+    /// need not be one of its roots of unity, returning the proof and the
+    /// evaluation. Unlike `compute_kzg_proof`, needs no data setup.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(length = polynomial.len()))
+    )]
+    pub fn compute_kzg_proof_at_point(
+        &self,
+        polynomial: &Polynomial,
+        z: Fr,
+    ) -> Result<(G1Affine, Fr), KzgError> {
+        if polynomial.len() > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+
+        let domain = Domain::new(polynomial.len())?;
+        let roots_of_unity = domain.get_roots_of_unity();
+        let evals = polynomial.as_slice();
+        let value = Self::evaluate_polynomial_in_evaluation_form(polynomial, z);
+
+        // (e_i - y) / (w_i - z), with the limit at w_i = z if z is a root
+        let mut denominators: Vec<Fr> = roots_of_unity.iter().map(|root| *root - z).collect();
+        batch_inversion(&mut denominators);
+        let quotient: Vec<Fr> = evals
+            .iter()
+            .zip(roots_of_unity)
+            .zip(&denominators)
+            .map(|((eval, root), inv)| {
+                if *root == z {
+                    Self::compute_quotient_eval_on_domain(z, evals, value, roots_of_unity)
+                } else {
+                    (*eval - value) * inv
+                }
             })
             .collect();
 
-        // Wait for the reader thread to finish
-        // reader_thread.join().expect("Reader thread panicked");
+        if polynomial.len() <= self.config.get_small_blob_threshold() {
+            let quotient_coeffs = Self::naive_ifft(&quotient, roots_of_unity);
+            let proof = Self::naive_msm(&self.g1[..quotient_coeffs.len()], &quotient_coeffs);
+            return Ok((proof, value));
+        }
 
-        match reader_thread.join() {
-            Ok(result) => match result {
-                Ok(_) => {},
-                Err(e) => return Err(KzgError::GenericError(e.to_string())),
-            },
-            Err(_) => return Err(KzgError::GenericError("Thread panicked".to_string())),
+        let g1_lagrange = self.g1_ifft(polynomial.len())?;
+        let proof = self.g1_msm(&g1_lagrange, &quotient, KzgError::SerializationError)?;
+        Ok((proof, value))
+    }
+
+    /// Proves the evaluation of `polynomial` at every one of its roots of
+    /// unity, returning the proofs in the order of the roots, with the FK20
+    /// algorithm of Feist and Khovratovich in O(n log n) group operations
+    /// instead of one O(n) MSM per proof.
+    ///
+    /// For `p(X) = sum f_j X^j` of degree `d`, the proof at `z` is
+    /// `sum_{i=1}^{d} h_i z^(i-1)` with `h_i = sum_{j=i}^{d} f_j [tau^(j-i)]G1`.
+    /// The `h_i` are a Toeplitz matrix of the coefficients times the SRS,
+    /// computed as a convolution with FFTs, and one more FFT evaluates their
+    /// sum at every root.
+    pub fn compute_all_proofs(&self, polynomial: &Polynomial) -> Result<Vec<G1Affine>, KzgError> {
+        let length = polynomial.len();
+        if !length.is_power_of_two() {
+            return Err(KzgError::FftError(
+                "length provided is not a power of 2".to_string(),
+            ));
+        }
+        if length > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+        if length == 1 {
+            return Ok(vec![G1Affine::identity()]);
+        }
+
+        // h_(m+1) is entry d - 1 - m of the convolution of f_d, ..., f_1 with
+        // [tau^0]G1, ..., [tau^(d-1)]G1
+        let coefficients = Self::monomial_coefficients(polynomial)?;
+        let degree = length - 1;
+        let reversed: Vec<Fr> = coefficients[1..].iter().rev().copied().collect();
+        let mut h = self.g1_convolution(&reversed, &self.g1[..degree])?;
+        h.reverse();
+        h.push(G1Projective::zero());
+
+        let domain = Domain::new(length)?;
+        self.config.install_fft(length, || {
+            domain.fft_in_place(&mut h);
+            G1Projective::normalize_batch(&h)
+        })
+    }
+
+    /// Proves the evaluations of `polynomial` over each coset
+    /// `w^i * <w^num_cosets>` of `coset_size` points, where `w` generates the
+    /// roots of unity of order `coset_size * num_cosets`, returning one
+    /// multireveal proof per coset in order of `i`. This is how the EigenDA
+    /// encoder proves frames, with the FK20 multi-proof algorithm.
+    ///
+    /// The proof for a coset is the quotient by its vanishing polynomial
+    /// `X^L - a`, with `a = w^(iL)`, which is `sum_m H_m a^m` for
+    /// `H_m = sum_{k>=0} f_((m+1)L+k) [tau^k]G1`. Splitting `k` by its residue
+    /// modulo `L` makes the `H_m` a sum of `L` Toeplitz products, each computed
+    /// as in `compute_all_proofs`, and the `a` are the `num_cosets`-th roots of
+    /// unity, so one FFT evaluates the sum for every coset.
+    pub fn compute_coset_proofs(
+        &self,
+        polynomial: &Polynomial,
+        coset_size: usize,
+        num_cosets: usize,
+    ) -> Result<Vec<G1Affine>, KzgError> {
+        let length = polynomial.len();
+        if !length.is_power_of_two()
+            || !coset_size.is_power_of_two()
+            || !num_cosets.is_power_of_two()
+        {
+            return Err(KzgError::FftError(
+                "length, coset size and number of cosets must be powers of 2".to_string(),
+            ));
+        }
+        if length > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+        if length <= coset_size {
+            return Ok(vec![G1Affine::identity(); num_cosets]);
+        }
+
+        let coefficients = Self::monomial_coefficients(polynomial)?;
+        let rows = length / coset_size;
+        let mut h = vec![G1Projective::zero(); rows];
+        for offset in 0..coset_size {
+            let reversed: Vec<Fr> = (1..rows)
+                .rev()
+                .map(|row| coefficients[row * coset_size + offset])
+                .collect();
+            let bases: Vec<G1Affine> = (0..rows - 1)
+                .map(|row| self.g1[row * coset_size + offset])
+                .collect();
+            let products = self.g1_convolution(&reversed, &bases)?;
+            for (sum, product) in h.iter_mut().zip(products.into_iter().rev()) {
+                *sum += product;
+            }
+        }
+
+        // a^num_cosets = 1, so terms beyond the number of cosets wrap around
+        let mut folded = vec![G1Projective::zero(); num_cosets];
+        for (m, term) in h.into_iter().enumerate() {
+            folded[m % num_cosets] += term;
+        }
+        let domain = Domain::new(num_cosets)?;
+        self.config.install_fft(num_cosets, || {
+            domain.fft_in_place(&mut folded);
+            G1Projective::normalize_batch(&folded)
+        })
+    }
+
+    /// returns the first `scalars.len()` entries of the convolution of
+    /// `scalars` with `bases`, `c_t = sum_k scalars[t - k] * bases[k]`,
+    /// multiplying pointwise between FFTs of twice the length
+    pub(crate) fn g1_convolution(
+        &self,
+        scalars: &[Fr],
+        bases: &[G1Affine],
+    ) -> Result<Vec<G1Projective>, KzgError> {
+        let length = scalars.len();
+        let domain = Domain::new((2 * length).next_power_of_two())?;
+        let mut scalars = scalars.to_vec();
+        scalars.resize(domain.get_size(), Fr::zero());
+        let mut points: Vec<G1Projective> = bases[..length].iter().map(|&p| p.into()).collect();
+        points.resize(domain.get_size(), G1Projective::zero());
+
+        self.config.install_fft(domain.get_size(), || {
+            domain.fft_in_place(&mut scalars);
+            domain.fft_in_place(&mut points);
+            let mut points: Vec<G1Projective> = G1Projective::normalize_batch(&points)
+                .par_iter()
+                .zip(&scalars)
+                .map(|(point, scalar)| glv::mul(point, scalar))
+                .collect();
+            domain.ifft_in_place(&mut points);
+            points.truncate(length);
+            points
+        })
+    }
+
+    /// Proves the evaluations of `polynomial` at all of `points` with a
+    /// single proof, returning it and the evaluations in the order of the
+    /// points. The proof commits to the quotient of the polynomial by
+    /// `Z(X) = prod (X - z_i)`, the polynomial vanishing on the points, which
+    /// must be distinct.
+    pub fn compute_multi_point_proof(
+        &self,
+        polynomial: &Polynomial,
+        points: &[Fr],
+    ) -> Result<(G1Affine, Vec<Fr>), KzgError> {
+        if polynomial.len() > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+        Self::check_distinct_points(points)?;
+
+        // dividing by each (X - z_i) in turn leaves the quotient by Z
+        let mut quotient = Self::monomial_coefficients(polynomial)?;
+        for z in points {
+            quotient = helpers::divide_by_linear(&quotient, *z).0;
+        }
+        let proof = self.commit_coefficients(&quotient)?;
+        let values = points
+            .iter()
+            .map(|z| Self::evaluate_polynomial_in_evaluation_form(polynomial, *z))
+            .collect();
+        Ok((proof, values))
+    }
+
+    /// Checks `proof` shows the polynomial under `commitment` evaluates to
+    /// `values[i]` at `points[i]` for every `i`, with the pairing check
+    /// `e(C - [I(tau)]G1, G2) = e(proof, [Z(tau)]G2)`, where `I` interpolates
+    /// the values and `Z` vanishes on the points. Needs one G1 point per point
+    /// and the full G2 table.
+    pub fn verify_multi_point_proof(
+        &self,
+        commitment: G1Affine,
+        proof: G1Affine,
+        values: &[Fr],
+        points: &[Fr],
+    ) -> Result<bool, KzgError> {
+        if values.len() != points.len() {
+            return Err(KzgError::GenericError(
+                "points and values differ in number".to_string(),
+            ));
+        }
+        Self::check_distinct_points(points)?;
+
+        let vanishing = points.iter().fold(vec![Fr::one()], |z, point| {
+            let mut next = vec![Fr::zero(); z.len() + 1];
+            for (i, coefficient) in z.iter().enumerate() {
+                next[i + 1] += coefficient;
+                next[i] -= *coefficient * point;
+            }
+            next
+        });
+        let vanishing_g2 = self
+            .full_g2_table()?
+            .get(..vanishing.len())
+            .ok_or_else(|| {
+                KzgError::SerializationError("G2 table is shorter than the points".to_string())
+            })
+            .and_then(|bases| Self::g2_msm(bases, &vanishing))?;
+
+        // Lagrange interpolation: I = sum y_i * Z / ((X - z_i) * Z'(z_i))
+        let bases: Vec<Vec<Fr>> = points
+            .iter()
+            .map(|point| helpers::divide_by_linear(&vanishing, *point).0)
+            .collect();
+        let mut weights: Vec<Fr> = points
+            .iter()
+            .zip(&bases)
+            .map(|(point, basis)| {
+                basis
+                    .iter()
+                    .rev()
+                    .fold(Fr::zero(), |acc, c| acc * point + c)
+            })
+            .collect();
+        batch_inversion(&mut weights);
+        let mut interpolation = vec![Fr::zero(); points.len()];
+        for ((basis, weight), value) in bases.iter().zip(&weights).zip(values) {
+            let scale = *weight * value;
+            for (coefficient, term) in interpolation.iter_mut().zip(basis) {
+                *coefficient += scale * term;
+            }
+        }
+        let interpolation_g1 = self.commit_coefficients(&interpolation)?;
+
+        Ok(Self::pairings_verify(
+            (commitment - interpolation_g1).into_affine(),
+            G2Affine::generator(),
+            proof,
+            vanishing_g2,
+        ))
+    }
+
+    /// Proves the evaluations of all of `polynomials` at `z` with a single
+    /// proof, returning it and the evaluations in order. The polynomials are
+    /// combined with the powers of a challenge hashed from their commitments,
+    /// `z` and the evaluations, and the combination is opened at `z`.
+    pub fn compute_aggregated_proof(
+        &self,
+        polynomials: &[Polynomial],
+        z: Fr,
+    ) -> Result<(G1Affine, Vec<Fr>), KzgError> {
+        let commitments = polynomials
+            .iter()
+            .map(|polynomial| self.commit(polynomial))
+            .collect::<Result<Vec<G1Affine>, KzgError>>()?;
+        self.compute_aggregated_proof_with_commitments(polynomials, &commitments, z)
+    }
+
+    /// `compute_aggregated_proof` for callers that already hold the
+    /// commitments
+    pub(crate) fn compute_aggregated_proof_with_commitments(
+        &self,
+        polynomials: &[Polynomial],
+        commitments: &[G1Affine],
+        z: Fr,
+    ) -> Result<(G1Affine, Vec<Fr>), KzgError> {
+        if polynomials.is_empty() || polynomials.len() != commitments.len() {
+            return Err(KzgError::GenericError(
+                "polynomials and commitments must be equal in number and not empty".to_string(),
+            ));
+        }
+        let values: Vec<Fr> = polynomials
+            .iter()
+            .map(|polynomial| Self::evaluate_polynomial_in_evaluation_form(polynomial, z))
+            .collect();
+        let gamma = self
+            .verifier()
+            .aggregation_challenge(commitments, &values, z)?;
+
+        let mut combined: Vec<Fr> = Vec::new();
+        let mut power = Fr::one();
+        for polynomial in polynomials {
+            let coefficients = Self::monomial_coefficients(polynomial)?;
+            if combined.len() < coefficients.len() {
+                combined.resize(coefficients.len(), Fr::zero());
+            }
+            for (coefficient, term) in combined.iter_mut().zip(&coefficients) {
+                *coefficient += power * term;
+            }
+            power *= gamma;
+        }
+        let proof = self.commit_coefficients(&helpers::divide_by_linear(&combined, z).0)?;
+        Ok((proof, values))
+    }
+
+    /// Checks a proof from `compute_aggregated_proof` shows the polynomials
+    /// under `commitments` evaluate to `values` at `z`, by combining the
+    /// commitments and values with the powers of the same challenge and
+    /// making one opening check.
+    pub fn verify_aggregated_proof(
+        &self,
+        commitments: &[G1Affine],
+        proof: G1Affine,
+        values: &[Fr],
+        z: Fr,
+    ) -> Result<bool, KzgError> {
+        self.verifier()
+            .verify_aggregated_proof(commitments, proof, values, z)
+    }
+
+    /// returns the commitment and value of the combination of polynomials
+    /// `compute_aggregated_proof` opens at `z`
+    pub(crate) fn combine_openings(
+        &self,
+        commitments: &[G1Affine],
+        values: &[Fr],
+        z: Fr,
+    ) -> Result<(G1Affine, Fr), KzgError> {
+        self.verifier().combine_openings(commitments, values, z)
+    }
+
+    /// checks there is at least one point and no point repeats, without
+    /// which the points have no vanishing polynomial with simple roots
+    fn check_distinct_points(points: &[Fr]) -> Result<(), KzgError> {
+        let mut sorted = points.to_vec();
+        sorted.sort_unstable();
+        if sorted.is_empty() || sorted.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(KzgError::GenericError(
+                "points must be distinct and not empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// commit the polynomial reusing the Lagrange bases and buffers held by
+    /// `workspace`
+    pub fn commit_with_workspace(
+        &self,
+        polynomial: &Polynomial,
+        workspace: &mut KzgWorkspace,
+    ) -> Result<G1Affine, KzgError> {
+        if polynomial.len() > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
         }
 
-        // Collect and sort results
-        let mut all_points = Vec::new();
-        for worker in workers {
-            let points = worker.join().expect("Worker thread panicked");
-            all_points.extend(points);
+        self.fill_lagrange_bases(polynomial.len(), workspace)?;
+        self.g1_msm(
+            &workspace.g1_lagrange,
+            polynomial.as_slice(),
+            KzgError::CommitError,
+        )
+    }
+
+    /// commits to the evaluations of `polynomial` in `range` only, i.e. the
+    /// share of `commit` that this range contributes. Partial commitments over
+    /// ranges covering the polynomial sum to its commitment, so one large MSM
+    /// can be split across workers with `shard_ranges` and summed with
+    /// `combine_partial_commitments`.
+    pub fn commit_partial(
+        &self,
+        polynomial: &Polynomial,
+        range: Range<usize>,
+    ) -> Result<G1Affine, KzgError> {
+        self.check_partial_range(polynomial, &range)?;
+        let bases = self.g1_ifft(polynomial.len())?;
+        self.g1_msm(
+            &bases[range.clone()],
+            &polynomial.as_slice()[range],
+            KzgError::CommitError,
+        )
+    }
+
+    /// same as `commit_partial`, but reuses the Lagrange bases held by
+    /// `workspace`
+    pub fn commit_partial_with_workspace(
+        &self,
+        polynomial: &Polynomial,
+        range: Range<usize>,
+        workspace: &mut KzgWorkspace,
+    ) -> Result<G1Affine, KzgError> {
+        self.check_partial_range(polynomial, &range)?;
+        self.fill_lagrange_bases(polynomial.len(), workspace)?;
+        self.g1_msm(
+            &workspace.g1_lagrange[range.clone()],
+            &polynomial.as_slice()[range],
+            KzgError::CommitError,
+        )
+    }
+
+    /// splits `0..length` into `num_shards` contiguous ranges whose sizes
+    /// differ by at most one, for `commit_partial`
+    pub fn shard_ranges(length: usize, num_shards: usize) -> Vec<Range<usize>> {
+        let num_shards = num_shards.max(1);
+        let (size, remainder) = (length / num_shards, length % num_shards);
+        let mut start = 0;
+        (0..num_shards)
+            .map(|shard| {
+                let end = start + size + usize::from(shard < remainder);
+                let range = start..end;
+                start = end;
+                range
+            })
+            .collect()
+    }
+
+    /// sums partial commitments into the commitment of the whole polynomial
+    pub fn combine_partial_commitments(partials: &[G1Affine]) -> G1Affine {
+        partials
+            .iter()
+            .fold(G1Projective::zero(), |acc, partial| acc + partial)
+            .into_affine()
+    }
+
+    fn check_partial_range(
+        &self,
+        polynomial: &Polynomial,
+        range: &Range<usize>,
+    ) -> Result<(), KzgError> {
+        if polynomial.len() > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+        if range.start > range.end || range.end > polynomial.len() {
+            return Err(KzgError::GenericError(
+                "range is out of bounds of the polynomial".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// same as `compute_kzg_proof`, but reuses the Lagrange bases and buffers
+    /// held by `workspace`
+    pub fn compute_kzg_proof_with_workspace(
+        &self,
+        polynomial: &Polynomial,
+        index: u64,
+        root_of_unities: &[Fr],
+        workspace: &mut KzgWorkspace,
+    ) -> Result<G1Affine, KzgError> {
+        if polynomial.len() != root_of_unities.len() {
+            return Err(KzgError::GenericError(
+                "inconsistent length between blob and root of unities".to_string(),
+            ));
+        }
+
+        let usized_index = index.to_usize().ok_or_else(|| {
+            KzgError::SerializationError("index couldn't be converted to usize".to_string())
+        })?;
+        if usized_index >= polynomial.len() {
+            return Err(KzgError::GenericError(
+                "index is out of range of the polynomial".to_string(),
+            ));
+        }
+
+        Self::fill_quotient_on_domain(
+            polynomial.as_slice(),
+            usized_index,
+            root_of_unities,
+            &mut workspace.denominators,
+            &mut workspace.quotient,
+        );
+
+        self.fill_lagrange_bases(polynomial.len(), workspace)?;
+        self.g1_msm(
+            &workspace.g1_lagrange,
+            &workspace.quotient,
+            KzgError::SerializationError,
+        )
+    }
+
+    /// prepares `polynomial` for proving at many indices over the roots of
+    /// unity of this instance, computing its coefficient form once
+    pub fn prepare_openings(&self, polynomial: &Polynomial) -> Result<BlobOpenings, KzgError> {
+        let domain = Domain::new(polynomial.len())?;
+
+        if polynomial.len() > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+
+        let coefficients = self
+            .config
+            .install_fft(polynomial.len(), || domain.ifft(polynomial.as_slice()))?;
+
+        Ok(BlobOpenings {
+            coefficients,
+            roots_of_unity: domain.get_roots_of_unity().to_vec(),
+        })
+    }
+
+    /// computes the proof at `index` for a polynomial prepared with
+    /// `prepare_openings`
+    pub fn compute_kzg_proof_with_openings(
+        &self,
+        openings: &BlobOpenings,
+        index: u64,
+    ) -> Result<G1Affine, KzgError> {
+        let z_fr = index
+            .to_usize()
+            .and_then(|i| openings.roots_of_unity.get(i))
+            .ok_or_else(|| {
+                KzgError::GenericError("index is out of range of the polynomial".to_string())
+            })?;
+
+        // synthetic division of p(X) by (X - z); the dropped remainder is p(z)
+        let coefficients = &openings.coefficients;
+        let mut quotient = vec![Fr::zero(); coefficients.len() - 1];
+        let mut acc = Fr::zero();
+        for i in (1..coefficients.len()).rev() {
+            acc = coefficients[i] + *z_fr * acc;
+            quotient[i - 1] = acc;
         }
 
-        // Sort by original position to maintain order
-        all_points.sort_by_key(|&(_, position)| position);
-
-        Ok(all_points.iter().map(|(point, _)| *point).collect())
-    }
-
-    /// obtain copy of g2 points
-    pub fn get_g2_points(&self) -> Vec<G2Affine> {
-        self.g2.to_vec()
+        self.commit_coefficients(&quotient)
     }
 
-    /// commit the actual polynomial with the values setup
-    pub fn commit(&self, polynomial: &Polynomial) -> Result<G1Affine, KzgError> {
-        if polynomial.len() > self.g1.len() {
+    /// commits to a polynomial in coefficient form with one MSM over the
+    /// monomial SRS
+    pub(crate) fn commit_coefficients(&self, coefficients: &[Fr]) -> Result<G1Affine, KzgError> {
+        if coefficients.len() > self.g1.len() {
             return Err(KzgError::SerializationError(
                 "polynomial length is not correct".to_string(),
             ));
         }
 
-        // Configure multi-threading
-        let config = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_cpus::get())
-            .build()
-            .map_err(|err| KzgError::CommitError(err.to_string()))?;
+        self.g1_msm(
+            &self.g1[..coefficients.len()],
+            coefficients,
+            KzgError::SerializationError,
+        )
+    }
+
+    /// commits to each polynomial and proves it at the matching index in one
+    /// pass. Polynomials are grouped by domain size so the roots of unity and
+    /// G1 Lagrange bases are computed once per size, and quotient and MSM work
+    /// runs across blobs in parallel on the configured thread pool. Returns
+    /// `(commitment, proof)` pairs in input order.
+    pub fn compute_commitments_and_proofs(
+        &self,
+        polynomials: &[Polynomial],
+        indices: &[u64],
+    ) -> Result<Vec<(G1Affine, G1Affine)>, KzgError> {
+        if polynomials.len() != indices.len() {
+            return Err(KzgError::GenericError(
+                "number of polynomials and indices don't match".to_string(),
+            ));
+        }
 
-        // Perform the multi-exponentiation
-        config.install(|| {
-            let bases = self.g1_ifft(polynomial.len()).unwrap();
-            match G1Projective::msm(&bases, &polynomial.to_vec()) {
-                Ok(res) => Ok(res.into_affine()),
-                Err(err) => Err(KzgError::CommitError(err.to_string())),
+        let mut by_domain_size: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (i, polynomial) in polynomials.iter().enumerate() {
+            if polynomial.len() > self.g1.len() {
+                return Err(KzgError::SerializationError(
+                    "polynomial length is not correct".to_string(),
+                ));
             }
-        })
-    }
+            if indices[i] >= polynomial.len() as u64 {
+                return Err(KzgError::GenericError(
+                    "index is out of range of the polynomial".to_string(),
+                ));
+            }
+            by_domain_size.entry(polynomial.len()).or_default().push(i);
+        }
 
-    pub fn commit_to_evaluation_polynomial(&self, polynomial: &Polynomial) -> Result<G1Affine, KzgError> {
-        if polynomial.len() > self.g1.len() {
-            return Err(KzgError::SerializationError("polynomial length is not correct".to_string()));
-        }
-    
-        // Configure multi-threading
-        let config = rayon::ThreadPoolBuilder::new().num_threads(num_cpus::get()).build().
-        map_err(|err| KzgError::CommitError(err.to_string()))?;
-    
-        // Perform the multi-exponentiation
-        config.install(|| {
-            let bases = self.g1[..polynomial.len()].to_vec();
-            match G1Projective::msm(&bases, &polynomial.to_vec()) {
-                Ok(res) => Ok(res.into_affine()),
-                Err(err) => Err(KzgError::CommitError(err.to_string())),
+        let mut results = vec![(G1Affine::zero(), G1Affine::zero()); polynomials.len()];
+        let mut workspace = KzgWorkspace::new();
+        for (domain_size, members) in by_domain_size {
+            let domain = Domain::new(domain_size)?;
+            self.fill_lagrange_bases(domain_size, &mut workspace)?;
+            let bases = &workspace.g1_lagrange;
+
+            let outputs = self.config.install(|| {
+                members
+                    .par_iter()
+                    .map(|&i| {
+                        let evals = polynomials[i].as_slice();
+                        let mut denominators = Vec::with_capacity(domain_size);
+                        let mut quotient = Vec::with_capacity(domain_size);
+                        Self::fill_quotient_on_domain(
+                            evals,
+                            indices[i] as usize,
+                            domain.get_roots_of_unity(),
+                            &mut denominators,
+                            &mut quotient,
+                        );
+                        let min_chunk_len = self.config.msm_chunking(domain_size);
+                        let commitment = glv::msm(bases, evals, min_chunk_len)
+                            .map_err(|err| KzgError::CommitError(err.to_string()))?;
+                        let proof = glv::msm(bases, &quotient, min_chunk_len)
+                            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+                        Ok((i, commitment, proof))
+                    })
+                    .collect::<Result<Vec<_>, KzgError>>()
+            })??;
+
+            for (i, commitment, proof) in outputs {
+                results[i] = (commitment.into_affine(), proof.into_affine());
             }
-        })
+        }
+        Ok(results)
     }
 
-    /// 4844 compatible helper function
-    pub fn blob_to_kzg_commitment(&self, blob: &Blob) -> Result<G1Affine, KzgError> {
-        let polynomial = blob
-            .to_polynomial()
-            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
-        let commitment = self.commit(&polynomial)?;
-        Ok(commitment)
+    /// computes the evaluations of (p(x) - p(z)) / (x - z) over the domain,
+    /// where z is the root of unity at `index`
+    fn fill_quotient_on_domain(
+        eval_fr: &[Fr],
+        index: usize,
+        root_of_unities: &[Fr],
+        denominators: &mut Vec<Fr>,
+        quotient: &mut Vec<Fr>,
+    ) {
+        let value_fr = eval_fr[index];
+        let z_fr = root_of_unities[index];
+
+        // (omega_i - z) is only zero at the opened index, which is replaced
+        // below, so invert all denominators at once
+        denominators.clear();
+        denominators.extend(root_of_unities.iter().map(|root| *root - z_fr));
+        denominators[index] = Fr::one();
+        batch_inversion(denominators);
+
+        quotient.clear();
+        quotient.extend(
+            eval_fr
+                .iter()
+                .zip(denominators.iter())
+                .map(|(eval, denom_inv)| (*eval - value_fr) * denom_inv),
+        );
+        quotient[index] =
+            Self::compute_quotient_eval_on_domain(z_fr, eval_fr, value_fr, root_of_unities);
     }
 
-    /// helper function to work with the library and the env of the kzg instance
-    pub fn compute_kzg_proof_with_roots_of_unity(
+    /// fills the workspace with the G1 Lagrange bases for `length`, unless
+    /// they are already cached for this SRS
+    pub(crate) fn fill_lagrange_bases(
         &self,
-        polynomial: &Polynomial,
-        index: u64,
-    ) -> Result<G1Affine, KzgError> {
-        self.compute_kzg_proof(polynomial, index, &self.expanded_roots_of_unity)
+        length: usize,
+        workspace: &mut KzgWorkspace,
+    ) -> Result<(), KzgError> {
+        let srs_tag = self.g1.get(1).copied();
+        if workspace.g1_lagrange.len() == length && workspace.srs_tag == srs_tag {
+            return Ok(());
+        }
+
+        if length > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+        if !length.is_power_of_two() {
+            return Err(KzgError::FftError(
+                "length provided is not a power of 2".to_string(),
+            ));
+        }
+
+        let domain = Domain::new(length)?;
+        workspace.g1_projective.clear();
+        workspace
+            .g1_projective
+            .extend(self.g1[..length].iter().map(|&p| G1Projective::from(p)));
+        let points = &mut workspace.g1_projective;
+        self.config
+            .install_fft(length, || domain.ifft_in_place(points))?;
+
+        workspace.g1_lagrange.clear();
+        workspace
+            .g1_lagrange
+            .extend(G1Projective::normalize_batch(&workspace.g1_projective));
+        workspace.srs_tag = srs_tag;
+        Ok(())
     }
 
-    /// function to compute the kzg proof given the values.
-    pub fn compute_kzg_proof(
+    pub fn compute_kzg_proof_with_evaluation_polynomial(
         &self,
         polynomial: &Polynomial,
         index: u64,
         root_of_unities: &Vec<Fr>,
     ) -> Result<G1Affine, KzgError> {
-        if !self.params.completed_setup {
-            return Err(KzgError::GenericError(
-                "setup is not complete, run the data_setup functions".to_string(),
-            ));
-        }
-
         if polynomial.len() != root_of_unities.len() {
             return Err(KzgError::GenericError(
                 "inconsistent length between blob and root of unities".to_string(),
@@ -487,116 +2200,109 @@ impl Kzg {
         let value_fr = eval_fr[usized_index];
         let z_fr = root_of_unities[usized_index];
 
-        for i in 0..eval_fr.len() {
-            poly_shift.push(eval_fr[i] - value_fr);
+        for fr in eval_fr.iter() {
+            poly_shift.push(*fr - value_fr);
         }
 
         let mut denom_poly = Vec::<Fr>::with_capacity(root_of_unities.len());
-        for i in 0..eval_fr.len() {
-            denom_poly.push(root_of_unities[i] - z_fr);
+        for root in root_of_unities.iter().take(eval_fr.len()) {
+            denom_poly.push(*root - z_fr);
         }
 
         let mut quotient_poly = Vec::<Fr>::with_capacity(root_of_unities.len());
 
         for i in 0..root_of_unities.len() {
             if denom_poly[i].is_zero() {
-                quotient_poly.push(self.compute_quotient_eval_on_domain(
+                quotient_poly.push(Self::compute_quotient_eval_on_domain(
                     z_fr,
                     &eval_fr,
                     value_fr,
-                    &root_of_unities,
+                    root_of_unities,
                 ));
             } else {
                 quotient_poly.push(poly_shift[i].div(denom_poly[i]));
             }
         }
 
-        let g1_lagrange = self.g1_ifft(polynomial.len())?;
-
-        match G1Projective::msm(&g1_lagrange, &quotient_poly) {
-            Ok(res) => Ok(G1Affine::from(res)),
-            Err(err) => Err(KzgError::SerializationError(err.to_string())),
-        }
-    }
-
-    pub fn compute_kzg_proof_with_evaluation_polynomial(&self, polynomial: &Polynomial, index: u64, root_of_unities: &Vec<Fr>) -> Result<G1Affine, KzgError> {
-
-        if !self.params.completed_setup {
-            return Err(KzgError::GenericError("setup is not complete, run the data_setup functions".to_string()));
-        }
-
-        if polynomial.len() != root_of_unities.len() {
-            return Err(KzgError::GenericError("inconsistent length between blob and root of unities".to_string()));
-        }
-    
-        let eval_fr = polynomial.to_vec();
-        let mut poly_shift: Vec<Fr> = Vec::with_capacity(eval_fr.len());
-        let usized_index = if let Some(x) = index.to_usize() {
-            x
-        } else {
-            return Err(KzgError::SerializationError("index couldn't be converted to usize".to_string()))
-        };
-
-        let value_fr = eval_fr[usized_index];
-        let z_fr = root_of_unities[usized_index];
-    
-        for i in 0..eval_fr.len() {
-            poly_shift.push(eval_fr[i] - value_fr);
-        }
-    
-        let mut denom_poly = Vec::<Fr>::with_capacity(root_of_unities.len());
-        for i in 0..eval_fr.len() {
-            denom_poly.push(root_of_unities[i] - z_fr);
-        }
-    
-        let mut quotient_poly = Vec::<Fr>::with_capacity(root_of_unities.len());
-    
-        for i in 0..root_of_unities.len() {
-            if denom_poly[i].is_zero() {
-                quotient_poly.push(self.compute_quotient_eval_on_domain(z_fr, &eval_fr, value_fr, &root_of_unities));
-            } else {
-                quotient_poly.push(poly_shift[i].div(denom_poly[i]));
-            }
-        }
-        
-        let g1 = self.g1[..polynomial.len()].to_vec();
-
-        match G1Projective::msm(&g1, &quotient_poly) {
-            Ok(res) => Ok(G1Affine::from(res)),
-            Err(err) => Err(KzgError::SerializationError(err.to_string())),
-        }
+        self.g1_msm(
+            &self.g1[..polynomial.len()],
+            &quotient_poly,
+            KzgError::SerializationError,
+        )
     }
 
     /// refer to DA for more context
     fn compute_quotient_eval_on_domain(
-        &self,
         z_fr: Fr,
-        eval_fr: &Vec<Fr>,
+        eval_fr: &[Fr],
         value_fr: Fr,
-        roots_of_unities: &Vec<Fr>,
+        roots_of_unities: &[Fr],
     ) -> Fr {
         let mut quotient = Fr::zero();
-        let mut fi = Fr::zero();
-        let mut numerator: Fr = Fr::zero();
-        let mut denominator: Fr = Fr::zero();
-        let mut temp: Fr = Fr::zero();
 
-        for i in 0..roots_of_unities.len() {
-            let omega_i = roots_of_unities[i];
+        for (i, &omega_i) in roots_of_unities.iter().enumerate() {
             if omega_i == z_fr {
                 continue;
             }
-            fi = eval_fr[i] - value_fr;
-            numerator = fi.mul(omega_i);
-            denominator = z_fr - omega_i;
-            denominator = denominator * z_fr;
-            temp = numerator.div(denominator);
-            quotient = quotient + temp;
+            let fi = eval_fr[i] - value_fr;
+            let numerator = fi.mul(omega_i);
+            let mut denominator = z_fr - omega_i;
+            denominator *= z_fr;
+            let temp = numerator.div(denominator);
+            quotient += temp;
         }
         quotient
     }
 
+    /// returns the `index`-th of the `length` roots of unity, for a power of
+    /// 2 `length`
+    pub(crate) fn root_of_unity(length: usize, index: usize) -> Fr {
+        Domain::new(length)
+            .expect("lengths of roots of unity are powers of 2")
+            .element(index)
+    }
+
+    /// converts evaluations over the roots of unity into monomial coefficients
+    /// with a direct O(n^2) inverse DFT, used for small blobs where setting
+    /// up an FFT domain costs more than it saves
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(length = evals.len()))
+    )]
+    fn naive_ifft(evals: &[Fr], roots_of_unity: &[Fr]) -> Vec<Fr> {
+        let n = evals.len();
+        let n_inv = Fr::from(n as u64).inverse().unwrap();
+        (0..n)
+            .map(|j| {
+                let mut acc = Fr::zero();
+                for (i, eval) in evals.iter().enumerate() {
+                    acc += *eval * roots_of_unity[(n - (i * j) % n) % n];
+                }
+                acc * n_inv
+            })
+            .collect()
+    }
+
+    /// multi-scalar multiplication as a plain sum of scalar multiplications
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(size = bases.len()))
+    )]
+    fn naive_msm(bases: &[G1Affine], scalars: &[Fr]) -> G1Affine {
+        bases
+            .iter()
+            .zip(scalars.iter())
+            .fold(G1Projective::zero(), |acc, (base, scalar)| {
+                acc + glv::mul(base, scalar)
+            })
+            .into_affine()
+    }
+
     /// function to compute the inverse FFT
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(length = length))
+    )]
     pub fn g1_ifft(&self, length: usize) -> Result<Vec<G1Affine>, KzgError> {
         // is not power of 2
         if !length.is_power_of_two() {
@@ -605,19 +2311,213 @@ impl Kzg {
             ));
         }
 
-        let domain = GeneralEvaluationDomain::<Fr>::new(length)
-            .expect("Failed to construct domain for IFFT");
+        let domain = Domain::new(length)?;
         let points_projective: Vec<G1Projective> = self.g1[..length]
             .iter()
             .map(|&p| G1Projective::from(p))
             .collect();
 
-        // Perform the IFFT
-        let ifft_result = domain.ifft(&points_projective);
-        let ifft_result_affine: Vec<_> = ifft_result.iter().map(|p| p.into_affine()).collect();
-        Ok(ifft_result_affine)
+        // Perform the IFFT on the configured thread pool
+        self.config.install_fft(length, || {
+            let ifft_result = domain.ifft(&points_projective);
+            G1Projective::normalize_batch(&ifft_result)
+        })
+    }
+
+    /// Precomputes the G1 Lagrange bases for a domain of `length`, so that
+    /// `commit` over evaluations of that length skips the G1 IFFT.
+    pub fn precompute_lagrange_srs(&mut self, length: usize) -> Result<(), KzgError> {
+        if length > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+        self.g1_lagrange = Arc::new(self.g1_ifft(length)?);
+        Ok(())
+    }
+
+    /// Returns the domain size of the precomputed Lagrange SRS, or 0 if there
+    /// is none.
+    pub fn get_lagrange_srs_length(&self) -> usize {
+        self.g1_lagrange.len()
+    }
+
+    /// Writes the precomputed Lagrange SRS to `path`: the compressed [tau]G1
+    /// point of the SRS it was computed from, followed by the compressed
+    /// Lagrange bases in order.
+    pub fn write_lagrange_srs(&self, path: &str) -> Result<(), KzgError> {
+        if self.g1_lagrange.is_empty() {
+            return Err(KzgError::GenericError(
+                "no Lagrange SRS has been precomputed".to_string(),
+            ));
+        }
+        let mut bytes =
+            Vec::with_capacity((self.g1_lagrange.len() + 1) * SIZE_OF_G1_AFFINE_COMPRESSED);
+        bytes.extend_from_slice(&g1_to_bytes(&self.lagrange_srs_tag()));
+        for point in self.g1_lagrange.iter() {
+            bytes.extend_from_slice(&g1_to_bytes(point));
+        }
+        std::fs::write(path, bytes).map_err(|e| KzgError::GenericError(e.to_string()))
+    }
+
+    /// Loads a Lagrange SRS written by `write_lagrange_srs`, so the G1 IFFT of
+    /// the setup is paid once across processes. Fails if the file was written
+    /// for a different SRS.
+    pub fn load_lagrange_srs(&mut self, path: &str) -> Result<(), KzgError> {
+        let bytes = std::fs::read(path).map_err(|e| KzgError::GenericError(e.to_string()))?;
+        if bytes.len() % SIZE_OF_G1_AFFINE_COMPRESSED != 0 || bytes.is_empty() {
+            return Err(KzgError::SerializationError(
+                "Lagrange SRS file is not a whole number of G1 points".to_string(),
+            ));
+        }
+        let (tag, points) = bytes.split_at(SIZE_OF_G1_AFFINE_COMPRESSED);
+        if g1_from_bytes(tag)? != self.lagrange_srs_tag() {
+            return Err(KzgError::GenericError(
+                "Lagrange SRS was computed from a different SRS".to_string(),
+            ));
+        }
+        let length = points.len() / SIZE_OF_G1_AFFINE_COMPRESSED;
+        if !length.is_power_of_two() || length > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "Lagrange SRS length is not a power of 2 within the SRS".to_string(),
+            ));
+        }
+        let g1_lagrange = self.config.install(|| {
+            points
+                .par_chunks_exact(SIZE_OF_G1_AFFINE_COMPRESSED)
+                .map(g1_from_bytes)
+                .collect::<Result<_, _>>()
+        })??;
+        self.g1_lagrange = Arc::new(g1_lagrange);
+        Ok(())
+    }
+
+    /// Drops the precomputed Lagrange SRS.
+    pub fn clear_lagrange_srs(&mut self) {
+        self.g1_lagrange = Arc::default();
+    }
+
+    fn lagrange_srs_tag(&self) -> G1Affine {
+        self.g1.get(1).copied().unwrap_or_default()
+    }
+
+    /// runs an MSM, on the calling thread below the configured sequential
+    /// cutoff and on the configured thread pool otherwise, mapping a length
+    /// mismatch to the error built by `error`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(size = bases.len()))
+    )]
+    fn g1_msm(
+        &self,
+        bases: &[G1Affine],
+        scalars: &[Fr],
+        error: fn(String) -> KzgError,
+    ) -> Result<G1Affine, KzgError> {
+        let result = match self.config.msm_chunking(bases.len()) {
+            None => glv::msm(bases, scalars, None),
+            Some(min_chunk_len) => self
+                .config
+                .install(|| glv::msm(bases, scalars, Some(min_chunk_len)))?,
+        };
+        result
+            .map(|res| res.into_affine())
+            .map_err(|err| error(err.to_string()))
+    }
+
+    /// returns the G1 Lagrange basis point for `index` over a domain of
+    /// `length`, i.e. a single entry of `g1_ifft(length)`, computed with one
+    /// MSM instead of a full IFFT
+    pub fn lagrange_basis_point(&self, length: usize, index: usize) -> Result<G1Affine, KzgError> {
+        if index >= length {
+            return Err(KzgError::GenericError(
+                "index is out of range of the polynomial".to_string(),
+            ));
+        }
+        self.lagrange_combination(length, &[(index, Fr::one())])
+    }
+
+    /// Returns `old_commitment` adjusted for the evaluations that changed, each
+    /// given as `(index, old_value, new_value)`.
+    ///
+    /// The deltas are folded into one set of scalars over the monomial SRS, so
+    /// any number of changes costs a single MSM over `length` points.
+    pub fn update_commitment(
+        &self,
+        old_commitment: &KzgCommitment,
+        changes: &[(u64, Fr, Fr)],
+    ) -> Result<KzgCommitment, KzgError> {
+        let length = old_commitment.get_length();
+        let terms = changes
+            .iter()
+            .filter(|(_, old_value, new_value)| old_value != new_value)
+            .map(|&(index, old_value, new_value)| {
+                usize::try_from(index)
+                    .ok()
+                    .filter(|&i| i < length)
+                    .map(|i| (i, new_value - old_value))
+                    .ok_or_else(|| {
+                        KzgError::GenericError(
+                            "index is out of range of the polynomial".to_string(),
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if terms.is_empty() {
+            return Ok(*old_commitment);
+        }
+
+        let delta = self.lagrange_combination(length, &terms)?;
+        Ok(KzgCommitment::new(
+            (old_commitment.get_point() + delta).into_affine(),
+            length,
+        ))
+    }
+
+    /// returns `sum_k coefficient_k * L_index_k(tau)` over a domain of
+    /// `length`, with a single MSM over the monomial SRS
+    fn lagrange_combination(
+        &self,
+        length: usize,
+        terms: &[(usize, Fr)],
+    ) -> Result<G1Affine, KzgError> {
+        if !length.is_power_of_two() {
+            return Err(KzgError::FftError(
+                "length provided is not a power of 2".to_string(),
+            ));
+        }
+        if length > self.g1.len() {
+            return Err(KzgError::SerializationError(
+                "polynomial length is not correct".to_string(),
+            ));
+        }
+
+        // L_index(tau) = 1/n * sum_j w^(-index * j) * tau^j
+        let domain = Domain::new(length)?;
+        let roots_of_unity = domain.get_roots_of_unity();
+        let n_inv = domain.get_size_inv();
+        let terms: Vec<(usize, Fr)> = terms
+            .iter()
+            .map(|&(index, coefficient)| (index, coefficient * n_inv))
+            .collect();
+        let scalars: Vec<Fr> = (0..length)
+            .map(|j| {
+                terms.iter().fold(Fr::zero(), |acc, &(index, coefficient)| {
+                    acc + roots_of_unity[(length - (index * j) % length) % length] * coefficient
+                })
+            })
+            .collect();
+
+        if length <= self.config.get_small_blob_threshold() {
+            return Ok(Self::naive_msm(&self.g1[..length], &scalars));
+        }
+
+        self.g1_msm(&self.g1[..length], &scalars, KzgError::CommitError)
     }
 
+    /// Checks `proof` opens `commitment` to `value_fr` at `z_fr`, with the
+    /// pairing check `e(C - [value]G1, G2) = e(proof, [tau - z]G2)`. Only
+    /// `[tau]G2` is needed, so this works after `release_g2_points`.
     pub fn verify_kzg_proof(
         &self,
         commitment: G1Affine,
@@ -625,32 +2525,271 @@ impl Kzg {
         value_fr: Fr,
         z_fr: Fr,
     ) -> bool {
-        let g2_tau = if self.g2.len() > 28 {
-            self.g2.get(1).unwrap().clone()
+        self.verifier()
+            .verify_kzg_proof(commitment, proof, value_fr, z_fr)
+    }
+
+    /// Checks many independent openings with one pairing product; see
+    /// `KzgVerifier::verify_proofs_batched`.
+    pub fn verify_proofs_batched(&self, items: &[OpeningClaim]) -> bool {
+        self.verifier().verify_proofs_batched(items)
+    }
+
+    /// Returns a verifier holding `[tau]G2` and the configured challenge
+    /// hash, which checks the same proofs without the G1 points.
+    pub fn verifier(&self) -> KzgVerifier {
+        KzgVerifier::new(self.g2_tau()).with_challenge_hash(self.config.get_challenge_hash())
+    }
+
+    /// returns the points `verify_kzg_proof` checks `e(a1, a2) = e(b1, b2)`
+    /// on: `C - [value]G1`, `G2`, the proof and `[tau]G2 - [z]G2`
+    pub(crate) fn kzg_proof_pairing_inputs(
+        &self,
+        commitment: G1Affine,
+        proof: G1Affine,
+        value_fr: Fr,
+        z_fr: Fr,
+    ) -> (G1Affine, G2Affine, G1Affine, G2Affine) {
+        self.verifier()
+            .kzg_proof_pairing_inputs(commitment, proof, value_fr, z_fr)
+    }
+
+    /// Commits to `polynomial` in G2, as `[p(tau)]G2`. Together with
+    /// `compute_length_proof`, this lets a verifier check the polynomial's
+    /// degree is below a length. Needs the full G2 table.
+    pub fn compute_length_commitment(&self, polynomial: &Polynomial) -> Result<G2Affine, KzgError> {
+        let coefficients = Self::monomial_coefficients(polynomial)?;
+        let bases = self
+            .full_g2_table()?
+            .get(..coefficients.len())
+            .ok_or_else(|| {
+                KzgError::SerializationError("polynomial length is not correct".to_string())
+            })?;
+        Self::g2_msm(bases, &coefficients)
+    }
+
+    /// The G2 commitment of an EigenDA blob header, see
+    /// `compute_length_commitment`.
+    pub fn commit_g2(&self, polynomial: &Polynomial) -> Result<G2Affine, KzgError> {
+        self.compute_length_commitment(polynomial)
+    }
+
+    /// Proves the degree of `polynomial` is below `length`, as
+    /// `[tau^(srs_order - length) p(tau)]G2`, which only exists when the
+    /// shifted polynomial fits in the SRS. EigenDA blob headers prove the
+    /// polynomial's own length, `polynomial.len()`. Fails if the degree is not
+    /// below `length`. Needs the full G2 table up to the SRS order.
+    pub fn compute_length_proof(
+        &self,
+        polynomial: &Polynomial,
+        length: usize,
+    ) -> Result<G2Affine, KzgError> {
+        let shift = self.length_shift(length)?;
+        let coefficients = Self::monomial_coefficients(polynomial)?;
+        if coefficients.iter().skip(length).any(|c| !c.is_zero()) {
+            return Err(KzgError::GenericError(
+                "polynomial degree is not below the length".to_string(),
+            ));
+        }
+        let coefficients = &coefficients[..coefficients.len().min(length)];
+        let bases = self
+            .full_g2_table()?
+            .get(shift..shift + coefficients.len())
+            .ok_or_else(|| {
+                KzgError::SerializationError("G2 table does not reach the SRS order".to_string())
+            })?;
+        Self::g2_msm(bases, coefficients)
+    }
+
+    /// Checks `length_proof` shows the polynomial under `length_commitment`
+    /// has fewer than `length` coefficients, i.e.
+    /// `e([tau^(srs_order - length)]G1, length_commitment) = e(G1, length_proof)`.
+    pub fn verify_length_proof(
+        &self,
+        length_commitment: G2Affine,
+        length_proof: G2Affine,
+        length: usize,
+    ) -> Result<bool, KzgError> {
+        Ok(Self::pairings_verify(
+            self.length_challenge(length)?,
+            length_commitment,
+            G1Affine::generator(),
+            length_proof,
+        ))
+    }
+
+    /// Checks the length proof of an EigenDA blob header, see
+    /// `verify_length_proof`.
+    pub fn verify_blob_length(
+        &self,
+        commitment_g2: G2Affine,
+        length_proof: G2Affine,
+        length: usize,
+    ) -> Result<bool, KzgError> {
+        self.verify_length_proof(commitment_g2, length_proof, length)
+    }
+
+    /// Proves the polynomial has degree below `bound` against its G1
+    /// commitment alone, as `[tau^(srs_order - bound) p(tau)]G1`, which only
+    /// exists when the shifted polynomial fits in the SRS. Unlike
+    /// `compute_length_proof`, it needs no G2 commitment. Fails if the degree
+    /// is not below `bound` or the G1 points loaded do not reach the SRS
+    /// order.
+    pub fn prove_degree_bound(
+        &self,
+        polynomial: &Polynomial,
+        bound: usize,
+    ) -> Result<G1Affine, KzgError> {
+        let shift = self.length_shift(bound)?;
+        let coefficients = Self::monomial_coefficients(polynomial)?;
+        if coefficients.iter().skip(bound).any(|c| !c.is_zero()) {
+            return Err(KzgError::GenericError(
+                "polynomial degree is not below the bound".to_string(),
+            ));
+        }
+        let coefficients = &coefficients[..coefficients.len().min(bound)];
+        let bases = self
+            .g1
+            .get(shift..shift + coefficients.len())
+            .ok_or_else(|| {
+                KzgError::SerializationError(
+                    "G1 points loaded do not reach the SRS order".to_string(),
+                )
+            })?;
+        self.g1_msm(bases, coefficients, KzgError::CommitError)
+    }
+
+    /// Checks `proof` shows the polynomial under `commitment` has degree
+    /// below `bound`, i.e. `e(C, [tau^(srs_order - bound)]G2) = e(proof, G2)`.
+    /// Needs the full G2 table.
+    pub fn verify_degree_bound(
+        &self,
+        commitment: G1Affine,
+        proof: G1Affine,
+        bound: usize,
+    ) -> Result<bool, KzgError> {
+        let shift = self.length_shift(bound)?;
+        let shifted_g2 = self.full_g2_table()?.get(shift).copied().ok_or_else(|| {
+            KzgError::SerializationError("G2 table does not reach the SRS order".to_string())
+        })?;
+        Ok(Self::pairings_verify(
+            commitment,
+            shifted_g2,
+            proof,
+            G2Affine::generator(),
+        ))
+    }
+
+    /// returns [tau^(srs_order - length)]G1, the point a length proof for
+    /// `length` is checked against
+    pub(crate) fn length_challenge(&self, length: usize) -> Result<G1Affine, KzgError> {
+        let shift = self.length_shift(length)?;
+        self.g1.get(shift).copied().ok_or_else(|| {
+            KzgError::SerializationError("G1 points loaded do not reach the SRS order".to_string())
+        })
+    }
+
+    /// returns the loaded G2 table if it holds consecutive powers of tau
+    fn full_g2_table(&self) -> Result<&[G2Affine], KzgError> {
+        match self.g2_source {
+            G2Source::Points { .. } | G2Source::Memory { power_of_2: false }
+                if !self.g2_released =>
+            {
+                Ok(&self.g2)
+            },
+            _ => Err(KzgError::GenericError(
+                "G2 commitments need the full G2 table".to_string(),
+            )),
+        }
+    }
+
+    /// returns srs_order - length, the power of tau a length proof shifts by
+    fn length_shift(&self, length: usize) -> Result<usize, KzgError> {
+        if length == 0 || length as u64 > self.srs_order {
+            return Err(KzgError::GenericError(
+                "length must be positive and at most the SRS order".to_string(),
+            ));
+        }
+        Ok((self.srs_order - length as u64) as usize)
+    }
+
+    /// converts the evaluations of `polynomial` to monomial coefficients
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(length = polynomial.len()))
+    )]
+    fn monomial_coefficients(polynomial: &Polynomial) -> Result<Vec<Fr>, KzgError> {
+        Ok(Domain::new(polynomial.len())?.ifft(polynomial.as_slice()))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(size = bases.len()))
+    )]
+    fn g2_msm(bases: &[G2Affine], scalars: &[Fr]) -> Result<G2Affine, KzgError> {
+        G2Projective::msm(bases, scalars)
+            .map(|res| res.into_affine())
+            .map_err(|err| KzgError::CommitError(err.to_string()))
+    }
+
+    /// returns [tau]G2, the second point of a full G2 table or the first
+    /// point of a power of 2 table
+    fn g2_tau(&self) -> G2Affine {
+        if self.g2_is_consecutive() {
+            *self.g2.get(1).unwrap()
         } else {
-            self.g2.get(0).unwrap().clone()
-        };
-        let value_g1 = (G1Affine::generator() * value_fr).into_affine();
-        let commit_minus_value = (commitment - value_g1).into_affine();
-        let z_g2 = (G2Affine::generator() * z_fr).into_affine();
-        let x_minus_z = (g2_tau - z_g2).into_affine();
-        Self::pairings_verify(commit_minus_value, G2Affine::generator(), proof, x_minus_z)
+            *self.g2.get(0).unwrap()
+        }
+    }
+
+    /// checks if the loaded G2 table holds consecutive powers of tau rather
+    /// than a power of 2 table
+    fn g2_is_consecutive(&self) -> bool {
+        match self.g2_source {
+            G2Source::Memory { power_of_2 } => !power_of_2 && !self.g2_released,
+            _ => self.g2.len() > 28,
+        }
+    }
+
+    /// returns [tau^(2^log2)]G2 from a full G2 table or a power of 2 table, or
+    /// None if the loaded table doesn't hold it
+    pub(crate) fn g2_tau_power_of_2(&self, log2: usize) -> Option<G2Affine> {
+        if self.g2_is_consecutive() {
+            1usize
+                .checked_shl(log2 as u32)
+                .and_then(|i| self.g2.get(i))
+                .copied()
+        } else {
+            self.g2.get(log2).copied()
+        }
     }
 
-    fn pairings_verify(a1: G1Affine, a2: G2Affine, b1: G1Affine, b2: G2Affine) -> bool {
-        let neg_b1 = -b1;
-        let p = [a1, neg_b1];
-        let q = [a2, b2];
-        let result = Bn254::multi_pairing(p, q);
-        result.is_zero()
+    /// checks e(a, G2) = e(b, [tau]G2)
+    pub(crate) fn verify_pairing(&self, a: G1Affine, b: G1Affine) -> bool {
+        self.verifier().verify_pairing(a, b)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub(crate) fn pairings_verify(a1: G1Affine, a2: G2Affine, b1: G1Affine, b2: G2Affine) -> bool {
+        Self::multi_pairing(&[a1, -b1], &[a2, b2])
+    }
+
+    /// checks the product of e(g1[i], g2[i]) is one, sharing the final
+    /// exponentiation between all the pairings
+    pub(crate) fn multi_pairing(g1: &[G1Affine], g2: &[G2Affine]) -> bool {
+        Bn254::multi_pairing(g1.iter().copied(), g2.iter().copied()).is_zero()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ark_ec::VariableBaseMSM;
+    use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+    use ark_std::str::FromStr;
     use lazy_static::lazy_static;
     use std::env;
+    use std::io::BufReader;
 
     // Function to determine the setup based on an environment variable
     fn determine_setup() -> Kzg {
@@ -784,7 +2923,7 @@ mod tests {
             let x = Fq2::new(x_c0, x_c1);
             let y = Fq2::new(y_c0, y_c1);
             let point = G2Affine::new_unchecked(x, y);
-            assert_eq!(is_on_curve_g2(&G2Projective::from(point)), true);
+            assert!(is_on_curve_g2(&G2Projective::from(point)));
             assert_eq!(point, kzg_g2_points[i]);
         }
     }
@@ -864,24 +3003,19 @@ mod tests {
                 .unwrap();
             let value_fr = input_poly.get_at_index(index).unwrap();
             let z_fr = kzg.get_nth_root_of_unity(index).unwrap();
-            let pairing_result =
-                kzg.verify_kzg_proof(commitment, proof, value_fr.clone(), z_fr.clone());
-            assert_eq!(pairing_result, true);
+            let pairing_result = kzg.verify_kzg_proof(commitment, proof, *value_fr, *z_fr);
+            assert!(pairing_result);
 
             // take random index, not the same index and check
-            assert_eq!(
-                kzg.verify_kzg_proof(
-                    commitment,
-                    proof,
-                    value_fr.clone(),
-                    kzg.get_nth_root_of_unity(
-                        (index + 1) % input_poly.get_length_of_padded_blob_as_fr_vector()
-                    )
-                    .unwrap()
-                    .clone()
-                ),
-                false
-            )
+            assert!(!kzg.verify_kzg_proof(
+                commitment,
+                proof,
+                *value_fr,
+                *kzg.get_nth_root_of_unity(
+                    (index + 1) % input_poly.get_length_of_padded_blob_as_fr_vector()
+                )
+                .unwrap()
+            ))
         })
     }
 
@@ -911,20 +3045,16 @@ mod tests {
             let proof = kzg
                 .compute_kzg_proof_with_roots_of_unity(&input_poly, index.try_into().unwrap())
                 .unwrap();
-            let value_fr = input_poly.get_at_index(index).unwrap();
-            let z_fr = kzg.get_nth_root_of_unity(index).unwrap();
-            let pairing_result =
-                kzg.verify_kzg_proof(commitment, proof, value_fr.clone(), z_fr.clone());
-            assert_eq!(pairing_result, true);
-            assert_eq!(
-                kzg.verify_kzg_proof(
-                    commitment,
-                    proof,
-                    value_fr.clone(),
-                    kzg.get_nth_root_of_unity(rand_index).unwrap().clone()
-                ),
-                false
-            )
+            let value_fr = input_poly.get_at_index(index).unwrap();
+            let z_fr = kzg.get_nth_root_of_unity(index).unwrap();
+            let pairing_result = kzg.verify_kzg_proof(commitment, proof, *value_fr, *z_fr);
+            assert!(pairing_result);
+            assert!(!kzg.verify_kzg_proof(
+                commitment,
+                proof,
+                *value_fr,
+                *kzg.get_nth_root_of_unity(rand_index).unwrap()
+            ))
         }
     }
 
@@ -1069,55 +3199,1293 @@ mod tests {
             "17229388088320038940941618493830445303168092387362094847263546333820121606543",
         ];
 
-        let roots_of_unities: Vec<Fr> = str_vec_to_fr_vec(roots_of_unities_raw).unwrap();
-        let padded_input_fr_elements: Vec<Fr> =
-            str_vec_to_fr_vec(padded_input_fr_elements_raw).unwrap();
+        let roots_of_unities: Vec<Fr> = str_vec_to_fr_vec(roots_of_unities_raw).unwrap();
+        let padded_input_fr_elements: Vec<Fr> =
+            str_vec_to_fr_vec(padded_input_fr_elements_raw).unwrap();
+
+        let file2 = File::open("src/test-files/kzg.proof.eq.input").unwrap();
+        let reader2 = BufReader::new(file2);
+
+        for line in reader2.lines() {
+            let line = line.unwrap();
+            let trimmed_line = line.trim_end(); // Trim whitespace from the end
+            let the_strings_str: Vec<&str> = trimmed_line.split(',').collect(); // Split the line on commas
+            let index = u64::from_str(the_strings_str[0]).unwrap();
+            let hard_coded_x = Fq::from_str(the_strings_str[1]).expect("should be fine");
+            let hard_coded_y = Fq::from_str(the_strings_str[2]).expect("should be fine");
+            let gnark_proof = G1Affine::new(hard_coded_x, hard_coded_y);
+            let poly = Polynomial::new(&padded_input_fr_elements, 30).unwrap();
+            kzg.data_setup_custom(4, poly.len().try_into().unwrap())
+                .unwrap();
+            let result = kzg
+                .compute_kzg_proof(&poly, index, &roots_of_unities)
+                .unwrap();
+            assert_eq!(gnark_proof, result)
+        }
+    }
+
+    #[test]
+    fn test_g1_ifft() {
+        use ark_bn254::Fq;
+        use std::io::BufRead;
+
+        let file = File::open("src/test-files/lagrangeG1SRS.txt").unwrap();
+        let reader = BufReader::new(file);
+
+        let kzg_g1_points = KZG_3000.g1_ifft(64).unwrap();
+
+        // Iterate over each line in the file
+        for (i, line_result) in reader.lines().enumerate() {
+            let mut line = line_result.unwrap(); // Retrieve the line, handling potential I/O errors
+            line = line.trim_end().to_string();
+
+            // Split the line at each comma and process the parts
+            let parts: Vec<&str> = line.split(',').collect();
+
+            let x = Fq::from_str(parts[0]).expect("should be fine");
+            let y = Fq::from_str(parts[1]).expect("should be fine");
+
+            let point = G1Affine::new_unchecked(x, y);
+            assert_eq!(point, kzg_g1_points[i], "failed on {i}");
+        }
+    }
+
+    #[test]
+    fn test_lagrange_srs() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let input = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let input_poly = input.to_polynomial().unwrap();
+        let expected = KZG_3000.commit(&input_poly).unwrap();
+
+        let mut kzg = KZG_3000.clone();
+        assert!(kzg.write_lagrange_srs("unused").is_err());
+        kzg.precompute_lagrange_srs(input_poly.len()).unwrap();
+        assert_eq!(kzg.get_lagrange_srs_length(), input_poly.len());
+        assert_eq!(kzg.commit(&input_poly).unwrap(), expected);
+
+        let path = env::temp_dir().join(format!("lagrange-srs-{}", std::process::id()));
+        let path = path.to_str().unwrap();
+        kzg.write_lagrange_srs(path).unwrap();
+
+        let mut loaded = KZG_3000.clone();
+        loaded.load_lagrange_srs(path).unwrap();
+        assert_eq!(loaded.get_lagrange_srs_length(), input_poly.len());
+        assert_eq!(loaded.commit(&input_poly).unwrap(), expected);
+        loaded.clear_lagrange_srs();
+        assert_eq!(loaded.get_lagrange_srs_length(), 0);
+
+        // a Lagrange SRS computed from another SRS is rejected
+        let mut other = KZG_3000.clone();
+        Arc::make_mut(&mut other.g1).swap(1, 2);
+        assert!(other.load_lagrange_srs(path).is_err());
+        std::fs::remove_file(path).unwrap();
+
+        assert!(kzg.precompute_lagrange_srs(3).is_err());
+        assert!(kzg.precompute_lagrange_srs(4096).is_err());
+    }
+
+    #[test]
+    fn test_small_blob_fast_path() {
+        use crate::consts::SMALL_BLOB_FAST_PATH_THRESHOLD;
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let mut kzg = KZG_3000.clone();
+
+        for length in [1, 2, 16, SMALL_BLOB_FAST_PATH_THRESHOLD] {
+            let evals: Vec<Fr> = (0..length).map(|_| Fr::from(rng.gen::<u64>())).collect();
+            let poly = Polynomial::new(&evals, length * BYTES_PER_FIELD_ELEMENT).unwrap();
+
+            // the fast path must agree with the G1 IFFT path
+            let commitment = kzg.commit(&poly).unwrap();
+            let expected = G1Projective::msm(&kzg.g1_ifft(length).unwrap(), &evals)
+                .unwrap()
+                .into_affine();
+            assert_eq!(commitment, expected, "failed on {length}");
+
+            kzg.calculate_roots_of_unity((length * BYTES_PER_FIELD_ELEMENT) as u64)
+                .unwrap();
+            let index = rng.gen_range(0..length);
+            let proof = kzg
+                .compute_kzg_proof_with_roots_of_unity(&poly, index as u64)
+                .unwrap();
+            let z_fr = *kzg.get_nth_root_of_unity(index).unwrap();
+            assert!(kzg.verify_kzg_proof(commitment, proof, evals[index], z_fr));
+        }
+    }
+
+    #[test]
+    fn test_commit_with_custom_thread_pool() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+        use std::sync::Arc;
+
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let mut kzg = KZG_3000.clone();
+        kzg.set_config(KzgConfig::new().with_thread_pool(pool));
+        assert_eq!(kzg.get_config().get_num_threads(), 2);
+
+        let input = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES.repeat(4));
+        let input_poly = input.to_polynomial().unwrap();
+        assert_eq!(
+            kzg.commit(&input_poly).unwrap(),
+            KZG_3000.commit(&input_poly).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_work_split_config_does_not_change_results() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+        let mut kzg = KZG_3000.clone();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = blob.to_polynomial().unwrap();
+        kzg.data_setup_custom(1, blob.len().try_into().unwrap())
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let proof = kzg
+            .compute_kzg_proof_with_roots_of_unity(&polynomial, 3)
+            .unwrap();
+
+        let configs = [
+            // everything on the calling thread, through the small-blob path
+            KzgConfig::new()
+                .with_small_blob_threshold(usize::MAX)
+                .with_msm_sequential_cutoff(usize::MAX)
+                .with_fft_sequential_cutoff(usize::MAX),
+            // everything split as finely as possible
+            KzgConfig::new()
+                .with_num_threads(4)
+                .with_small_blob_threshold(0)
+                .with_msm_sequential_cutoff(0)
+                .with_msm_min_chunk_len(1)
+                .with_fft_sequential_cutoff(0),
+        ];
+        for config in configs {
+            kzg.set_config(config);
+            assert_eq!(kzg.commit(&polynomial).unwrap(), commitment);
+            assert_eq!(
+                kzg.compute_kzg_proof_with_roots_of_unity(&polynomial, 3)
+                    .unwrap(),
+                proof
+            );
+        }
+    }
+
+    #[test]
+    fn test_workspace_matches_allocating_path() {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let mut kzg = KZG_3000.clone();
+        let mut workspace = KzgWorkspace::new();
+
+        // alternate sizes so the cached bases get invalidated and rebuilt
+        for blob_length in [2000, 500, 2000, 2000] {
+            let random_blob: Vec<u8> = (0..blob_length)
+                .map(|_| rng.gen_range(32..=126) as u8)
+                .collect();
+            let input = Blob::from_bytes_and_pad(&random_blob);
+            let input_poly = input.to_polynomial().unwrap();
+            kzg.data_setup_custom(1, input.len().try_into().unwrap())
+                .unwrap();
+
+            let commitment = kzg
+                .commit_with_workspace(&input_poly, &mut workspace)
+                .unwrap();
+            assert_eq!(commitment, kzg.commit(&input_poly).unwrap());
+            assert_eq!(workspace.cached_domain_size(), input_poly.len());
+
+            let index =
+                rng.gen_range(0..input_poly.get_length_of_padded_blob_as_fr_vector()) as u64;
+            let proof = kzg
+                .compute_kzg_proof_with_workspace(
+                    &input_poly,
+                    index,
+                    kzg.get_expanded_roots_of_unity(),
+                    &mut workspace,
+                )
+                .unwrap();
+            assert_eq!(
+                proof,
+                kzg.compute_kzg_proof_with_roots_of_unity(&input_poly, index)
+                    .unwrap()
+            );
+
+            let out_of_range = input_poly.len() as u64;
+            assert!(kzg
+                .compute_kzg_proof_with_workspace(
+                    &input_poly,
+                    out_of_range,
+                    kzg.get_expanded_roots_of_unity(),
+                    &mut workspace,
+                )
+                .is_err());
+        }
+
+        workspace.clear();
+        assert_eq!(workspace.cached_domain_size(), 0);
+    }
+
+    #[test]
+    fn test_release_and_reload_g2_points() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        for mut kzg in [KZG_3000.clone(), KZG_INSTANCE.clone()] {
+            let g2_points = kzg.get_g2_points();
+            let input = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+            let input_poly = input.to_polynomial().unwrap();
+            kzg.data_setup_custom(1, input.len().try_into().unwrap())
+                .unwrap();
+
+            kzg.release_g2_points();
+            assert!(!kzg.is_g2_loaded());
+            assert_eq!(kzg.get_g2_points().len(), 1);
+
+            // proving and verifying keep working without the G2 table
+            let commitment = kzg.commit(&input_poly).unwrap();
+            let proof = kzg
+                .compute_kzg_proof_with_roots_of_unity(&input_poly, 3)
+                .unwrap();
+            let value_fr = *input_poly.get_at_index(3).unwrap();
+            let z_fr = *kzg.get_nth_root_of_unity(3).unwrap();
+            assert!(kzg.verify_kzg_proof(commitment, proof, value_fr, z_fr));
+
+            kzg.reload_g2_points().unwrap();
+            assert!(kzg.is_g2_loaded());
+            assert_eq!(kzg.get_g2_points(), g2_points);
+            assert!(kzg.verify_kzg_proof(commitment, proof, value_fr, z_fr));
+        }
+    }
+
+    #[test]
+    fn test_setup_from_reader() {
+        let g1_bytes = std::fs::read("src/test-files/g1.point").unwrap();
+        let g2_bytes = std::fs::read("src/test-files/g2.point").unwrap();
+        let power_of_2_bytes = std::fs::read("src/test-files/g2.point.powerOf2").unwrap();
+
+        let kzg = Kzg::setup_from_bytes(&g1_bytes, &g2_bytes, false, 3000, 3000).unwrap();
+        assert_eq!(kzg.get_g1_points(), KZG_3000.get_g1_points());
+        assert_eq!(kzg.get_g2_points(), KZG_3000.get_g2_points());
+        let file = File::open("src/test-files/g1.point").unwrap();
+        let from_reader =
+            Kzg::setup_from_reader(file, &power_of_2_bytes[..], true, 3000, 3000).unwrap();
+        assert_eq!(from_reader.get_g1_points(), KZG_3000.get_g1_points());
+        let from_files = Kzg::setup(
+            "src/test-files/g1.point",
+            "",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        assert_eq!(from_reader.get_g2_points(), from_files.get_g2_points());
+        assert_eq!(from_reader.verifier(), from_files.verifier());
+
+        // short input loads fewer points, ignoring a partial trailing point
+        let short = Kzg::setup_from_bytes(&g1_bytes[..100], &g2_bytes, false, 3000, 3000).unwrap();
+        assert_eq!(short.get_g1_points(), KZG_3000.get_g1_points()[..3]);
+
+        let mut released = kzg;
+        released.release_g2_points();
+        assert!(released.reload_g2_points().is_err());
+        assert!(Kzg::setup_from_bytes(&g1_bytes, &g2_bytes, false, 3000, 3001).is_err());
+        assert!(Kzg::setup_from_bytes(&g1_bytes, &g2_bytes[..64], false, 3000, 3000).is_err());
+        assert!(Kzg::setup_from_bytes(&g1_bytes, &[], true, 3000, 3000).is_err());
+    }
+
+    #[test]
+    fn test_compute_kzg_proof_at_point() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+        use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial as _};
+
+        let kzg = KZG_3000.clone();
+        for payload in [&GETTYSBURG_ADDRESS_BYTES[..200], GETTYSBURG_ADDRESS_BYTES] {
+            let polynomial = Blob::from_bytes_and_pad(payload).to_polynomial().unwrap();
+            let commitment = kzg.commit(&polynomial).unwrap();
+            let domain = GeneralEvaluationDomain::<Fr>::new(polynomial.len()).unwrap();
+            let coefficients =
+                DensePolynomial::from_coefficients_vec(domain.ifft(polynomial.as_slice()));
+
+            for z in [Fr::from(12345u64), domain.element(3)] {
+                let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap();
+                assert_eq!(value, coefficients.evaluate(&z));
+                assert_eq!(
+                    Kzg::evaluate_polynomial_in_evaluation_form(&polynomial, z),
+                    value
+                );
+                assert!(kzg.verify_kzg_proof(commitment, proof, value, z));
+                assert!(!kzg.verify_kzg_proof(commitment, proof, value + Fr::one(), z));
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_in_evaluation_form() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+        use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial as _};
+
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = blob.to_polynomial().unwrap();
+        let domain = GeneralEvaluationDomain::<Fr>::new(polynomial.len()).unwrap();
+        let coefficients =
+            DensePolynomial::from_coefficients_vec(domain.ifft(polynomial.as_slice()));
+        for z in [Fr::from(12345u64), domain.element(5), Fr::zero()] {
+            let value = coefficients.evaluate(&z);
+            assert_eq!(
+                helpers::evaluate_polynomial_in_evaluation_form(polynomial.as_slice(), z),
+                Some(value)
+            );
+            assert_eq!(
+                Kzg::evaluate_blob_in_evaluation_form(&blob, z).unwrap(),
+                value
+            );
+        }
+        assert_eq!(
+            helpers::evaluate_polynomial_in_evaluation_form(&polynomial.as_slice()[..3], Fr::one()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compute_all_proofs() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let kzg = KZG_3000.clone();
+        for payload in [
+            &GETTYSBURG_ADDRESS_BYTES[..20],
+            &GETTYSBURG_ADDRESS_BYTES[..200],
+            GETTYSBURG_ADDRESS_BYTES,
+        ] {
+            let polynomial = Blob::from_bytes_and_pad(payload).to_polynomial().unwrap();
+            let commitment = kzg.commit(&polynomial).unwrap();
+            let proofs = kzg.compute_all_proofs(&polynomial).unwrap();
+            assert_eq!(proofs.len(), polynomial.len());
+            for (index, proof) in proofs.iter().enumerate() {
+                let z = Kzg::root_of_unity(polynomial.len(), index);
+                assert_eq!(
+                    *proof,
+                    kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap().0
+                );
+                assert!(kzg.verify_kzg_proof(commitment, *proof, polynomial.as_slice()[index], z));
+            }
+        }
+        assert!(kzg
+            .compute_all_proofs(&Polynomial::new(&vec![Fr::one(); 4096], 4096 * 32).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_compute_coset_proofs() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let kzg = KZG_3000.clone();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let domain = GeneralEvaluationDomain::<Fr>::new(polynomial.len()).unwrap();
+        let coefficients = domain.ifft(polynomial.as_slice());
+
+        for (coset_size, num_cosets) in [(1, 64), (4, 32), (8, 4), (16, 16), (64, 2), (128, 8)] {
+            let proofs = kzg
+                .compute_coset_proofs(&polynomial, coset_size, num_cosets)
+                .unwrap();
+            assert_eq!(proofs.len(), num_cosets);
+            let generator = Kzg::root_of_unity(coset_size * num_cosets, 1);
+            for (i, proof) in proofs.iter().enumerate() {
+                // the quotient by X^L - a, divided from the top down
+                let a = generator.pow([(i * coset_size) as u64]);
+                let mut quotient = vec![Fr::zero(); coefficients.len().saturating_sub(coset_size)];
+                for j in (0..quotient.len()).rev() {
+                    let carry = quotient.get(j + coset_size).copied().unwrap_or_default();
+                    quotient[j] = coefficients[j + coset_size] + a * carry;
+                }
+                assert_eq!(*proof, kzg.commit_coefficients(&quotient).unwrap());
+            }
+        }
+        assert!(kzg.compute_coset_proofs(&polynomial, 3, 4).is_err());
+    }
+
+    #[test]
+    fn test_multi_point_proof() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let kzg = KZG_3000.clone();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let domain = GeneralEvaluationDomain::<Fr>::new(polynomial.len()).unwrap();
+        let points = [
+            Fr::from(12345u64),
+            domain.element(5),
+            Fr::from(7u64),
+            Fr::from(99u64),
+        ];
+
+        let (proof, values) = kzg.compute_multi_point_proof(&polynomial, &points).unwrap();
+        assert_eq!(values[1], polynomial.as_slice()[5]);
+        assert!(kzg
+            .verify_multi_point_proof(commitment, proof, &values, &points)
+            .unwrap());
+        let mut wrong = values.clone();
+        wrong[2] += Fr::one();
+        assert!(!kzg
+            .verify_multi_point_proof(commitment, proof, &wrong, &points)
+            .unwrap());
+        assert!(!kzg
+            .verify_multi_point_proof(commitment, proof, &values[..3], &points[..3])
+            .unwrap());
+
+        // a single point gives the same proof as an opening at that point
+        let (single, value) = kzg
+            .compute_kzg_proof_at_point(&polynomial, points[0])
+            .unwrap();
+        assert_eq!(
+            kzg.compute_multi_point_proof(&polynomial, &points[..1])
+                .unwrap(),
+            (single, vec![value])
+        );
+
+        // as many points as evaluations leave a zero quotient
+        let all: Vec<Fr> = domain.elements().collect();
+        let (proof, values) = kzg.compute_multi_point_proof(&polynomial, &all).unwrap();
+        assert!(proof.is_zero());
+        assert_eq!(values, polynomial.as_slice());
+        assert!(kzg
+            .verify_multi_point_proof(commitment, proof, &values, &all)
+            .unwrap());
+
+        assert!(kzg.compute_multi_point_proof(&polynomial, &[]).is_err());
+        assert!(kzg
+            .compute_multi_point_proof(&polynomial, &[points[0], points[0]])
+            .is_err());
+        assert!(kzg
+            .verify_multi_point_proof(commitment, proof, &values[1..], &all)
+            .is_err());
+    }
+
+    #[test]
+    fn test_aggregated_proof() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let kzg = KZG_3000.clone();
+        let polynomials: Vec<Polynomial> = [30, 500, GETTYSBURG_ADDRESS_BYTES.len()]
+            .iter()
+            .map(|&len| {
+                Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..len])
+                    .to_polynomial()
+                    .unwrap()
+            })
+            .collect();
+        let commitments: Vec<G1Affine> = polynomials
+            .iter()
+            .map(|polynomial| kzg.commit(polynomial).unwrap())
+            .collect();
+
+        for z in [Fr::from(424242u64), Fr::one()] {
+            let (proof, values) = kzg.compute_aggregated_proof(&polynomials, z).unwrap();
+            for (polynomial, value) in polynomials.iter().zip(&values) {
+                assert_eq!(
+                    *value,
+                    Kzg::evaluate_polynomial_in_evaluation_form(polynomial, z)
+                );
+            }
+            assert!(kzg
+                .verify_aggregated_proof(&commitments, proof, &values, z)
+                .unwrap());
+
+            let mut wrong = values.clone();
+            wrong[1] += Fr::one();
+            assert!(!kzg
+                .verify_aggregated_proof(&commitments, proof, &wrong, z)
+                .unwrap());
+            let mut swapped = commitments.clone();
+            swapped.swap(0, 2);
+            assert!(!kzg
+                .verify_aggregated_proof(&swapped, proof, &values, z)
+                .unwrap());
+            assert!(!kzg
+                .verify_aggregated_proof(&commitments, proof, &values, z + Fr::one())
+                .unwrap());
+        }
+
+        // one polynomial aggregates to its own opening
+        let z = Fr::from(5u64);
+        assert_eq!(
+            kzg.compute_aggregated_proof(&polynomials[..1], z).unwrap(),
+            {
+                let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomials[0], z).unwrap();
+                (proof, vec![value])
+            }
+        );
+        assert!(kzg.compute_aggregated_proof(&[], z).is_err());
+        assert!(kzg
+            .verify_aggregated_proof(&commitments, G1Affine::generator(), &[Fr::one()], z)
+            .is_err());
+    }
+
+    #[test]
+    fn test_blob_kzg_proof() {
+        use crate::{config::ChallengeHash, consts::GETTYSBURG_ADDRESS_BYTES};
+        use ark_ff::{BigInteger, PrimeField};
+        use sha2::{Digest, Sha256};
+
+        let mut kzg = KZG_3000.clone();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let other = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..200]);
+        let commitment = kzg.blob_to_kzg_commitment(&blob).unwrap();
+        let proof = kzg.compute_blob_kzg_proof(&blob, &commitment).unwrap();
+        assert!(kzg
+            .verify_blob_kzg_proof(&blob, &commitment, &proof)
+            .unwrap());
+        assert!(!kzg
+            .verify_blob_kzg_proof(&other, &commitment, &proof)
+            .unwrap());
+        let other_commitment = kzg.blob_to_kzg_commitment(&other).unwrap();
+        assert!(!kzg
+            .verify_blob_kzg_proof(&blob, &other_commitment, &proof)
+            .unwrap());
+        assert!(!kzg
+            .verify_blob_kzg_proof(&blob, &commitment, &G1Affine::generator())
+            .unwrap());
+
+        let blob_bytes = blob.get_blob_data();
+        let (commitment_bytes, proof_bytes) = (g1_to_bytes(&commitment), g1_to_bytes(&proof));
+        assert!(kzg
+            .verify_from_bytes(&blob_bytes, &commitment_bytes, &proof_bytes)
+            .unwrap());
+        assert!(!kzg
+            .verify_from_bytes(&other.get_blob_data(), &commitment_bytes, &proof_bytes)
+            .unwrap());
+        assert!(kzg
+            .verify_from_bytes(&blob_bytes, &commitment_bytes, &proof_bytes[1..])
+            .is_err());
+        // an x coordinate with no point on the curve is rejected
+        let mut not_on_curve = commitment_bytes;
+        not_on_curve[0] ^= 1;
+        assert!(kzg
+            .verify_from_bytes(&blob_bytes, &not_on_curve, &proof_bytes)
+            .is_err());
+
+        // the challenge is laid out as c-kzg-4844 lays it out
+        let polynomial = blob.to_polynomial().unwrap();
+        let mut hasher = Sha256::new();
+        hasher.update(b"FSBLOBVERIFY_V1_");
+        hasher.update((polynomial.len() as u128).to_be_bytes());
+        for element in polynomial.as_slice() {
+            hasher.update(element.into_bigint().to_bytes_be());
+        }
+        hasher.update(g1_to_bytes(&commitment));
+        let z = Fr::from_be_bytes_mod_order(&hasher.finalize());
+        assert_eq!(
+            proof,
+            kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap().0
+        );
+
+        assert_eq!(kzg.compute_challenge(&blob, &commitment).unwrap(), z);
+
+        // proofs only verify under the challenge hash they were made with
+        kzg.set_config(KzgConfig::default().with_challenge_hash(ChallengeHash::Keccak256));
+        assert!(!kzg
+            .verify_blob_kzg_proof(&blob, &commitment, &proof)
+            .unwrap());
+        assert_ne!(kzg.compute_challenge(&blob, &commitment).unwrap(), z);
+        let proof = kzg.compute_blob_kzg_proof(&blob, &commitment).unwrap();
+        assert!(kzg
+            .verify_blob_kzg_proof(&blob, &commitment, &proof)
+            .unwrap());
+        assert!(kzg
+            .compute_blob_kzg_proof(&Blob::new(vec![1u8; 32], false), &commitment)
+            .is_err());
+        assert!(kzg
+            .compute_challenge(&Blob::new(vec![1u8; 32], false), &commitment)
+            .is_err());
+    }
+
+    #[test]
+    fn test_blob_kzg_proof_batch() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let kzg = KZG_3000.clone();
+        let blobs: Vec<Blob> = [10, 200, 700, GETTYSBURG_ADDRESS_BYTES.len()]
+            .iter()
+            .map(|&len| Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..len]))
+            .collect();
+        let commitments: Vec<G1Affine> = blobs
+            .iter()
+            .map(|blob| kzg.blob_to_kzg_commitment(blob).unwrap())
+            .collect();
+        let mut proofs: Vec<G1Affine> = blobs
+            .iter()
+            .zip(&commitments)
+            .map(|(blob, commitment)| kzg.compute_blob_kzg_proof(blob, commitment).unwrap())
+            .collect();
+        let results = kzg
+            .verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+            .unwrap();
+        assert_eq!(results, vec![Ok(()); 4]);
+        assert!(kzg
+            .verify_blob_kzg_proof_batch(&[], &[], &[])
+            .unwrap()
+            .is_empty());
+        assert!(kzg
+            .verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs[1..])
+            .is_err());
+
+        // only the bad items are reported
+        proofs.swap(1, 3);
+        let mut blobs = blobs;
+        blobs[2] = Blob::new(vec![1u8; 32], false);
+        let results = kzg
+            .verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+            .unwrap();
+        assert_eq!(results[0], Ok(()));
+        assert_eq!(results[1], Err(KzgError::InvalidProofError(1)));
+        assert!(matches!(results[2], Err(KzgError::SerializationError(_))));
+        assert_eq!(results[3], Err(KzgError::InvalidProofError(3)));
+    }
+
+    #[test]
+    fn test_commit_partial() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let kzg = KZG_3000.clone();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let mut workspace = KzgWorkspace::new();
+
+        for num_shards in [1, 3, 7, polynomial.len() + 2] {
+            let ranges = Kzg::shard_ranges(polynomial.len(), num_shards);
+            assert_eq!(ranges.len(), num_shards);
+            assert_eq!(
+                ranges.iter().map(|r| r.len()).sum::<usize>(),
+                polynomial.len()
+            );
+            let partials: Vec<G1Affine> = ranges
+                .iter()
+                .map(|range| kzg.commit_partial(&polynomial, range.clone()).unwrap())
+                .collect();
+            assert_eq!(Kzg::combine_partial_commitments(&partials), commitment);
+
+            let partials: Vec<G1Affine> = ranges
+                .iter()
+                .map(|range| {
+                    kzg.commit_partial_with_workspace(&polynomial, range.clone(), &mut workspace)
+                        .unwrap()
+                })
+                .collect();
+            assert_eq!(Kzg::combine_partial_commitments(&partials), commitment);
+        }
+
+        assert!(kzg
+            .commit_partial(&polynomial, 0..polynomial.len() + 1)
+            .is_err());
+        assert_eq!(Kzg::combine_partial_commitments(&[]), G1Affine::identity());
+    }
+
+    #[test]
+    fn test_commit_blob_and_bytes() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
 
-        let file2 = File::open("src/test-files/kzg.proof.eq.input").unwrap();
-        let reader2 = BufReader::new(file2);
+        let kzg = KZG_3000.clone();
+        let padded = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = padded.to_polynomial().unwrap();
+        let commitment = KzgCommitment::new(kzg.commit(&polynomial).unwrap(), polynomial.len());
+        assert_eq!(kzg.commit_blob(&padded).unwrap(), commitment);
+        let raw = Blob::new(GETTYSBURG_ADDRESS_BYTES.to_vec(), false);
+        assert_eq!(kzg.commit_blob(&raw).unwrap(), commitment);
+        assert!(!raw.is_padded());
+        assert_eq!(
+            kzg.commit_bytes(GETTYSBURG_ADDRESS_BYTES).unwrap(),
+            commitment
+        );
+    }
 
-        for line in reader2.lines() {
-            let line = line.unwrap();
-            let trimmed_line = line.trim_end(); // Trim whitespace from the end
-            let the_strings_str: Vec<&str> = trimmed_line.split(',').collect(); // Split the line on commas
-            let index = u64::from_str(the_strings_str[0]).unwrap();
-            let hard_coded_x = Fq::from_str(the_strings_str[1]).expect("should be fine");
-            let hard_coded_y = Fq::from_str(the_strings_str[2]).expect("should be fine");
-            let gnark_proof = G1Affine::new(hard_coded_x, hard_coded_y);
-            let poly = Polynomial::new(&padded_input_fr_elements, 30).unwrap();
-            kzg.data_setup_custom(4, poly.len().try_into().unwrap())
+    #[test]
+    fn test_update_commitment() {
+        use crate::{commitment::KzgCommitment, consts::GETTYSBURG_ADDRESS_BYTES};
+        use ark_std::UniformRand;
+
+        let kzg = KZG_3000.clone();
+        let mut rng = ark_std::test_rng();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let commitment = KzgCommitment::from_polynomial(&polynomial, &kzg).unwrap();
+
+        let mut evaluations = polynomial.to_vec();
+        let mut changes = Vec::new();
+        for index in [0, 3, 17, polynomial.len() - 1] {
+            let new_value = Fr::rand(&mut rng);
+            changes.push((index as u64, evaluations[index], new_value));
+            evaluations[index] = new_value;
+        }
+        // an unchanged entry is skipped
+        changes.push((5, evaluations[5], evaluations[5]));
+        let updated = Polynomial::new(&evaluations, polynomial.len() * 32).unwrap();
+        assert_eq!(
+            kzg.update_commitment(&commitment, &changes).unwrap(),
+            KzgCommitment::from_polynomial(&updated, &kzg).unwrap()
+        );
+        assert_eq!(kzg.update_commitment(&commitment, &[]).unwrap(), commitment);
+
+        let out_of_range = [(polynomial.len() as u64, Fr::zero(), Fr::one())];
+        assert!(kzg.update_commitment(&commitment, &out_of_range).is_err());
+    }
+
+    #[test]
+    fn test_length_commitment_and_proof() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let mut kzg = KZG_3000.clone();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let length = polynomial.len();
+        let length_commitment = kzg.compute_length_commitment(&polynomial).unwrap();
+        let length_proof = kzg.compute_length_proof(&polynomial, length).unwrap();
+
+        // the G2 commitment opens to the same value as the G1 commitment
+        let commitment = kzg.commit(&polynomial).unwrap();
+        assert!(Kzg::pairings_verify(
+            commitment,
+            G2Affine::generator(),
+            G1Affine::generator(),
+            length_commitment
+        ));
+        assert!(kzg
+            .verify_length_proof(length_commitment, length_proof, length)
+            .unwrap());
+        // claiming a shorter polynomial fails
+        assert!(!kzg
+            .verify_length_proof(length_commitment, length_proof, length / 2)
+            .unwrap());
+        assert!(kzg
+            .verify_length_proof(length_commitment, length_proof, 0)
+            .is_err());
+
+        // the names used for EigenDA blob headers
+        assert_eq!(kzg.commit_g2(&polynomial).unwrap(), length_commitment);
+        assert!(kzg
+            .verify_blob_length(length_commitment, length_proof, length)
+            .unwrap());
+        assert!(kzg.compute_length_proof(&polynomial, length / 2).is_err());
+
+        // a constant polynomial proves any length
+        let constant = Polynomial::new(&vec![Fr::one(); 8], 256).unwrap();
+        let constant_commitment = kzg.commit_g2(&constant).unwrap();
+        for short_length in [1, 3, 8] {
+            let proof = kzg.compute_length_proof(&constant, short_length).unwrap();
+            assert!(kzg
+                .verify_blob_length(constant_commitment, proof, short_length)
+                .unwrap());
+        }
+
+        kzg.release_g2_points();
+        assert!(kzg.compute_length_commitment(&polynomial).is_err());
+        assert!(kzg.compute_length_proof(&polynomial, length).is_err());
+    }
+
+    #[test]
+    fn test_degree_bound() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+        let mut kzg = KZG_3000.clone();
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let length = polynomial.len();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let proof = kzg.prove_degree_bound(&polynomial, length).unwrap();
+        assert!(kzg.verify_degree_bound(commitment, proof, length).unwrap());
+        assert!(!kzg
+            .verify_degree_bound(commitment, proof, length / 2)
+            .unwrap());
+        let loose = kzg.prove_degree_bound(&polynomial, 2000).unwrap();
+        assert!(kzg.verify_degree_bound(commitment, loose, 2000).unwrap());
+        assert!(kzg.prove_degree_bound(&polynomial, length - 1).is_err());
+        assert!(kzg.prove_degree_bound(&polynomial, 0).is_err());
+        assert!(kzg.prove_degree_bound(&polynomial, 3001).is_err());
+
+        // a polynomial of degree 9 in evaluation form over 64 points
+        let domain = GeneralEvaluationDomain::<Fr>::new(64).unwrap();
+        let mut coefficients: Vec<Fr> = (1..=10u64).map(Fr::from).collect();
+        coefficients.resize(64, Fr::zero());
+        let low_degree = Polynomial::new(&domain.fft(&coefficients), 64 * 32).unwrap();
+        let commitment = kzg.commit(&low_degree).unwrap();
+        let proof = kzg.prove_degree_bound(&low_degree, 10).unwrap();
+        assert!(kzg.verify_degree_bound(commitment, proof, 10).unwrap());
+        assert!(!kzg.verify_degree_bound(commitment, proof, 9).unwrap());
+        assert!(kzg.prove_degree_bound(&low_degree, 9).is_err());
+
+        kzg.release_g2_points();
+        assert!(kzg.verify_degree_bound(commitment, proof, 10).is_err());
+    }
+
+    #[test]
+    fn test_compute_commitments_and_proofs() {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let mut kzg = KZG_3000.clone();
+
+        let blobs: Vec<Blob> = [300, 5000, 20000, 5000, 40]
+            .iter()
+            .map(|&blob_length| {
+                let random_blob: Vec<u8> = (0..blob_length)
+                    .map(|_| rng.gen_range(32..=126) as u8)
+                    .collect();
+                Blob::from_bytes_and_pad(&random_blob)
+            })
+            .collect();
+        let polys: Vec<Polynomial> = blobs.iter().map(|b| b.to_polynomial().unwrap()).collect();
+        let indices: Vec<u64> = polys
+            .iter()
+            .map(|p| rng.gen_range(0..p.get_length_of_padded_blob_as_fr_vector()) as u64)
+            .collect();
+
+        let results = kzg
+            .compute_commitments_and_proofs(&polys, &indices)
+            .unwrap();
+        assert_eq!(results.len(), polys.len());
+        for (i, (commitment, proof)) in results.into_iter().enumerate() {
+            kzg.data_setup_custom(1, blobs[i].len().try_into().unwrap())
                 .unwrap();
-            let result = kzg
-                .compute_kzg_proof(&poly, index, &roots_of_unities)
+            assert_eq!(commitment, kzg.commit(&polys[i]).unwrap());
+            assert_eq!(
+                proof,
+                kzg.compute_kzg_proof_with_roots_of_unity(&polys[i], indices[i])
+                    .unwrap()
+            );
+        }
+
+        assert_eq!(
+            kzg.compute_commitments_and_proofs(&polys, &indices[1..]),
+            Err(KzgError::GenericError(
+                "number of polynomials and indices don't match".to_string()
+            ))
+        );
+        assert_eq!(
+            kzg.compute_commitments_and_proofs(&polys[..1], &[u64::MAX]),
+            Err(KzgError::GenericError(
+                "index is out of range of the polynomial".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_compute_kzg_proof_with_openings() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+        let mut kzg = KZG_3000.clone();
+
+        // cover both a small blob and one past the fast path threshold
+        for bytes in [&[7u8; 300][..], GETTYSBURG_ADDRESS_BYTES] {
+            let blob = Blob::from_bytes_and_pad(bytes);
+            let polynomial = blob.to_polynomial().unwrap();
+            kzg.data_setup_custom(1, blob.len().try_into().unwrap())
                 .unwrap();
-            assert_eq!(gnark_proof, result)
+            let commitment = kzg.commit(&polynomial).unwrap();
+
+            let openings = kzg.prepare_openings(&polynomial).unwrap();
+            assert_eq!(openings.len(), polynomial.len());
+            for index in 0..polynomial.len() as u64 {
+                let proof = kzg
+                    .compute_kzg_proof_with_openings(&openings, index)
+                    .unwrap();
+                assert_eq!(
+                    proof,
+                    kzg.compute_kzg_proof_with_roots_of_unity(&polynomial, index)
+                        .unwrap()
+                );
+                let value_fr = polynomial.get_at_index(index as usize).unwrap();
+                let z_fr = kzg.get_nth_root_of_unity(index as usize).unwrap();
+                assert!(kzg.verify_kzg_proof(commitment, proof, *value_fr, *z_fr));
+            }
+
+            assert_eq!(
+                kzg.compute_kzg_proof_with_openings(&openings, polynomial.len() as u64),
+                Err(KzgError::GenericError(
+                    "index is out of range of the polynomial".to_string()
+                ))
+            );
         }
+
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        // the roots of unity follow the polynomial, not the last data setup
+        let openings = kzg.prepare_openings(&polynomial).unwrap();
+        kzg.data_setup_custom(1, 32).unwrap();
+        assert_eq!(kzg.prepare_openings(&polynomial).unwrap(), openings);
     }
 
     #[test]
-    fn test_g1_ifft() {
-        use ark_bn254::Fq;
-        use std::io::BufRead;
+    fn test_setup_truncated() {
+        let kzg = Kzg::setup_truncated(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            1024,
+        )
+        .unwrap();
+        assert_eq!(kzg.get_g1_points(), KZG_3000.get_g1_points()[..1024]);
+        assert_eq!(kzg.get_g2_points().len(), 1024);
 
-        let file = File::open("src/test-files/lagrangeG1SRS.txt").unwrap();
-        let reader = BufReader::new(file);
+        // setup loads what the file holds, setup_truncated insists on n
+        let g1_len = KZG_3000.get_g1_points().len() as u32;
+        let too_many = Kzg::setup_truncated(
+            "src/test-files/g1.point",
+            "",
+            "src/test-files/g2.point.powerOf2",
+            4000,
+            g1_len + 1,
+        );
+        assert!(
+            matches!(too_many, Err(KzgError::GenericError(message)) if message.contains("fewer than"))
+        );
+        assert!(Kzg::setup(
+            "src/test-files/g1.point",
+            "",
+            "src/test-files/g2.point.powerOf2",
+            4000,
+            g1_len + 1,
+        )
+        .is_ok());
+        assert!(Kzg::setup_truncated(
+            "src/test-files/missing.point",
+            "",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            16,
+        )
+        .is_err());
+    }
 
-        let kzg_g1_points = KZG_3000.g1_ifft(64).unwrap();
+    #[test]
+    fn test_verify_srs() {
+        KZG_3000.verify_srs().unwrap();
+        // a power of 2 table of the same tau
+        let g2 = KZG_3000.get_g2_points();
+        let mut power_of_2 = KZG_3000.clone();
+        power_of_2.g2 = Arc::new((0..12).map(|k| g2[1 << k]).collect());
+        power_of_2.g2_source = G2Source::Memory { power_of_2: true };
+        power_of_2.verify_srs().unwrap();
+
+        let with_g1 = |g1: Vec<G1Affine>| {
+            Kzg::from_keys(ProvingKey::new(g1, 3000).unwrap(), KZG_3000.verifying_key()).unwrap()
+        };
+        // every power is sampled when there are fewer than the sample size
+        let mut g1 = KZG_3000.get_g1_points()[..32].to_vec();
+        with_g1(g1.clone()).verify_srs().unwrap();
+        g1.swap(10, 11);
+        assert!(with_g1(g1.clone()).verify_srs().is_err());
+        g1.swap(10, 11);
+        g1[20] = G1Affine::zero();
+        let invalid = with_g1(g1).verify_srs();
+        assert!(
+            matches!(invalid, Err(KzgError::GenericError(message)) if message.contains("G1 point 20"))
+        );
 
-        // Iterate over each line in the file
-        for (i, line_result) in reader.lines().enumerate() {
-            let mut line = line_result.unwrap(); // Retrieve the line, handling potential I/O errors
-            line = line.trim_end().to_string();
+        // the test power of 2 table is from another tau than the G1 points
+        let mismatched = Kzg::setup(
+            "src/test-files/g1.point",
+            "",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        assert!(mismatched.verify_srs().is_err());
+        Arc::make_mut(&mut power_of_2.g2)[5] = power_of_2.g2[4];
+        assert!(power_of_2.verify_srs().is_err());
+    }
 
-            // Split the line at each comma and process the parts
-            let parts: Vec<&str> = line.split(',').collect();
+    #[test]
+    fn test_export_srs() {
+        let dir = env::temp_dir().join("rust-kzg-bn254-test-export-srs");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            1000,
+        )
+        .unwrap();
+        kzg.export_srs(dir.to_str().unwrap()).unwrap();
+        assert_eq!(
+            std::fs::read(path("g1.point")).unwrap(),
+            std::fs::read("src/test-files/g1.point").unwrap()[..32 * 1000]
+        );
+        let reloaded = Kzg::setup(&path("g1.point"), &path("g2.point"), "", 3000, 1000).unwrap();
+        assert_eq!(reloaded.get_g1_points(), kzg.get_g1_points());
+        assert_eq!(reloaded.get_g2_points(), kzg.get_g2_points());
 
-            let x = Fq::from_str(parts[0]).expect("should be fine");
-            let y = Fq::from_str(parts[1]).expect("should be fine");
+        // a power of 2 table cut from the consecutive powers
+        let from_table = Kzg::setup(
+            &path("g1.point"),
+            "",
+            &path("g2.point.powerOf2"),
+            3000,
+            1000,
+        )
+        .unwrap();
+        assert_eq!(from_table.get_g2_points().len(), 10);
+        assert_eq!(from_table.verifier(), kzg.verifier());
+        from_table.export_srs(dir.to_str().unwrap()).unwrap();
+        assert_eq!(
+            Kzg::read_g2_point_on_power_of_2(&path("g2.point.powerOf2")).unwrap(),
+            from_table.get_g2_points()
+        );
 
-            let point = G1Affine::new_unchecked(x, y);
-            assert_eq!(point, kzg_g1_points[i], "failed on {i}");
+        let mut released = kzg.clone();
+        released.release_g2_points();
+        assert!(released.export_srs(dir.to_str().unwrap()).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_shared_srs() {
+        let setup_shared = |srs_points_to_load| {
+            Kzg::setup_shared(
+                "src/test-files/g1.point",
+                "src/test-files/g2.point",
+                "",
+                3000,
+                srs_points_to_load,
+            )
+            .unwrap()
+        };
+        let first = setup_shared(2000);
+        let workers: Vec<Kzg> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| setup_shared(2000))).collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        for worker in &workers {
+            assert!(Arc::ptr_eq(&worker.g1, &first.g1));
+            assert!(Arc::ptr_eq(&worker.g2, &first.g2));
+        }
+        assert!(!Arc::ptr_eq(&setup_shared(1000).g1, &first.g1));
+        assert!(Kzg::setup_shared("src/test-files/missing.point", "", "", 3000, 2000).is_err());
+
+        // changing a clone's points leaves the others alone
+        let mut clone = first.clone();
+        clone.precompute_lagrange_srs(1024).unwrap();
+        clone.release_g2_points();
+        assert!(Arc::ptr_eq(&clone.g1, &first.g1));
+        assert_eq!(first.get_lagrange_srs_length(), 0);
+        assert_eq!(first.get_g2_points().len(), 2000);
+        assert_eq!(setup_shared(2000).get_g2_points().len(), 2000);
+
+        // clearing the cache loads the files again
+        Kzg::clear_shared_setups();
+        let reloaded = setup_shared(2000);
+        assert!(!Arc::ptr_eq(&reloaded.g1, &first.g1));
+        assert_eq!(reloaded.get_g1_points(), first.get_g1_points());
+    }
+
+    #[test]
+    fn test_setup_with_digests() {
+        let sha256 = |path| -> [u8; 32] { Sha256::digest(std::fs::read(path).unwrap()).into() };
+        let g1_sha256 = sha256("src/test-files/g1.point");
+        let g2_sha256 = sha256("src/test-files/g2.point.powerOf2");
+        let setup = |g1_sha256, g2_sha256| {
+            Kzg::setup_with_digests(
+                "src/test-files/g1.point",
+                "",
+                "src/test-files/g2.point.powerOf2",
+                3000,
+                1000,
+                g1_sha256,
+                g2_sha256,
+            )
+        };
+        let kzg = setup(Some(g1_sha256), Some(g2_sha256)).unwrap();
+        assert_eq!(kzg.get_g1_points().len(), 1000);
+        assert!(setup(None, None).is_ok());
+
+        // the G2 digest is checked against the power of 2 file in use
+        let mismatch = setup(Some(g1_sha256), Some(g1_sha256));
+        assert!(
+            matches!(mismatch, Err(KzgError::GenericError(message)) if message.contains("g2.point.powerOf2 has SHA-256 digest"))
+        );
+        let mut corrupted = g1_sha256;
+        corrupted[31] ^= 1;
+        assert!(setup(Some(corrupted), None).is_err());
+    }
+
+    #[test]
+    fn test_setup_sharded() {
+        let dir = env::temp_dir().join("rust-kzg-bn254-test-setup-sharded");
+        std::fs::create_dir_all(&dir).unwrap();
+        let g1 = std::fs::read("src/test-files/g1.point").unwrap();
+        let shards: Vec<String> = g1
+            .chunks(1100 * SIZE_OF_G1_AFFINE_COMPRESSED)
+            .enumerate()
+            .map(|(i, shard)| {
+                let path = dir.join(format!("g1.point.{i}"));
+                std::fs::write(&path, shard).unwrap();
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
+        let shards: Vec<&str> = shards.iter().map(String::as_str).collect();
+        let setup = |shards: &[&str], srs_points_to_load| {
+            Kzg::setup_sharded(
+                shards,
+                "src/test-files/g2.point",
+                "",
+                3000,
+                srs_points_to_load,
+            )
+        };
+
+        let expected = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let kzg = setup(&shards, 3000).unwrap();
+        assert_eq!(kzg.get_g1_points(), expected.get_g1_points());
+        assert_eq!(kzg.get_g2_points(), expected.get_g2_points());
+        assert_eq!(
+            setup(&shards, 1500).unwrap().get_g1_points(),
+            expected.get_g1_points()[..1500]
+        );
+
+        // missing, swapped and misaligned shards
+        assert!(setup(&[shards[0], shards[2]], 3000).is_err());
+        assert!(setup(&[shards[1], shards[0], shards[2]], 3000).is_err());
+        assert!(setup(&[shards[0], shards[0]], 2000).is_err());
+        let misaligned = dir.join("g1.point.misaligned");
+        std::fs::write(&misaligned, &g1[..100]).unwrap();
+        assert!(setup(&[misaligned.to_str().unwrap(), shards[1]], 2000).is_err());
+        assert!(setup(&[], 3000).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prove_from_shared_setup() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Kzg>();
+
+        // proving needs no data setup, and each thread proves blobs of its
+        // own size
+        let kzg = Arc::new(KZG_3000.clone());
+        let sizes = [31 * 4, 31 * 64, 31 * 256];
+        let proofs: Vec<G1Affine> = std::thread::scope(|scope| {
+            let handles: Vec<_> = sizes
+                .iter()
+                .map(|&size| {
+                    let kzg = &kzg;
+                    scope.spawn(move || {
+                        let blob = Blob::from_bytes_and_pad(&vec![7u8; size]);
+                        let polynomial = blob.to_polynomial().unwrap();
+                        kzg.compute_kzg_proof_with_roots_of_unity(&polynomial, 3)
+                            .unwrap()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        for (size, proof) in sizes.iter().zip(proofs) {
+            let blob = Blob::from_bytes_and_pad(&vec![7u8; *size]);
+            let polynomial = blob.to_polynomial().unwrap();
+            let mut set_up = KZG_3000.clone();
+            set_up.data_setup_custom(1, blob.len() as u64).unwrap();
+            assert_eq!(
+                Kzg::roots_of_unity(polynomial.len()).unwrap(),
+                *set_up.get_expanded_roots_of_unity()
+            );
+            assert_eq!(
+                set_up
+                    .compute_kzg_proof_with_roots_of_unity(&polynomial, 3)
+                    .unwrap(),
+                proof
+            );
+            let commitment = kzg.commit(&polynomial).unwrap();
+            let z = set_up.get_nth_root_of_unity(3).unwrap();
+            let value = polynomial.get_at_index(3).unwrap();
+            assert!(kzg.verify_kzg_proof(commitment, proof, *value, *z));
+        }
+        assert!(Kzg::roots_of_unity(3).is_err());
+        assert!(Kzg::roots_of_unity(1 << 29).is_err());
+    }
+
+    #[test]
+    fn test_setup_with_progress() {
+        use std::cell::RefCell;
+
+        let reports = RefCell::new(Vec::new());
+        let kzg = Kzg::setup_with_progress(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            3000,
+            &|read, total| reports.borrow_mut().push((read, total)),
+        )
+        .unwrap();
+        assert_eq!(kzg.get_g1_points(), KZG_3000.get_g1_points());
+        let reports = reports.into_inner();
+        let total = kzg.get_g1_points().len() + kzg.get_g2_points().len();
+        // one report per window of each file
+        assert!(reports.len() >= 2);
+        assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(reports.iter().all(|&(_, reported)| reported == total));
+        assert_eq!(reports.last().unwrap().0, total);
+    }
+
+    #[test]
+    fn test_read_points_in_windows() {
+        let g1_points = KZG_3000.get_g1_points();
+        // window sizes that do and don't divide the number of points
+        for window_points in [1, 7, 1000, 5000] {
+            let points: Vec<G1Affine> = Kzg::read_points_in_windows(
+                "src/test-files/g1.point",
+                SIZE_OF_G1_AFFINE_COMPRESSED,
+                3000,
+                window_points,
+                &|_| {},
+            )
+            .unwrap();
+            assert_eq!(points, g1_points);
         }
+
+        // asking for more points than the file holds loads the whole file
+        let points: Vec<G1Affine> = Kzg::read_points_in_windows(
+            "src/test-files/g1.point",
+            SIZE_OF_G1_AFFINE_COMPRESSED,
+            usize::MAX,
+            7,
+            &|_| {},
+        )
+        .unwrap();
+        assert_eq!(points, g1_points);
+
+        let missing: Result<Vec<G1Affine>, KzgError> = Kzg::read_points_in_windows(
+            "src/test-files/missing.point",
+            SIZE_OF_G1_AFFINE_COMPRESSED,
+            10,
+            7,
+            &|_| {},
+        );
+        assert!(matches!(missing, Err(KzgError::GenericError(_))));
     }
 
     #[test]
@@ -1327,12 +4695,12 @@ mod tests {
 
         let mut eval_fr: Vec<Fr> = vec![];
         let roots_of_unities: Vec<Fr> = helpers::str_vec_to_fr_vec(roots_of_unities_raw).unwrap();
-        for i in 0..eval_raw.len() {
-            eval_fr.push(Fr::from_str(eval_raw[i]).expect("yes"));
+        for raw in eval_raw.iter() {
+            eval_fr.push(Fr::from_str(raw).expect("yes"));
         }
 
         let result =
-            KZG_3000.compute_quotient_eval_on_domain(z_fr, &eval_fr, value_fr, &roots_of_unities);
+            Kzg::compute_quotient_eval_on_domain(z_fr, &eval_fr, value_fr, &roots_of_unities);
         let confirmed_result = Fr::from_str(
             "20008798420615294489302706738008175134837093401197634135729610787152508035605",
         )
@@ -1340,4 +4708,76 @@ mod tests {
 
         assert_eq!(confirmed_result, result);
     }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_spans() {
+        use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+        use std::{
+            fmt::Debug,
+            sync::{Arc, Mutex},
+        };
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id},
+            Subscriber,
+        };
+        use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+        // records each span as its name followed by its fields
+        #[derive(Clone, Default)]
+        struct Spans(Arc<Mutex<Vec<String>>>);
+
+        impl Visit for Spans {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                let mut spans = self.0.lock().unwrap();
+                let span = spans.last_mut().unwrap();
+                span.push_str(&format!(" {}={:?}", field.name(), value));
+            }
+        }
+
+        impl<S: Subscriber> Layer<S> for Spans {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(attrs.metadata().name().to_string());
+                attrs.record(&mut self.clone());
+            }
+        }
+
+        let spans = Spans::default();
+        let subscriber = tracing_subscriber::registry().with(spans.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let mut kzg = Kzg::setup(
+                "src/test-files/g1.point",
+                "src/test-files/g2.point",
+                "src/test-files/g2.point.powerOf2",
+                3000,
+                3000,
+            )
+            .unwrap();
+            kzg.set_config(KzgConfig::default().with_small_blob_threshold(0));
+            let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+                .to_polynomial()
+                .unwrap();
+            let commitment = kzg.commit(&polynomial).unwrap();
+            let z = Fr::from(5u64);
+            let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap();
+            assert!(kzg.verify_kzg_proof(commitment, proof, value, z));
+        });
+
+        let spans = spans.0.lock().unwrap();
+        for expected in [
+            "setup srs_order=3000 srs_points_to_load=3000",
+            "read_points_in_windows file=\"src/test-files/g1.point\" num_points=3000",
+            "commit length=64",
+            "g1_ifft length=64",
+            "g1_msm size=64",
+            "compute_kzg_proof_at_point length=64",
+            "pairings_verify",
+        ] {
+            assert!(spans.iter().any(|span| span == expected), "{}", expected);
+        }
+    }
 }
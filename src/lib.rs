@@ -1,8 +1,120 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod accel;
+#[cfg(feature = "std")]
+pub mod aggregation;
 mod arith;
+#[cfg(feature = "std")]
+pub mod batch;
 pub mod blob;
+#[cfg(feature = "std")]
+pub mod bls;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "peerdas")]
+pub mod cells;
+#[cfg(feature = "std")]
+pub mod ceremony;
+#[cfg(feature = "std")]
+pub mod cert;
+#[cfg(feature = "std")]
+pub mod circuit;
+#[cfg(feature = "std")]
+pub mod commitment;
+#[cfg(feature = "std")]
+pub mod config;
+// most constants tune or separate the std-only modules
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 mod consts;
+#[cfg(feature = "std")]
+pub mod disperse;
+#[cfg(feature = "std")]
+pub mod domain;
+pub mod embedded;
+#[cfg(feature = "embedded-srs")]
+pub mod embedded_srs;
+#[cfg(feature = "std")]
+pub mod encoder;
+pub mod encoding;
+#[cfg(feature = "std")]
+pub mod equivalence;
 pub mod errors;
+#[cfg(feature = "std")]
+pub mod eth;
+#[cfg(feature = "std")]
+pub mod evm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod fraud;
+#[cfg(feature = "std")]
+mod glv;
 pub mod helpers;
+#[cfg(feature = "std")]
+pub mod ignition;
+#[cfg(feature = "std")]
+pub mod insecure_srs;
+#[cfg(feature = "std")]
+pub mod keys;
+#[cfg(feature = "std")]
 pub mod kzg;
+#[cfg(feature = "std")]
+pub mod merkle;
+#[cfg(feature = "mobile")]
+pub mod mobile;
+#[cfg(feature = "node")]
+pub mod node;
+#[cfg(feature = "std")]
+pub mod openings;
+#[cfg(feature = "std")]
+pub mod operator;
+#[cfg(feature = "peerdas")]
+pub mod peerdas;
+#[cfg(feature = "std")]
+pub mod pipeline;
 pub mod polynomial;
+#[cfg(feature = "std")]
+pub mod ptau;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "std")]
+pub mod retrieve;
+#[cfg(feature = "std")]
+pub mod sampling;
+#[cfg(feature = "service")]
+pub mod service;
+#[cfg(feature = "std")]
+pub mod setup_config;
+#[cfg(feature = "std")]
+pub mod sidecar;
+#[cfg(feature = "std")]
+pub mod srs;
+#[cfg(feature = "std")]
+pub mod srs_cache;
+#[cfg(feature = "std")]
+pub mod stream;
+#[cfg(feature = "std")]
 mod traits;
+#[cfg(feature = "std")]
+pub mod transcript;
+#[cfg(feature = "std")]
+pub mod vector;
+#[cfg(feature = "std")]
+pub mod verifier;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+pub mod workspace;
+
+// the UniFFI scaffolding must live at the crate root, next to the types of
+// src/rust_kzg_bn254.udl
+#[cfg(feature = "mobile")]
+use {errors::KzgError, mobile::Verifier};
+#[cfg(feature = "mobile")]
+uniffi::include_scaffolding!("rust_kzg_bn254");
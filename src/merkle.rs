@@ -0,0 +1,202 @@
+use ark_bn254::G1Affine;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use sha3::{Digest, Keccak256};
+
+/// Returns the keccak256 hash of `data`, as the EVM computes it.
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Encodes `point` as the contract's `BN254.G1Point`: its `X` and `Y`
+/// coordinates as two 32-byte big-endian words, both zero for the identity.
+pub fn g1_to_abi_words(point: &G1Affine) -> [u8; 64] {
+    let mut words = [0u8; 64];
+    if let Some((x, y)) = point.xy() {
+        words[..32].copy_from_slice(&x.into_bigint().to_bytes_be());
+        words[32..].copy_from_slice(&y.into_bigint().to_bytes_be());
+    }
+    words
+}
+
+/// A keccak256 Merkle tree over the serialized blob headers of a batch,
+/// matching the batch root the EigenDA contracts check inclusion against.
+///
+/// Leaves are the keccak256 hashes of the serialized headers, and each node
+/// hashes the concatenation of its left and right children. The leaves are
+/// padded with zero hashes up to a power of 2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    // layers[0] holds the padded leaves and the last layer holds the root
+    layers: Vec<Vec<[u8; 32]>>,
+    num_leaves: usize,
+}
+
+impl MerkleTree {
+    /// Builds the tree over the serialized `headers`.
+    pub fn new<T: AsRef<[u8]>>(headers: &[T]) -> Self {
+        let leaves: Vec<[u8; 32]> = headers.iter().map(|h| keccak256(h.as_ref())).collect();
+        Self::from_leaf_hashes(&leaves)
+    }
+
+    /// Builds the tree over already hashed leaves. The root of an empty tree
+    /// is the zero hash.
+    pub fn from_leaf_hashes(leaves: &[[u8; 32]]) -> Self {
+        let mut layer = leaves.to_vec();
+        layer.resize(leaves.len().next_power_of_two(), [0u8; 32]);
+        let mut layers = vec![layer];
+        while layers[layers.len() - 1].len() > 1 {
+            let next = layers[layers.len() - 1]
+                .chunks(2)
+                .map(|pair| hash_pair(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next);
+        }
+        MerkleTree {
+            layers,
+            num_leaves: leaves.len(),
+        }
+    }
+
+    /// Returns the batch root.
+    pub fn get_root(&self) -> [u8; 32] {
+        self.layers[self.layers.len() - 1][0]
+    }
+
+    /// Returns the number of leaves, not counting padding.
+    pub fn get_num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Returns the inclusion proof of the leaf at `index`, or None if there
+    /// is no such leaf.
+    pub fn prove(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.num_leaves {
+            return None;
+        }
+        let siblings = self.layers[..self.layers.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(depth, layer)| layer[(index >> depth) ^ 1])
+            .collect();
+        Some(MerkleProof { index, siblings })
+    }
+}
+
+/// The siblings on the path from a leaf to the root, bottom up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+    index: usize,
+    siblings: Vec<[u8; 32]>,
+}
+
+impl MerkleProof {
+    /// Creates the proof for the leaf at `index` from its `siblings`, bottom
+    /// up.
+    pub fn new(index: usize, siblings: Vec<[u8; 32]>) -> Self {
+        MerkleProof { index, siblings }
+    }
+
+    /// Returns the index of the proven leaf.
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the siblings, bottom up.
+    pub fn get_siblings(&self) -> &[[u8; 32]] {
+        &self.siblings
+    }
+
+    /// Returns the siblings concatenated, the `bytes` inclusion proof the
+    /// contracts take.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.siblings.concat()
+    }
+
+    /// Checks the serialized `header` is the leaf at this proof's index of
+    /// the tree with `root`.
+    pub fn verify(&self, root: &[u8; 32], header: &[u8]) -> bool {
+        self.verify_leaf_hash(root, &keccak256(header))
+    }
+
+    /// Checks `leaf` is the leaf hash at this proof's index of the tree with
+    /// `root`.
+    pub fn verify_leaf_hash(&self, root: &[u8; 32], leaf: &[u8; 32]) -> bool {
+        if self.siblings.len() < usize::BITS as usize && self.index >> self.siblings.len() != 0 {
+            return false;
+        }
+        let computed = self
+            .siblings
+            .iter()
+            .enumerate()
+            .fold(*leaf, |node, (depth, sibling)| {
+                if (self.index >> depth) & 1 == 0 {
+                    hash_pair(&node, sibling)
+                } else {
+                    hash_pair(sibling, &node)
+                }
+            });
+        computed == *root
+    }
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Keccak256::new()
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            keccak256(b""),
+            hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+        let mut generator = [0u8; 64];
+        generator[31] = 1;
+        generator[63] = 2;
+        assert_eq!(g1_to_abi_words(&G1Affine::generator()), generator);
+        assert_eq!(g1_to_abi_words(&G1Affine::identity()), [0u8; 64]);
+    }
+
+    #[test]
+    fn test_merkle_tree() {
+        let headers: Vec<Vec<u8>> = (0u8..5).map(|i| vec![i; 40]).collect();
+        let tree = MerkleTree::new(&headers);
+        assert_eq!(tree.get_num_leaves(), 5);
+
+        // the root is built from pairs of leaves, padded with zero hashes
+        let leaves: Vec<[u8; 32]> = headers.iter().map(|h| keccak256(h)).collect();
+        let zero = [0u8; 32];
+        let root = hash_pair(
+            &hash_pair(
+                &hash_pair(&leaves[0], &leaves[1]),
+                &hash_pair(&leaves[2], &leaves[3]),
+            ),
+            &hash_pair(&hash_pair(&leaves[4], &zero), &hash_pair(&zero, &zero)),
+        );
+        assert_eq!(tree.get_root(), root);
+
+        for (i, header) in headers.iter().enumerate() {
+            let proof = tree.prove(i).unwrap();
+            assert_eq!(proof.get_siblings().len(), 3);
+            assert_eq!(proof.to_bytes().len(), 96);
+            assert!(proof.verify(&root, header));
+            assert!(!proof.verify(&root, &headers[(i + 1) % 5]));
+            assert!(!MerkleProof::new(i + 8, proof.get_siblings().to_vec()).verify(&root, header));
+        }
+        assert!(tree.prove(5).is_none());
+
+        let single = MerkleTree::new(&headers[..1]);
+        assert_eq!(single.get_root(), leaves[0]);
+        assert!(single.prove(0).unwrap().verify(&leaves[0], &headers[0]));
+        assert_eq!(MerkleTree::new::<Vec<u8>>(&[]).get_root(), zero);
+    }
+}
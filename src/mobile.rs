@@ -0,0 +1,88 @@
+//! UniFFI bindings for Kotlin and Swift light clients.
+//!
+//! The interface is declared in `src/rust_kzg_bn254.udl`. Only verification
+//! is exposed: it needs `[tau]G2` and no G1 points, so an app checks blob
+//! proofs on-device without shipping or loading the SRS files. Encodings
+//! match the proving service, so proofs it serves verify as they are.
+
+use crate::{
+    embedded::EmbeddedKzg,
+    encoding::{fr_from_bytes, g1_from_bytes},
+    errors::KzgError,
+};
+
+/// A verifier holding only `[tau]G2`.
+pub struct Verifier {
+    inner: EmbeddedKzg,
+}
+
+impl Verifier {
+    /// Takes `[tau]G2` as the 64-byte big-endian point of the SRS files.
+    pub fn new(g2_tau: Vec<u8>) -> Result<Self, KzgError> {
+        let inner = EmbeddedKzg::from_bytes(&[], &g2_tau)?;
+        Ok(Verifier { inner })
+    }
+
+    /// Checks `proof` opens `commitment` to `value` at `z`.
+    pub fn verify_proof(
+        &self,
+        commitment: Vec<u8>,
+        proof: Vec<u8>,
+        value: Vec<u8>,
+        z: Vec<u8>,
+    ) -> Result<bool, KzgError> {
+        Ok(self.inner.verify_proof(
+            g1_from_bytes(&commitment)?,
+            g1_from_bytes(&proof)?,
+            fr_from_bytes(&value)?,
+            fr_from_bytes(&z)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        blob::Blob,
+        consts::GETTYSBURG_ADDRESS_BYTES,
+        encoding::{fr_to_bytes, g1_to_bytes},
+        kzg::Kzg,
+    };
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_verifier() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let g2 = std::fs::read("src/test-files/g2.point").unwrap();
+        let verifier = Verifier::new(g2[64..128].to_vec()).unwrap();
+        assert!(Verifier::new(g2[64..127].to_vec()).is_err());
+
+        let polynomial = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES)
+            .to_polynomial()
+            .unwrap();
+        let z = Fr::from(99u64);
+        let (proof, value) = kzg.compute_kzg_proof_at_point(&polynomial, z).unwrap();
+        let commitment = g1_to_bytes(&kzg.commit(&polynomial).unwrap()).to_vec();
+        let verify = |value: Fr| {
+            verifier.verify_proof(
+                commitment.clone(),
+                g1_to_bytes(&proof).to_vec(),
+                fr_to_bytes(&value).to_vec(),
+                fr_to_bytes(&z).to_vec(),
+            )
+        };
+        assert!(verify(value).unwrap());
+        assert!(!verify(value + Fr::from(1u64)).unwrap());
+        assert!(verifier
+            .verify_proof(commitment.clone(), vec![0; 3], vec![0; 32], vec![0; 32])
+            .is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! Node.js bindings, a napi-rs addon.
+//!
+//! One `Kzg` instance loads the SRS and is shared by every call. `commit`,
+//! `prove` and `verify` return promises and run on the libuv thread pool, so
+//! a dispersal service proves without blocking its event loop or spawning
+//! subprocesses. Payloads are padded into blobs as `Blob::from_bytes_and_pad`
+//! does. Points are encoded as their gnark compressed big-endian encoding,
+//! with the flag bits in the top byte, and field elements as their 32
+//! big-endian bytes, as in the proving service.
+
+use crate::{
+    blob::Blob,
+    encoding::{fr_from_bytes, fr_to_bytes, g1_from_bytes, g1_to_bytes},
+    kzg::Kzg,
+    polynomial::Polynomial,
+};
+use ark_bn254::Fr;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use napi::{bindgen_prelude::*, Env, Task};
+use napi_derive::napi;
+use std::sync::Arc;
+
+/// A KZG setup shared by all the calls made on it.
+#[napi(js_name = "Kzg")]
+pub struct NodeKzg {
+    inner: Arc<Kzg>,
+}
+
+/// A blob's commitment with the opening of one of its field elements.
+#[napi(object)]
+pub struct Opening {
+    pub commitment: Buffer,
+    pub proof: Buffer,
+    pub value: Buffer,
+    pub z: Buffer,
+}
+
+#[napi]
+impl NodeKzg {
+    /// Loads `srs_points_to_load` points of an SRS of `srs_order` points, as
+    /// `Kzg::setup` does.
+    #[napi(constructor)]
+    pub fn new(
+        g1_path: String,
+        g2_path: String,
+        g2_power_of_2_path: String,
+        srs_order: u32,
+        srs_points_to_load: u32,
+    ) -> Result<Self> {
+        let kzg = Kzg::setup(
+            &g1_path,
+            &g2_path,
+            &g2_power_of_2_path,
+            srs_order,
+            srs_points_to_load,
+        )
+        .map_err(invalid_arg)?;
+        Ok(NodeKzg {
+            inner: Arc::new(kzg),
+        })
+    }
+
+    /// Resolves to the compressed commitment to the padded payload.
+    #[napi(ts_return_type = "Promise<Buffer>")]
+    pub fn commit(&self, payload: Buffer) -> AsyncTask<CommitTask> {
+        AsyncTask::new(CommitTask {
+            kzg: self.inner.clone(),
+            payload: payload.to_vec(),
+        })
+    }
+
+    /// Resolves to the opening of the padded payload at its `index`-th
+    /// field element.
+    #[napi(ts_return_type = "Promise<Opening>")]
+    pub fn prove(&self, payload: Buffer, index: u32) -> AsyncTask<ProveTask> {
+        AsyncTask::new(ProveTask {
+            kzg: self.inner.clone(),
+            payload: payload.to_vec(),
+            index: index as usize,
+        })
+    }
+
+    /// Resolves to whether `proof` opens `commitment` to `value` at `z`.
+    #[napi(ts_return_type = "Promise<boolean>")]
+    pub fn verify(
+        &self,
+        commitment: Buffer,
+        proof: Buffer,
+        value: Buffer,
+        z: Buffer,
+    ) -> AsyncTask<VerifyTask> {
+        AsyncTask::new(VerifyTask {
+            kzg: self.inner.clone(),
+            commitment: commitment.to_vec(),
+            proof: proof.to_vec(),
+            value: value.to_vec(),
+            z: z.to_vec(),
+        })
+    }
+}
+
+pub struct CommitTask {
+    kzg: Arc<Kzg>,
+    payload: Vec<u8>,
+}
+
+impl Task for CommitTask {
+    type Output = Vec<u8>;
+    type JsValue = Buffer;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let commitment = self
+            .kzg
+            .commit(&to_polynomial(&self.payload)?)
+            .map_err(invalid_arg)?;
+        Ok(g1_to_bytes(&commitment).to_vec())
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// the fields of an `Opening`, kept off the JS heap until it resolves
+type OpeningBytes = [Vec<u8>; 4];
+
+pub struct ProveTask {
+    kzg: Arc<Kzg>,
+    payload: Vec<u8>,
+    index: usize,
+}
+
+impl Task for ProveTask {
+    type Output = OpeningBytes;
+    type JsValue = Opening;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let polynomial = to_polynomial(&self.payload)?;
+        if self.index >= polynomial.len() {
+            return Err(invalid_arg("index is out of range of the polynomial"));
+        }
+        let z = GeneralEvaluationDomain::<Fr>::new(polynomial.len())
+            .ok_or_else(|| invalid_arg("blob is larger than the field supports"))?
+            .element(self.index);
+        let commitment = self.kzg.commit(&polynomial).map_err(invalid_arg)?;
+        let (proof, value) = self
+            .kzg
+            .compute_kzg_proof_at_point(&polynomial, z)
+            .map_err(invalid_arg)?;
+        Ok([
+            g1_to_bytes(&commitment).to_vec(),
+            g1_to_bytes(&proof).to_vec(),
+            fr_to_bytes(&value).to_vec(),
+            fr_to_bytes(&z).to_vec(),
+        ])
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        let [commitment, proof, value, z] = output;
+        Ok(Opening {
+            commitment: commitment.into(),
+            proof: proof.into(),
+            value: value.into(),
+            z: z.into(),
+        })
+    }
+}
+
+pub struct VerifyTask {
+    kzg: Arc<Kzg>,
+    commitment: Vec<u8>,
+    proof: Vec<u8>,
+    value: Vec<u8>,
+    z: Vec<u8>,
+}
+
+impl Task for VerifyTask {
+    type Output = bool;
+    type JsValue = bool;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(self.kzg.verify_kzg_proof(
+            g1_from_bytes(&self.commitment).map_err(invalid_arg)?,
+            g1_from_bytes(&self.proof).map_err(invalid_arg)?,
+            fr_from_bytes(&self.value).map_err(invalid_arg)?,
+            fr_from_bytes(&self.z).map_err(invalid_arg)?,
+        ))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+fn invalid_arg(err: impl ToString) -> Error {
+    Error::new(Status::InvalidArg, err.to_string())
+}
+
+fn to_polynomial(payload: &[u8]) -> Result<Polynomial> {
+    Blob::from_bytes_and_pad(payload)
+        .to_polynomial()
+        .map_err(invalid_arg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    // the tasks compute without a JS environment; only resolving needs one
+    #[test]
+    fn test_node_tasks() {
+        let kzg = Arc::new(
+            Kzg::setup(
+                "src/test-files/g1.point",
+                "src/test-files/g2.point",
+                "src/test-files/g2.point.powerOf2",
+                3000,
+                3000,
+            )
+            .unwrap(),
+        );
+        let payload = GETTYSBURG_ADDRESS_BYTES.to_vec();
+        let commitment = CommitTask {
+            kzg: kzg.clone(),
+            payload: payload.clone(),
+        }
+        .compute()
+        .unwrap();
+        let [opened_commitment, proof, value, z] = ProveTask {
+            kzg: kzg.clone(),
+            payload: payload.clone(),
+            index: 11,
+        }
+        .compute()
+        .unwrap();
+        assert_eq!(opened_commitment, commitment);
+
+        let mut verify = VerifyTask {
+            kzg: kzg.clone(),
+            commitment,
+            proof,
+            value,
+            z,
+        };
+        assert!(verify.compute().unwrap());
+        verify.value[31] ^= 1;
+        assert!(!verify.compute().unwrap());
+        verify.value.pop();
+        assert!(verify.compute().is_err());
+
+        let mut out_of_range = ProveTask {
+            kzg,
+            payload,
+            index: 1 << 20,
+        };
+        assert!(out_of_range.compute().is_err());
+    }
+}
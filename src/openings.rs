@@ -0,0 +1,26 @@
+use ark_bn254::Fr;
+
+/// A polynomial prepared for proving at many indices.
+///
+/// Created by `Kzg::prepare_openings`, which converts the evaluations to
+/// coefficient form once. Each proof from `Kzg::compute_kzg_proof_with_openings`
+/// is then a synthetic division by `(X - z)` and one MSM over the monomial SRS,
+/// skipping the G1 IFFT, the inversions and the on-domain quotient term that a
+/// standalone proof pays for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlobOpenings {
+    pub(crate) coefficients: Vec<Fr>,
+    pub(crate) roots_of_unity: Vec<Fr>,
+}
+
+impl BlobOpenings {
+    /// Returns the number of field elements in the prepared polynomial.
+    pub fn len(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// Checks if the prepared polynomial is empty.
+    pub fn is_empty(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{
+    cert::{self, BlobCert},
+    encoder::{Chunk, Encoder},
+    errors::CertError,
+    kzg::Kzg,
+    retrieve::Frame,
+};
+use ark_bn254::G1Affine;
+
+/// The contiguous range of chunk indices a DA node is responsible for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Assignment {
+    start_index: usize,
+    num_chunks: usize,
+}
+
+impl Assignment {
+    /// Assigns the `num_chunks` chunks starting at `start_index`.
+    pub fn new(start_index: usize, num_chunks: usize) -> Self {
+        Assignment {
+            start_index,
+            num_chunks,
+        }
+    }
+
+    /// Returns the index of the first assigned chunk.
+    pub fn get_start_index(&self) -> usize {
+        self.start_index
+    }
+
+    /// Returns the number of assigned chunks.
+    pub fn get_num_chunks(&self) -> usize {
+        self.num_chunks
+    }
+
+    /// Returns the index of the `position`-th assigned chunk.
+    pub fn chunk_index(&self, position: usize) -> usize {
+        self.start_index + position
+    }
+}
+
+/// Splits chunks between operators in proportion to `stakes`, in operator
+/// order.
+///
+/// Operator `i` gets `ceil(stakes[i] * num_chunks / total_stake)` chunks,
+/// starting right after operator `i - 1`'s, so every operator with stake gets
+/// at least one chunk. Rounding up can assign a few more than `num_chunks`
+/// chunks in total; blobs must then be encoded into at least `total_chunks`
+/// chunks.
+pub fn stake_assignments(stakes: &[u128], num_chunks: usize) -> Vec<Assignment> {
+    let total_stake: u128 = stakes.iter().sum();
+    let mut start_index = 0;
+    stakes
+        .iter()
+        .map(|&stake| {
+            let count = if total_stake == 0 {
+                0
+            } else {
+                (stake * num_chunks as u128).div_ceil(total_stake) as usize
+            };
+            let assignment = Assignment::new(start_index, count);
+            start_index += count;
+            assignment
+        })
+        .collect()
+}
+
+/// Returns the number of chunks `assignments` cover.
+pub fn total_chunks(assignments: &[Assignment]) -> usize {
+    assignments
+        .iter()
+        .map(|a| a.start_index + a.num_chunks)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Validates the chunks a DA node received for a blob before it signs for
+/// it: the chunks must be exactly those of `assignment`, in order, every
+/// multireveal proof must open against the commitment in `header`, and the
+/// header's commitments and length proof must hold.
+///
+/// Any frames already in `header` are ignored.
+pub fn validate_assignment(
+    kzg: &Kzg,
+    encoder: &Encoder,
+    header: &BlobCert,
+    assignment: &Assignment,
+    chunks: &[Chunk],
+    proofs: &[G1Affine],
+) -> Result<(), CertError> {
+    if chunks.len() != assignment.num_chunks || proofs.len() != assignment.num_chunks {
+        return Err(CertError::InvalidAssignmentError(format!(
+            "expected {} chunks and proofs, got {} chunks and {} proofs",
+            assignment.num_chunks,
+            chunks.len(),
+            proofs.len()
+        )));
+    }
+    if assignment.start_index + assignment.num_chunks > encoder.get_num_chunks() {
+        return Err(CertError::InvalidAssignmentError(
+            "assignment is beyond the chunks of the encoding".to_string(),
+        ));
+    }
+    for (position, chunk) in chunks.iter().enumerate() {
+        if chunk.get_index() != assignment.chunk_index(position) {
+            return Err(CertError::InvalidAssignmentError(format!(
+                "chunk {} is not assigned at position {}",
+                chunk.get_index(),
+                position
+            )));
+        }
+    }
+
+    let with_frames = BlobCert::new(
+        header.get_commitment(),
+        header.get_length_commitment(),
+        header.get_length_proof(),
+        header.get_length(),
+        chunks
+            .iter()
+            .cloned()
+            .zip(proofs.iter().copied())
+            .map(|(chunk, proof)| Frame::new(chunk, proof))
+            .collect(),
+    );
+    cert::verify_batch(kzg, encoder, &[with_frames])
+        .pop()
+        .unwrap_or(Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consts::GETTYSBURG_ADDRESS_BYTES, pipeline::BlobEncoder};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_stake_assignments() {
+        let assignments = stake_assignments(&[50, 30, 20, 0], 10);
+        assert_eq!(
+            assignments,
+            vec![
+                Assignment::new(0, 5),
+                Assignment::new(5, 3),
+                Assignment::new(8, 2),
+                Assignment::new(10, 0)
+            ]
+        );
+        assert_eq!(total_chunks(&assignments), 10);
+
+        // rounding up gives small operators a chunk each
+        let assignments = stake_assignments(&[1000, 1, 1], 8);
+        assert_eq!(
+            assignments
+                .iter()
+                .map(|a| a.get_num_chunks())
+                .collect::<Vec<_>>(),
+            [8, 1, 1]
+        );
+        assert_eq!(total_chunks(&assignments), 10);
+        assert!(stake_assignments(&[0, 0], 8)
+            .iter()
+            .all(|a| a.get_num_chunks() == 0));
+    }
+
+    #[test]
+    fn test_validate_assignment() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let assignments = stake_assignments(&[40, 35, 25], 16);
+        let pipeline = BlobEncoder::new(Arc::new(kzg), total_chunks(&assignments), 2).unwrap();
+        let encoded = pipeline.encode(GETTYSBURG_ADDRESS_BYTES).unwrap();
+        let header = encoded.to_cert(&[]);
+        let (kzg, encoder) = (pipeline.get_kzg(), pipeline.get_encoder());
+
+        let mine = |assignment: &Assignment| {
+            let frames = &encoded.get_frames()[assignment.get_start_index()..]
+                [..assignment.get_num_chunks()];
+            let chunks: Vec<Chunk> = frames.iter().map(|f| f.get_chunk().clone()).collect();
+            let proofs: Vec<G1Affine> = frames.iter().map(|f| f.get_proof()).collect();
+            (chunks, proofs)
+        };
+        for assignment in &assignments {
+            let (chunks, proofs) = mine(assignment);
+            assert_eq!(
+                validate_assignment(kzg, encoder, &header, assignment, &chunks, &proofs),
+                Ok(())
+            );
+        }
+
+        // chunks of another operator, missing proofs and a bad proof fail
+        let (chunks, proofs) = mine(&assignments[1]);
+        assert!(matches!(
+            validate_assignment(kzg, encoder, &header, &assignments[0], &chunks, &proofs),
+            Err(CertError::InvalidAssignmentError(_))
+        ));
+        assert!(matches!(
+            validate_assignment(
+                kzg,
+                encoder,
+                &header,
+                &assignments[1],
+                &chunks,
+                &proofs[1..]
+            ),
+            Err(CertError::InvalidAssignmentError(_))
+        ));
+        let mut swapped = proofs.clone();
+        swapped.swap(0, 1);
+        assert!(matches!(
+            validate_assignment(kzg, encoder, &header, &assignments[1], &chunks, &swapped),
+            Err(CertError::InvalidChunkProofError(_))
+        ));
+        let wrong_length = BlobCert::new(
+            header.get_commitment(),
+            header.get_length_commitment(),
+            header.get_length_proof(),
+            header.get_length() / 2,
+            vec![],
+        );
+        assert_eq!(
+            validate_assignment(
+                kzg,
+                encoder,
+                &wrong_length,
+                &assignments[1],
+                &chunks,
+                &proofs
+            ),
+            Err(CertError::InvalidLengthProofError)
+        );
+    }
+}
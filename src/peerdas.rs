@@ -0,0 +1,453 @@
+//! PeerDAS-style two-dimensional extension of a matrix of blobs.
+//!
+//! Each blob is a row. Rows are Reed-Solomon extended and split into cells
+//! with the row `Encoder`, and every column is then extended by a factor of 2
+//! across rows. Rows `0..num_rows` of the extended matrix are the original
+//! blobs and rows `num_rows..2 * num_rows` are the extension. Commitments and
+//! cell proofs are linear in the row polynomial, so those of the extension
+//! rows are computed by extending the original ones in G1 instead of proving
+//! the extension rows from scratch.
+
+use crate::{
+    consts::BYTES_PER_FIELD_ELEMENT,
+    domain::reverse_bits,
+    encoder::{Chunk, Encoder, EncodingParams},
+    errors::EncoderError,
+    kzg::Kzg,
+    polynomial::Polynomial,
+};
+use ark_bn254::{Fr, G1Affine, G1Projective};
+use ark_ec::CurveGroup;
+use ark_poly::{domain::DomainCoeff, EvaluationDomain, GeneralEvaluationDomain};
+
+/// The evaluations of one cell of the extended matrix, with the proof opening
+/// them against the commitment of the cell's row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cell {
+    row: usize,
+    column: usize,
+    evaluations: Vec<Fr>,
+    proof: G1Affine,
+}
+
+impl Cell {
+    /// Creates a cell received for `row` and `column` of an extended matrix.
+    pub fn new(row: usize, column: usize, evaluations: Vec<Fr>, proof: G1Affine) -> Self {
+        Cell {
+            row,
+            column,
+            evaluations,
+            proof,
+        }
+    }
+
+    /// Returns the row of the cell in the extended matrix.
+    pub fn get_row(&self) -> usize {
+        self.row
+    }
+
+    /// Returns the column of the cell, which is its chunk index in the row.
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the cell's evaluations.
+    pub fn get_evaluations(&self) -> &[Fr] {
+        &self.evaluations
+    }
+
+    /// Returns the proof of the cell.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+}
+
+/// A matrix of blobs extended in both dimensions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedMatrix {
+    params: EncodingParams,
+    row_commitments: Vec<G1Affine>,
+    cells: Vec<Cell>,
+}
+
+impl ExtendedMatrix {
+    /// Returns the layout of the cells within each row.
+    pub fn get_params(&self) -> EncodingParams {
+        self.params
+    }
+
+    /// Returns the number of rows, twice the number of blobs.
+    pub fn num_rows(&self) -> usize {
+        self.row_commitments.len()
+    }
+
+    /// Returns the commitment of every row of the extended matrix.
+    pub fn get_row_commitments(&self) -> &[G1Affine] {
+        &self.row_commitments
+    }
+
+    /// Returns every cell, row by row.
+    pub fn get_cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Returns the cell at `row` and `column`, if it exists.
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<&Cell> {
+        if column >= self.params.get_num_chunks() {
+            return None;
+        }
+        self.cells.get(row * self.params.get_num_chunks() + column)
+    }
+}
+
+/// Extends matrices of blobs in two dimensions, and verifies and
+/// reconstructs them cell by cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatrixEncoder {
+    row_encoder: Encoder,
+}
+
+impl MatrixEncoder {
+    /// Creates an encoder splitting each row into `cells_per_row` cells after
+    /// extending it by `coding_rate`.
+    pub fn new(cells_per_row: usize, coding_rate: usize) -> Result<Self, EncoderError> {
+        Ok(MatrixEncoder {
+            row_encoder: Encoder::new(cells_per_row, coding_rate)?,
+        })
+    }
+
+    /// Returns the encoder used for rows.
+    pub fn get_row_encoder(&self) -> &Encoder {
+        &self.row_encoder
+    }
+
+    /// Extends `polynomials`, one per row, which must be a power of 2 in
+    /// number and all of the same length.
+    pub fn extend(
+        &self,
+        kzg: &Kzg,
+        polynomials: &[Polynomial],
+    ) -> Result<ExtendedMatrix, EncoderError> {
+        let num_rows = polynomials.len();
+        if !num_rows.is_power_of_two() {
+            return Err(EncoderError::InvalidParametersError(
+                "number of rows must be a power of 2".to_string(),
+            ));
+        }
+        let length = polynomials[0].len();
+        if polynomials.iter().any(|p| p.len() != length) {
+            return Err(EncoderError::InvalidParametersError(
+                "rows must all have the same length".to_string(),
+            ));
+        }
+        let params = self.row_encoder.params(length)?;
+
+        let mut commitments = Vec::with_capacity(num_rows);
+        let mut proofs = Vec::with_capacity(num_rows);
+        for polynomial in polynomials {
+            commitments.push(
+                kzg.commit(polynomial)
+                    .map_err(|err| EncoderError::GenericError(err.to_string()))?,
+            );
+            proofs.push(self.row_encoder.prove_chunks(kzg, polynomial)?);
+        }
+
+        // extend the evaluations, commitments and proofs down each column
+        let mut rows = vec![Vec::with_capacity(length); 2 * num_rows];
+        for position in 0..length {
+            let column: Vec<Fr> = polynomials.iter().map(|p| p.as_slice()[position]).collect();
+            for (row, eval) in rows.iter_mut().zip(extend_column(&column)?) {
+                row.push(eval);
+            }
+        }
+        let row_commitments = extend_g1_column(&commitments)?;
+        let mut row_proofs = vec![Vec::with_capacity(params.get_num_chunks()); 2 * num_rows];
+        for column in 0..params.get_num_chunks() {
+            let column_proofs: Vec<G1Affine> = proofs.iter().map(|p| p[column]).collect();
+            for (row, proof) in row_proofs.iter_mut().zip(extend_g1_column(&column_proofs)?) {
+                row.push(proof);
+            }
+        }
+
+        let mut cells = Vec::with_capacity(2 * num_rows * params.get_num_chunks());
+        for (row, (evaluations, proofs)) in rows.iter().zip(&row_proofs).enumerate() {
+            let polynomial = Polynomial::new(evaluations, length * BYTES_PER_FIELD_ELEMENT)
+                .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+            for (chunk, proof) in self
+                .row_encoder
+                .encode_polynomial(&polynomial)?
+                .into_iter()
+                .zip(proofs)
+            {
+                cells.push(Cell::new(
+                    row,
+                    chunk.get_index(),
+                    chunk.get_evaluations().to_vec(),
+                    *proof,
+                ));
+            }
+        }
+
+        Ok(ExtendedMatrix {
+            params,
+            row_commitments,
+            cells,
+        })
+    }
+
+    /// Checks the proof of `cell` against the commitment of its row, for rows
+    /// of `length` field elements.
+    pub fn verify_cell(
+        &self,
+        kzg: &Kzg,
+        row_commitment: &G1Affine,
+        length: usize,
+        cell: &Cell,
+    ) -> Result<bool, EncoderError> {
+        let chunk = Chunk::new(cell.column, cell.evaluations.clone());
+        self.row_encoder
+            .verify_chunk(kzg, row_commitment, length, &chunk, &cell.proof)
+    }
+
+    /// Reconstructs the original blobs' polynomials from any sufficient subset
+    /// of the extended matrix's cells, given the padded length in bytes of
+    /// each blob.
+    ///
+    /// Alternates between recovering every row that holds enough cells and
+    /// every column that holds at least half of its rows, until nothing more
+    /// can be recovered, so cells missing from both a row and a column can
+    /// still be filled in from the other dimension.
+    pub fn reconstruct(
+        &self,
+        cells: &[Cell],
+        lengths_of_padded_blobs: &[usize],
+    ) -> Result<Vec<Polynomial>, EncoderError> {
+        let num_rows = lengths_of_padded_blobs.len();
+        if !num_rows.is_power_of_two() {
+            return Err(EncoderError::InvalidParametersError(
+                "number of rows must be a power of 2".to_string(),
+            ));
+        }
+        let length = lengths_of_padded_blobs
+            .iter()
+            .map(|len| len.div_ceil(BYTES_PER_FIELD_ELEMENT).next_power_of_two())
+            .max()
+            .unwrap_or_default();
+        let params = self.row_encoder.params(length)?;
+        let num_columns = params.get_num_chunks();
+        let chunk_length = params.get_chunk_length();
+        let row_bytes = length * BYTES_PER_FIELD_ELEMENT;
+
+        let mut grid: Vec<Vec<Option<Vec<Fr>>>> = vec![vec![None; num_columns]; 2 * num_rows];
+        for cell in cells {
+            if cell.row >= 2 * num_rows
+                || cell.column >= num_columns
+                || cell.evaluations.len() != chunk_length
+            {
+                return Err(EncoderError::InvalidParametersError(
+                    "cell does not match the encoding parameters".to_string(),
+                ));
+            }
+            grid[cell.row][cell.column] = Some(cell.evaluations.clone());
+        }
+
+        let column_encoder = Encoder::new(2 * num_rows, 2)?;
+        let column_bytes = num_rows * BYTES_PER_FIELD_ELEMENT;
+        loop {
+            let mut progress = false;
+
+            for row in grid.iter_mut() {
+                let received: Vec<Chunk> = row
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(column, evals)| {
+                        evals.as_ref().map(|e| Chunk::new(column, e.clone()))
+                    })
+                    .collect();
+                if received.len() == num_columns || received.len() * chunk_length < length {
+                    continue;
+                }
+                let polynomial = self.row_encoder.decode(&received, row_bytes)?;
+                for chunk in self.row_encoder.encode_polynomial(&polynomial)? {
+                    row[chunk.get_index()] = Some(chunk.get_evaluations().to_vec());
+                }
+                progress = true;
+            }
+
+            for column in 0..num_columns {
+                let present: Vec<usize> = (0..2 * num_rows)
+                    .filter(|&row| grid[row][column].is_some())
+                    .collect();
+                if present.len() == 2 * num_rows || present.len() < num_rows {
+                    continue;
+                }
+                let mut recovered = vec![Vec::with_capacity(chunk_length); 2 * num_rows];
+                for t in 0..chunk_length {
+                    let received: Vec<Chunk> = present
+                        .iter()
+                        .map(|&row| Chunk::new(row, vec![grid[row][column].as_ref().unwrap()[t]]))
+                        .collect();
+                    // the column polynomial holds the rows in bit-reversed order
+                    let decoded = column_encoder.decode(&received, column_bytes)?;
+                    let original: Vec<Fr> = (0..num_rows)
+                        .map(|row| decoded.as_slice()[reverse_bits(row, num_rows)])
+                        .collect();
+                    for (row, eval) in recovered.iter_mut().zip(extend_column(&original)?) {
+                        row.push(eval);
+                    }
+                }
+                for (row, evals) in recovered.into_iter().enumerate() {
+                    grid[row][column] = Some(evals);
+                }
+                progress = true;
+            }
+
+            if !progress {
+                break;
+            }
+        }
+
+        grid.iter()
+            .zip(lengths_of_padded_blobs)
+            .map(|(row, &length_of_padded_blob)| {
+                let chunks = row
+                    .iter()
+                    .enumerate()
+                    .map(|(column, evals)| {
+                        evals
+                            .as_ref()
+                            .map(|e| Chunk::new(column, e.clone()))
+                            .ok_or_else(|| {
+                                EncoderError::InvalidParametersError(
+                                    "not enough cells to reconstruct the matrix".to_string(),
+                                )
+                            })
+                    })
+                    .collect::<Result<Vec<Chunk>, EncoderError>>()?;
+                self.row_encoder.decode(&chunks, length_of_padded_blob)
+            })
+            .collect()
+    }
+}
+
+/// extends the values of the original rows in one column to all rows of the
+/// extended matrix, with the same layout as `Encoder` chunks of length 1
+fn extend_column<T: DomainCoeff<Fr>>(values: &[T]) -> Result<Vec<T>, EncoderError> {
+    let num_rows = values.len();
+    let domain = GeneralEvaluationDomain::<Fr>::new(num_rows);
+    let extended_domain = GeneralEvaluationDomain::<Fr>::new(2 * num_rows);
+    let (Some(domain), Some(extended_domain)) = (domain, extended_domain) else {
+        return Err(EncoderError::FftError(
+            "failed to construct domain for FFT".to_string(),
+        ));
+    };
+
+    let mut evaluations: Vec<T> = (0..num_rows)
+        .map(|k| values[reverse_bits(k, num_rows)])
+        .collect();
+    domain.ifft_in_place(&mut evaluations);
+    extended_domain.fft_in_place(&mut evaluations);
+    Ok((0..2 * num_rows)
+        .map(|row| evaluations[reverse_bits(row, 2 * num_rows)])
+        .collect())
+}
+
+fn extend_g1_column(points: &[G1Affine]) -> Result<Vec<G1Affine>, EncoderError> {
+    let projective: Vec<G1Projective> = points.iter().map(|&p| p.into()).collect();
+    Ok(G1Projective::normalize_batch(&extend_column(&projective)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob::Blob;
+
+    fn blobs() -> Vec<Blob> {
+        (0..4u8)
+            .map(|i| Blob::from_bytes_and_pad(&vec![i + 1; 300 + 30 * i as usize]))
+            .collect()
+    }
+
+    fn setup_kzg() -> Kzg {
+        Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extend_and_verify_cells() {
+        let kzg = setup_kzg();
+        let blobs = blobs();
+        let polynomials: Vec<Polynomial> =
+            blobs.iter().map(|b| b.to_polynomial().unwrap()).collect();
+        let encoder = MatrixEncoder::new(4, 2).unwrap();
+        let matrix = encoder.extend(&kzg, &polynomials).unwrap();
+        let length = polynomials[0].len();
+
+        assert_eq!(matrix.num_rows(), 8);
+        assert_eq!(matrix.get_cells().len(), 8 * 4);
+        for (row, polynomial) in polynomials.iter().enumerate() {
+            assert_eq!(
+                matrix.get_row_commitments()[row],
+                kzg.commit(polynomial).unwrap()
+            );
+            let chunks = encoder
+                .get_row_encoder()
+                .encode_polynomial(polynomial)
+                .unwrap();
+            for chunk in chunks {
+                let cell = matrix.get_cell(row, chunk.get_index()).unwrap();
+                assert_eq!(cell.get_evaluations(), chunk.get_evaluations());
+            }
+        }
+
+        for cell in matrix.get_cells() {
+            let commitment = matrix.get_row_commitments()[cell.get_row()];
+            assert!(encoder
+                .verify_cell(&kzg, &commitment, length, cell)
+                .unwrap());
+        }
+        let cell = matrix.get_cell(6, 1).unwrap();
+        let wrong_commitment = matrix.get_row_commitments()[5];
+        assert!(!encoder
+            .verify_cell(&kzg, &wrong_commitment, length, cell)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_reconstruct() {
+        let kzg = setup_kzg();
+        let blobs = blobs();
+        let lengths: Vec<usize> = blobs.iter().map(|b| b.len()).collect();
+        let polynomials: Vec<Polynomial> =
+            blobs.iter().map(|b| b.to_polynomial().unwrap()).collect();
+        let encoder = MatrixEncoder::new(4, 2).unwrap();
+        let matrix = encoder.extend(&kzg, &polynomials).unwrap();
+
+        // the first three rows hold one cell each, too few to decode, so they
+        // are only recovered once the other rows fill in the columns
+        let cells: Vec<Cell> = matrix
+            .get_cells()
+            .iter()
+            .filter(|cell| match cell.get_row() {
+                0..=2 => cell.get_column() == 0,
+                _ => cell.get_column() != 0,
+            })
+            .cloned()
+            .collect();
+        assert_eq!(encoder.reconstruct(&cells, &lengths).unwrap(), polynomials);
+
+        let too_few: Vec<Cell> = matrix
+            .get_cells()
+            .iter()
+            .filter(|cell| cell.get_row() < 3 && cell.get_column() < 2)
+            .cloned()
+            .collect();
+        assert!(encoder.reconstruct(&too_few, &lengths).is_err());
+    }
+}
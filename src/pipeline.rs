@@ -0,0 +1,171 @@
+use crate::{
+    cert::BlobCert,
+    disperse,
+    encoder::Encoder,
+    errors::EncoderError,
+    kzg::Kzg,
+    retrieve::{BlobHeader, Frame},
+};
+use ark_bn254::{G1Affine, G2Affine};
+use std::sync::Arc;
+
+/// A payload encoded for dispersal: its commitments and every chunk with its
+/// proof.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncodedBlob {
+    commitment: G1Affine,
+    length_commitment: G2Affine,
+    length_proof: G2Affine,
+    length: usize,
+    length_of_padded_blob: usize,
+    frames: Vec<Frame>,
+}
+
+impl EncodedBlob {
+    /// Returns the commitment to the blob's polynomial.
+    pub fn get_commitment(&self) -> G1Affine {
+        self.commitment
+    }
+
+    /// Returns the commitment to the blob's polynomial in G2.
+    pub fn get_length_commitment(&self) -> G2Affine {
+        self.length_commitment
+    }
+
+    /// Returns the proof that the polynomial has at most `get_length`
+    /// coefficients.
+    pub fn get_length_proof(&self) -> G2Affine {
+        self.length_proof
+    }
+
+    /// Returns the number of field elements the polynomial is over, a power
+    /// of 2.
+    pub fn get_length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the frames, ordered by chunk index.
+    pub fn get_frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Returns the header retrievers check the frames against.
+    pub fn get_header(&self) -> BlobHeader {
+        BlobHeader::new(self.commitment, self.length_of_padded_blob)
+    }
+
+    /// Returns the certificate of the blob, carrying the frames at
+    /// `sampled_indices` for spot checks.
+    pub fn to_cert(&self, sampled_indices: &[usize]) -> BlobCert {
+        BlobCert::new(
+            self.commitment,
+            self.length_commitment,
+            self.length_proof,
+            self.length,
+            sampled_indices
+                .iter()
+                .filter_map(|&i| self.frames.get(i).cloned())
+                .collect(),
+        )
+    }
+}
+
+/// The whole payload-to-proofs pipeline behind one call, configured once with
+/// an SRS and an encoding.
+///
+/// Named apart from `encoder::Encoder`, which it uses for the Reed-Solomon
+/// extension and the chunk proofs.
+#[derive(Clone, Debug)]
+pub struct BlobEncoder {
+    kzg: Arc<Kzg>,
+    encoder: Encoder,
+}
+
+impl BlobEncoder {
+    /// Creates a pipeline splitting blobs extended by `coding_rate` into
+    /// `num_chunks` chunks, with the same constraints as `Encoder::new`.
+    ///
+    /// Length proofs need `kzg` to hold the full G2 table up to its SRS order.
+    pub fn new(kzg: Arc<Kzg>, num_chunks: usize, coding_rate: usize) -> Result<Self, EncoderError> {
+        Ok(BlobEncoder {
+            kzg,
+            encoder: Encoder::new(num_chunks, coding_rate)?,
+        })
+    }
+
+    /// Returns the SRS the pipeline commits with.
+    pub fn get_kzg(&self) -> &Kzg {
+        &self.kzg
+    }
+
+    /// Returns the Reed-Solomon encoder the pipeline chunks with.
+    pub fn get_encoder(&self) -> &Encoder {
+        &self.encoder
+    }
+
+    /// Pads `payload` into a blob, commits to it, proves its length, and
+    /// splits it into chunks each with its multireveal proof.
+    pub fn encode(&self, payload: &[u8]) -> Result<EncodedBlob, EncoderError> {
+        let prepared = disperse::prepare(&self.kzg, &self.encoder, payload)?;
+        let polynomial = prepared
+            .get_blob()
+            .to_polynomial()
+            .map_err(|err| EncoderError::GenericError(err.to_string()))?;
+        let proofs = self.encoder.prove_chunks(&self.kzg, &polynomial)?;
+
+        Ok(EncodedBlob {
+            commitment: prepared.get_commitment(),
+            length_commitment: prepared.get_length_commitment(),
+            length_proof: prepared.get_length_proof(),
+            length: prepared.get_length(),
+            length_of_padded_blob: prepared.get_blob().len(),
+            frames: prepared
+                .get_chunks()
+                .iter()
+                .cloned()
+                .zip(proofs)
+                .map(|(chunk, proof)| Frame::new(chunk, proof))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cert, consts::GETTYSBURG_ADDRESS_BYTES, retrieve};
+
+    #[test]
+    fn test_blob_encoder_round_trip() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let pipeline = BlobEncoder::new(Arc::new(kzg), 16, 4).unwrap();
+        assert!(BlobEncoder::new(pipeline.kzg.clone(), 16, 3).is_err());
+
+        let encoded = pipeline.encode(GETTYSBURG_ADDRESS_BYTES).unwrap();
+        assert_eq!(encoded.get_frames().len(), 16);
+
+        let payload = retrieve::verify_and_reassemble(
+            pipeline.get_kzg(),
+            pipeline.get_encoder(),
+            &encoded.get_frames()[3..7],
+            &encoded.get_header(),
+        )
+        .unwrap();
+        assert_eq!(payload, GETTYSBURG_ADDRESS_BYTES);
+
+        let results = cert::verify_batch(
+            pipeline.get_kzg(),
+            pipeline.get_encoder(),
+            &[encoded.to_cert(&[0, 5, 15])],
+        );
+        assert_eq!(results, vec![Ok(())]);
+        assert_eq!(encoded.to_cert(&[0, 5, 16]).get_frames().len(), 2);
+    }
+}
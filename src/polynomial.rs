@@ -1,6 +1,8 @@
 use crate::{errors::PolynomialError, helpers};
 use ark_bn254::Fr;
-use ark_std::Zero;
+use ark_std::{string::ToString, vec, vec::Vec, Zero};
+#[cfg(feature = "std")]
+use {crate::domain::Domain, std::sync::Arc};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Polynomial {
@@ -55,10 +57,22 @@ impl Polynomial {
         helpers::to_byte_array(&self.elements, self.length_of_padded_blob)
     }
 
+    /// Returns the elements as a slice.
+    pub fn as_slice(&self) -> &[Fr] {
+        &self.elements
+    }
+
     /// Returns a clone of the elements as a `Vec<Fr>`.
     pub fn to_vec(&self) -> Vec<Fr> {
         self.elements.clone()
     }
+
+    /// Returns the domain of the roots of unity the elements are evaluations
+    /// over, shared with every polynomial of the same length.
+    #[cfg(feature = "std")]
+    pub fn domain(&self) -> Result<Arc<Domain>, PolynomialError> {
+        Domain::new(self.len()).map_err(|err| PolynomialError::GenericError(err.to_string()))
+    }
 }
 
 #[cfg(test)]
@@ -77,7 +91,7 @@ mod tests {
         );
 
         let polynomial_non_empty = Polynomial::new(&vec![Fr::one()], 2);
-        assert_eq!(polynomial_non_empty.unwrap().is_empty(), false);
+        assert!(!polynomial_non_empty.unwrap().is_empty());
     }
 
     #[test]
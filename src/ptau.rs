@@ -0,0 +1,314 @@
+//! Loading the SRS from snarkjs `.ptau` files.
+//!
+//! A `.ptau` file holds a powers of tau ceremony as numbered sections: the
+//! header (section 1) gives the field and the power `p` of the ceremony, the
+//! `2^(p+1) - 1` G1 powers of tau are in section 2 and the `2^p` G2 powers in
+//! section 3. Coordinates are 32-byte little-endian integers in Montgomery
+//! form, which is also how arkworks stores them, so they are read without
+//! conversion and only checked to be canonical and on the curve.
+
+use crate::{
+    errors::KzgError,
+    keys::{ProvingKey, VerifyingKey},
+    kzg::Kzg,
+};
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ff::{BigInteger, BigInteger256, PrimeField};
+use rayon::prelude::*;
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+};
+
+const MAGIC: &[u8; 4] = b"ptau";
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+// bytes per base field element
+const N8: usize = 32;
+
+/// The powers of tau read from a `.ptau` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PtauSrs {
+    g1: Vec<G1Affine>,
+    g2: Vec<G2Affine>,
+    power: u32,
+}
+
+impl PtauSrs {
+    /// Returns the G1 powers of tau that were read.
+    pub fn get_g1_points(&self) -> &[G1Affine] {
+        &self.g1
+    }
+
+    /// Returns the G2 powers of tau that were read.
+    pub fn get_g2_points(&self) -> &[G2Affine] {
+        &self.g2
+    }
+
+    /// Returns the power of the ceremony, which holds `2^power` G2 powers of
+    /// tau.
+    pub fn get_power(&self) -> u32 {
+        self.power
+    }
+}
+
+/// Reads the first `num_points` G1 and G2 powers of tau from a `.ptau`
+/// file, erroring if the ceremony is not over BN254 or has fewer than
+/// `num_points` powers in G2.
+pub fn read_ptau<R: Read + Seek>(mut reader: R, num_points: usize) -> Result<PtauSrs, KzgError> {
+    let mut magic = [0u8; 4];
+    read_bytes(&mut reader, &mut magic)?;
+    if &magic != MAGIC {
+        return Err(KzgError::SerializationError("not a ptau file".to_string()));
+    }
+    let _version = read_u32(&mut reader)?;
+    let num_sections = read_u32(&mut reader)?;
+
+    // sections may come in any order, so note where each one starts
+    let mut sections = Vec::with_capacity(num_sections as usize);
+    for _ in 0..num_sections {
+        let kind = read_u32(&mut reader)?;
+        let size = read_u64(&mut reader)?;
+        let start = reader.stream_position().map_err(io_error)?;
+        sections.push((kind, start, size));
+        reader
+            .seek(SeekFrom::Current(size as i64))
+            .map_err(io_error)?;
+    }
+    // seeking past the end succeeds, so a truncated file shows up here
+    let end = reader.seek(SeekFrom::End(0)).map_err(io_error)?;
+    if sections.iter().any(|&(_, start, size)| start + size > end) {
+        return Err(KzgError::SerializationError(
+            "ptau file is truncated".to_string(),
+        ));
+    }
+
+    seek_section(&mut reader, &sections, HEADER_SECTION)?;
+    let n8 = read_u32(&mut reader)? as usize;
+    let mut modulus = vec![0u8; n8];
+    read_bytes(&mut reader, &mut modulus)?;
+    if n8 != N8 || modulus != Fq::MODULUS.to_bytes_le() {
+        return Err(KzgError::SerializationError(
+            "ptau file is not over BN254".to_string(),
+        ));
+    }
+    let power = read_u32(&mut reader)?;
+    if power >= 32 || num_points > 1 << power {
+        return Err(KzgError::GenericError(format!(
+            "ptau file of power {power} does not hold {num_points} powers of tau"
+        )));
+    }
+
+    let g1_size = seek_section(&mut reader, &sections, TAU_G1_SECTION)?;
+    if g1_size < ((2u64 << power) - 1) * 2 * N8 as u64 {
+        return Err(KzgError::SerializationError(
+            "ptau G1 section is shorter than its power".to_string(),
+        ));
+    }
+    let g1 = read_points(&mut reader, num_points, 2 * N8, |bytes| {
+        let point = G1Affine::new_unchecked(read_fq(&bytes[..N8])?, read_fq(&bytes[N8..])?);
+        Ok(point)
+    })?;
+
+    let g2_size = seek_section(&mut reader, &sections, TAU_G2_SECTION)?;
+    if g2_size < (1u64 << power) * 4 * N8 as u64 {
+        return Err(KzgError::SerializationError(
+            "ptau G2 section is shorter than its power".to_string(),
+        ));
+    }
+    let g2 = read_points(&mut reader, num_points, 4 * N8, |bytes| {
+        let x = Fq2::new(read_fq(&bytes[..N8])?, read_fq(&bytes[N8..2 * N8])?);
+        let y = Fq2::new(read_fq(&bytes[2 * N8..3 * N8])?, read_fq(&bytes[3 * N8..])?);
+        Ok(G2Affine::new_unchecked(x, y))
+    })?;
+
+    Ok(PtauSrs { g1, g2, power })
+}
+
+impl Kzg {
+    /// Creates a setup from the first `srs_points_to_load` powers of tau of a
+    /// snarkjs `.ptau` file, with an SRS order of the `2^power` powers the
+    /// ceremony holds in G2. The G2 points cannot be reloaded once released.
+    pub fn setup_from_ptau(path: &str, srs_points_to_load: u32) -> Result<Self, KzgError> {
+        let file = File::open(path).map_err(|e| KzgError::GenericError(e.to_string()))?;
+        let srs = read_ptau(BufReader::new(file), srs_points_to_load as usize)?;
+        Self::from_keys(
+            ProvingKey::new(srs.g1, 1u64 << srs.power)?,
+            VerifyingKey::from_powers(srs.g2)?,
+        )
+    }
+}
+
+/// Moves `reader` to the start of the first section of `kind`, returning
+/// its size.
+fn seek_section<R: Seek>(
+    reader: &mut R,
+    sections: &[(u32, u64, u64)],
+    kind: u32,
+) -> Result<u64, KzgError> {
+    let &(_, start, size) = sections
+        .iter()
+        .find(|(section, ..)| *section == kind)
+        .ok_or_else(|| KzgError::SerializationError(format!("ptau file has no section {kind}")))?;
+    reader.seek(SeekFrom::Start(start)).map_err(io_error)?;
+    Ok(size)
+}
+
+/// Reads `count` points of `point_size` bytes, parsing them in parallel and
+/// rejecting any off the curve or outside the prime order subgroup. All-zero
+/// bytes encode the point at infinity.
+pub(crate) fn read_points<R: Read, C: SWCurveConfig>(
+    reader: &mut R,
+    count: usize,
+    point_size: usize,
+    parse: impl Fn(&[u8]) -> Result<Affine<C>, KzgError> + Sync,
+) -> Result<Vec<Affine<C>>, KzgError> {
+    let mut bytes = vec![0u8; count * point_size];
+    read_bytes(reader, &mut bytes)?;
+    bytes
+        .par_chunks_exact(point_size)
+        .map(|chunk| {
+            if chunk.iter().all(|&byte| byte == 0) {
+                return Ok(Affine::identity());
+            }
+            let point = parse(chunk)?;
+            if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+                return Err(KzgError::SerializationError(
+                    "SRS point is not in the subgroup".to_string(),
+                ));
+            }
+            Ok(point)
+        })
+        .collect()
+}
+
+/// Reads a base field element stored as a little-endian integer in
+/// Montgomery form.
+fn read_fq(bytes: &[u8]) -> Result<Fq, KzgError> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("chunks are 8 bytes"));
+    }
+    let montgomery = BigInteger256::new(limbs);
+    if montgomery >= Fq::MODULUS {
+        return Err(KzgError::SerializationError(
+            "ptau coordinate is not a field element".to_string(),
+        ));
+    }
+    Ok(Fq::new_unchecked(montgomery))
+}
+
+fn read_bytes<R: Read>(reader: &mut R, bytes: &mut [u8]) -> Result<(), KzgError> {
+    reader.read_exact(bytes).map_err(io_error)
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, KzgError> {
+    let mut bytes = [0u8; 4];
+    read_bytes(reader, &mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, KzgError> {
+    let mut bytes = [0u8; 8];
+    read_bytes(reader, &mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn io_error(err: std::io::Error) -> KzgError {
+    KzgError::SerializationError(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+    use ark_ec::AffineRepr;
+    use std::io::Cursor;
+
+    fn write_fq(bytes: &mut Vec<u8>, value: &Fq) {
+        for limb in value.0 .0 {
+            bytes.extend_from_slice(&limb.to_le_bytes());
+        }
+    }
+
+    fn write_section(bytes: &mut Vec<u8>, kind: u32, data: &[u8]) {
+        bytes.extend_from_slice(&kind.to_le_bytes());
+        bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(data);
+    }
+
+    // writes the points of `kzg` as a ptau file of `power`, sections in
+    // reverse order
+    fn write_ptau(kzg: &Kzg, power: u32) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend_from_slice(&(N8 as u32).to_le_bytes());
+        header.extend_from_slice(&Fq::MODULUS.to_bytes_le());
+        header.extend_from_slice(&power.to_le_bytes());
+        header.extend_from_slice(&power.to_le_bytes());
+        let mut tau_g1 = Vec::new();
+        for point in &kzg.get_g1_points()[..(2 << power) - 1] {
+            let (x, y) = point.xy().unwrap();
+            write_fq(&mut tau_g1, x);
+            write_fq(&mut tau_g1, y);
+        }
+        let mut tau_g2 = Vec::new();
+        for point in &kzg.get_g2_points()[..1 << power] {
+            let (x, y) = point.xy().unwrap();
+            for coordinate in [x.c0, x.c1, y.c0, y.c1] {
+                write_fq(&mut tau_g2, &coordinate);
+            }
+        }
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        write_section(&mut bytes, TAU_G2_SECTION, &tau_g2);
+        write_section(&mut bytes, TAU_G1_SECTION, &tau_g1);
+        write_section(&mut bytes, HEADER_SECTION, &header);
+        bytes
+    }
+
+    #[test]
+    fn test_read_ptau() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let ptau = write_ptau(&kzg, 10);
+        let srs = read_ptau(Cursor::new(&ptau), 1000).unwrap();
+        assert_eq!(srs.get_power(), 10);
+        assert_eq!(srs.get_g1_points(), &kzg.get_g1_points()[..1000]);
+        assert_eq!(srs.get_g2_points(), &kzg.get_g2_points()[..1000]);
+
+        let path = std::env::temp_dir().join("rust-kzg-bn254-test.ptau");
+        std::fs::write(&path, &ptau).unwrap();
+        let from_ptau = Kzg::setup_from_ptau(path.to_str().unwrap(), 1024).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let commitment = from_ptau.blob_to_kzg_commitment(&blob).unwrap();
+        assert_eq!(commitment, kzg.blob_to_kzg_commitment(&blob).unwrap());
+        let proof = from_ptau
+            .compute_blob_kzg_proof(&blob, &commitment)
+            .unwrap();
+        assert!(kzg
+            .verify_blob_kzg_proof(&blob, &commitment, &proof)
+            .unwrap());
+
+        // more points than the ceremony's power, or a damaged file
+        assert!(read_ptau(Cursor::new(&ptau), 1025).is_err());
+        assert!(read_ptau(Cursor::new(&ptau[..ptau.len() - 1]), 16).is_err());
+        let mut corrupted = ptau.clone();
+        corrupted[0] = b'x';
+        assert!(read_ptau(Cursor::new(&corrupted), 16).is_err());
+        // the first G2 point sits after the section header
+        let mut corrupted = ptau;
+        corrupted[12 + 12] ^= 1;
+        assert!(read_ptau(Cursor::new(&corrupted), 16).is_err());
+    }
+}
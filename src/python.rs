@@ -0,0 +1,249 @@
+//! Python bindings, the `rust_kzg_bn254` extension module.
+//!
+//! Wraps `Blob` and `Kzg` with bytes-in, bytes-out methods for data
+//! pipelines and research scripts. Points are encoded as their gnark
+//! compressed big-endian encoding, with the flag bits in the top byte, and
+//! field elements as their 32 big-endian bytes, as in the proving service. Build the wheel with
+//! `maturin build --features python`.
+
+use crate::{
+    blob::Blob,
+    encoding::{fr_from_bytes, fr_to_bytes, g1_from_bytes, g1_to_bytes},
+    errors::KzgError,
+    kzg::Kzg,
+};
+use ark_bn254::Fr;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use std::borrow::Cow;
+
+/// A padded blob.
+#[pyclass(name = "Blob", module = "rust_kzg_bn254")]
+#[derive(Clone)]
+pub struct PyBlob {
+    inner: Blob,
+}
+
+#[pymethods]
+impl PyBlob {
+    /// Pads `data` into a blob.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> Self {
+        PyBlob {
+            inner: Blob::from_bytes_and_pad(data),
+        }
+    }
+
+    /// Returns the padded blob bytes.
+    fn to_bytes(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.inner.get_blob_data())
+    }
+
+    /// Returns the unpadded data.
+    fn to_data(&self) -> PyResult<Cow<'static, [u8]>> {
+        let mut blob = self.inner.clone();
+        blob.remove_padding().map_err(value_error)?;
+        Ok(Cow::Owned(blob.get_blob_data()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+/// The opening of a blob at one of its field elements.
+#[pyclass(name = "Proof", module = "rust_kzg_bn254")]
+#[derive(Clone)]
+pub struct PyProof {
+    commitment: Vec<u8>,
+    proof: Vec<u8>,
+    value: Vec<u8>,
+    z: Vec<u8>,
+}
+
+#[pymethods]
+impl PyProof {
+    /// Returns the compressed commitment to the blob.
+    #[getter]
+    fn commitment(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.commitment.clone())
+    }
+
+    /// Returns the compressed proof.
+    #[getter]
+    fn proof(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.proof.clone())
+    }
+
+    /// Returns the field element at the index.
+    #[getter]
+    fn value(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.value.clone())
+    }
+
+    /// Returns the root of unity the blob is opened at.
+    #[getter]
+    fn z(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.z.clone())
+    }
+}
+
+/// A KZG setup loaded from SRS files.
+#[pyclass(name = "Kzg", module = "rust_kzg_bn254", frozen)]
+pub struct PyKzg {
+    inner: Kzg,
+}
+
+#[pymethods]
+impl PyKzg {
+    /// Loads `srs_points_to_load` points of an SRS of `srs_order` points, as
+    /// `Kzg::setup` does.
+    #[new]
+    fn new(
+        g1_path: &str,
+        g2_path: &str,
+        g2_power_of_2_path: &str,
+        srs_order: u32,
+        srs_points_to_load: u32,
+    ) -> PyResult<Self> {
+        let inner = Kzg::setup(
+            g1_path,
+            g2_path,
+            g2_power_of_2_path,
+            srs_order,
+            srs_points_to_load,
+        )
+        .map_err(value_error)?;
+        Ok(PyKzg { inner })
+    }
+
+    /// Returns the compressed commitment to `blob`.
+    fn commit(&self, py: Python<'_>, blob: &PyBlob) -> PyResult<Cow<'static, [u8]>> {
+        let polynomial = blob.inner.to_polynomial().map_err(value_error)?;
+        let commitment = py
+            .allow_threads(|| self.inner.commit(&polynomial))
+            .map_err(value_error)?;
+        Ok(Cow::Owned(g1_to_bytes(&commitment).to_vec()))
+    }
+
+    /// Opens `blob` at its `index`-th field element.
+    fn compute_proof(&self, py: Python<'_>, blob: &PyBlob, index: u64) -> PyResult<PyProof> {
+        let polynomial = blob.inner.to_polynomial().map_err(value_error)?;
+        let domain = GeneralEvaluationDomain::<Fr>::new(polynomial.len())
+            .ok_or_else(|| PyValueError::new_err("blob is larger than the field supports"))?;
+        let index = usize::try_from(index)
+            .ok()
+            .filter(|&i| i < polynomial.len())
+            .ok_or_else(|| PyValueError::new_err("index is out of range of the polynomial"))?;
+        let z = domain.element(index);
+        let (commitment, (proof, value)) = py
+            .allow_threads(|| {
+                Ok::<_, KzgError>((
+                    self.inner.commit(&polynomial)?,
+                    self.inner.compute_kzg_proof_at_point(&polynomial, z)?,
+                ))
+            })
+            .map_err(value_error)?;
+        Ok(PyProof {
+            commitment: g1_to_bytes(&commitment).to_vec(),
+            proof: g1_to_bytes(&proof).to_vec(),
+            value: fr_to_bytes(&value).to_vec(),
+            z: fr_to_bytes(&z).to_vec(),
+        })
+    }
+
+    /// Checks `proof` opens `commitment` to `value` at `z`.
+    fn verify_proof(
+        &self,
+        commitment: &[u8],
+        proof: &[u8],
+        value: &[u8],
+        z: &[u8],
+    ) -> PyResult<bool> {
+        Ok(self.inner.verify_kzg_proof(
+            g1_from_bytes(commitment).map_err(value_error)?,
+            g1_from_bytes(proof).map_err(value_error)?,
+            fr_from_bytes(value).map_err(value_error)?,
+            fr_from_bytes(z).map_err(value_error)?,
+        ))
+    }
+
+    /// Checks an opening from `compute_proof`.
+    fn verify(&self, proof: &PyProof) -> PyResult<bool> {
+        self.verify_proof(&proof.commitment, &proof.proof, &proof.value, &proof.z)
+    }
+}
+
+#[pymodule]
+fn rust_kzg_bn254(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyBlob>()?;
+    m.add_class::<PyProof>()?;
+    m.add_class::<PyKzg>()?;
+    Ok(())
+}
+
+fn value_error(err: impl ToString) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    #[test]
+    fn test_python_bindings() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let kzg = PyKzg::new(
+                "src/test-files/g1.point",
+                "src/test-files/g2.point",
+                "src/test-files/g2.point.powerOf2",
+                3000,
+                3000,
+            )
+            .unwrap();
+            let blob = PyBlob::from_bytes(GETTYSBURG_ADDRESS_BYTES);
+            assert_eq!(blob.to_data().unwrap().as_ref(), GETTYSBURG_ADDRESS_BYTES);
+
+            let proof = kzg.compute_proof(py, &blob, 3).unwrap();
+            assert_eq!(
+                kzg.commit(py, &blob).unwrap().as_ref(),
+                proof.commitment.as_slice()
+            );
+            assert!(kzg.verify(&proof).unwrap());
+            let mut wrong = proof.clone();
+            wrong.value[31] ^= 1;
+            assert!(!kzg.verify(&wrong).unwrap());
+            wrong.value = vec![1];
+            assert!(kzg.verify(&wrong).is_err());
+            assert!(kzg.compute_proof(py, &blob, 1 << 20).is_err());
+
+            // the module exposes the classes under their Python names
+            let module = PyModule::new_bound(py, "rust_kzg_bn254").unwrap();
+            rust_kzg_bn254(&module).unwrap();
+            let blob = module
+                .getattr("Blob")
+                .unwrap()
+                .call_method1("from_bytes", (&b"abc"[..],))
+                .unwrap();
+            assert_eq!(blob.len().unwrap(), 4);
+            let proof = module
+                .getattr("Kzg")
+                .unwrap()
+                .call1((
+                    "src/test-files/g1.point",
+                    "src/test-files/g2.point",
+                    "src/test-files/g2.point.powerOf2",
+                    3000,
+                    3000,
+                ))
+                .unwrap()
+                .call_method1("compute_proof", (blob, 0))
+                .unwrap();
+            let value = proof.getattr("value").unwrap();
+            assert!(value.is_instance_of::<pyo3::types::PyBytes>());
+            assert_eq!(value.len().unwrap(), 32);
+        });
+    }
+}
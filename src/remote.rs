@@ -0,0 +1,173 @@
+//! Fetching the SRS over HTTP.
+//!
+//! `Kzg::setup_from_url` downloads the point files a service would otherwise
+//! ship with, checking each against a SHA-256 digest. The points are parsed
+//! as the response streams in, so the files are never held in memory or on
+//! disk, and the rest of a file past the points that are loaded is still
+//! read to complete its digest. The download runs on tokio's blocking pool.
+
+use crate::{errors::KzgError, kzg::Kzg};
+use sha2::{Digest, Sha256};
+use std::io::{self, Read};
+
+/// Reads a response body while hashing it.
+struct DigestReader<R> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> DigestReader<R> {
+    fn new(inner: R) -> Self {
+        DigestReader {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// reads the rest of the body and checks its digest is `expected`
+    fn finish(mut self, url: &str, expected: &[u8; 32]) -> Result<(), KzgError> {
+        io::copy(&mut self, &mut io::sink())
+            .map_err(|e| KzgError::GenericError(format!("{url}: {e}")))?;
+        let digest: [u8; 32] = self.hasher.finalize().into();
+        if digest != *expected {
+            return Err(KzgError::GenericError(format!(
+                "{url} does not match the expected SHA-256 digest"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DigestReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+fn get(url: &str) -> Result<DigestReader<impl Read>, KzgError> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| KzgError::GenericError(format!("{url}: {e}")))?;
+    Ok(DigestReader::new(response.into_reader()))
+}
+
+impl Kzg {
+    /// Creates a setup like `setup_from_reader` from the SRS files at
+    /// `g1_url` and `g2_url`, which must have the SHA-256 digests
+    /// `g1_sha256` and `g2_sha256`. Must be called from within a tokio
+    /// runtime.
+    pub async fn setup_from_url(
+        g1_url: &str,
+        g1_sha256: [u8; 32],
+        g2_url: &str,
+        g2_sha256: [u8; 32],
+        g2_is_power_of_2: bool,
+        srs_order: u32,
+        srs_points_to_load: u32,
+    ) -> Result<Self, KzgError> {
+        let (g1_url, g2_url) = (g1_url.to_string(), g2_url.to_string());
+        tokio::task::spawn_blocking(move || {
+            let mut g1_reader = get(&g1_url)?;
+            let mut g2_reader = get(&g2_url)?;
+            let kzg = Kzg::setup_from_reader(
+                &mut g1_reader,
+                &mut g2_reader,
+                g2_is_power_of_2,
+                srs_order,
+                srs_points_to_load,
+            )?;
+            g1_reader.finish(&g1_url, &g1_sha256)?;
+            g2_reader.finish(&g2_url, &g2_sha256)?;
+            Ok(kzg)
+        })
+        .await
+        .map_err(|e| KzgError::GenericError(e.to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{TcpListener, TcpStream},
+        sync::Arc,
+    };
+
+    // serves `files` by path until the test ends
+    fn serve(files: Vec<(&'static str, Vec<u8>)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let files = Arc::new(files);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let files = files.clone();
+                std::thread::spawn(move || respond(stream.unwrap(), &files));
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    fn respond(mut stream: TcpStream, files: &[(&str, Vec<u8>)]) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        // skip the headers
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        let path = request_line.split(' ').nth(1).unwrap_or_default();
+        let (status, body) = match files.iter().find(|(name, _)| *name == path) {
+            Some((_, body)) => ("200 OK", body.as_slice()),
+            None => ("404 Not Found", &[][..]),
+        };
+        let mut response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        // the client may hang up early
+        let _ = stream.write_all(&response);
+    }
+
+    #[test]
+    fn test_setup_from_url() {
+        let g1 = std::fs::read("src/test-files/g1.point").unwrap();
+        let g2 = std::fs::read("src/test-files/g2.point").unwrap();
+        let (g1_sha256, g2_sha256): ([u8; 32], [u8; 32]) =
+            (Sha256::digest(&g1).into(), Sha256::digest(&g2).into());
+        let base = serve(vec![("/g1.point", g1), ("/g2.point", g2)]);
+        let (g1_url, g2_url) = (format!("{base}/g1.point"), format!("{base}/g2.point"));
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let setup = |g1_sha256, g2_url: &str| {
+            runtime.block_on(Kzg::setup_from_url(
+                &g1_url, g1_sha256, g2_url, g2_sha256, false, 3000, 1000,
+            ))
+        };
+
+        let kzg = setup(g1_sha256, &g2_url).unwrap();
+        let expected = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            1000,
+        )
+        .unwrap();
+        assert_eq!(kzg.get_g1_points(), expected.get_g1_points());
+        assert_eq!(kzg.get_g2_points(), expected.get_g2_points());
+
+        // the digest covers the points past those that are loaded
+        let mut wrong = g1_sha256;
+        wrong[0] ^= 1;
+        let mismatch = setup(wrong, &g2_url);
+        assert!(
+            matches!(mismatch, Err(KzgError::GenericError(message)) if message.contains("SHA-256"))
+        );
+        assert!(setup(g1_sha256, &format!("{base}/missing.point")).is_err());
+    }
+}
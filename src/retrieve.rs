@@ -0,0 +1,185 @@
+use crate::{
+    consts::BYTES_PER_FIELD_ELEMENT,
+    encoder::{Chunk, Encoder},
+    errors::EncoderError,
+    helpers,
+    kzg::Kzg,
+};
+use ark_bn254::G1Affine;
+use rayon::prelude::*;
+
+/// What a client knows about a dispersed blob before retrieving it: the
+/// commitment to its polynomial and the length of the padded blob in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlobHeader {
+    commitment: G1Affine,
+    length_of_padded_blob: usize,
+}
+
+impl BlobHeader {
+    /// Creates a header for a blob of `length_of_padded_blob` bytes committed
+    /// to by `commitment`.
+    pub fn new(commitment: G1Affine, length_of_padded_blob: usize) -> Self {
+        BlobHeader {
+            commitment,
+            length_of_padded_blob,
+        }
+    }
+
+    /// Returns the commitment to the blob's polynomial.
+    pub fn get_commitment(&self) -> G1Affine {
+        self.commitment
+    }
+
+    /// Returns the length of the padded blob in bytes.
+    pub fn get_length_of_padded_blob(&self) -> usize {
+        self.length_of_padded_blob
+    }
+
+    /// Returns the number of field elements of the blob's polynomial, a power
+    /// of 2.
+    pub fn get_length(&self) -> usize {
+        self.length_of_padded_blob
+            .div_ceil(BYTES_PER_FIELD_ELEMENT)
+            .next_power_of_two()
+    }
+}
+
+/// A chunk as served by a DA node, together with its multireveal proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    chunk: Chunk,
+    proof: G1Affine,
+}
+
+impl Frame {
+    /// Pairs `chunk` with the `proof` of its evaluations.
+    pub fn new(chunk: Chunk, proof: G1Affine) -> Self {
+        Frame { chunk, proof }
+    }
+
+    /// Returns the chunk.
+    pub fn get_chunk(&self) -> &Chunk {
+        &self.chunk
+    }
+
+    /// Returns the proof of the chunk's evaluations.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+
+    /// Checks the frame's evaluations over the coset of its chunk index
+    /// against the blob commitment in `header`, so a DA node can validate
+    /// the frames it receives without the rest of the blob.
+    pub fn verify(
+        &self,
+        kzg: &Kzg,
+        encoder: &Encoder,
+        header: &BlobHeader,
+    ) -> Result<bool, EncoderError> {
+        encoder.verify_chunk(
+            kzg,
+            &header.commitment,
+            header.get_length(),
+            &self.chunk,
+            &self.proof,
+        )
+    }
+}
+
+/// Checks every frame against the commitment in `header`, decodes the blob's
+/// polynomial from them and returns the original payload with the padding
+/// stripped.
+///
+/// Frames are verified in parallel on the thread pool configured on `kzg`.
+/// The first frame with an invalid proof fails the whole call with
+/// `InvalidChunkProofError` carrying its chunk index, so the caller can fetch
+/// that chunk elsewhere and retry.
+pub fn verify_and_reassemble(
+    kzg: &Kzg,
+    encoder: &Encoder,
+    frames: &[Frame],
+    header: &BlobHeader,
+) -> Result<Vec<u8>, EncoderError> {
+    if header.length_of_padded_blob == 0 {
+        return Err(EncoderError::InvalidParametersError(
+            "blob length must be positive".to_string(),
+        ));
+    }
+    kzg.get_config()
+        .install(|| {
+            frames.par_iter().try_for_each(|frame| {
+                if frame.verify(kzg, encoder, header)? {
+                    Ok(())
+                } else {
+                    Err(EncoderError::InvalidChunkProofError(
+                        frame.chunk.get_index(),
+                    ))
+                }
+            })
+        })
+        .map_err(|err| EncoderError::GenericError(err.to_string()))??;
+
+    let chunks: Vec<Chunk> = frames.iter().map(|frame| frame.chunk.clone()).collect();
+    let polynomial = encoder.decode(&chunks, header.length_of_padded_blob)?;
+    Ok(helpers::remove_empty_byte_from_padded_bytes(
+        &polynomial.to_bytes_be(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_verify_and_reassemble() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let encoder = Encoder::new(16, 4).unwrap();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = blob.to_polynomial().unwrap();
+        let header = BlobHeader::new(kzg.commit(&polynomial).unwrap(), blob.len());
+        assert_eq!(header.get_length(), polynomial.len());
+
+        let chunks = encoder.encode_polynomial(&polynomial).unwrap();
+        let proofs = encoder.prove_chunks(&kzg, &polynomial).unwrap();
+        let frames: Vec<Frame> = chunks
+            .into_iter()
+            .zip(proofs)
+            .map(|(c, p)| Frame::new(c, p))
+            .collect();
+
+        for frame in &frames {
+            assert!(frame.verify(&kzg, &encoder, &header).unwrap());
+        }
+        let payload = verify_and_reassemble(&kzg, &encoder, &frames, &header).unwrap();
+        assert_eq!(payload, GETTYSBURG_ADDRESS_BYTES);
+        let payload = verify_and_reassemble(&kzg, &encoder, &frames[5..9], &header).unwrap();
+        assert_eq!(payload, GETTYSBURG_ADDRESS_BYTES);
+
+        // too few frames, a tampered frame and the wrong commitment all fail
+        assert!(verify_and_reassemble(&kzg, &encoder, &frames[..3], &header).is_err());
+        let mut tampered = frames[4..8].to_vec();
+        let mut evaluations = tampered[2].chunk.get_evaluations().to_vec();
+        evaluations[0] += Fr::from(1u64);
+        tampered[2] = Frame::new(Chunk::new(6, evaluations), tampered[2].proof);
+        assert!(!tampered[2].verify(&kzg, &encoder, &header).unwrap());
+        let evaluations = frames[6].chunk.get_evaluations().to_vec();
+        let moved = Frame::new(Chunk::new(7, evaluations), frames[6].proof);
+        assert!(!moved.verify(&kzg, &encoder, &header).unwrap());
+        assert_eq!(
+            verify_and_reassemble(&kzg, &encoder, &tampered, &header),
+            Err(EncoderError::InvalidChunkProofError(6))
+        );
+        let other = BlobHeader::new(G1Affine::identity(), blob.len());
+        assert!(verify_and_reassemble(&kzg, &encoder, &frames[..4], &other).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{
+    consts::{SAMPLE_CHALLENGE_DOMAIN, SAMPLE_INDICES_DOMAIN, SAMPLE_SEED_INDICES_DOMAIN},
+    encoding::g1_to_bytes,
+    errors::KzgError,
+    kzg::Kzg,
+    polynomial::Polynomial,
+    transcript::Transcript,
+};
+use ark_bn254::{Fr, G1Affine, G1Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{FftField, Field};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// An opening of a committed polynomial at the `index`-th root of unity of
+/// its domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sample {
+    index: u64,
+    value: Fr,
+    proof: G1Affine,
+}
+
+impl Sample {
+    /// Creates a sample claiming the polynomial evaluates to `value` at
+    /// `index`, as proven by `proof`.
+    pub fn new(index: u64, value: Fr, proof: G1Affine) -> Self {
+        Sample {
+            index,
+            value,
+            proof,
+        }
+    }
+
+    /// Returns the index of the sampled evaluation.
+    pub fn get_index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the claimed evaluation.
+    pub fn get_value(&self) -> Fr {
+        self.value
+    }
+
+    /// Returns the proof of the evaluation.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+}
+
+/// Version of the index derivation below. Samplers and serving nodes must
+/// agree on it; any change to the derivation bumps it along with the domain
+/// separation tags.
+pub const SAMPLE_INDICES_VERSION: u32 = 2;
+
+/// Derives `num_samples` distinct indices in `0..length` from `commitment`
+/// and a caller-chosen `seed`.
+///
+/// Light clients should use fresh local randomness as the seed, so a server
+/// can't predict which evaluations will be sampled. At most `length` indices
+/// are returned.
+///
+/// Version 2 hashes `RUST_KZG_BN254_SAMPLE_INDICES_V2`, the commitment
+/// compressed as `encoding::g1_to_bytes` encodes it, the seed length as a
+/// big-endian u64 and the seed. See `sample_indices_from_seed` for how
+/// indices are drawn from that prefix.
+pub fn sample_indices(
+    commitment: &G1Affine,
+    seed: &[u8],
+    num_samples: usize,
+    length: usize,
+) -> Result<Vec<u64>, KzgError> {
+    let mut hasher = Sha256::new();
+    hasher.update(SAMPLE_INDICES_DOMAIN);
+    hasher.update(g1_to_bytes(commitment));
+    hasher.update((seed.len() as u64).to_be_bytes());
+    hasher.update(seed);
+    Ok(derive_indices(hasher, num_samples, length))
+}
+
+/// Derives `num_samples` distinct indices in `0..length` from `seed` alone,
+/// e.g. a shared beacon value, so every party holding the seed agrees on the
+/// indices without knowing the commitment.
+///
+/// Version 1 hashes `RUST_KZG_BN254_SAMPLE_SEED_INDICES_V1`, the seed length
+/// as a big-endian u64 and the seed into a SHA-256 prefix. For counter
+/// `c = 0, 1, ...`, the first 16 bytes of `SHA-256(prefix || c)`, with `c` as
+/// a big-endian u64, are read as a big-endian u128 and reduced modulo
+/// `length`; repeated indices are skipped until `min(num_samples, length)`
+/// indices are drawn, in the order they were drawn.
+pub fn sample_indices_from_seed(seed: &[u8], num_samples: usize, length: usize) -> Vec<u64> {
+    let mut hasher = Sha256::new();
+    hasher.update(SAMPLE_SEED_INDICES_DOMAIN);
+    hasher.update((seed.len() as u64).to_be_bytes());
+    hasher.update(seed);
+    derive_indices(hasher, num_samples, length)
+}
+
+/// draws distinct indices from the hashes of `prefix` and a counter
+fn derive_indices(prefix: Sha256, num_samples: usize, length: usize) -> Vec<u64> {
+    let num_samples = num_samples.min(length);
+    let mut seen = HashSet::with_capacity(num_samples);
+    let mut indices = Vec::with_capacity(num_samples);
+    let mut counter = 0u64;
+    while indices.len() < num_samples {
+        let digest = prefix
+            .clone()
+            .chain_update(counter.to_be_bytes())
+            .finalize();
+        counter += 1;
+        // the modulo bias is negligible for any realistic domain size
+        let wide = u128::from_be_bytes(digest[..16].try_into().unwrap());
+        let index = (wide % length as u128) as u64;
+        if seen.insert(index) {
+            indices.push(index);
+        }
+    }
+    indices
+}
+
+/// Opens `polynomial` at each of `indices` over the roots of unity `kzg` was
+/// set up with, converting the polynomial to coefficient form once.
+pub fn open_samples(
+    kzg: &Kzg,
+    polynomial: &Polynomial,
+    indices: &[u64],
+) -> Result<Vec<Sample>, KzgError> {
+    let openings = kzg.prepare_openings(polynomial)?;
+    indices
+        .iter()
+        .map(|&index| {
+            let value = usize::try_from(index)
+                .ok()
+                .and_then(|i| polynomial.get_at_index(i))
+                .ok_or_else(|| {
+                    KzgError::GenericError("index is out of range of the polynomial".to_string())
+                })?;
+            let proof = kzg.compute_kzg_proof_with_openings(&openings, index)?;
+            Ok(Sample::new(index, *value, proof))
+        })
+        .collect()
+}
+
+/// Verifies all `samples` of a polynomial of `length` field elements against
+/// `commitment` with a single pairing check.
+///
+/// Each opening satisfies `e(C - [y_i], G2) = e(pi_i, [tau - z_i])`. With
+/// `r` derived from the commitment and all samples, the openings hold
+/// together, except with negligible probability, exactly when
+/// `e(sum r^i (C - [y_i] + z_i pi_i), G2) = e(sum r^i pi_i, [tau])`.
+pub fn verify_samples(
+    kzg: &Kzg,
+    commitment: &G1Affine,
+    samples: &[Sample],
+    length: usize,
+) -> Result<bool, KzgError> {
+    if !length.is_power_of_two() {
+        return Err(KzgError::FftError(
+            "length provided is not a power of 2".to_string(),
+        ));
+    }
+    if samples.is_empty() {
+        return Ok(true);
+    }
+
+    let root_of_unity = Fr::get_root_of_unity(length as u64).ok_or_else(|| {
+        KzgError::FftError("length is larger than the field supports".to_string())
+    })?;
+    let points = samples
+        .iter()
+        .map(|sample| {
+            if sample.index >= length as u64 {
+                return Err(KzgError::GenericError(
+                    "index is out of range of the polynomial".to_string(),
+                ));
+            }
+            Ok(root_of_unity.pow([sample.index]))
+        })
+        .collect::<Result<Vec<Fr>, KzgError>>()?;
+
+    let challenge = sample_challenge(kzg, commitment, samples)?;
+    let mut powers = Vec::with_capacity(samples.len());
+    let mut power = Fr::from(1u64);
+    for _ in samples {
+        powers.push(power);
+        power *= challenge;
+    }
+
+    // sum r^i C - [sum r^i y_i] + sum (r^i z_i) pi_i
+    let proofs: Vec<G1Affine> = samples.iter().map(|s| s.proof).collect();
+    let weighted_points: Vec<Fr> = powers.iter().zip(&points).map(|(r, z)| *r * z).collect();
+    let power_sum: Fr = powers.iter().sum();
+    let value_sum: Fr = powers.iter().zip(samples).map(|(r, s)| *r * s.value).sum();
+    let lhs = *commitment * power_sum - G1Affine::generator() * value_sum
+        + G1Projective::msm(&proofs, &weighted_points)
+            .map_err(|err| KzgError::GenericError(err.to_string()))?;
+    let rhs = G1Projective::msm(&proofs, &powers)
+        .map_err(|err| KzgError::GenericError(err.to_string()))?;
+
+    Ok(kzg.verify_pairing(lhs.into_affine(), rhs.into_affine()))
+}
+
+/// hashes the commitment and every sample into the batching challenge, with
+/// the challenge hash configured on `kzg`
+fn sample_challenge(kzg: &Kzg, commitment: &G1Affine, samples: &[Sample]) -> Result<Fr, KzgError> {
+    let mut transcript = Transcript::new(
+        kzg.get_config().get_challenge_hash(),
+        SAMPLE_CHALLENGE_DOMAIN,
+    );
+    transcript.append_g1(commitment)?;
+    transcript.append_u64(samples.len() as u64);
+    for sample in samples {
+        transcript.append_u64(sample.index);
+        transcript.append_fr(&sample.value);
+        transcript.append_g1(&sample.proof)?;
+    }
+    transcript.challenge()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::Blob, consts::GETTYSBURG_ADDRESS_BYTES};
+
+    #[test]
+    fn test_sample_indices() {
+        let commitment = G1Affine::generator();
+        let indices = sample_indices(&commitment, b"seed", 16, 64).unwrap();
+        assert_eq!(indices.len(), 16);
+        assert!(indices.iter().all(|&i| i < 64));
+        assert_eq!(indices.iter().collect::<HashSet<_>>().len(), 16);
+        assert_eq!(
+            indices,
+            sample_indices(&commitment, b"seed", 16, 64).unwrap()
+        );
+        assert_ne!(
+            indices,
+            sample_indices(&commitment, b"other", 16, 64).unwrap()
+        );
+
+        let all = sample_indices(&commitment, b"seed", 100, 8).unwrap();
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 8);
+    }
+
+    #[test]
+    fn test_sample_indices_known_answers() {
+        // pins version 2 of the derivation; changing these means bumping
+        // SAMPLE_INDICES_VERSION
+        assert_eq!(SAMPLE_INDICES_VERSION, 2);
+        assert_eq!(
+            sample_indices(&G1Affine::generator(), b"seed", 8, 4096).unwrap(),
+            [3736, 2447, 3710, 212, 2766, 3365, 713, 703]
+        );
+        assert_eq!(
+            sample_indices_from_seed(b"seed", 8, 4096),
+            [3410, 3267, 343, 511, 1182, 1152, 1261, 2605]
+        );
+        let from_seed = sample_indices_from_seed(b"seed", 16, 64);
+        assert_eq!(from_seed.len(), 16);
+        assert_eq!(from_seed, sample_indices_from_seed(b"seed", 16, 64));
+        assert!(sample_indices_from_seed(b"seed", 4, 0).is_empty());
+    }
+
+    #[test]
+    fn test_verify_samples() {
+        let mut kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let polynomial = blob.to_polynomial().unwrap();
+        kzg.data_setup_custom(1, blob.len().try_into().unwrap())
+            .unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+
+        let indices = sample_indices(&commitment, b"seed", 8, polynomial.len()).unwrap();
+        let samples = open_samples(&kzg, &polynomial, &indices).unwrap();
+        assert!(verify_samples(&kzg, &commitment, &samples, polynomial.len()).unwrap());
+        assert!(verify_samples(&kzg, &commitment, &samples[..1], polynomial.len()).unwrap());
+
+        let mut wrong_value = samples.clone();
+        wrong_value[3].value += Fr::from(1u64);
+        assert!(!verify_samples(&kzg, &commitment, &wrong_value, polynomial.len()).unwrap());
+
+        let mut swapped_proofs = samples.clone();
+        swapped_proofs[0].proof = samples[1].proof;
+        swapped_proofs[1].proof = samples[0].proof;
+        assert!(!verify_samples(&kzg, &commitment, &swapped_proofs, polynomial.len()).unwrap());
+
+        let other_commitment = (commitment + G1Affine::generator()).into_affine();
+        assert!(!verify_samples(&kzg, &other_commitment, &samples, polynomial.len()).unwrap());
+
+        let mut out_of_range = samples.clone();
+        out_of_range[0].index = polynomial.len() as u64;
+        assert!(verify_samples(&kzg, &commitment, &out_of_range, polynomial.len()).is_err());
+    }
+}
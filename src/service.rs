@@ -0,0 +1,289 @@
+//! HTTP proving sidecar.
+//!
+//! Serves `POST /commit`, `POST /prove` and `POST /verify`, each taking and
+//! returning a protobuf message from this module. The SRS is loaded once and
+//! shared by every request, and the work runs on tokio's blocking pool, so a
+//! sidecar scales with the cores it is given and horizontally behind a load
+//! balancer.
+//!
+//! Points are encoded as their gnark compressed big-endian encoding, with the
+//! flag bits in the top byte, and field elements as their 32 big-endian
+//! bytes.
+
+use crate::{
+    blob::Blob,
+    encoding::{fr_from_bytes, fr_to_bytes, g1_from_bytes, g1_to_bytes},
+    kzg::Kzg,
+};
+use ark_bn254::Fr;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
+use prost::Message;
+use std::{net::SocketAddr, sync::Arc};
+use tokio::net::TcpListener;
+
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommitRequest {
+    /// unpadded payload, padded into a blob by the server
+    #[prost(bytes = "vec", tag = "1")]
+    pub payload: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommitReply {
+    #[prost(bytes = "vec", tag = "1")]
+    pub commitment: Vec<u8>,
+    /// number of field elements in the blob
+    #[prost(uint64, tag = "2")]
+    pub length: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProveRequest {
+    /// unpadded payload, padded into a blob by the server
+    #[prost(bytes = "vec", tag = "1")]
+    pub payload: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub index: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProveReply {
+    #[prost(bytes = "vec", tag = "1")]
+    pub commitment: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub proof: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub value: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub z: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct VerifyRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub commitment: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub proof: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub value: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub z: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct VerifyReply {
+    #[prost(bool, tag = "1")]
+    pub valid: bool,
+}
+
+/// Builds the routes of the service around a loaded `Kzg`.
+///
+/// Proving requires `kzg` to have run one of the data setup functions; each
+/// proof then uses the roots of unity of its own blob's size.
+pub fn router(kzg: Arc<Kzg>) -> Router {
+    Router::new()
+        .route("/commit", post(commit))
+        .route("/prove", post(prove))
+        .route("/verify", post(verify))
+        .with_state(kzg)
+}
+
+/// Serves the routes of `router` on `listener` until the task is dropped.
+pub async fn serve(kzg: Arc<Kzg>, listener: TcpListener) -> std::io::Result<()> {
+    axum::serve(listener, router(kzg)).await
+}
+
+/// Binds `addr` and serves the routes of `router` on it.
+pub async fn bind_and_serve(kzg: Arc<Kzg>, addr: SocketAddr) -> std::io::Result<()> {
+    serve(kzg, TcpListener::bind(addr).await?).await
+}
+
+/// an error reply, carrying the status and a plain text message
+struct ServiceError(StatusCode, String);
+
+impl IntoResponse for ServiceError {
+    fn into_response(self) -> Response {
+        (self.0, self.1).into_response()
+    }
+}
+
+fn bad_request(err: impl ToString) -> ServiceError {
+    ServiceError(StatusCode::BAD_REQUEST, err.to_string())
+}
+
+fn protobuf_reply<M: Message>(message: M) -> Response {
+    (
+        [(header::CONTENT_TYPE, PROTOBUF_CONTENT_TYPE)],
+        message.encode_to_vec(),
+    )
+        .into_response()
+}
+
+/// decodes the request and runs `handle` on the blocking pool
+async fn handle_blocking<Req, Reply>(
+    kzg: Arc<Kzg>,
+    body: Bytes,
+    handle: fn(&Kzg, Req) -> Result<Reply, ServiceError>,
+) -> Result<Response, ServiceError>
+where
+    Req: Message + Default + Send + 'static,
+    Reply: Message + Send + 'static,
+{
+    let request = Req::decode(body).map_err(bad_request)?;
+    let reply = tokio::task::spawn_blocking(move || handle(&kzg, request))
+        .await
+        .map_err(|err| ServiceError(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))??;
+    Ok(protobuf_reply(reply))
+}
+
+async fn commit(State(kzg): State<Arc<Kzg>>, body: Bytes) -> Result<Response, ServiceError> {
+    handle_blocking(kzg, body, |kzg, request: CommitRequest| {
+        let polynomial = Blob::from_bytes_and_pad(&request.payload)
+            .to_polynomial()
+            .map_err(bad_request)?;
+        let commitment = kzg.commit(&polynomial).map_err(bad_request)?;
+        Ok(CommitReply {
+            commitment: g1_to_bytes(&commitment).to_vec(),
+            length: polynomial.get_length_of_padded_blob_as_fr_vector() as u64,
+        })
+    })
+    .await
+}
+
+async fn prove(State(kzg): State<Arc<Kzg>>, body: Bytes) -> Result<Response, ServiceError> {
+    handle_blocking(kzg, body, |kzg, request: ProveRequest| {
+        let polynomial = Blob::from_bytes_and_pad(&request.payload)
+            .to_polynomial()
+            .map_err(bad_request)?;
+        let roots_of_unity: Vec<Fr> = GeneralEvaluationDomain::<Fr>::new(polynomial.len())
+            .ok_or_else(|| bad_request("blob is larger than the field supports"))?
+            .elements()
+            .collect();
+        let index = usize::try_from(request.index)
+            .ok()
+            .filter(|&i| i < polynomial.len())
+            .ok_or_else(|| bad_request("index is out of range of the polynomial"))?;
+
+        let commitment = kzg.commit(&polynomial).map_err(bad_request)?;
+        let proof = kzg
+            .compute_kzg_proof(&polynomial, request.index, &roots_of_unity)
+            .map_err(bad_request)?;
+        Ok(ProveReply {
+            commitment: g1_to_bytes(&commitment).to_vec(),
+            proof: g1_to_bytes(&proof).to_vec(),
+            value: fr_to_bytes(&polynomial.as_slice()[index]).to_vec(),
+            z: fr_to_bytes(&roots_of_unity[index]).to_vec(),
+        })
+    })
+    .await
+}
+
+async fn verify(State(kzg): State<Arc<Kzg>>, body: Bytes) -> Result<Response, ServiceError> {
+    handle_blocking(kzg, body, |kzg, request: VerifyRequest| {
+        Ok(VerifyReply {
+            valid: kzg.verify_kzg_proof(
+                g1_from_bytes(&request.commitment).map_err(bad_request)?,
+                g1_from_bytes(&request.proof).map_err(bad_request)?,
+                fr_from_bytes(&request.value).map_err(bad_request)?,
+                fr_from_bytes(&request.z).map_err(bad_request)?,
+            ),
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+    use std::io::Read;
+
+    fn post<Req: Message, Reply: Message + Default>(
+        addr: SocketAddr,
+        path: &str,
+        request: Req,
+    ) -> Result<Reply, u16> {
+        let response = ureq::post(&format!("http://{}{}", addr, path))
+            .set("Content-Type", PROTOBUF_CONTENT_TYPE)
+            .send_bytes(&request.encode_to_vec())
+            .map_err(|err| match err {
+                ureq::Error::Status(status, _) => status,
+                _ => panic!("request failed: {}", err),
+            })?;
+        let mut body = Vec::new();
+        response.into_reader().read_to_end(&mut body).unwrap();
+        Ok(Reply::decode(body.as_slice()).unwrap())
+    }
+
+    #[test]
+    fn test_service_round_trip() {
+        let kzg = Arc::new(
+            Kzg::setup(
+                "src/test-files/g1.point",
+                "src/test-files/g2.point",
+                "src/test-files/g2.point.powerOf2",
+                3000,
+                3000,
+            )
+            .unwrap(),
+        );
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let listener = runtime.block_on(TcpListener::bind("127.0.0.1:0")).unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(serve(kzg.clone(), listener));
+
+        let payload = GETTYSBURG_ADDRESS_BYTES.to_vec();
+        let commit: CommitReply = post(
+            addr,
+            "/commit",
+            CommitRequest {
+                payload: payload.clone(),
+            },
+        )
+        .unwrap();
+        let polynomial = Blob::from_bytes_and_pad(&payload).to_polynomial().unwrap();
+        assert_eq!(
+            g1_from_bytes(&commit.commitment).ok(),
+            kzg.commit(&polynomial).ok()
+        );
+
+        let prove: ProveReply = post(addr, "/prove", ProveRequest { payload, index: 5 }).unwrap();
+        assert_eq!(prove.commitment, commit.commitment);
+        let mut request = VerifyRequest {
+            commitment: prove.commitment,
+            proof: prove.proof,
+            value: prove.value,
+            z: prove.z,
+        };
+        let reply: VerifyReply = post(addr, "/verify", request.clone()).unwrap();
+        assert!(reply.valid);
+
+        request.value = fr_to_bytes(&Fr::from(1u64)).to_vec();
+        let reply: VerifyReply = post(addr, "/verify", request.clone()).unwrap();
+        assert!(!reply.valid);
+
+        request.value = vec![0xff; 32];
+        assert_eq!(post::<_, VerifyReply>(addr, "/verify", request), Err(400));
+        assert_eq!(
+            post::<_, ProveReply>(
+                addr,
+                "/prove",
+                ProveRequest {
+                    payload: vec![1; 100],
+                    index: 64,
+                }
+            ),
+            Err(400)
+        );
+    }
+}
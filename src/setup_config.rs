@@ -0,0 +1,260 @@
+//! Setup parameters loaded from a TOML file or the environment.
+//!
+//! `KzgSetupConfig` holds what `Kzg::setup` needs, the SRS paths, order and
+//! points to load, plus the thread count, so services can configure the
+//! library in deployment instead of hard-coding paths. (`KzgConfig` is the
+//! runtime tuning of a loaded setup.) With the `serde` feature it
+//! deserializes from TOML, a flat list of keys:
+//!
+//! ```toml
+//! g1_path = "resources/g1.point"
+//! g2_path = "resources/g2.point"
+//! srs_order = 268435456
+//! srs_points_to_load = 524288
+//! num_threads = 8
+//! lagrange_srs_path = "resources/lagrange.4096.point"
+//! ```
+//!
+//! `g2_power_of_2_path` can replace `g2_path`. Each key can also be set by
+//! its upper-case environment variable with a `KZG_` prefix, e.g.
+//! `KZG_G1_PATH` or `KZG_SRS_ORDER`.
+
+use crate::{builder::KzgBuilder, errors::KzgError, kzg::Kzg};
+
+/// Prefix of the environment variables read by `KzgSetupConfig::from_env`.
+pub const SETUP_CONFIG_ENV_PREFIX: &str = "KZG_";
+
+/// The parameters of a setup, see the module docs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct KzgSetupConfig {
+    /// Path of the G1 points, like `g1.point`.
+    pub g1_path: String,
+    /// Path of consecutive G2 powers, like `g2.point`.
+    pub g2_path: Option<String>,
+    /// Path of a G2 power of 2 table, like `g2.point.powerOf2`, used when
+    /// `g2_path` is not set.
+    pub g2_power_of_2_path: Option<String>,
+    /// The number of G1 points of the SRS.
+    pub srs_order: u32,
+    /// The number of points to load, the whole order if not set.
+    pub srs_points_to_load: Option<u32>,
+    /// The number of threads parallel work runs on, one per CPU if not set.
+    pub num_threads: Option<usize>,
+    /// Path of a Lagrange SRS written by `Kzg::write_lagrange_srs`, loaded
+    /// after the points.
+    pub lagrange_srs_path: Option<String>,
+}
+
+impl KzgSetupConfig {
+    /// Parses a config from TOML.
+    #[cfg(feature = "serde")]
+    pub fn from_toml_str(toml: &str) -> Result<Self, KzgError> {
+        toml::from_str(toml).map_err(|e| KzgError::GenericError(format!("setup config: {e}")))
+    }
+
+    /// Reads and parses the TOML file at `path`.
+    #[cfg(feature = "serde")]
+    pub fn from_toml_file(path: &str) -> Result<Self, KzgError> {
+        let toml = std::fs::read_to_string(path)
+            .map_err(|e| KzgError::GenericError(format!("{path}: {e}")))?;
+        Self::from_toml_str(&toml)
+    }
+
+    /// Reads a config from the `KZG_`-prefixed environment variables.
+    pub fn from_env() -> Result<Self, KzgError> {
+        Self::from_vars(std::env::vars())
+    }
+
+    fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Result<Self, KzgError> {
+        let mut config = KzgSetupConfig::default();
+        for (name, value) in vars {
+            let Some(key) = name.strip_prefix(SETUP_CONFIG_ENV_PREFIX) else {
+                continue;
+            };
+            let key = key.to_ascii_lowercase();
+            // other KZG_ variables, like KZG_SRS_CACHE_DIR, are not ours
+            if KEYS.contains(&key.as_str()) {
+                config.set(&key, &value)?;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Overrides the fields set in the environment, e.g. to let operators
+    /// adjust a config file.
+    pub fn with_env_overrides(self) -> Result<Self, KzgError> {
+        let env = Self::from_env()?;
+        Ok(self.merge(env))
+    }
+
+    fn merge(self, other: Self) -> Self {
+        KzgSetupConfig {
+            g1_path: if other.g1_path.is_empty() {
+                self.g1_path
+            } else {
+                other.g1_path
+            },
+            g2_path: other.g2_path.or(self.g2_path),
+            g2_power_of_2_path: other.g2_power_of_2_path.or(self.g2_power_of_2_path),
+            srs_order: if other.srs_order == 0 {
+                self.srs_order
+            } else {
+                other.srs_order
+            },
+            srs_points_to_load: other.srs_points_to_load.or(self.srs_points_to_load),
+            num_threads: other.num_threads.or(self.num_threads),
+            lagrange_srs_path: other.lagrange_srs_path.or(self.lagrange_srs_path),
+        }
+    }
+
+    /// Returns a builder with these parameters.
+    pub fn to_builder(&self) -> KzgBuilder {
+        let mut builder = KzgBuilder::new().with_g1_path(&self.g1_path);
+        if let Some(path) = &self.g2_path {
+            builder = builder.with_g2_path(path);
+        }
+        if let Some(path) = &self.g2_power_of_2_path {
+            builder = builder.with_g2_power_of_2_path(path);
+        }
+        if self.srs_order != 0 {
+            builder = builder.with_srs_order(self.srs_order);
+        }
+        if let Some(srs_points_to_load) = self.srs_points_to_load {
+            builder = builder.with_points_to_load(srs_points_to_load);
+        }
+        if let Some(num_threads) = self.num_threads {
+            builder = builder.with_num_threads(num_threads);
+        }
+        if let Some(path) = &self.lagrange_srs_path {
+            builder = builder.with_lagrange_srs_path(path);
+        }
+        builder
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), KzgError> {
+        match key {
+            "g1_path" => self.g1_path = value.to_string(),
+            "g2_path" => self.g2_path = Some(value.to_string()),
+            "g2_power_of_2_path" => self.g2_power_of_2_path = Some(value.to_string()),
+            "srs_order" => self.srs_order = parse_integer(key, value)?,
+            "srs_points_to_load" => self.srs_points_to_load = Some(parse_integer(key, value)?),
+            "num_threads" => self.num_threads = Some(parse_integer(key, value)?),
+            "lagrange_srs_path" => self.lagrange_srs_path = Some(value.to_string()),
+            _ => {
+                return Err(KzgError::GenericError(format!(
+                    "unknown setup config key {key}"
+                )))
+            },
+        }
+        Ok(())
+    }
+}
+
+const KEYS: [&str; 7] = [
+    "g1_path",
+    "g2_path",
+    "g2_power_of_2_path",
+    "srs_order",
+    "srs_points_to_load",
+    "num_threads",
+    "lagrange_srs_path",
+];
+
+fn parse_integer<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, KzgError> {
+    value.trim().parse().map_err(|_| {
+        KzgError::GenericError(format!(
+            "setup config key {key} must be an integer in range"
+        ))
+    })
+}
+
+impl Kzg {
+    /// Creates a setup from `config`, e.g. one read with
+    /// `KzgSetupConfig::from_toml_file` or `KzgSetupConfig::from_env`.
+    pub fn from_config(config: &KzgSetupConfig) -> Result<Self, KzgError> {
+        config.to_builder().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> KzgSetupConfig {
+        KzgSetupConfig {
+            g1_path: "src/test-files/g1.point".to_string(),
+            g2_path: Some("src/test-files/g2.point".to_string()),
+            srs_order: 3000,
+            srs_points_to_load: Some(1000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_setup_config() {
+        let config = test_config();
+        let kzg = Kzg::from_config(&config).unwrap();
+        let expected = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            1000,
+        )
+        .unwrap();
+        assert_eq!(kzg.get_g1_points(), expected.get_g1_points());
+        assert_eq!(kzg.get_g2_points(), expected.get_g2_points());
+
+        let env = KzgSetupConfig::from_vars([
+            ("KZG_SRS_POINTS_TO_LOAD".to_string(), "2000".to_string()),
+            ("KZG_NUM_THREADS".to_string(), "2".to_string()),
+            ("KZG_SRS_CACHE_DIR".to_string(), "/tmp".to_string()),
+            ("PATH".to_string(), "/bin".to_string()),
+        ])
+        .unwrap();
+        let merged = config.clone().merge(env);
+        assert_eq!(merged.srs_points_to_load, Some(2000));
+        assert_eq!(merged.num_threads, Some(2));
+        assert_eq!(merged.g1_path, config.g1_path);
+        assert_eq!(
+            Kzg::from_config(&merged).unwrap().get_g1_points().len(),
+            2000
+        );
+        assert!(
+            KzgSetupConfig::from_vars([("KZG_SRS_ORDER".to_string(), "-1".to_string())]).is_err()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_setup_config_toml() {
+        let config = KzgSetupConfig::from_toml_str(
+            r#"
+            # the test SRS
+            g1_path = "src/test-files/g1.point"
+            g2_path = 'src/test-files/g2.point' # consecutive powers
+            srs_order = 3_000
+            srs_points_to_load = 1000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config, test_config());
+
+        for toml in [
+            "g1_path = 1",
+            "srs_order = \"many\"",
+            "srs_order = 4294967296",
+            "srs_order = 3000\nsrs_order = 3000",
+            "order = 3000",
+            "[kzg]\nsrs_order = 3000",
+            "g1_path = \"unterminated",
+        ] {
+            assert!(KzgSetupConfig::from_toml_str(toml).is_err(), "{toml}");
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! A blob bundled with its commitment and proof.
+//!
+//! Dispersal hands the blob, its commitment and the blob proof around
+//! together, so `Kzg::compute_sidecar` builds all three in one call and
+//! `verify_sidecar` checks them as one object. With the `serde` feature a
+//! sidecar serializes as the padded blob data and the compressed points:
+//! `0x`-prefixed hex strings in human-readable formats like JSON, and plain
+//! bytes otherwise. Deserializing checks the points are on the curve and in
+//! the subgroup.
+
+use crate::{blob::Blob, errors::KzgError, kzg::Kzg, verifier::KzgVerifier};
+use ark_bn254::G1Affine;
+
+/// A padded blob with its commitment and blob proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "serde_repr::SidecarRepr", into = "serde_repr::SidecarRepr")
+)]
+pub struct BlobSidecar {
+    blob: Blob,
+    commitment: G1Affine,
+    proof: G1Affine,
+}
+
+impl BlobSidecar {
+    /// Bundles a padded blob with its commitment and proof.
+    pub fn new(blob: Blob, commitment: G1Affine, proof: G1Affine) -> Result<Self, KzgError> {
+        if !blob.is_padded() {
+            return Err(KzgError::GenericError(
+                "sidecar blobs must be padded".to_string(),
+            ));
+        }
+        Ok(BlobSidecar {
+            blob,
+            commitment,
+            proof,
+        })
+    }
+
+    /// Returns the padded blob.
+    pub fn get_blob(&self) -> &Blob {
+        &self.blob
+    }
+
+    /// Returns the commitment to the blob.
+    pub fn get_commitment(&self) -> G1Affine {
+        self.commitment
+    }
+
+    /// Returns the blob proof.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+}
+
+impl Kzg {
+    /// Commits to `blob` and proves it with `compute_blob_kzg_proof`, padding
+    /// a copy first if it is not padded yet.
+    pub fn compute_sidecar(&self, blob: &Blob) -> Result<BlobSidecar, KzgError> {
+        let mut blob = blob.clone();
+        if !blob.is_padded() {
+            blob.pad_data()
+                .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        }
+        let commitment = self.blob_to_kzg_commitment(&blob)?;
+        let proof = self.compute_blob_kzg_proof(&blob, &commitment)?;
+        BlobSidecar::new(blob, commitment, proof)
+    }
+
+    /// Checks the proof of `sidecar` against its blob and commitment.
+    pub fn verify_sidecar(&self, sidecar: &BlobSidecar) -> Result<bool, KzgError> {
+        self.verifier().verify_sidecar(sidecar)
+    }
+}
+
+impl KzgVerifier {
+    /// Checks the proof of `sidecar` against its blob and commitment.
+    pub fn verify_sidecar(&self, sidecar: &BlobSidecar) -> Result<bool, KzgError> {
+        self.verify_blob_kzg_proof(&sidecar.blob, &sidecar.commitment, &sidecar.proof)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_repr {
+    use super::{Blob, BlobSidecar};
+    use crate::encoding::{g1_from_bytes, g1_to_bytes};
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Bytes as `0x`-prefixed hex in human-readable formats.
+    pub(super) struct Bytes(Vec<u8>);
+
+    impl Serialize for Bytes {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                let mut hex = String::with_capacity(2 + 2 * self.0.len());
+                hex.push_str("0x");
+                for byte in &self.0 {
+                    hex.push_str(&format!("{byte:02x}"));
+                }
+                serializer.serialize_str(&hex)
+            } else {
+                self.0.serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Bytes {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                let hex = String::deserialize(deserializer)?;
+                let digits = hex.strip_prefix("0x").unwrap_or(&hex).as_bytes();
+                if digits.len() % 2 != 0 {
+                    return Err(D::Error::custom("hex string has an odd length"));
+                }
+                digits
+                    .chunks(2)
+                    .map(|pair| {
+                        std::str::from_utf8(pair)
+                            .ok()
+                            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                            .ok_or_else(|| D::Error::custom("invalid hex digit"))
+                    })
+                    .collect::<Result<_, _>>()
+                    .map(Bytes)
+            } else {
+                Vec::deserialize(deserializer).map(Bytes)
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub(super) struct SidecarRepr {
+        blob: Bytes,
+        commitment: Bytes,
+        proof: Bytes,
+    }
+
+    impl From<BlobSidecar> for SidecarRepr {
+        fn from(sidecar: BlobSidecar) -> Self {
+            SidecarRepr {
+                blob: Bytes(sidecar.blob.get_blob_data()),
+                commitment: Bytes(g1_to_bytes(&sidecar.commitment).to_vec()),
+                proof: Bytes(g1_to_bytes(&sidecar.proof).to_vec()),
+            }
+        }
+    }
+
+    impl TryFrom<SidecarRepr> for BlobSidecar {
+        type Error = String;
+
+        fn try_from(repr: SidecarRepr) -> Result<Self, Self::Error> {
+            let commitment = g1_from_bytes(&repr.commitment.0).map_err(|err| err.to_string())?;
+            let proof = g1_from_bytes(&repr.proof.0).map_err(|err| err.to_string())?;
+            Ok(BlobSidecar {
+                blob: Blob::new(repr.blob.0, true),
+                commitment,
+                proof,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    fn setup_kzg() -> Kzg {
+        Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sidecar() {
+        let kzg = setup_kzg();
+        let padded = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let sidecar = kzg.compute_sidecar(&padded).unwrap();
+        assert_eq!(sidecar.get_blob(), &padded);
+        assert_eq!(
+            sidecar.get_commitment(),
+            kzg.blob_to_kzg_commitment(&padded).unwrap()
+        );
+        assert!(kzg.verify_sidecar(&sidecar).unwrap());
+        assert!(kzg.verifier().verify_sidecar(&sidecar).unwrap());
+
+        let raw = Blob::new(GETTYSBURG_ADDRESS_BYTES.to_vec(), false);
+        assert_eq!(kzg.compute_sidecar(&raw).unwrap(), sidecar);
+        assert!(BlobSidecar::new(raw, sidecar.commitment, sidecar.proof).is_err());
+
+        let other = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..200]);
+        let forged = BlobSidecar::new(other, sidecar.commitment, sidecar.proof).unwrap();
+        assert!(!kzg.verify_sidecar(&forged).unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_sidecar_serde() {
+        let kzg = setup_kzg();
+        let sidecar = kzg
+            .compute_sidecar(&Blob::from_bytes_and_pad(b"sidecar"))
+            .unwrap();
+        let json = serde_json::to_value(&sidecar).unwrap();
+        assert!(json["commitment"].as_str().unwrap().starts_with("0x"));
+        let decoded: BlobSidecar = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(decoded, sidecar);
+
+        let mut bad = json.clone();
+        bad["proof"] = serde_json::Value::from("0x1234");
+        assert!(serde_json::from_value::<BlobSidecar>(bad).is_err());
+        let mut bad = json;
+        bad["blob"] = serde_json::Value::from("0xzz");
+        assert!(serde_json::from_value::<BlobSidecar>(bad).is_err());
+    }
+}
@@ -0,0 +1,360 @@
+//! Converting an SRS between file formats.
+//!
+//! An `Srs` of G1 and G2 powers of tau can be read from and written to:
+//! - the EigenDA `.point` files, compressed big-endian points as `Kzg::setup`
+//!   reads them;
+//! - the layout of the Ethereum trusted setup, as the `trusted_setup.txt`
+//!   text of c-kzg-4844 or the JSON of the consensus specs, holding the G1
+//!   points in Lagrange form over a power of 2 domain, the G2 powers and the
+//!   G1 powers, each point hex-encoded as in the `.point` files;
+//! - a compact binary format of the compressed arkworks serialization.
+//!
+//! The Ethereum ceremony itself is over BLS12-381, so its files carry 48 and
+//! 96-byte points that have no BN254 counterpart. They are rejected with an
+//! error saying so, rather than misread. Reading checks the declared point
+//! counts, that the G1 powers cover a power of 2 domain, and that the
+//! Lagrange points are the inverse FFT of the G1 powers.
+
+use crate::{
+    ceremony::Srs,
+    consts::{SIZE_OF_G1_AFFINE_COMPRESSED, SIZE_OF_G2_AFFINE_COMPRESSED},
+    errors::KzgError,
+    helpers::{
+        g1_point_to_bytes_be, g2_point_to_bytes_be, read_g1_point_from_bytes_be,
+        read_g2_point_from_bytes_be,
+    },
+    keys::{ProvingKey, VerifyingKey},
+    kzg::Kzg,
+};
+use ark_bn254::{G1Affine, G2Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use rayon::prelude::*;
+use std::fmt::Write;
+
+/// Version of the binary format written by `to_binary`.
+pub const SRS_BINARY_VERSION: u32 = 1;
+
+const BINARY_MAGIC: &[u8; 8] = b"BN254SRS";
+// sizes of compressed BLS12-381 points, as in the Ethereum trusted setup
+const BLS12_381_G1_SIZE: usize = 48;
+const BLS12_381_G2_SIZE: usize = 96;
+
+/// Reads the first `num_points` G1 and G2 powers from `.point` files.
+pub fn read_point_files(g1_path: &str, g2_path: &str, num_points: u32) -> Result<Srs, KzgError> {
+    Srs::new(
+        Kzg::parallel_read_g1_points(g1_path.to_string(), num_points)?,
+        Kzg::parallel_read_g2_points(g2_path.to_string(), num_points)?,
+    )
+}
+
+/// Writes the G1 and G2 powers of `srs` to `.point` files.
+pub fn write_point_files(srs: &Srs, g1_path: &str, g2_path: &str) -> Result<(), KzgError> {
+    Kzg::write_g1_points(g1_path, srs.get_g1_powers())?;
+    Kzg::write_g2_points(g2_path, srs.get_g2_powers())
+}
+
+/// Returns `srs` in the layout of c-kzg-4844's `trusted_setup.txt`: the
+/// numbers of G1 and G2 points, then the G1 Lagrange points, the G2 powers
+/// and the G1 powers, one hex-encoded point per line.
+pub fn to_trusted_setup_text(srs: &Srs) -> Result<String, KzgError> {
+    let lagrange = lagrange_points(srs)?;
+    let mut text = format!(
+        "{}\n{}\n",
+        srs.get_g1_powers().len(),
+        srs.get_g2_powers().len()
+    );
+    for line in g1_hex(&lagrange, "")
+        .into_iter()
+        .chain(g2_hex(srs.get_g2_powers(), ""))
+        .chain(g1_hex(srs.get_g1_powers(), ""))
+    {
+        text.push_str(&line);
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Reads an SRS written in the layout of `to_trusted_setup_text`.
+pub fn from_trusted_setup_text(text: &str) -> Result<Srs, KzgError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    let mut count = |what: &str| {
+        lines
+            .next()
+            .and_then(|line| line.parse::<usize>().ok())
+            .ok_or_else(|| {
+                KzgError::SerializationError(format!("trusted setup has no number of {what}"))
+            })
+    };
+    let num_g1 = count("G1 points")?;
+    let num_g2 = count("G2 points")?;
+    let lines: Vec<&str> = lines.collect();
+    if lines.len() != 2 * num_g1 + num_g2 {
+        return Err(KzgError::SerializationError(format!(
+            "trusted setup has {} points, not the {} its counts declare",
+            lines.len(),
+            2 * num_g1 + num_g2
+        )));
+    }
+    let (lagrange, rest) = lines.split_at(num_g1);
+    let (g2, g1) = rest.split_at(num_g2);
+    checked_srs(parse_g1(g1)?, parse_g2(g2)?, &parse_g1(lagrange)?)
+}
+
+/// Returns `srs` in the JSON layout of the consensus specs' trusted setups,
+/// with `g1_monomial`, `g1_lagrange` and `g2_monomial` arrays of
+/// `0x`-prefixed hex points.
+pub fn to_trusted_setup_json(srs: &Srs) -> Result<String, KzgError> {
+    let lagrange = lagrange_points(srs)?;
+    let array = |points: Vec<String>| {
+        let quoted: Vec<String> = points.iter().map(|point| format!("\"{point}\"")).collect();
+        format!("[{}]", quoted.join(", "))
+    };
+    Ok(format!(
+        "{{\"g1_monomial\": {}, \"g1_lagrange\": {}, \"g2_monomial\": {}}}\n",
+        array(g1_hex(srs.get_g1_powers(), "0x")),
+        array(g1_hex(&lagrange, "0x")),
+        array(g2_hex(srs.get_g2_powers(), "0x")),
+    ))
+}
+
+/// Reads an SRS written in the layout of `to_trusted_setup_json`.
+pub fn from_trusted_setup_json(json: &str) -> Result<Srs, KzgError> {
+    checked_srs(
+        parse_g1(&json_array(json, "g1_monomial")?)?,
+        parse_g2(&json_array(json, "g2_monomial")?)?,
+        &parse_g1(&json_array(json, "g1_lagrange")?)?,
+    )
+}
+
+/// Returns `srs` in the binary format: a magic string, the little-endian
+/// `SRS_BINARY_VERSION`, then the compressed arkworks serialization of the
+/// G1 and G2 powers.
+pub fn to_binary(srs: &Srs) -> Result<Vec<u8>, KzgError> {
+    let points = (srs.get_g1_powers(), srs.get_g2_powers());
+    let mut bytes = Vec::with_capacity(BINARY_MAGIC.len() + 4 + points.compressed_size());
+    bytes.extend_from_slice(BINARY_MAGIC);
+    bytes.extend_from_slice(&SRS_BINARY_VERSION.to_le_bytes());
+    points
+        .serialize_compressed(&mut bytes)
+        .map_err(|e| KzgError::SerializationError(e.to_string()))?;
+    Ok(bytes)
+}
+
+/// Reads an SRS written by `to_binary`.
+pub fn from_binary(bytes: &[u8]) -> Result<Srs, KzgError> {
+    let header_size = BINARY_MAGIC.len() + 4;
+    if bytes.len() < header_size || &bytes[..BINARY_MAGIC.len()] != BINARY_MAGIC {
+        return Err(KzgError::SerializationError("not a binary SRS".to_string()));
+    }
+    if bytes[BINARY_MAGIC.len()..header_size] != SRS_BINARY_VERSION.to_le_bytes() {
+        return Err(KzgError::SerializationError(
+            "unsupported binary SRS version".to_string(),
+        ));
+    }
+    let (g1, g2): (Vec<G1Affine>, Vec<G2Affine>) = CanonicalDeserialize::deserialize_with_mode(
+        &bytes[header_size..],
+        Compress::Yes,
+        Validate::Yes,
+    )
+    .map_err(|e| KzgError::SerializationError(e.to_string()))?;
+    Srs::new(g1, g2)
+}
+
+/// returns the G1 points of `srs` in Lagrange form over the domain of their
+/// number, which must be a power of 2
+fn lagrange_points(srs: &Srs) -> Result<Vec<G1Affine>, KzgError> {
+    let g1 = srs.get_g1_powers();
+    if !g1.len().is_power_of_two() {
+        return Err(KzgError::GenericError(format!(
+            "{} G1 points don't make a power of 2 Lagrange domain",
+            g1.len()
+        )));
+    }
+    Kzg::from_keys(
+        ProvingKey::new(g1.to_vec(), g1.len() as u64)?,
+        VerifyingKey::from_powers(srs.get_g2_powers().to_vec())?,
+    )?
+    .g1_ifft(g1.len())
+}
+
+/// wraps the powers in an `Srs` after checking `lagrange` matches them
+fn checked_srs(
+    g1: Vec<G1Affine>,
+    g2: Vec<G2Affine>,
+    lagrange: &[G1Affine],
+) -> Result<Srs, KzgError> {
+    let srs = Srs::new(g1, g2)?;
+    if srs.get_g1_powers().len() != lagrange.len() || lagrange_points(&srs)? != lagrange {
+        return Err(KzgError::GenericError(
+            "the G1 Lagrange points are not the inverse FFT of the G1 powers".to_string(),
+        ));
+    }
+    Ok(srs)
+}
+
+fn g1_hex(points: &[G1Affine], prefix: &str) -> Vec<String> {
+    points
+        .par_iter()
+        .map(|point| format!("{prefix}{}", to_hex(&g1_point_to_bytes_be(point))))
+        .collect()
+}
+
+fn g2_hex(points: &[G2Affine], prefix: &str) -> Vec<String> {
+    points
+        .par_iter()
+        .map(|point| format!("{prefix}{}", to_hex(&g2_point_to_bytes_be(point))))
+        .collect()
+}
+
+fn parse_g1<S: AsRef<str> + Sync>(lines: &[S]) -> Result<Vec<G1Affine>, KzgError> {
+    lines
+        .par_iter()
+        .map(|line| {
+            let bytes = point_bytes(
+                line.as_ref(),
+                SIZE_OF_G1_AFFINE_COMPRESSED,
+                BLS12_381_G1_SIZE,
+            )?;
+            read_g1_point_from_bytes_be(&bytes)
+                .map_err(|e| KzgError::SerializationError(e.to_string()))
+        })
+        .collect()
+}
+
+fn parse_g2<S: AsRef<str> + Sync>(lines: &[S]) -> Result<Vec<G2Affine>, KzgError> {
+    lines
+        .par_iter()
+        .map(|line| {
+            let bytes = point_bytes(
+                line.as_ref(),
+                SIZE_OF_G2_AFFINE_COMPRESSED,
+                BLS12_381_G2_SIZE,
+            )?;
+            read_g2_point_from_bytes_be(&bytes)
+                .map_err(|e| KzgError::SerializationError(e.to_string()))
+        })
+        .collect()
+}
+
+/// decodes a hex point of `size` bytes, telling BLS12-381 points apart
+fn point_bytes(hex: &str, size: usize, bls12_381_size: usize) -> Result<Vec<u8>, KzgError> {
+    let bytes = from_hex(hex.strip_prefix("0x").unwrap_or(hex))?;
+    if bytes.len() == bls12_381_size {
+        return Err(KzgError::SerializationError(format!(
+            "{bls12_381_size}-byte points are BLS12-381, as in the Ethereum KZG ceremony, \
+             and can't be converted to BN254"
+        )));
+    }
+    if bytes.len() != size {
+        return Err(KzgError::SerializationError(format!(
+            "point is {} bytes, not {size}",
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
+/// returns the strings of the JSON array under `key`
+fn json_array(json: &str, key: &str) -> Result<Vec<String>, KzgError> {
+    let missing = || KzgError::SerializationError(format!("trusted setup has no {key} array"));
+    let start = json.find(&format!("\"{key}\"")).ok_or_else(missing)?;
+    let rest = &json[start + key.len() + 2..];
+    let open = rest.find('[').ok_or_else(missing)?;
+    let close = rest.find(']').ok_or_else(missing)?;
+    if close < open
+        || !rest[..open]
+            .trim()
+            .trim_start_matches(':')
+            .trim()
+            .is_empty()
+    {
+        return Err(missing());
+    }
+    Ok(rest[open + 1..close]
+        .split(',')
+        .map(|item| item.trim().trim_matches('"').to_string())
+        .filter(|item| !item.is_empty())
+        .collect())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 * bytes.len());
+    for byte in bytes {
+        // writing to a String can't fail
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, KzgError> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(KzgError::SerializationError(format!(
+            "invalid hex point {hex}"
+        )));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| KzgError::SerializationError(format!("invalid hex point {hex}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn srs() -> Srs {
+        read_point_files("src/test-files/g1.point", "src/test-files/g2.point", 256).unwrap()
+    }
+
+    #[test]
+    fn test_convert_srs() {
+        let srs = srs();
+
+        let text = to_trusted_setup_text(&srs).unwrap();
+        assert!(text.starts_with("256\n256\n"));
+        assert_eq!(from_trusted_setup_text(&text).unwrap(), srs);
+        let json = to_trusted_setup_json(&srs).unwrap();
+        assert_eq!(from_trusted_setup_json(&json).unwrap(), srs);
+        let binary = to_binary(&srs).unwrap();
+        assert_eq!(binary.len(), 12 + 8 + 256 * 32 + 8 + 256 * 64);
+        assert_eq!(from_binary(&binary).unwrap(), srs);
+
+        let dir = std::env::temp_dir().join("rust-kzg-bn254-test-convert-srs");
+        std::fs::create_dir_all(&dir).unwrap();
+        let g1_path = dir.join("g1.point");
+        let g2_path = dir.join("g2.point");
+        let (g1_path, g2_path) = (g1_path.to_str().unwrap(), g2_path.to_str().unwrap());
+        write_point_files(&srs, g1_path, g2_path).unwrap();
+        assert_eq!(read_point_files(g1_path, g2_path, 256).unwrap(), srs);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // the G1 powers must cover a power of 2 domain
+        let odd = Srs::new(
+            srs.get_g1_powers()[..100].to_vec(),
+            srs.get_g2_powers().to_vec(),
+        )
+        .unwrap();
+        assert!(to_trusted_setup_text(&odd).is_err());
+        // Lagrange points from another SRS
+        let mut lines: Vec<&str> = text.lines().collect();
+        lines.swap(2, 3);
+        assert!(from_trusted_setup_text(&lines.join("\n")).is_err());
+        // a declared count that doesn't match
+        assert!(from_trusted_setup_text(&text.replacen("256", "255", 1)).is_err());
+        assert!(from_binary(&binary[1..]).is_err());
+    }
+
+    #[test]
+    fn test_reject_bls12_381_setup() {
+        // a point in the layout of the Ethereum trusted_setup.txt
+        let point = "a".repeat(2 * BLS12_381_G1_SIZE);
+        let text = format!("1\n0\n{point}\n{point}\n");
+        assert!(matches!(
+            from_trusted_setup_text(&text),
+            Err(KzgError::SerializationError(message)) if message.contains("BLS12-381")
+        ));
+    }
+}
@@ -0,0 +1,3 @@
+//! Tools for working with SRS artifacts outside of a `Kzg` setup.
+
+pub mod convert;
@@ -0,0 +1,211 @@
+//! A binary cache of a parsed SRS.
+//!
+//! Parsing the SRS files decompresses every point, which takes seconds for
+//! mainnet-sized setups. The cache stores the parsed points uncompressed, so
+//! they load without square roots, with a SHA-256 checksum guarding against
+//! corruption. The points are still checked to be on the curve and in the
+//! prime order subgroup as they load, so a cache file that was tampered with
+//! along with its checksum can't introduce invalid points. A cache file is
+//! keyed by the SHA-256 digests of the contents of the SRS files it was
+//! parsed from and the setup parameters, so changing a file misses the cache
+//! instead of loading stale points. See `Kzg::setup_with_cache`.
+
+use crate::errors::KzgError;
+use ark_bn254::{G1Affine, G2Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+/// Version of the cache format, bumped whenever it changes so older caches
+/// miss instead of being misread.
+pub const SRS_CACHE_VERSION: u32 = 2;
+
+const MAGIC: &[u8; 8] = b"KZGSRSC\0";
+const HEADER_SIZE: usize = MAGIC.len() + 4 + 32 + 32;
+
+/// The points stored in a cache file.
+pub(crate) struct CachedSrs {
+    pub(crate) g1: Vec<G1Affine>,
+    pub(crate) g2: Vec<G2Affine>,
+    pub(crate) g1_lagrange: Vec<G1Affine>,
+}
+
+/// Returns the SHA-256 digest of the contents of the file at `path`.
+pub(crate) fn file_sha256(path: &str) -> Result<[u8; 32], KzgError> {
+    let mut file = File::open(path).map_err(|e| KzgError::GenericError(format!("{path}: {e}")))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| KzgError::GenericError(format!("{path}: {e}")))?;
+    Ok(hasher.finalize().into())
+}
+
+/// Returns the key of an SRS parsed from `files`, empty paths standing for
+/// absent files, with the setup parameters `params`.
+pub(crate) fn cache_key(files: &[&str], params: &[u64]) -> Result<[u8; 32], KzgError> {
+    let mut hasher = Sha256::new();
+    hasher.update(SRS_CACHE_VERSION.to_le_bytes());
+    for file in files {
+        if file.is_empty() {
+            hasher.update([0u8]);
+            continue;
+        }
+        hasher.update([1u8]);
+        hasher.update(file_sha256(file)?);
+    }
+    for param in params {
+        hasher.update(param.to_le_bytes());
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Returns the path of the cache file for `key` in `dir`.
+pub(crate) fn cache_path(dir: &Path, key: &[u8; 32]) -> PathBuf {
+    let mut name = String::from("srs-");
+    for byte in &key[..16] {
+        name.push_str(&format!("{byte:02x}"));
+    }
+    name.push_str(".bin");
+    dir.join(name)
+}
+
+/// Writes the points to `path` under `key`, through a temporary file so
+/// readers never see a partial cache.
+pub(crate) fn write_cache(
+    path: &Path,
+    key: &[u8; 32],
+    g1: &[G1Affine],
+    g2: &[G2Affine],
+    g1_lagrange: &[G1Affine],
+) -> Result<(), KzgError> {
+    let points = (g1, g2, g1_lagrange);
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + points.uncompressed_size());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&SRS_CACHE_VERSION.to_le_bytes());
+    bytes.extend_from_slice(key);
+    bytes.extend_from_slice(&[0u8; 32]);
+    points
+        .serialize_uncompressed(&mut bytes)
+        .map_err(|e| KzgError::SerializationError(e.to_string()))?;
+    let checksum: [u8; 32] = Sha256::digest(&bytes[HEADER_SIZE..]).into();
+    bytes[HEADER_SIZE - 32..HEADER_SIZE].copy_from_slice(&checksum);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| KzgError::GenericError(e.to_string()))?;
+    }
+    let temporary = path.with_extension(format!("tmp{}", std::process::id()));
+    fs::write(&temporary, bytes).map_err(|e| KzgError::GenericError(e.to_string()))?;
+    fs::rename(&temporary, path).map_err(|e| KzgError::GenericError(e.to_string()))
+}
+
+/// Reads the cache at `path` if it holds an SRS for `key`. A missing,
+/// outdated or corrupted cache reads as `None`, to be parsed and written
+/// again.
+pub(crate) fn read_cache(path: &Path, key: &[u8; 32]) -> Option<CachedSrs> {
+    let bytes = fs::read(path).ok()?;
+    if bytes.len() < HEADER_SIZE {
+        return None;
+    }
+    let (header, payload) = bytes.split_at(HEADER_SIZE);
+    let checksum: [u8; 32] = Sha256::digest(payload).into();
+    if &header[..MAGIC.len()] != MAGIC
+        || header[MAGIC.len()..MAGIC.len() + 4] != SRS_CACHE_VERSION.to_le_bytes()
+        || &header[MAGIC.len() + 4..HEADER_SIZE - 32] != key
+        || header[HEADER_SIZE - 32..] != checksum
+    {
+        return None;
+    }
+    // the checksum only catches accidental corruption, so the points are
+    // checked again as they load
+    let (g1, g2, g1_lagrange) =
+        CanonicalDeserialize::deserialize_with_mode(payload, Compress::No, Validate::Yes).ok()?;
+    Some(CachedSrs {
+        g1,
+        g2,
+        g1_lagrange,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kzg::Kzg;
+
+    fn setup(cache_dir: &str, srs_points_to_load: u32) -> Kzg {
+        Kzg::setup_with_cache(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            srs_points_to_load,
+            cache_dir,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_srs_cache() {
+        let dir = std::env::temp_dir().join("rust-kzg-bn254-test-srs-cache");
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache_dir = dir.to_str().unwrap();
+        let parsed = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "",
+            3000,
+            3000,
+        )
+        .unwrap();
+
+        // the first setup parses and writes the cache, the second loads it
+        let written = setup(cache_dir, 3000);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        let loaded = setup(cache_dir, 3000);
+        for kzg in [&written, &loaded] {
+            assert_eq!(kzg.get_g1_points(), parsed.get_g1_points());
+            assert_eq!(kzg.get_g2_points(), parsed.get_g2_points());
+        }
+
+        // a Lagrange SRS written later is loaded along with the points
+        let mut kzg = loaded;
+        kzg.precompute_lagrange_srs(1024).unwrap();
+        kzg.write_srs_cache().unwrap();
+        assert_eq!(setup(cache_dir, 3000).get_lagrange_srs_length(), 1024);
+        kzg.release_g2_points();
+        assert!(kzg.write_srs_cache().is_err());
+
+        // other parameters get their own cache
+        assert_eq!(setup(cache_dir, 1000).get_g1_points().len(), 1000);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+        // the cache is keyed by the contents of the files, not their paths
+        let copy = std::env::temp_dir().join("rust-kzg-bn254-test-srs-cache-g1.point");
+        std::fs::copy("src/test-files/g1.point", &copy).unwrap();
+        let from_copy = Kzg::setup_with_cache(
+            copy.to_str().unwrap(),
+            "src/test-files/g2.point",
+            "",
+            3000,
+            3000,
+            cache_dir,
+        )
+        .unwrap();
+        std::fs::remove_file(&copy).unwrap();
+        assert_eq!(from_copy.get_lagrange_srs_length(), 1024);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+        // a corrupted cache is parsed again and rewritten
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let mut bytes = std::fs::read(&path).unwrap();
+            let last = bytes.len() - 1;
+            bytes[last] ^= 1;
+            std::fs::write(&path, bytes).unwrap();
+        }
+        let reparsed = setup(cache_dir, 3000);
+        assert_eq!(reparsed.get_g1_points(), parsed.get_g1_points());
+        assert_eq!(reparsed.get_lagrange_srs_length(), 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+//! Dispersal of payloads larger than one blob.
+//!
+//! `StreamEncoder` reads a payload from any `Read` source in segments of at
+//! most `blob_payload_size` bytes and encodes each segment into its own blob,
+//! so only one blob is held in memory at a time however large the payload.
+//! The `Manifest` it builds records the order, length, digest and header of
+//! every segment, which is all a retriever needs to check and reassemble the
+//! payload from the blobs' frames.
+
+use crate::{
+    errors::EncoderError,
+    pipeline::{BlobEncoder, EncodedBlob},
+    retrieve::{self, BlobHeader, Frame},
+};
+use sha2::{Digest, Sha256};
+use std::io::{ErrorKind, Read};
+
+/// One segment of a streamed payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    header: BlobHeader,
+    payload_len: usize,
+    digest: [u8; 32],
+}
+
+impl ManifestEntry {
+    /// Returns the header of the blob the segment is encoded into.
+    pub fn get_header(&self) -> BlobHeader {
+        self.header
+    }
+
+    /// Returns the length of the segment in bytes.
+    pub fn get_payload_len(&self) -> usize {
+        self.payload_len
+    }
+
+    /// Returns the sha256 digest of the segment.
+    pub fn get_digest(&self) -> [u8; 32] {
+        self.digest
+    }
+}
+
+/// The segments of a streamed payload, in payload order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Returns the segments, in payload order.
+    pub fn get_entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    /// Returns the length of the whole payload in bytes.
+    pub fn get_total_len(&self) -> usize {
+        self.entries.iter().map(|entry| entry.payload_len).sum()
+    }
+
+    /// Verifies and decodes every segment from its blob's frames, given in
+    /// manifest order, checks each against its length and digest, and
+    /// returns the whole payload.
+    ///
+    /// Fails with `InvalidSegmentError` carrying the index of the first
+    /// segment that does not match the manifest.
+    pub fn reassemble(
+        &self,
+        pipeline: &BlobEncoder,
+        frames: &[Vec<Frame>],
+    ) -> Result<Vec<u8>, EncoderError> {
+        if frames.len() != self.entries.len() {
+            return Err(EncoderError::InvalidParametersError(format!(
+                "manifest has {} segments, got frames for {}",
+                self.entries.len(),
+                frames.len()
+            )));
+        }
+        let mut payload = Vec::with_capacity(self.get_total_len());
+        for (index, (entry, frames)) in self.entries.iter().zip(frames).enumerate() {
+            let mut segment = retrieve::verify_and_reassemble(
+                pipeline.get_kzg(),
+                pipeline.get_encoder(),
+                frames,
+                &entry.header,
+            )?;
+            if segment.len() < entry.payload_len {
+                return Err(EncoderError::InvalidSegmentError(index));
+            }
+            segment.truncate(entry.payload_len);
+            if sha256(&segment) != entry.digest {
+                return Err(EncoderError::InvalidSegmentError(index));
+            }
+            payload.extend_from_slice(&segment);
+        }
+        Ok(payload)
+    }
+}
+
+/// Encodes a payload read from `reader` into a sequence of blobs, one per
+/// iteration, adding each to the manifest.
+///
+/// Iteration stops at the end of the payload or after the first error.
+#[derive(Debug)]
+pub struct StreamEncoder<R: Read> {
+    pipeline: BlobEncoder,
+    reader: R,
+    blob_payload_size: usize,
+    manifest: Manifest,
+    done: bool,
+}
+
+impl<R: Read> StreamEncoder<R> {
+    /// Streams `reader` through `pipeline` in segments of `blob_payload_size`
+    /// bytes, the last one possibly shorter. An empty payload gives no blobs.
+    pub fn new(
+        pipeline: BlobEncoder,
+        reader: R,
+        blob_payload_size: usize,
+    ) -> Result<Self, EncoderError> {
+        if blob_payload_size == 0 {
+            return Err(EncoderError::InvalidParametersError(
+                "blob payload size must be positive".to_string(),
+            ));
+        }
+        Ok(StreamEncoder {
+            pipeline,
+            reader,
+            blob_payload_size,
+            manifest: Manifest::default(),
+            done: false,
+        })
+    }
+
+    /// Returns the manifest of the segments encoded so far.
+    pub fn get_manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Consumes the stream, returning its manifest.
+    pub fn into_manifest(self) -> Manifest {
+        self.manifest
+    }
+
+    /// reads the next segment, returning an empty one at the end of the
+    /// payload
+    fn read_segment(&mut self) -> Result<Vec<u8>, EncoderError> {
+        let mut segment = vec![0u8; self.blob_payload_size];
+        let mut filled = 0;
+        while filled < segment.len() {
+            match self.reader.read(&mut segment[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {},
+                Err(err) => return Err(EncoderError::GenericError(err.to_string())),
+            }
+        }
+        segment.truncate(filled);
+        Ok(segment)
+    }
+
+    fn encode_next(&mut self) -> Result<Option<EncodedBlob>, EncoderError> {
+        let segment = self.read_segment()?;
+        if segment.is_empty() {
+            return Ok(None);
+        }
+        let encoded = self.pipeline.encode(&segment)?;
+        self.manifest.entries.push(ManifestEntry {
+            header: encoded.get_header(),
+            payload_len: segment.len(),
+            digest: sha256(&segment),
+        });
+        Ok(Some(encoded))
+    }
+}
+
+impl<R: Read> Iterator for StreamEncoder<R> {
+    type Item = Result<EncodedBlob, EncoderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.encode_next().transpose();
+        if !matches!(next, Some(Ok(_))) {
+            self.done = true;
+        }
+        next
+    }
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consts::GETTYSBURG_ADDRESS_BYTES, kzg::Kzg};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_stream_encoder_round_trip() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let pipeline = BlobEncoder::new(Arc::new(kzg), 8, 2).unwrap();
+        assert!(StreamEncoder::new(pipeline.clone(), GETTYSBURG_ADDRESS_BYTES, 0).is_err());
+
+        let mut stream =
+            StreamEncoder::new(pipeline.clone(), GETTYSBURG_ADDRESS_BYTES, 500).unwrap();
+        let blobs: Vec<EncodedBlob> = stream.by_ref().map(Result::unwrap).collect();
+        let manifest = stream.into_manifest();
+        assert_eq!(blobs.len(), GETTYSBURG_ADDRESS_BYTES.len().div_ceil(500));
+        assert_eq!(manifest.get_entries().len(), blobs.len());
+        assert_eq!(manifest.get_total_len(), GETTYSBURG_ADDRESS_BYTES.len());
+        assert_eq!(manifest.get_entries()[0].get_payload_len(), 500);
+        assert_eq!(
+            manifest.get_entries()[0].get_header(),
+            blobs[0].get_header()
+        );
+
+        // any half of each blob's frames recovers the payload
+        let frames: Vec<Vec<Frame>> = blobs
+            .iter()
+            .map(|blob| blob.get_frames()[4..].to_vec())
+            .collect();
+        assert_eq!(
+            manifest.reassemble(&pipeline, &frames).unwrap(),
+            GETTYSBURG_ADDRESS_BYTES
+        );
+
+        // segments out of order, or missing, fail
+        let mut swapped = frames.clone();
+        swapped.swap(0, 1);
+        assert!(manifest.reassemble(&pipeline, &swapped).is_err());
+        assert!(matches!(
+            manifest.reassemble(&pipeline, &frames[1..]),
+            Err(EncoderError::InvalidParametersError(_))
+        ));
+        let mut tampered = manifest.clone();
+        tampered.entries[1].digest[0] ^= 1;
+        assert_eq!(
+            tampered.reassemble(&pipeline, &frames),
+            Err(EncoderError::InvalidSegmentError(1))
+        );
+
+        let mut empty = StreamEncoder::new(pipeline, &[][..], 500).unwrap();
+        assert!(empty.next().is_none());
+        assert_eq!(empty.into_manifest(), Manifest::default());
+    }
+}
@@ -10,14 +10,14 @@ pub trait ReadPointFromBytes: AffineRepr {
 // Implement this trait for G1Affine and G2Affine
 impl ReadPointFromBytes for G1Affine {
     fn read_point_from_bytes_be(bytes: &[u8]) -> io::Result<G1Affine> {
-        helpers::read_g1_point_from_bytes_be(&bytes.to_vec())
+        helpers::read_g1_point_from_bytes_be(bytes)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 }
 
 impl ReadPointFromBytes for G2Affine {
     fn read_point_from_bytes_be(bytes: &[u8]) -> io::Result<G2Affine> {
-        helpers::read_g2_point_from_bytes_be(&bytes.to_vec())
+        helpers::read_g2_point_from_bytes_be(bytes)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 }
@@ -0,0 +1,186 @@
+//! Fiat-Shamir transcripts for the challenges of batched verifications.
+//!
+//! With SHA-256 or Keccak-256, everything appended is hashed as bytes, points
+//! compressed, and the digest is reduced into the scalar field. With
+//! Poseidon, everything is absorbed as scalars instead: bytes packed 31 to an
+//! element after their length, and points as the limbs of their coordinates
+//! from `circuit`, so a circuit re-derives the challenge from the same public
+//! inputs it already takes.
+
+use crate::{
+    config::ChallengeHash,
+    encoding::{fr_to_bytes, g1_to_bytes},
+    errors::KzgError,
+};
+use ark_bn254::{Fr, G1Affine};
+use ark_ff::PrimeField;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// Number of scalars absorbed per Poseidon permutation, next to the state.
+#[cfg(feature = "poseidon")]
+const POSEIDON_RATE: usize = 11;
+
+#[derive(Clone, Debug)]
+enum State {
+    Sha256(Sha256),
+    Keccak256(Box<Keccak256>),
+    #[cfg(feature = "poseidon")]
+    Poseidon(Vec<Fr>),
+}
+
+/// Accumulates the inputs of a challenge, starting from a domain tag.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    state: State,
+}
+
+impl Transcript {
+    /// Starts a transcript hashed with `hash`, separated from others by
+    /// `domain`.
+    pub fn new(hash: ChallengeHash, domain: &[u8]) -> Self {
+        let state = match hash {
+            ChallengeHash::Sha256 => State::Sha256(Sha256::new()),
+            ChallengeHash::Keccak256 => State::Keccak256(Box::default()),
+            #[cfg(feature = "poseidon")]
+            ChallengeHash::Poseidon => State::Poseidon(Vec::new()),
+        };
+        let mut transcript = Transcript { state };
+        transcript.append_bytes(domain);
+        transcript
+    }
+
+    /// Appends raw bytes.
+    pub fn append_bytes(&mut self, bytes: &[u8]) {
+        match &mut self.state {
+            State::Sha256(hasher) => hasher.update(bytes),
+            State::Keccak256(hasher) => hasher.update(bytes),
+            #[cfg(feature = "poseidon")]
+            State::Poseidon(elements) => {
+                elements.push(Fr::from(bytes.len() as u64));
+                elements.extend(bytes.chunks(31).map(Fr::from_be_bytes_mod_order));
+            },
+        }
+    }
+
+    /// Appends a 64-bit integer, as 8 big-endian bytes or one scalar.
+    pub fn append_u64(&mut self, value: u64) {
+        #[cfg(feature = "poseidon")]
+        if let State::Poseidon(elements) = &mut self.state {
+            elements.push(Fr::from(value));
+            return;
+        }
+        self.append_bytes(&value.to_be_bytes());
+    }
+
+    /// Appends a scalar, as 32 big-endian bytes or itself.
+    pub fn append_fr(&mut self, value: &Fr) {
+        #[cfg(feature = "poseidon")]
+        if let State::Poseidon(elements) = &mut self.state {
+            elements.push(*value);
+            return;
+        }
+        self.append_bytes(&fr_to_bytes(value));
+    }
+
+    /// Appends a G1 point, compressed or as the limbs of its coordinates.
+    pub fn append_g1(&mut self, point: &G1Affine) -> Result<(), KzgError> {
+        #[cfg(feature = "poseidon")]
+        if let State::Poseidon(elements) = &mut self.state {
+            elements.extend(crate::circuit::g1_to_limbs(point));
+            return Ok(());
+        }
+        self.append_bytes(&g1_to_bytes(point));
+        Ok(())
+    }
+
+    /// Derives the challenge from everything appended.
+    pub fn challenge(self) -> Result<Fr, KzgError> {
+        match self.state {
+            State::Sha256(hasher) => Ok(Fr::from_be_bytes_mod_order(&hasher.finalize())),
+            State::Keccak256(hasher) => Ok(Fr::from_be_bytes_mod_order(&hasher.finalize())),
+            #[cfg(feature = "poseidon")]
+            State::Poseidon(elements) => poseidon_sponge(&elements),
+        }
+    }
+}
+
+/// absorbs `elements` `POSEIDON_RATE` at a time into a state chained through
+/// one permutation per block, starting from zero
+#[cfg(feature = "poseidon")]
+fn poseidon_sponge(elements: &[Fr]) -> Result<Fr, KzgError> {
+    use light_poseidon::{Poseidon, PoseidonHasher};
+
+    let mut state = Fr::from(0u64);
+    for block in elements.chunks(POSEIDON_RATE) {
+        let mut inputs = Vec::with_capacity(block.len() + 1);
+        inputs.push(state);
+        inputs.extend_from_slice(block);
+        state = Poseidon::<Fr>::new_circom(inputs.len())
+            .and_then(|mut poseidon| poseidon.hash(&inputs))
+            .map_err(|err| KzgError::GenericError(err.to_string()))?;
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::AffineRepr;
+    use ark_ff::BigInteger;
+
+    fn challenge(hash: ChallengeHash, value: u64) -> Fr {
+        let mut transcript = Transcript::new(hash, b"domain");
+        transcript.append_bytes(b"bytes");
+        transcript.append_u64(value);
+        transcript.append_fr(&Fr::from(7u64));
+        transcript.append_g1(&G1Affine::generator()).unwrap();
+        transcript.challenge().unwrap()
+    }
+
+    #[test]
+    fn test_transcript() {
+        // the byte hashes see the concatenation of the serialized inputs
+        let mut bytes = b"domainbytes".to_vec();
+        bytes.extend_from_slice(&3u64.to_be_bytes());
+        bytes.extend_from_slice(&Fr::from(7u64).into_bigint().to_bytes_be());
+        bytes.extend_from_slice(&g1_to_bytes(&G1Affine::generator()));
+        assert_eq!(
+            challenge(ChallengeHash::Sha256, 3),
+            Fr::from_be_bytes_mod_order(&Sha256::digest(&bytes))
+        );
+        assert_eq!(
+            challenge(ChallengeHash::Keccak256, 3),
+            Fr::from_be_bytes_mod_order(&Keccak256::digest(&bytes))
+        );
+        assert_ne!(
+            challenge(ChallengeHash::Sha256, 3),
+            challenge(ChallengeHash::Sha256, 4)
+        );
+    }
+
+    #[cfg(feature = "poseidon")]
+    #[test]
+    fn test_poseidon_transcript() {
+        use light_poseidon::{Poseidon, PoseidonHasher};
+
+        let poseidon = challenge(ChallengeHash::Poseidon, 3);
+        assert_ne!(poseidon, challenge(ChallengeHash::Poseidon, 4));
+        assert_ne!(poseidon, challenge(ChallengeHash::Sha256, 3));
+
+        // a short transcript is one permutation over the state and its
+        // elements
+        let mut transcript = Transcript::new(ChallengeHash::Poseidon, b"ab");
+        transcript.append_u64(5);
+        let expected = Poseidon::<Fr>::new_circom(4)
+            .unwrap()
+            .hash(&[
+                Fr::from(0u64),
+                Fr::from(2u64),
+                Fr::from(0x6162u64),
+                Fr::from(5u64),
+            ])
+            .unwrap();
+        assert_eq!(transcript.challenge().unwrap(), expected);
+    }
+}
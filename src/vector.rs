@@ -0,0 +1,180 @@
+//! KZG as a vector commitment: an authenticated array of 31-byte records,
+//! each openable by its index.
+//!
+//! Record `i` is the evaluation of the committed polynomial at the `i`-th
+//! root of unity of the array's length rounded up to a power of 2, the same
+//! layout a blob's field elements have, so the records need no padding byte.
+
+use crate::{errors::KzgError, kzg::Kzg, polynomial::Polynomial};
+use ark_bn254::{Fr, G1Affine};
+use ark_ff::{BigInteger, PrimeField};
+
+/// Number of bytes in a record, the most a field element holds for any value.
+pub const RECORD_SIZE: usize = 31;
+
+/// A commitment to an array of records.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VectorCommitment {
+    commitment: G1Affine,
+    num_records: usize,
+}
+
+impl VectorCommitment {
+    /// Returns the KZG commitment to the records.
+    pub fn get_commitment(&self) -> G1Affine {
+        self.commitment
+    }
+
+    /// Returns the number of records committed to.
+    pub fn get_num_records(&self) -> usize {
+        self.num_records
+    }
+
+    /// Checks `proof` opens the committed array to its record at its index.
+    pub fn verify(&self, kzg: &Kzg, proof: &RecordProof) -> bool {
+        if proof.index >= self.num_records {
+            return false;
+        }
+        let z = Kzg::root_of_unity(self.num_records.next_power_of_two(), proof.index);
+        kzg.verify_kzg_proof(self.commitment, proof.proof, record_to_fr(&proof.record), z)
+    }
+}
+
+/// A record with the proof it is at `index` in a committed array.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecordProof {
+    index: usize,
+    record: [u8; RECORD_SIZE],
+    proof: G1Affine,
+}
+
+impl RecordProof {
+    /// Returns the index of the record.
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the record.
+    pub fn get_record(&self) -> [u8; RECORD_SIZE] {
+        self.record
+    }
+
+    /// Returns the KZG proof of the record.
+    pub fn get_proof(&self) -> G1Affine {
+        self.proof
+    }
+}
+
+/// An array of records with its commitment, kept to open records from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordVector {
+    records: Vec<[u8; RECORD_SIZE]>,
+    polynomial: Polynomial,
+    commitment: VectorCommitment,
+}
+
+impl RecordVector {
+    /// Commits to `records`, which must not be empty.
+    pub fn commit(kzg: &Kzg, records: Vec<[u8; RECORD_SIZE]>) -> Result<Self, KzgError> {
+        let elements: Vec<Fr> = records.iter().map(record_to_fr).collect();
+        let polynomial = Polynomial::new(&elements, records.len() * RECORD_SIZE)
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        let commitment = VectorCommitment {
+            commitment: kzg.commit(&polynomial)?,
+            num_records: records.len(),
+        };
+        Ok(RecordVector {
+            records,
+            polynomial,
+            commitment,
+        })
+    }
+
+    /// Returns the records.
+    pub fn get_records(&self) -> &[[u8; RECORD_SIZE]] {
+        &self.records
+    }
+
+    /// Returns the commitment to the records.
+    pub fn get_commitment(&self) -> VectorCommitment {
+        self.commitment
+    }
+
+    /// Proves the record at `index`.
+    pub fn open(&self, kzg: &Kzg, index: usize) -> Result<RecordProof, KzgError> {
+        let record = *self.records.get(index).ok_or_else(|| {
+            KzgError::GenericError(format!(
+                "index {} is out of bounds for {} records",
+                index,
+                self.records.len()
+            ))
+        })?;
+        let z = Kzg::root_of_unity(self.polynomial.len(), index);
+        let (proof, _) = kzg.compute_kzg_proof_at_point(&self.polynomial, z)?;
+        Ok(RecordProof {
+            index,
+            record,
+            proof,
+        })
+    }
+}
+
+/// reads a record as a big-endian integer, always below the modulus
+fn record_to_fr(record: &[u8; RECORD_SIZE]) -> Fr {
+    Fr::from_be_bytes_mod_order(record)
+}
+
+/// Returns the record a field element holds, if it fits in one.
+pub fn fr_to_record(value: &Fr) -> Option<[u8; RECORD_SIZE]> {
+    let bytes = value.into_bigint().to_bytes_be();
+    let (high, low) = bytes.split_at(bytes.len() - RECORD_SIZE);
+    if high.iter().any(|b| *b != 0) {
+        return None;
+    }
+    low.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_vector() {
+        let kzg = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        let records: Vec<[u8; RECORD_SIZE]> = (0..37u8)
+            .map(|i| [i.wrapping_mul(7); RECORD_SIZE])
+            .collect();
+        let vector = RecordVector::commit(&kzg, records.clone()).unwrap();
+        let commitment = vector.get_commitment();
+        assert_eq!(commitment.get_num_records(), 37);
+        assert!(RecordVector::commit(&kzg, vec![]).is_err());
+
+        for index in [0, 1, 20, 36] {
+            let proof = vector.open(&kzg, index).unwrap();
+            assert_eq!(proof.get_record(), records[index]);
+            assert!(commitment.verify(&kzg, &proof));
+        }
+        assert!(vector.open(&kzg, 37).is_err());
+
+        // a different record, or the record claimed at another index, fails
+        let proof = vector.open(&kzg, 5).unwrap();
+        let mut wrong_record = proof;
+        wrong_record.record[0] ^= 1;
+        assert!(!commitment.verify(&kzg, &wrong_record));
+        let mut wrong_index = proof;
+        wrong_index.index = 6;
+        assert!(!commitment.verify(&kzg, &wrong_index));
+        wrong_index.index = 40;
+        assert!(!commitment.verify(&kzg, &wrong_index));
+
+        assert_eq!(fr_to_record(&record_to_fr(&records[9])), Some(records[9]));
+        assert_eq!(fr_to_record(&-Fr::from(1u64)), None);
+    }
+}
@@ -0,0 +1,474 @@
+//! Verification without the G1 powers of tau.
+//!
+//! Checking an opening only takes `[tau]G2` next to the generators, but
+//! `Kzg::setup` loads the whole G1 table before anything can be verified.
+//! `KzgVerifier` holds just `[tau]G2` and the challenge hash, so services
+//! that only check proofs start without reading the G1 file. `Kzg` runs its
+//! own verifications through the `KzgVerifier` returned by `Kzg::verifier`,
+//! so both always agree. Proofs whose check needs more of the SRS, like
+//! multi-point, length and degree-bound proofs, stay on `Kzg`.
+
+use crate::{
+    blob::Blob,
+    circuit::KzgAccumulator,
+    config::ChallengeHash,
+    consts::{AGGREGATION_CHALLENGE_DOMAIN, BLOB_BATCH_CHALLENGE_DOMAIN, BLOB_CHALLENGE_DOMAIN},
+    encoding::{fr_to_bytes, g1_from_bytes},
+    errors::KzgError,
+    glv, helpers,
+    kzg::Kzg,
+    polynomial::Polynomial,
+    transcript::Transcript,
+};
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_std::{One, UniformRand};
+use rayon::prelude::*;
+
+/// A claim that `proof` opens `commitment` to `value` at `z`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpeningClaim {
+    commitment: G1Affine,
+    proof: G1Affine,
+    value: Fr,
+    z: Fr,
+}
+
+impl OpeningClaim {
+    /// Creates the claim that `proof` opens `commitment` to `value` at `z`.
+    pub fn new(commitment: G1Affine, proof: G1Affine, value: Fr, z: Fr) -> Self {
+        OpeningClaim {
+            commitment,
+            proof,
+            value,
+            z,
+        }
+    }
+}
+
+/// A verifier holding only `[tau]G2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KzgVerifier {
+    g2_tau: G2Affine,
+    challenge_hash: ChallengeHash,
+}
+
+impl KzgVerifier {
+    /// Creates a verifier from `[tau]G2`, deriving challenges with SHA-256.
+    pub fn new(g2_tau: G2Affine) -> Self {
+        KzgVerifier {
+            g2_tau,
+            challenge_hash: ChallengeHash::default(),
+        }
+    }
+
+    /// Creates a verifier from the G2 SRS files `Kzg::setup` takes, reading
+    /// only `[tau]G2`. As with `Kzg::setup`, the full G2 table is used when
+    /// its path is not empty and the power of 2 table otherwise.
+    pub fn setup(path_to_g2_points: &str, g2_power_of2_path: &str) -> Result<Self, KzgError> {
+        let g2_tau = if !path_to_g2_points.is_empty() {
+            Kzg::parallel_read_g2_points(path_to_g2_points.to_owned(), 2)?
+                .get(1)
+                .copied()
+        } else if !g2_power_of2_path.is_empty() {
+            Kzg::read_g2_point_on_power_of_2(g2_power_of2_path)?
+                .first()
+                .copied()
+        } else {
+            return Err(KzgError::GenericError(
+                "both g2 point files are empty, need the proper file specified".to_string(),
+            ));
+        };
+        g2_tau.map(KzgVerifier::new).ok_or_else(|| {
+            KzgError::SerializationError("G2 file does not hold [tau]G2".to_string())
+        })
+    }
+
+    /// Creates a verifier from `[tau]G2` as the 64-byte big-endian point of
+    /// the SRS files, e.g. compiled in with `include_bytes!`.
+    pub fn from_bytes(g2_tau_bytes: &[u8]) -> Result<Self, KzgError> {
+        let g2_tau = helpers::read_g2_point_from_bytes_be(g2_tau_bytes)
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        Ok(KzgVerifier::new(g2_tau))
+    }
+
+    /// Returns the verifier deriving challenges with `challenge_hash`, which
+    /// has to match the one the proofs were made with.
+    pub fn with_challenge_hash(mut self, challenge_hash: ChallengeHash) -> Self {
+        self.challenge_hash = challenge_hash;
+        self
+    }
+
+    /// Returns `[tau]G2`.
+    pub fn get_g2_tau(&self) -> G2Affine {
+        self.g2_tau
+    }
+
+    /// Returns the hash challenges are derived with.
+    pub fn get_challenge_hash(&self) -> ChallengeHash {
+        self.challenge_hash
+    }
+
+    /// Checks `proof` opens `commitment` to `value_fr` at `z_fr`, with the
+    /// pairing check `e(C - [value]G1, G2) = e(proof, [tau - z]G2)`.
+    pub fn verify_kzg_proof(
+        &self,
+        commitment: G1Affine,
+        proof: G1Affine,
+        value_fr: Fr,
+        z_fr: Fr,
+    ) -> bool {
+        let (a1, a2, b1, b2) = self.kzg_proof_pairing_inputs(commitment, proof, value_fr, z_fr);
+        Kzg::pairings_verify(a1, a2, b1, b2)
+    }
+
+    /// Checks many independent openings at once. Each check
+    /// `e(C_i - [y_i]G1, G2) = e(proof_i, [tau - z_i]G2)` is rearranged as in
+    /// `KzgAccumulator::from_opening` and the checks are folded with powers
+    /// of a random scalar, so all of them cost two MSMs and a single product
+    /// of two pairings instead of a pairing check each. A false claim passes
+    /// only with negligible probability, but a failure does not tell which
+    /// claim is false.
+    pub fn verify_proofs_batched(&self, items: &[OpeningClaim]) -> bool {
+        if items.is_empty() {
+            return true;
+        }
+        let accumulators: Vec<KzgAccumulator> = items
+            .iter()
+            .map(|item| {
+                KzgAccumulator::from_opening(&item.commitment, &item.proof, item.z, item.value)
+            })
+            .collect();
+        let r = Fr::rand(&mut rand::thread_rng());
+        let folded = KzgAccumulator::fold(&accumulators, r);
+        Kzg::multi_pairing(
+            &[folded.get_lhs(), -folded.get_rhs()],
+            &[G2Affine::generator(), self.g2_tau],
+        )
+    }
+
+    /// Checks a proof from `Kzg::compute_blob_kzg_proof`, re-deriving the
+    /// challenge and the blob's evaluation there, like c-kzg-4844's
+    /// `verify_blob_kzg_proof`.
+    pub fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment: &G1Affine,
+        proof: &G1Affine,
+    ) -> Result<bool, KzgError> {
+        let polynomial = blob
+            .to_polynomial()
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        let z = self.blob_challenge(&polynomial, commitment)?;
+        let value = Kzg::evaluate_polynomial_in_evaluation_form(&polynomial, z);
+        Ok(self.verify_kzg_proof(*commitment, *proof, value, z))
+    }
+
+    /// Same as `verify_blob_kzg_proof`, over the padded blob data as returned
+    /// by `Blob::get_blob_data` and the compressed commitment and proof.
+    /// Points that are not on the curve or not in the subgroup are errors
+    /// rather than failed checks.
+    pub fn verify_from_bytes(
+        &self,
+        blob_bytes: &[u8],
+        commitment_bytes: &[u8],
+        proof_bytes: &[u8],
+    ) -> Result<bool, KzgError> {
+        let commitment = g1_from_bytes(commitment_bytes)?;
+        let proof = g1_from_bytes(proof_bytes)?;
+        self.verify_blob_kzg_proof(&Blob::new(blob_bytes.to_vec(), true), &commitment, &proof)
+    }
+
+    /// Checks many proofs from `Kzg::compute_blob_kzg_proof` with a single
+    /// pairing check, folding the openings with powers of a challenge hashed
+    /// from all of them. Returns one result per blob, in input order. Blobs
+    /// are only checked one by one when the combined check fails, to find
+    /// which proofs are invalid.
+    pub fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments: &[G1Affine],
+        proofs: &[G1Affine],
+    ) -> Result<Vec<Result<(), KzgError>>, KzgError> {
+        if blobs.len() != commitments.len() || blobs.len() != proofs.len() {
+            return Err(KzgError::GenericError(
+                "blobs, commitments and proofs differ in number".to_string(),
+            ));
+        }
+        let openings: Vec<Result<(Fr, Fr), KzgError>> = blobs
+            .par_iter()
+            .zip(commitments)
+            .map(|(blob, commitment)| {
+                let polynomial = blob
+                    .to_polynomial()
+                    .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+                let z = self.blob_challenge(&polynomial, commitment)?;
+                let value = Kzg::evaluate_polynomial_in_evaluation_form(&polynomial, z);
+                Ok((z, value))
+            })
+            .collect();
+        let batch: Vec<(usize, Fr, Fr)> = openings
+            .iter()
+            .enumerate()
+            .filter_map(|(i, opening)| opening.as_ref().ok().map(|&(z, value)| (i, z, value)))
+            .collect();
+
+        let batch_valid = self
+            .blob_batch_challenge(commitments, proofs, &batch)
+            .map(|r| {
+                let accumulators: Vec<KzgAccumulator> = batch
+                    .iter()
+                    .map(|&(i, z, value)| {
+                        KzgAccumulator::from_opening(&commitments[i], &proofs[i], z, value)
+                    })
+                    .collect();
+                let folded = KzgAccumulator::fold(&accumulators, r);
+                self.verify_pairing(folded.get_lhs(), folded.get_rhs())
+            })
+            .unwrap_or(false);
+        let mut results: Vec<Result<(), KzgError>> = openings
+            .into_iter()
+            .map(|opening| opening.map(|_| ()))
+            .collect();
+        if !batch_valid {
+            let invalid: Vec<usize> = batch
+                .par_iter()
+                .filter(|&&(i, z, value)| {
+                    !self.verify_kzg_proof(commitments[i], proofs[i], value, z)
+                })
+                .map(|&(i, _, _)| i)
+                .collect();
+            for i in invalid {
+                results[i] = Err(KzgError::InvalidProofError(i));
+            }
+        }
+        Ok(results)
+    }
+
+    /// Checks a proof from `Kzg::compute_aggregated_proof` that the
+    /// polynomials committed to by `commitments` evaluate to `values` at `z`.
+    pub fn verify_aggregated_proof(
+        &self,
+        commitments: &[G1Affine],
+        proof: G1Affine,
+        values: &[Fr],
+        z: Fr,
+    ) -> Result<bool, KzgError> {
+        if commitments.is_empty() || commitments.len() != values.len() {
+            return Err(KzgError::GenericError(
+                "commitments and values must be equal in number and not empty".to_string(),
+            ));
+        }
+        let (commitment, value) = self.combine_openings(commitments, values, z)?;
+        Ok(self.verify_kzg_proof(commitment, proof, value, z))
+    }
+
+    /// Derives the point `Kzg::compute_blob_kzg_proof` opens the blob at; see
+    /// `Kzg::compute_challenge`.
+    pub fn compute_challenge(&self, blob: &Blob, commitment: &G1Affine) -> Result<Fr, KzgError> {
+        let polynomial = blob
+            .to_polynomial()
+            .map_err(|err| KzgError::SerializationError(err.to_string()))?;
+        self.blob_challenge(&polynomial, commitment)
+    }
+
+    /// derives the point a blob is opened at as c-kzg-4844 does, hashing the
+    /// domain, the number of field elements as 16 big-endian bytes, the
+    /// field elements and the commitment, with the configured challenge hash
+    pub(crate) fn blob_challenge(
+        &self,
+        polynomial: &Polynomial,
+        commitment: &G1Affine,
+    ) -> Result<Fr, KzgError> {
+        let mut transcript = Transcript::new(self.challenge_hash, BLOB_CHALLENGE_DOMAIN);
+        transcript.append_bytes(&(polynomial.len() as u128).to_be_bytes());
+        for element in polynomial.as_slice() {
+            transcript.append_bytes(&fr_to_bytes(element));
+        }
+        transcript.append_g1(commitment)?;
+        transcript.challenge()
+    }
+
+    /// hashes the commitment, point, value and proof of every opening in a
+    /// blob batch into the challenge the openings are folded with
+    fn blob_batch_challenge(
+        &self,
+        commitments: &[G1Affine],
+        proofs: &[G1Affine],
+        batch: &[(usize, Fr, Fr)],
+    ) -> Result<Fr, KzgError> {
+        let mut transcript = Transcript::new(self.challenge_hash, BLOB_BATCH_CHALLENGE_DOMAIN);
+        transcript.append_u64(batch.len() as u64);
+        for &(i, z, value) in batch {
+            transcript.append_g1(&commitments[i])?;
+            transcript.append_fr(&z);
+            transcript.append_fr(&value);
+            transcript.append_g1(&proofs[i])?;
+        }
+        transcript.challenge()
+    }
+
+    /// returns the commitment and value of the combination of polynomials
+    /// `Kzg::compute_aggregated_proof` opens at `z`
+    pub(crate) fn combine_openings(
+        &self,
+        commitments: &[G1Affine],
+        values: &[Fr],
+        z: Fr,
+    ) -> Result<(G1Affine, Fr), KzgError> {
+        let gamma = self.aggregation_challenge(commitments, values, z)?;
+        let mut powers = Vec::with_capacity(commitments.len());
+        let mut power = Fr::one();
+        for _ in commitments {
+            powers.push(power);
+            power *= gamma;
+        }
+        let commitment = glv::msm(commitments, &powers, None)
+            .map_err(|err| KzgError::GenericError(err.to_string()))?
+            .into_affine();
+        let value = values
+            .iter()
+            .zip(&powers)
+            .map(|(value, power)| *value * power)
+            .sum();
+        Ok((commitment, value))
+    }
+
+    /// hashes the point, then every commitment and evaluation, into the
+    /// challenge polynomials opened at one point are combined with
+    pub(crate) fn aggregation_challenge(
+        &self,
+        commitments: &[G1Affine],
+        values: &[Fr],
+        z: Fr,
+    ) -> Result<Fr, KzgError> {
+        let mut transcript = Transcript::new(self.challenge_hash, AGGREGATION_CHALLENGE_DOMAIN);
+        transcript.append_u64(commitments.len() as u64);
+        transcript.append_fr(&z);
+        for (commitment, value) in commitments.iter().zip(values) {
+            transcript.append_g1(commitment)?;
+            transcript.append_fr(value);
+        }
+        transcript.challenge()
+    }
+
+    /// returns the points `verify_kzg_proof` checks `e(a1, a2) = e(b1, b2)`
+    /// on: `C - [value]G1`, `G2`, the proof and `[tau]G2 - [z]G2`
+    pub(crate) fn kzg_proof_pairing_inputs(
+        &self,
+        commitment: G1Affine,
+        proof: G1Affine,
+        value_fr: Fr,
+        z_fr: Fr,
+    ) -> (G1Affine, G2Affine, G1Affine, G2Affine) {
+        let value_g1 = glv::mul(&G1Affine::generator(), &value_fr).into_affine();
+        let commit_minus_value = (commitment - value_g1).into_affine();
+        let z_g2 = (G2Affine::generator() * z_fr).into_affine();
+        let x_minus_z = (self.g2_tau - z_g2).into_affine();
+        (commit_minus_value, G2Affine::generator(), proof, x_minus_z)
+    }
+
+    /// checks e(a, G2) = e(b, [tau]G2)
+    pub(crate) fn verify_pairing(&self, a: G1Affine, b: G1Affine) -> bool {
+        Kzg::pairings_verify(a, G2Affine::generator(), b, self.g2_tau)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::GETTYSBURG_ADDRESS_BYTES;
+
+    fn setup_kzg() -> Kzg {
+        Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_verifier_matches_kzg() {
+        let kzg = setup_kzg();
+        let verifier = KzgVerifier::setup("src/test-files/g2.point", "").unwrap();
+        assert_eq!(verifier, kzg.verifier());
+        let kzg_power_of2 = Kzg::setup(
+            "src/test-files/g1.point",
+            "",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        assert_eq!(
+            KzgVerifier::setup("", "src/test-files/g2.point.powerOf2").unwrap(),
+            kzg_power_of2.verifier()
+        );
+        let g2 = std::fs::read("src/test-files/g2.point").unwrap();
+        assert_eq!(KzgVerifier::from_bytes(&g2[64..128]).unwrap(), verifier);
+        assert!(KzgVerifier::from_bytes(&g2[64..127]).is_err());
+        assert!(KzgVerifier::setup("", "").is_err());
+
+        let blob = Blob::from_bytes_and_pad(GETTYSBURG_ADDRESS_BYTES);
+        let other = Blob::from_bytes_and_pad(&GETTYSBURG_ADDRESS_BYTES[..200]);
+        let commitment = kzg.blob_to_kzg_commitment(&blob).unwrap();
+        let proof = kzg.compute_blob_kzg_proof(&blob, &commitment).unwrap();
+        assert!(verifier
+            .verify_blob_kzg_proof(&blob, &commitment, &proof)
+            .unwrap());
+        assert!(!verifier
+            .verify_blob_kzg_proof(&other, &commitment, &proof)
+            .unwrap());
+        assert_eq!(
+            verifier.compute_challenge(&blob, &commitment).unwrap(),
+            kzg.compute_challenge(&blob, &commitment).unwrap()
+        );
+        let results = verifier
+            .verify_blob_kzg_proof_batch(
+                &[blob.clone(), other.clone()],
+                &[commitment, commitment],
+                &[proof, proof],
+            )
+            .unwrap();
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(KzgError::InvalidProofError(1))));
+
+        let polynomials = [blob, other]
+            .iter()
+            .map(|blob| blob.to_polynomial().unwrap())
+            .collect::<Vec<_>>();
+        let z = Fr::from(5u64);
+        let commitments: Vec<G1Affine> = polynomials
+            .iter()
+            .map(|polynomial| kzg.commit(polynomial).unwrap())
+            .collect();
+        let (proof, values) = kzg.compute_aggregated_proof(&polynomials, z).unwrap();
+        assert!(verifier
+            .verify_aggregated_proof(&commitments, proof, &values, z)
+            .unwrap());
+
+        let claims: Vec<OpeningClaim> = commitments
+            .iter()
+            .zip(&polynomials)
+            .map(|(commitment, polynomial)| {
+                let (proof, value) = kzg.compute_kzg_proof_at_point(polynomial, z).unwrap();
+                OpeningClaim::new(*commitment, proof, value, z)
+            })
+            .collect();
+        assert!(verifier.verify_proofs_batched(&claims));
+        assert!(kzg.verify_proofs_batched(&claims));
+        assert!(verifier.verify_proofs_batched(&[]));
+        let mut forged = claims.clone();
+        forged[1].value += Fr::one();
+        assert!(!verifier.verify_proofs_batched(&forged));
+
+        // proofs only verify under the challenge hash they were made with
+        let keccak = verifier.with_challenge_hash(ChallengeHash::Keccak256);
+        assert!(!keccak
+            .verify_aggregated_proof(&commitments, proof, &values, z)
+            .unwrap());
+    }
+}
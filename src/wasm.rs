@@ -0,0 +1,208 @@
+//! wasm-bindgen bindings for verifying and producing proofs in the browser.
+//!
+//! Built on `embedded::EmbeddedKzg`, so the crate compiles to
+//! `wasm32-unknown-unknown` with `default-features = false` and the SRS is
+//! handed over as bytes, e.g. fetched by the page. Payloads are padded into
+//! blobs as `Blob::from_bytes_and_pad` does. Points are encoded as their
+//! gnark compressed big-endian encoding, with the flag bits in the top byte,
+//! and field elements as their 32 big-endian bytes, as the proving service
+//! does, so proofs from either verify with the other.
+
+use crate::{
+    blob::Blob,
+    embedded::EmbeddedKzg,
+    encoding::{fr_from_bytes, fr_to_bytes, g1_from_bytes, g1_to_bytes},
+    errors::KzgError,
+    polynomial::Polynomial,
+};
+use ark_bn254::Fr;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_std::{format, string::ToString, vec::Vec};
+use wasm_bindgen::prelude::*;
+
+/// A KZG setup over an SRS passed in from JavaScript.
+#[wasm_bindgen]
+pub struct WasmKzg {
+    inner: EmbeddedKzg,
+}
+
+/// A blob's commitment with the opening of one of its field elements.
+#[wasm_bindgen]
+pub struct WasmOpening {
+    commitment: Vec<u8>,
+    proof: Vec<u8>,
+    value: Vec<u8>,
+    z: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl WasmOpening {
+    /// Returns the compressed commitment to the blob.
+    #[wasm_bindgen(getter)]
+    pub fn commitment(&self) -> Vec<u8> {
+        self.commitment.clone()
+    }
+
+    /// Returns the compressed proof.
+    #[wasm_bindgen(getter)]
+    pub fn proof(&self) -> Vec<u8> {
+        self.proof.clone()
+    }
+
+    /// Returns the field element at the index.
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> Vec<u8> {
+        self.value.clone()
+    }
+
+    /// Returns the root of unity the blob is opened at.
+    #[wasm_bindgen(getter)]
+    pub fn z(&self) -> Vec<u8> {
+        self.z.clone()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmKzg {
+    /// Creates a setup from the concatenated 32-byte G1 points and the
+    /// 64-byte `[tau]G2` in the big-endian encoding of the SRS files.
+    #[wasm_bindgen(constructor)]
+    pub fn new(g1_bytes: &[u8], g2_tau_bytes: &[u8]) -> Result<WasmKzg, JsError> {
+        let inner = EmbeddedKzg::from_bytes(g1_bytes, g2_tau_bytes).map_err(js_error)?;
+        Ok(WasmKzg { inner })
+    }
+
+    /// Creates a setup from the SRS compiled in with the `embedded-srs`
+    /// feature.
+    #[cfg(feature = "embedded-srs")]
+    pub fn embedded() -> Result<WasmKzg, JsError> {
+        let inner = EmbeddedKzg::from_embedded_srs().map_err(js_error)?;
+        Ok(WasmKzg { inner })
+    }
+
+    /// Pads `payload` into a blob and returns its compressed commitment.
+    pub fn commit(&self, payload: &[u8]) -> Result<Vec<u8>, JsError> {
+        self.commit_payload(payload).map_err(js_error)
+    }
+
+    /// Pads `payload` into a blob and opens it at its `index`-th field
+    /// element.
+    pub fn prove(&self, payload: &[u8], index: u32) -> Result<WasmOpening, JsError> {
+        self.prove_payload(payload, index as usize)
+            .map_err(js_error)
+    }
+
+    /// Checks `proof` opens `commitment` to `value` at `z`.
+    pub fn verify(
+        &self,
+        commitment: &[u8],
+        proof: &[u8],
+        value: &[u8],
+        z: &[u8],
+    ) -> Result<bool, JsError> {
+        self.verify_opening(commitment, proof, value, z)
+            .map_err(js_error)
+    }
+}
+
+impl WasmKzg {
+    fn commit_payload(&self, payload: &[u8]) -> Result<Vec<u8>, KzgError> {
+        Ok(g1_to_bytes(&self.inner.commit(&to_polynomial(payload)?)?).to_vec())
+    }
+
+    fn prove_payload(&self, payload: &[u8], index: usize) -> Result<WasmOpening, KzgError> {
+        let polynomial = to_polynomial(payload)?;
+        if index >= polynomial.len() {
+            return Err(KzgError::GenericError(format!(
+                "index {} is out of range of the polynomial",
+                index
+            )));
+        }
+        let z = GeneralEvaluationDomain::<Fr>::new(polynomial.len())
+            .ok_or_else(|| KzgError::FftError("failed to construct domain".to_string()))?
+            .element(index);
+        let commitment = self.inner.commit(&polynomial)?;
+        let (proof, value) = self.inner.compute_proof_at_point(&polynomial, z)?;
+        Ok(WasmOpening {
+            commitment: g1_to_bytes(&commitment).to_vec(),
+            proof: g1_to_bytes(&proof).to_vec(),
+            value: fr_to_bytes(&value).to_vec(),
+            z: fr_to_bytes(&z).to_vec(),
+        })
+    }
+
+    fn verify_opening(
+        &self,
+        commitment: &[u8],
+        proof: &[u8],
+        value: &[u8],
+        z: &[u8],
+    ) -> Result<bool, KzgError> {
+        Ok(self.inner.verify_proof(
+            g1_from_bytes(commitment)?,
+            g1_from_bytes(proof)?,
+            fr_from_bytes(value)?,
+            fr_from_bytes(z)?,
+        ))
+    }
+}
+
+fn js_error(err: KzgError) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+fn to_polynomial(payload: &[u8]) -> Result<Polynomial, KzgError> {
+    Blob::from_bytes_and_pad(payload)
+        .to_polynomial()
+        .map_err(|err| KzgError::SerializationError(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consts::GETTYSBURG_ADDRESS_BYTES, kzg::Kzg};
+
+    // the exported methods only wrap these, and `JsError` needs a JS host
+    #[test]
+    fn test_wasm_kzg() {
+        let g1_bytes = std::fs::read("src/test-files/g1.point").unwrap();
+        let g2_bytes = std::fs::read("src/test-files/g2.point").unwrap();
+        let kzg = WasmKzg {
+            inner: EmbeddedKzg::from_bytes(&g1_bytes[..32 * 3000], &g2_bytes[64..128]).unwrap(),
+        };
+
+        let opening = kzg.prove_payload(GETTYSBURG_ADDRESS_BYTES, 17).unwrap();
+        assert_eq!(
+            opening.commitment(),
+            kzg.commit_payload(GETTYSBURG_ADDRESS_BYTES).unwrap()
+        );
+        let verify = |value: &[u8]| {
+            kzg.verify_opening(&opening.commitment(), &opening.proof(), value, &opening.z())
+        };
+        assert!(verify(&opening.value()).unwrap());
+
+        // the opening is the one the file-backed prover computes
+        let reference = Kzg::setup(
+            "src/test-files/g1.point",
+            "src/test-files/g2.point",
+            "src/test-files/g2.point.powerOf2",
+            3000,
+            3000,
+        )
+        .unwrap();
+        assert!(reference.verify_kzg_proof(
+            g1_from_bytes(&opening.commitment()).unwrap(),
+            g1_from_bytes(&opening.proof()).unwrap(),
+            fr_from_bytes(&opening.value()).unwrap(),
+            fr_from_bytes(&opening.z()).unwrap(),
+        ));
+
+        let mut value = opening.value();
+        value[31] ^= 1;
+        assert!(!verify(&value).unwrap());
+        assert!(verify(&[1]).is_err());
+        assert!(kzg
+            .prove_payload(GETTYSBURG_ADDRESS_BYTES, 1 << 20)
+            .is_err());
+    }
+}
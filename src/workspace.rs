@@ -0,0 +1,51 @@
+use ark_bn254::{Fr, G1Affine, G1Projective};
+
+/// Scratch memory for the commit/prove path that can be reused across calls.
+///
+/// Holding on to a workspace keeps the G1 Lagrange bases for the last domain
+/// size along with the buffers used to build quotient polynomials, so repeated
+/// commitments and proofs of same-sized blobs don't reallocate or redo the G1
+/// IFFT. A workspace is tied to the `Kzg` instance it was first used with and
+/// is rebuilt automatically if used with a different SRS.
+#[derive(Clone, Debug, Default)]
+pub struct KzgWorkspace {
+    pub(crate) g1_projective: Vec<G1Projective>,
+    pub(crate) g1_lagrange: Vec<G1Affine>,
+    pub(crate) srs_tag: Option<G1Affine>,
+    pub(crate) denominators: Vec<Fr>,
+    pub(crate) quotient: Vec<Fr>,
+}
+
+impl KzgWorkspace {
+    /// Creates an empty workspace. Buffers grow on first use.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a workspace with buffers preallocated for polynomials of up to
+    /// `length` field elements.
+    pub fn with_capacity(length: usize) -> Self {
+        KzgWorkspace {
+            g1_projective: Vec::with_capacity(length),
+            g1_lagrange: Vec::with_capacity(length),
+            srs_tag: None,
+            denominators: Vec::with_capacity(length),
+            quotient: Vec::with_capacity(length),
+        }
+    }
+
+    /// Returns the domain size of the cached Lagrange bases, or 0 if none are
+    /// cached.
+    pub fn cached_domain_size(&self) -> usize {
+        self.g1_lagrange.len()
+    }
+
+    /// Drops the cached Lagrange bases while keeping the allocated buffers.
+    pub fn clear(&mut self) {
+        self.g1_projective.clear();
+        self.g1_lagrange.clear();
+        self.srs_tag = None;
+        self.denominators.clear();
+        self.quotient.clear();
+    }
+}